@@ -0,0 +1,184 @@
+//! Attack queries that answer without playing a move: which pieces attack a
+//! square, and whether a move gives check.
+
+use cozy_chess::{
+    BitBoard, Board, Color, File, Move, Piece, Square, get_bishop_moves, get_king_moves,
+    get_knight_moves, get_pawn_attacks, get_rook_moves,
+};
+
+/// Pieces of `by` attacking `square`, with sliders blocked by `occupied`.
+/// Pieces outside `occupied` are left out, so a caller can lift pieces off
+/// the board by clearing their squares.
+pub fn attackers_to(board: &Board, square: Square, by: Color, occupied: BitBoard) -> BitBoard {
+    let pieces = |piece| board.colored_pieces(by, piece) & occupied;
+    let queens = pieces(Piece::Queen);
+    (get_pawn_attacks(square, !by) & pieces(Piece::Pawn))
+        | (get_knight_moves(square) & pieces(Piece::Knight))
+        | (get_bishop_moves(square, occupied) & (pieces(Piece::Bishop) | queens))
+        | (get_rook_moves(square, occupied) & (pieces(Piece::Rook) | queens))
+        | (get_king_moves(square) & pieces(Piece::King))
+}
+
+/// Whether the legal move `mv` checks the opponent, directly or by
+/// uncovering a slider, including en passant, castling and promotions.
+pub fn gives_check(board: &Board, mv: Move) -> bool {
+    let us = board.side_to_move();
+    let king = board.king(!us);
+    let Some(piece) = board.piece_on(mv.from) else {
+        return false;
+    };
+
+    // cozy-chess encodes castling as the king capturing its own rook.
+    if piece == Piece::King && board.colors(us).has(mv.to) {
+        let (king_file, rook_file) = if mv.to.file() > mv.from.file() {
+            (File::G, File::F)
+        } else {
+            (File::C, File::D)
+        };
+        let rook_to = Square::new(rook_file, mv.from.rank());
+        let occupied = (board.occupied() - mv.from.bitboard() - mv.to.bitboard())
+            | Square::new(king_file, mv.from.rank()).bitboard()
+            | rook_to.bitboard();
+        return get_rook_moves(rook_to, occupied).has(king)
+            || !attackers_to(board, king, us, occupied).is_empty();
+    }
+
+    let mut occupied = (board.occupied() - mv.from.bitboard()) | mv.to.bitboard();
+    if piece == Piece::Pawn && mv.from.file() != mv.to.file() && board.piece_on(mv.to).is_none() {
+        occupied -= Square::new(mv.to.file(), mv.from.rank()).bitboard();
+    }
+    let direct = match mv.promotion.unwrap_or(piece) {
+        Piece::Pawn => get_pawn_attacks(mv.to, us),
+        Piece::Knight => get_knight_moves(mv.to),
+        Piece::Bishop => get_bishop_moves(mv.to, occupied),
+        Piece::Rook => get_rook_moves(mv.to, occupied),
+        Piece::Queen => get_bishop_moves(mv.to, occupied) | get_rook_moves(mv.to, occupied),
+        Piece::King => BitBoard::EMPTY,
+    };
+    // The moved piece left `from`, so only the pieces it uncovered remain.
+    direct.has(king) || !attackers_to(board, king, us, occupied).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(fen: &str, uci: &str) -> bool {
+        let board: Board = fen.parse().unwrap();
+        gives_check(&board, uci.parse().unwrap())
+    }
+
+    fn played_check(board: &Board, mv: Move) -> bool {
+        let mut next = board.clone();
+        next.play_unchecked(mv);
+        !next.checkers().is_empty()
+    }
+
+    #[test]
+    fn attackers_include_every_piece_kind_and_respect_the_occupancy() {
+        let board: Board = "4k3/8/8/3p4/2N1n3/3K4/8/3R4 b - - 0 1".parse().unwrap();
+        let d4: Square = "d4".parse().unwrap();
+        let d3: Square = "d3".parse().unwrap();
+        let d1: Square = "d1".parse().unwrap();
+
+        let white = attackers_to(&board, d4, Color::White, board.occupied());
+        assert_eq!(white, d3.bitboard());
+        assert_eq!(
+            attackers_to(
+                &board,
+                "c4".parse().unwrap(),
+                Color::Black,
+                board.occupied()
+            ),
+            "d5".parse::<Square>().unwrap().bitboard()
+        );
+        assert!(attackers_to(&board, d3, Color::Black, board.occupied()).is_empty());
+        // Lifting the king off the file lets the rook through.
+        let lifted = board.occupied() - d3.bitboard();
+        assert_eq!(
+            attackers_to(&board, d4, Color::White, lifted),
+            d1.bitboard()
+        );
+    }
+
+    #[test]
+    fn direct_and_discovered_checks() {
+        assert!(check("4k3/8/8/8/8/8/8/R3K3 w Q - 0 1", "a1a8"));
+        assert!(!check("4k3/8/8/8/8/8/8/R3K3 w Q - 0 1", "a1a7"));
+        // The knight steps off the bishop's diagonal.
+        assert!(check("7k/8/8/8/3N4/8/1B6/K7 w - - 0 1", "d4b5"));
+        assert!(!check("7k/8/8/8/3N4/8/1B6/K7 w - - 0 1", "a1a2"));
+    }
+
+    #[test]
+    fn en_passant_can_check_directly_or_by_discovery() {
+        // The capturing pawn lands next to the king.
+        assert!(check("8/3k4/8/3Pp3/8/8/8/4K3 w - e6 0 1", "d5e6"));
+        // Removing the captured pawn opens the bishop's diagonal.
+        assert!(check("1k6/8/8/3Pp3/8/8/7B/4K3 w - e6 0 1", "d5e6"));
+        // Removing both pawns opens the rank to the rook.
+        assert!(check("8/8/8/k2Pp2R/8/8/8/4K3 w - e6 0 1", "d5e6"));
+        assert!(!check("8/8/8/3Pp3/8/8/k7/4K3 w - e6 0 1", "d5e6"));
+    }
+
+    #[test]
+    fn castling_checks_with_the_rook() {
+        assert!(check("5k2/8/8/8/8/8/8/4K2R w K - 0 1", "e1h1"));
+        assert!(check("3k4/8/8/8/8/8/8/R3K3 w Q - 0 1", "e1a1"));
+        assert!(!check("6k1/8/8/8/8/8/8/4K2R w K - 0 1", "e1h1"));
+    }
+
+    #[test]
+    fn promotions_check_with_the_new_piece() {
+        let on_the_rank = "5k2/3P4/8/8/8/8/8/4K3 w - - 0 1";
+        assert!(check(on_the_rank, "d7d8q"));
+        assert!(check(on_the_rank, "d7d8r"));
+        assert!(!check(on_the_rank, "d7d8b"));
+        assert!(!check(on_the_rank, "d7d8n"));
+        let knight_fork = "8/3P1k2/8/8/8/8/8/4K3 w - - 0 1";
+        assert!(check(knight_fork, "d7d8n"));
+        assert!(!check(knight_fork, "d7d8q"));
+    }
+
+    #[test]
+    fn matches_playing_the_move_over_the_perft_positions() {
+        fn walk(board: &Board, depth: u32) -> usize {
+            let mut moves = Vec::new();
+            board.generate_moves(|piece_moves| {
+                moves.extend(piece_moves);
+                false
+            });
+            let mut checked = 0;
+            for mv in moves {
+                assert_eq!(
+                    gives_check(board, mv),
+                    played_check(board, mv),
+                    "{board} {mv}"
+                );
+                checked += 1;
+                if depth > 1 {
+                    let mut next = board.clone();
+                    next.play_unchecked(mv);
+                    checked += walk(&next, depth - 1);
+                }
+            }
+            checked
+        }
+
+        let mut checked = 0;
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        ] {
+            checked += walk(&fen.parse().unwrap(), 3);
+        }
+        // Chess960 castling, where the king or rook may not move at all.
+        let chess960 = "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9";
+        checked += walk(&Board::from_fen(chess960, true).unwrap(), 3);
+        assert!(checked > 100_000);
+    }
+}
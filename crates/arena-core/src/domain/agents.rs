@@ -73,6 +73,9 @@ pub struct AgentVersion {
     pub working_directory: Option<String>,
     pub args: Vec<String>,
     pub env: BTreeMap<String, String>,
+    /// `setoption` values sent after `uci`, before every game.
+    #[serde(default)]
+    pub uci_options: BTreeMap<String, String>,
     pub capabilities: AgentCapabilities,
     pub declared_name: Option<String>,
     pub tags: Vec<String>,
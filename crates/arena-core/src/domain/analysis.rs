@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An engine evaluation as reported over UCI: centipawns, or moves to mate
+/// (negative when the engine is being mated).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum EngineScore {
+    Centipawns(i32),
+    Mate(i32),
+}
+
+impl EngineScore {
+    /// The same evaluation seen from the other side.
+    pub fn flipped(self) -> Self {
+        match self {
+            Self::Centipawns(value) => Self::Centipawns(-value),
+            Self::Mate(moves) => Self::Mate(-moves),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionAnalysis {
+    pub fen: String,
+    pub engine_version_id: Uuid,
+    /// Evaluation from White's point of view, if the engine reported one.
+    pub score: Option<EngineScore>,
+    /// Depth of the main line, for analysis that reports as it searches.
+    #[serde(default)]
+    pub depth: Option<u32>,
+    /// The engine's best lines, best first, when it reports several.
+    #[serde(default)]
+    pub lines: Vec<AnalysisLine>,
+}
+
+/// One ranked line of an analysis, scored from White's point of view.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalysisLine {
+    pub score: EngineScore,
+    pub moves_uci: Vec<String>,
+}
@@ -50,6 +50,52 @@ pub struct FairnessConfig {
     pub swap_colors: bool,
     pub opening_suite_id: Option<Uuid>,
     pub opening_seed: Option<u64>,
+    #[serde(default)]
+    pub adjudication: AdjudicationConfig,
+}
+
+/// Score-based early ends for engine games. Both rules are off unless the
+/// pool turns them on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdjudicationConfig {
+    pub resign: Option<ResignAdjudication>,
+    pub draw: Option<DrawAdjudication>,
+}
+
+/// Lose a game for the side both engines score at `score_cp` or worse on
+/// each of their last `move_count` moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResignAdjudication {
+    pub score_cp: i32,
+    pub move_count: u16,
+}
+
+impl Default for ResignAdjudication {
+    fn default() -> Self {
+        Self {
+            score_cp: 800,
+            move_count: 4,
+        }
+    }
+}
+
+/// Draw a game once both engines score it within `score_cp` of level on each
+/// of their last `move_count` moves, from move `min_move_number` onwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DrawAdjudication {
+    pub score_cp: i32,
+    pub move_count: u16,
+    pub min_move_number: u16,
+}
+
+impl Default for DrawAdjudication {
+    fn default() -> Self {
+        Self {
+            score_cp: 10,
+            move_count: 12,
+            min_move_number: 40,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
@@ -5,7 +7,7 @@ use uuid::Uuid;
 
 use super::{MatchStatus, ProtocolLiveSide, Variant};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum GameTermination {
     Checkmate,
@@ -17,6 +19,7 @@ pub enum GameTermination {
     Resignation,
     IllegalMove,
     MoveLimit,
+    Adjudication,
     EngineFailure,
     Unknown,
 }
@@ -165,6 +168,44 @@ pub struct GameRecord {
     pub completed_at: DateTime<Utc>,
 }
 
+/// Summary of a set of finished games, such as one tournament's.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameStats {
+    pub games: u32,
+    pub white_wins: u32,
+    pub black_wins: u32,
+    pub draws: u32,
+    pub average_plies: f64,
+    pub average_duration_ms: u64,
+    pub terminations: BTreeMap<GameTermination, u32>,
+}
+
+impl GameStats {
+    pub fn from_games<'a>(games: impl IntoIterator<Item = &'a GameRecord>) -> Self {
+        let mut stats = Self::default();
+        let mut total_plies = 0;
+        let mut total_duration_ms = 0;
+        for game in games {
+            stats.games += 1;
+            match game.result {
+                GameResult::WhiteWin => stats.white_wins += 1,
+                GameResult::BlackWin => stats.black_wins += 1,
+                GameResult::Draw => stats.draws += 1,
+            }
+            *stats.terminations.entry(game.termination).or_default() += 1;
+            total_plies += game.moves_uci.len() as u64;
+            total_duration_ms += (game.completed_at - game.started_at)
+                .num_milliseconds()
+                .max(0) as u64;
+        }
+        if stats.games > 0 {
+            stats.average_plies = total_plies as f64 / f64::from(stats.games);
+            stats.average_duration_ms = total_duration_ms / u64::from(stats.games);
+        }
+        stats
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LiveSide {
@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::EngineScore;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LiveStatus {
@@ -32,6 +34,7 @@ pub enum LiveTermination {
     FiftyMoveRule,
     IllegalMove,
     MoveLimit,
+    Adjudication,
     EngineFailure,
     None,
 }
@@ -51,6 +54,7 @@ pub enum LiveEventType {
     MoveCommitted,
     ClockSync,
     GameFinished,
+    EngineThinking,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -66,6 +70,10 @@ pub struct LiveMatchSnapshot {
     pub start_fen: String,
     pub fen: String,
     pub moves: Vec<String>,
+    /// Thinking time of each entry in `moves`, or `None` for moves that were
+    /// not played on this server, such as those of a continued PGN.
+    #[serde(default)]
+    pub move_times_ms: Vec<Option<u64>>,
     pub white_remaining_ms: u64,
     pub black_remaining_ms: u64,
     pub side_to_move: ProtocolLiveSide,
@@ -84,6 +92,8 @@ pub struct MoveCommittedEvent {
     pub start_fen: String,
     pub fen: String,
     pub moves: Vec<String>,
+    #[serde(default)]
+    pub move_times_ms: Vec<Option<u64>>,
     pub white_remaining_ms: u64,
     pub black_remaining_ms: u64,
     pub side_to_move: ProtocolLiveSide,
@@ -117,12 +127,34 @@ pub struct GameFinishedEvent {
     pub start_fen: String,
     pub fen: String,
     pub moves: Vec<String>,
+    #[serde(default)]
+    pub move_times_ms: Vec<Option<u64>>,
     pub white_remaining_ms: u64,
     pub black_remaining_ms: u64,
     pub side_to_move: ProtocolLiveSide,
     pub turn_started_server_unix_ms: i64,
 }
 
+/// Progress of the engine to move, broadcast while it searches. It is never
+/// stored or replayed, and `seq` is that of the confirmed state it was
+/// searched from, so clients drop it once a newer event arrives.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineThinkingEvent {
+    pub protocol_version: u32,
+    pub event_type: LiveEventType,
+    pub match_id: Uuid,
+    pub seq: u64,
+    pub server_now_unix_ms: i64,
+    pub side: ProtocolLiveSide,
+    pub depth: Option<u32>,
+    /// From the engine's point of view.
+    pub score: Option<EngineScore>,
+    pub nodes: Option<u64>,
+    pub nps: Option<u64>,
+    /// Principal variation in SAN.
+    pub pv: Vec<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum LiveEventEnvelope {
@@ -130,6 +162,7 @@ pub enum LiveEventEnvelope {
     MoveCommitted(MoveCommittedEvent),
     ClockSync(ClockSyncEvent),
     GameFinished(GameFinishedEvent),
+    EngineThinking(EngineThinkingEvent),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -142,6 +175,8 @@ pub struct LiveRuntimeCheckpoint {
     pub start_fen: String,
     pub fen: String,
     pub moves: Vec<String>,
+    #[serde(default)]
+    pub move_times_ms: Vec<Option<u64>>,
     pub white_remaining_ms: u64,
     pub black_remaining_ms: u64,
     pub side_to_move: ProtocolLiveSide,
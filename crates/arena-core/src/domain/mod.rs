@@ -1,4 +1,5 @@
 mod agents;
+mod analysis;
 mod competition;
 mod games;
 mod live;
@@ -6,6 +7,7 @@ mod openings;
 mod ratings;
 
 pub use agents::*;
+pub use analysis::*;
 pub use competition::*;
 pub use games::*;
 pub use live::*;
@@ -1,10 +1,14 @@
+pub mod attacks;
 pub mod domain;
+pub mod notation;
 pub mod opening;
 pub mod pairing;
 pub mod rating;
 pub mod testing;
 
+pub use attacks::*;
 pub use domain::*;
+pub use notation::*;
 pub use opening::*;
 pub use pairing::*;
 pub use rating::*;
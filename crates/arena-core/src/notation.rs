@@ -0,0 +1,392 @@
+use cozy_chess::{Board, Color, FenParseError, GameStatus, Move, Piece, Rank};
+use thiserror::Error;
+
+use crate::{Variant, gives_check};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PgnError {
+    #[error("invalid FEN tag: {0}")]
+    InvalidFen(String),
+    #[error("illegal or unreadable move {san:?} at ply {ply}")]
+    IllegalMove { ply: usize, san: String },
+}
+
+/// A game read from PGN: its start position, the moves played, and the
+/// resulting position.
+#[derive(Debug, Clone)]
+pub struct PgnGame {
+    pub start: Board,
+    pub moves: Vec<Move>,
+    pub board: Board,
+}
+
+/// Standard Algebraic Notation for a legal `mv` on `board`, including
+/// disambiguation, promotions, castling, and `+`/`#` suffixes.
+pub fn move_to_san(board: &Board, mv: Move) -> String {
+    let piece = board.piece_on(mv.from).unwrap_or(Piece::Pawn);
+    let mut san = String::new();
+
+    if piece == Piece::King && board.color_on(mv.to) == Some(board.side_to_move()) {
+        // cozy-chess encodes castling as the king capturing its own rook.
+        san.push_str(if mv.to.file() > mv.from.file() {
+            "O-O"
+        } else {
+            "O-O-O"
+        });
+    } else {
+        let is_capture = board.color_on(mv.to) == Some(!board.side_to_move())
+            || (piece == Piece::Pawn && mv.from.file() != mv.to.file());
+        if piece == Piece::Pawn {
+            if is_capture {
+                san.push(char::from(mv.from.file()));
+            }
+        } else {
+            san.push(piece_letter(piece));
+            san.push_str(&disambiguation(board, piece, mv));
+        }
+        if is_capture {
+            san.push('x');
+        }
+        san.push_str(&mv.to.to_string());
+        if let Some(promotion) = mv.promotion {
+            san.push('=');
+            san.push(piece_letter(promotion));
+        }
+    }
+
+    // Only a check can be mate, so most moves never need to be played.
+    if gives_check(board, mv) {
+        let mut next = board.clone();
+        next.play_unchecked(mv);
+        san.push(if next.status() == GameStatus::Won {
+            '#'
+        } else {
+            '+'
+        });
+    }
+    san
+}
+
+/// SAN for a line of UCI moves played from `board`, such as an engine's
+/// principal variation. The line is cut at the first move that cannot be
+/// read or played.
+pub fn uci_line_to_san(board: &Board, moves: &[String]) -> Vec<String> {
+    let mut board = board.clone();
+    let mut line = Vec::with_capacity(moves.len());
+    for uci in moves {
+        let Ok(mv) = cozy_chess::util::parse_uci_move(&board, uci) else {
+            break;
+        };
+        if !board.is_legal(mv) {
+            break;
+        }
+        line.push(move_to_san(&board, mv));
+        board.play_unchecked(mv);
+    }
+    line
+}
+
+/// Resolve a SAN token against the legal moves of `board`. Accepts `0-0`
+/// castling, a missing `=` before the promotion piece, and trailing check or
+/// annotation marks such as `+`, `#`, `!` and `?`.
+pub fn parse_san(board: &Board, san: &str) -> Option<Move> {
+    let wanted = normalize_san(san);
+    if wanted.is_empty() {
+        return None;
+    }
+
+    let mut found = None;
+    board.generate_moves(|piece_moves| {
+        for mv in piece_moves {
+            if normalize_san(&move_to_san(board, mv)) == wanted {
+                found = Some(mv);
+                return true;
+            }
+        }
+        false
+    });
+    found
+}
+
+/// Parse a FEN for `variant`. Chess960 positions take Shredder-FEN castling
+/// files or X-FEN `KQkq`, where each letter names that side's outermost rook.
+pub fn board_from_fen(fen: &str, variant: Variant) -> Result<Board, FenParseError> {
+    Board::from_fen(fen, variant.is_chess960()).or_else(|err| {
+        variant
+            .is_chess960()
+            .then(|| shredder_fen(fen))
+            .flatten()
+            .and_then(|fen| Board::from_fen(&fen, true).ok())
+            .ok_or(err)
+    })
+}
+
+/// Parse a single PGN game. Tag pairs are skipped apart from `FEN`; move
+/// numbers, comments, variations, NAGs and the result token are ignored.
+pub fn parse_pgn_game(pgn: &str, chess960: bool) -> Result<PgnGame, PgnError> {
+    let mut start = None;
+    let mut movetext = String::new();
+    for line in pgn.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            if let Some(value) = line.strip_prefix("[FEN \"")
+                && let Some(end) = value.find("\"]")
+            {
+                let fen = &value[..end];
+                start = Some(
+                    Board::from_fen(fen, chess960)
+                        .map_err(|_| PgnError::InvalidFen(fen.to_string()))?,
+                );
+            }
+        } else {
+            movetext.push_str(line);
+            movetext.push('\n');
+        }
+    }
+
+    let start = start.unwrap_or_default();
+    let mut board = start.clone();
+    let mut moves = Vec::new();
+    for token in movetext_tokens(&movetext) {
+        let mv = parse_san(&board, &token).ok_or_else(|| PgnError::IllegalMove {
+            ply: moves.len() + 1,
+            san: token.clone(),
+        })?;
+        board.play_unchecked(mv);
+        moves.push(mv);
+    }
+
+    Ok(PgnGame {
+        start,
+        moves,
+        board,
+    })
+}
+
+/// Rewrite an X-FEN castling field, where `KQkq` name each side's outermost
+/// rook, as the Shredder-FEN rook files Chess960 parsing expects.
+fn shredder_fen(fen: &str) -> Option<String> {
+    let mut fields: Vec<String> = fen.split_whitespace().map(str::to_string).collect();
+    let castling = fields.get(2)?.clone();
+    fields[2] = "-".to_string();
+    let board = Board::from_fen(&fields.join(" "), true).ok()?;
+
+    let mut rights = String::new();
+    for right in castling.chars() {
+        let (color, kingside) = match right {
+            'K' => (Color::White, true),
+            'Q' => (Color::White, false),
+            'k' => (Color::Black, true),
+            'q' => (Color::Black, false),
+            _ => {
+                rights.push(right);
+                continue;
+            }
+        };
+        let king = board.king(color);
+        let rooks =
+            board.colored_pieces(color, Piece::Rook) & Rank::First.relative_to(color).bitboard();
+        let rook = rooks
+            .into_iter()
+            .filter(|rook| rook.rank() == king.rank() && (rook.file() > king.file()) == kingside)
+            .max_by_key(|rook| {
+                if kingside {
+                    rook.file() as i8
+                } else {
+                    -(rook.file() as i8)
+                }
+            })?;
+        let file = char::from(rook.file());
+        rights.push(match color {
+            Color::White => file.to_ascii_uppercase(),
+            Color::Black => file,
+        });
+    }
+    fields[2] = rights;
+    Some(fields.join(" "))
+}
+
+fn movetext_tokens(movetext: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut variation_depth = 0usize;
+    let mut chars = movetext.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' => {
+                for ch in chars.by_ref() {
+                    if ch == '}' {
+                        break;
+                    }
+                }
+            }
+            ';' => {
+                for ch in chars.by_ref() {
+                    if ch == '\n' {
+                        break;
+                    }
+                }
+            }
+            '(' => variation_depth += 1,
+            ')' => variation_depth = variation_depth.saturating_sub(1),
+            ch if ch.is_whitespace() => push_move_token(&mut tokens, &mut current),
+            ch if variation_depth == 0 => current.push(ch),
+            _ => {}
+        }
+        if matches!(ch, '{' | ';' | '(' | ')') {
+            push_move_token(&mut tokens, &mut current);
+        }
+    }
+    push_move_token(&mut tokens, &mut current);
+    tokens
+}
+
+fn push_move_token(tokens: &mut Vec<String>, current: &mut String) {
+    let token = std::mem::take(current);
+    if matches!(token.as_str(), "1-0" | "0-1" | "1/2-1/2" | "*") {
+        return;
+    }
+    // Move numbers may be glued to the move, as in `12.Nf3` or `12...Nf6`.
+    let token = match token.rfind('.') {
+        Some(dot)
+            if token[..dot]
+                .chars()
+                .all(|ch| ch.is_ascii_digit() || ch == '.') =>
+        {
+            &token[dot + 1..]
+        }
+        _ => token.as_str(),
+    };
+    if token.is_empty() || token.starts_with('$') {
+        return;
+    }
+    tokens.push(token.to_string());
+}
+
+fn normalize_san(san: &str) -> String {
+    san.trim()
+        .trim_end_matches(['+', '#', '!', '?'])
+        .replace('0', "O")
+        .replace('=', "")
+}
+
+fn disambiguation(board: &Board, piece: Piece, mv: Move) -> String {
+    let mut rivals = Vec::new();
+    board.generate_moves_for(board.pieces(piece), |piece_moves| {
+        if piece_moves.from != mv.from && piece_moves.to.has(mv.to) {
+            rivals.push(piece_moves.from);
+        }
+        false
+    });
+
+    if rivals.is_empty() {
+        String::new()
+    } else if rivals.iter().all(|from| from.file() != mv.from.file()) {
+        char::from(mv.from.file()).to_string()
+    } else if rivals.iter().all(|from| from.rank() != mv.from.rank()) {
+        char::from(mv.from.rank()).to_string()
+    } else {
+        mv.from.to_string()
+    }
+}
+
+fn piece_letter(piece: Piece) -> char {
+    char::from(piece).to_ascii_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn san_covers_disambiguation_special_moves_and_suffixes() {
+        let cases = [
+            ("4k3/8/8/8/8/8/4K3/R6R w - - 0 1", "a1d1", "Rad1"),
+            ("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1", "e1g1", "O-O"),
+            ("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1", "e1c1", "O-O-O"),
+            ("4k3/8/8/8/8/2N3N1/8/4K3 w - - 0 1", "c3e4", "Nce4"),
+            ("4k3/8/8/8/1N6/8/1N6/4K3 w - - 0 1", "b4d3", "N4d3"),
+            ("8/7k/8/8/Q2Q4/8/8/Q3K3 w - - 0 1", "a4d1", "Qa4d1"),
+            ("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2", "e5d6", "exd6"),
+            ("3rk3/2P5/8/8/8/8/8/4K3 w - - 0 1", "c7d8q", "cxd8=Q+"),
+            ("3k4/1P6/8/8/8/8/8/4K3 w - - 0 1", "b7b8q", "b8=Q+"),
+            ("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8", "Ra8#"),
+        ];
+
+        for (fen, uci, expected) in cases {
+            let board: Board = fen.parse().unwrap();
+            let mv = cozy_chess::util::parse_uci_move(&board, uci).unwrap();
+            assert_eq!(move_to_san(&board, mv), expected, "{fen} {uci}");
+        }
+    }
+
+    #[test]
+    fn uci_lines_convert_to_san_up_to_the_first_bad_move() {
+        let board: Board = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+            .parse()
+            .unwrap();
+        let line = ["e5f7", "e8g8", "f7h8", "g8h8", "e1g1", "e7e2"].map(String::from);
+
+        assert_eq!(
+            uci_line_to_san(&board, &line),
+            ["Nxf7", "O-O", "Nh8", "Kxh8", "O-O"]
+        );
+        assert!(uci_line_to_san(&board, &["e2e5".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn parse_san_round_trips_move_to_san() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        ] {
+            let board: Board = fen.parse().unwrap();
+            board.generate_moves(|piece_moves| {
+                for mv in piece_moves {
+                    let mut next = board.clone();
+                    next.play(mv);
+                    assert_eq!(parse_san(&board, &move_to_san(&board, mv)), Some(mv));
+                    next.generate_moves(|replies| {
+                        for reply in replies {
+                            let san = move_to_san(&next, reply);
+                            assert_eq!(parse_san(&next, &san), Some(reply), "{next} {san}");
+                        }
+                        false
+                    });
+                }
+                false
+            });
+        }
+    }
+
+    #[test]
+    fn parse_san_tolerates_common_spellings() {
+        let board: Board = "4k3/1P6/8/8/8/8/8/R3K2R w KQ - 0 1".parse().unwrap();
+
+        assert_eq!(parse_san(&board, "0-0"), parse_san(&board, "O-O"));
+        assert_eq!(parse_san(&board, "O-O-O!?").unwrap().to_string(), "e1a1");
+        assert_eq!(parse_san(&board, "b8Q+").unwrap().to_string(), "b7b8q");
+        assert_eq!(parse_san(&board, "Nf3"), None);
+    }
+
+    #[test]
+    fn parse_pgn_game_reads_a_short_game() {
+        let pgn = r#"[Event "Scholar's mate"]
+[Result "1-0"]
+
+1. e4 e5 2. Bc4 {aiming at f7} Nc6 3. Qh5 Nf6?? (3... g6 4. Qf3) 4.Qxf7# 1-0"#;
+
+        let game = parse_pgn_game(pgn, false).unwrap();
+
+        assert_eq!(game.moves.len(), 7);
+        assert_eq!(game.board.status(), GameStatus::Won);
+        assert_eq!(
+            parse_pgn_game("1. e4 e4", false).unwrap_err(),
+            PgnError::IllegalMove {
+                ply: 2,
+                san: "e4".to_string()
+            }
+        );
+    }
+}
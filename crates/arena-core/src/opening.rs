@@ -6,7 +6,10 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::{OpeningPosition, OpeningSourceKind, OpeningSuite, Variant};
+use crate::{
+    GameRecord, GameResult, OpeningPosition, OpeningSourceKind, OpeningSuite, PgnError, Variant,
+    board_from_fen, parse_pgn_game,
+};
 
 #[derive(Debug, Error)]
 pub enum OpeningImportError {
@@ -14,6 +17,10 @@ pub enum OpeningImportError {
     Empty,
     #[error("invalid FEN on line {line}: {message}")]
     InvalidFen { line: usize, message: String },
+    #[error("illegal move {mv} on line {line}")]
+    IllegalMove { line: usize, mv: String },
+    #[error("invalid PGN in game {game}: {source}")]
+    InvalidPgn { game: usize, source: PgnError },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -32,7 +39,9 @@ pub fn import_opening_suite(
 ) -> Result<OpeningSuite, OpeningImportError> {
     let normalized_text = match input.source_kind {
         OpeningSourceKind::Starter | OpeningSourceKind::FenList => input.text.clone(),
-        OpeningSourceKind::PgnImport => extract_fens_from_pgn(&input.text),
+        OpeningSourceKind::PgnImport => {
+            extract_fens_from_pgn(&input.text, input.variant.is_chess960())?
+        }
     };
 
     let suite_id = Uuid::new_v4();
@@ -40,22 +49,27 @@ pub fn import_opening_suite(
     let mut positions = Vec::new();
 
     for (line_idx, raw_line) in normalized_text.lines().enumerate() {
-        let fen = raw_line.trim();
-        if fen.is_empty() {
+        let line = raw_line.trim();
+        if line.is_empty() {
             continue;
         }
-        let board = fen
-            .parse::<Board>()
-            .map_err(|err| OpeningImportError::InvalidFen {
-                line: line_idx + 1,
-                message: err.to_string(),
-            })?;
-        let normalized = board.to_string();
+        let board = parse_opening_line(line, line_idx + 1, input.variant)?;
+        let normalized = match input.variant {
+            Variant::Standard => board.to_string(),
+            Variant::Chess960 => format!("{board:#}"),
+        };
         if seen.insert(normalized.clone()) {
+            // Move lines keep their text so results can be read per opening;
+            // bare FENs have nothing better than their place in the suite.
+            let label = if line.split_whitespace().any(|token| token == "moves") {
+                line.split_whitespace().collect::<Vec<_>>().join(" ")
+            } else {
+                format!("Position {}", positions.len() + 1)
+            };
             positions.push(OpeningPosition {
                 id: Uuid::new_v4(),
                 suite_id,
-                label: format!("Position {}", positions.len() + 1),
+                label,
                 fen: normalized,
                 variant: input.variant,
             });
@@ -98,17 +112,136 @@ pub fn starter_suite() -> OpeningSuite {
     .expect("starter suite should be valid")
 }
 
-fn extract_fens_from_pgn(text: &str) -> String {
+/// Results of the games played from one start position, for spotting an
+/// opening that favours one color.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpeningScore {
+    pub opening_id: Option<Uuid>,
+    /// The suite's label for the position, such as `startpos moves e2e4`.
+    pub label: Option<String>,
+    pub start_fen: String,
+    pub games: u32,
+    pub white_wins: u32,
+    pub black_wins: u32,
+    pub draws: u32,
+    /// White's points per game, with draws counting half.
+    pub white_score: f64,
+}
+
+/// Groups games by start position in the order the positions were first
+/// played, naming each after the matching entry of `positions`.
+pub fn opening_scores<'a>(
+    games: impl IntoIterator<Item = &'a GameRecord>,
+    positions: &[OpeningPosition],
+) -> Vec<OpeningScore> {
+    let mut scores: Vec<OpeningScore> = Vec::new();
+    for game in games {
+        let index = match scores
+            .iter()
+            .position(|score| score.start_fen == game.start_fen)
+        {
+            Some(index) => index,
+            None => {
+                let position = positions
+                    .iter()
+                    .find(|position| Some(position.id) == game.opening_id);
+                scores.push(OpeningScore {
+                    opening_id: game.opening_id,
+                    label: position.map(|position| position.label.clone()),
+                    start_fen: game.start_fen.clone(),
+                    games: 0,
+                    white_wins: 0,
+                    black_wins: 0,
+                    draws: 0,
+                    white_score: 0.0,
+                });
+                scores.len() - 1
+            }
+        };
+        let score = &mut scores[index];
+        score.games += 1;
+        match game.result {
+            GameResult::WhiteWin => score.white_wins += 1,
+            GameResult::BlackWin => score.black_wins += 1,
+            GameResult::Draw => score.draws += 1,
+        }
+    }
+    for score in &mut scores {
+        score.white_score =
+            (f64::from(score.white_wins) + f64::from(score.draws) / 2.0) / f64::from(score.games);
+    }
+    scores
+}
+
+/// Resolves one line of a FEN list: a bare FEN, or `startpos` or a FEN
+/// followed by `moves` and UCI moves played from it, as in the UCI
+/// `position` command. In a Chess960 suite `startpos` is the standard array
+/// with Chess960 castling, and FENs may use X-FEN or Shredder-FEN castling.
+fn parse_opening_line(
+    line: &str,
+    line_number: usize,
+    variant: Variant,
+) -> Result<Board, OpeningImportError> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let (position, moves) = match tokens.iter().position(|token| *token == "moves") {
+        Some(index) => (tokens[..index].join(" "), &tokens[index + 1..]),
+        None => (tokens.join(" "), &[][..]),
+    };
+    let mut board = match (position.as_str(), variant) {
+        ("startpos", Variant::Standard) => Board::startpos(),
+        // Scharnagl number 518 is the standard arrangement.
+        ("startpos", Variant::Chess960) => Board::chess960_startpos(518),
+        (fen, _) => board_from_fen(fen, variant).map_err(|err| OpeningImportError::InvalidFen {
+            line: line_number,
+            message: err.to_string(),
+        })?,
+    };
+    for mv in moves {
+        let illegal = || OpeningImportError::IllegalMove {
+            line: line_number,
+            mv: mv.to_string(),
+        };
+        let parsed = cozy_chess::util::parse_uci_move(&board, mv).map_err(|_| illegal())?;
+        board.try_play(parsed).map_err(|_| illegal())?;
+    }
+    Ok(board)
+}
+
+/// Each PGN game contributes the position reached after its movetext, so a
+/// book line such as `1. e4 e5 2. Nf3` yields the position to start from.
+fn extract_fens_from_pgn(text: &str, chess960: bool) -> Result<String, OpeningImportError> {
     let mut fens = Vec::new();
+    for (index, game) in split_pgn_games(text).into_iter().enumerate() {
+        let parsed =
+            parse_pgn_game(&game, chess960).map_err(|source| OpeningImportError::InvalidPgn {
+                game: index + 1,
+                source,
+            })?;
+        fens.push(parsed.board.to_string());
+    }
+    Ok(fens.join("\n"))
+}
+
+fn split_pgn_games(text: &str) -> Vec<String> {
+    let mut games = Vec::new();
+    let mut current = String::new();
+    let mut has_movetext = false;
     for line in text.lines() {
-        let line = line.trim();
-        if let Some(value) = line.strip_prefix("[FEN \"") {
-            if let Some(end) = value.find("\"]") {
-                fens.push(value[..end].to_string());
-            }
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && has_movetext {
+            games.push(std::mem::take(&mut current));
+            has_movetext = false;
         }
+        if !trimmed.is_empty() && !trimmed.starts_with('[') {
+            has_movetext = true;
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    if !current.trim().is_empty() {
+        games.push(current);
     }
-    fens.join("\n")
+    games
 }
 
 #[cfg(test)]
@@ -150,4 +283,176 @@ mod tests {
 
         assert_eq!(suite.positions.len(), 1);
     }
+
+    #[test]
+    fn pgn_import_plays_movetext_to_the_book_position() {
+        let suite = import_opening_suite(OpeningImportRequest {
+            registry_key: None,
+            name: "pgn".to_string(),
+            description: None,
+            variant: Variant::Standard,
+            text: "[Event \"Italian\"]\n\n1. e4 e5 2. Nf3 Nc6 3. Bc4 *\n\n[Event \"Sicilian\"]\n\n1. e4 c5 *\n".to_string(),
+            source_kind: OpeningSourceKind::PgnImport,
+            starter: false,
+        })
+        .unwrap();
+
+        let fens: Vec<_> = suite
+            .positions
+            .iter()
+            .map(|position| position.fen.as_str())
+            .collect();
+        assert_eq!(
+            fens,
+            [
+                "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3",
+                "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2",
+            ]
+        );
+    }
+
+    fn fen_list(text: &str) -> Result<OpeningSuite, OpeningImportError> {
+        fen_list_for(Variant::Standard, text)
+    }
+
+    fn fen_list_for(variant: Variant, text: &str) -> Result<OpeningSuite, OpeningImportError> {
+        import_opening_suite(OpeningImportRequest {
+            registry_key: None,
+            name: "lines".to_string(),
+            description: None,
+            variant,
+            text: text.to_string(),
+            source_kind: OpeningSourceKind::FenList,
+            starter: false,
+        })
+    }
+
+    #[test]
+    fn fen_lists_accept_bare_fens_and_move_lines() {
+        let suite = fen_list(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1\n\
+             startpos moves e2e4 e7e5\n\
+             rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1 moves c7c5\n",
+        )
+        .unwrap();
+
+        let positions: Vec<_> = suite
+            .positions
+            .iter()
+            .map(|position| (position.label.as_str(), position.fen.as_str()))
+            .collect();
+        assert_eq!(
+            positions,
+            [
+                (
+                    "Position 1",
+                    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+                ),
+                (
+                    "startpos moves e2e4 e7e5",
+                    "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2"
+                ),
+                (
+                    "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1 moves c7c5",
+                    "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn fen_lists_reject_bad_lines_with_their_line_number() {
+        assert!(matches!(
+            fen_list("startpos moves e2e4\nnot a fen"),
+            Err(OpeningImportError::InvalidFen { line: 2, .. })
+        ));
+        assert!(matches!(
+            fen_list("startpos moves e2e4 e2e4"),
+            Err(OpeningImportError::IllegalMove { line: 1, ref mv }) if mv == "e2e4"
+        ));
+        assert!(matches!(
+            fen_list("startpos movese2e4"),
+            Err(OpeningImportError::InvalidFen { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn chess960_fen_lists_store_shredder_castling() {
+        let suite = fen_list_for(
+            Variant::Chess960,
+            "startpos moves e2e4\n\
+             bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w KQkq - 2 9\n",
+        )
+        .unwrap();
+
+        let fens: Vec<_> = suite
+            .positions
+            .iter()
+            .map(|position| position.fen.as_str())
+            .collect();
+        assert_eq!(
+            fens,
+            [
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b HAha e3 0 1",
+                "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+            ]
+        );
+    }
+
+    #[test]
+    fn opening_scores_total_each_start_position() {
+        let suite = fen_list("startpos moves e2e4\nstartpos moves d2d4").unwrap();
+        let game = |position: &OpeningPosition, result| GameRecord {
+            id: Uuid::new_v4(),
+            tournament_id: Uuid::nil(),
+            match_id: Uuid::new_v4(),
+            pool_id: Uuid::nil(),
+            variant: Variant::Standard,
+            opening_id: Some(position.id),
+            white_version_id: Uuid::nil(),
+            black_version_id: Uuid::nil(),
+            result,
+            termination: crate::GameTermination::Unknown,
+            start_fen: position.fen.clone(),
+            pgn: String::new(),
+            moves_uci: Vec::new(),
+            white_time_left_ms: 0,
+            black_time_left_ms: 0,
+            logs: Vec::new(),
+            started_at: Utc::now(),
+            completed_at: Utc::now(),
+        };
+        let (e4, d4) = (&suite.positions[0], &suite.positions[1]);
+        let games = [
+            game(e4, GameResult::WhiteWin),
+            game(d4, GameResult::Draw),
+            game(e4, GameResult::Draw),
+            game(e4, GameResult::BlackWin),
+            game(e4, GameResult::WhiteWin),
+        ];
+
+        let scores = opening_scores(&games, &suite.positions);
+
+        let totals: Vec<_> = scores
+            .iter()
+            .map(|score| {
+                (
+                    score.label.as_deref(),
+                    score.games,
+                    score.white_wins,
+                    score.black_wins,
+                    score.draws,
+                    score.white_score,
+                )
+            })
+            .collect();
+        assert_eq!(
+            totals,
+            [
+                (Some("startpos moves e2e4"), 4, 2, 1, 1, 0.625),
+                (Some("startpos moves d2d4"), 1, 0, 0, 1, 0.5),
+            ]
+        );
+        assert_eq!(opening_scores(&games, &[])[0].label, None);
+    }
 }
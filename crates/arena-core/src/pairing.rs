@@ -1,6 +1,6 @@
 use uuid::Uuid;
 
-use crate::{TournamentKind, TournamentStatus};
+use crate::{OpeningPosition, TournamentKind, TournamentStatus};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Pairing {
@@ -95,8 +95,19 @@ pub fn build_ladder_pairings(participants_by_rating: &[Uuid], rounds: u16) -> Ve
     pairings
 }
 
+/// Opening for the `pair_index`-th game pair. Both games of a pair share the
+/// opening with colours swapped, and the suite wraps around once exhausted.
+pub fn opening_for_pair(openings: &[OpeningPosition], pair_index: u32) -> Option<&OpeningPosition> {
+    if openings.is_empty() {
+        return None;
+    }
+    openings.get(pair_index as usize % openings.len())
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::Variant;
+
     use super::*;
 
     #[test]
@@ -118,4 +129,39 @@ mod tests {
         assert_eq!(pairings.len(), 2);
         assert_ne!(pairings[0].white_version_id, pairings[1].white_version_id);
     }
+
+    #[test]
+    fn openings_cycle_once_per_pair_and_wrap_around() {
+        let suite_id = Uuid::new_v4();
+        let openings: Vec<_> = (0..3)
+            .map(|index| OpeningPosition {
+                id: Uuid::new_v4(),
+                suite_id,
+                label: format!("Position {}", index + 1),
+                fen: "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string(),
+                variant: Variant::Standard,
+            })
+            .collect();
+        let participants = vec![Uuid::new_v4(), Uuid::new_v4()];
+        let colour_pair = build_round_robin_pairings(&participants, 1, true);
+
+        let games: Vec<_> = (0..3)
+            .flat_map(|pair_index| {
+                let opening = opening_for_pair(&openings, pair_index).unwrap().id;
+                colour_pair
+                    .iter()
+                    .map(move |pairing| (opening, pairing.white_version_id))
+            })
+            .collect();
+
+        assert_eq!(games.len(), 6);
+        for (index, opening) in openings.iter().enumerate() {
+            let uses: Vec<_> = games.iter().filter(|(id, _)| *id == opening.id).collect();
+            assert_eq!(uses.len(), 2);
+            assert_ne!(uses[0].1, uses[1].1);
+            assert_eq!(games[index * 2].0, opening.id);
+        }
+        assert_eq!(opening_for_pair(&openings, 3).unwrap().id, openings[0].id);
+        assert!(opening_for_pair(&[], 0).is_none());
+    }
 }
@@ -55,6 +55,74 @@ pub fn update_leaderboard_entry(
     }
 }
 
+/// Elo difference implied by a W/D/L record, with a 95% confidence margin and
+/// the likelihood of superiority (LOS) as a probability in `0.0..=1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EloEstimate {
+    pub elo: f64,
+    pub error_margin: f64,
+    pub los: f64,
+}
+
+impl std::fmt::Display for EloEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:+.0} ±{:.0} Elo, LOS {:.0}%",
+            self.elo,
+            self.error_margin,
+            self.los * 100.0
+        )
+    }
+}
+
+/// Score-based Elo estimate; draws count as half a point. Returns `None`
+/// before any game has been played.
+pub fn elo_difference(wins: u32, draws: u32, losses: u32) -> Option<EloEstimate> {
+    let games = f64::from(wins + draws + losses);
+    if games == 0.0 {
+        return None;
+    }
+
+    let (wins, draws, losses) = (f64::from(wins), f64::from(draws), f64::from(losses));
+    let score = (wins + 0.5 * draws) / games;
+    let variance =
+        (wins * (1.0 - score).powi(2) + draws * (0.5 - score).powi(2) + losses * score.powi(2))
+            / games;
+    let standard_error = (variance / games).sqrt();
+    let low = elo_from_score(score - 1.96 * standard_error);
+    let high = elo_from_score(score + 1.96 * standard_error);
+    let decisive = wins + losses;
+    let los = if decisive == 0.0 {
+        0.5
+    } else {
+        0.5 * (1.0 + erf((wins - losses) / (2.0 * decisive).sqrt()))
+    };
+
+    Some(EloEstimate {
+        elo: elo_from_score(score),
+        error_margin: (high - low) / 2.0,
+        los,
+    })
+}
+
+fn elo_from_score(score: f64) -> f64 {
+    // A perfect or zero score has no finite Elo; clamp to keep the estimate usable.
+    let score = score.clamp(0.001, 0.999);
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+/// Abramowitz–Stegun 7.1.26 approximation, accurate to about 1.5e-7.
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let value = 1.0 - poly * (-x * x).exp();
+    value.copysign(x)
+}
+
 pub fn snapshot_from_entry(pool_id: Option<Uuid>, entry: &LeaderboardEntry) -> RatingSnapshot {
     RatingSnapshot {
         id: Uuid::new_v4(),
@@ -86,4 +154,23 @@ mod tests {
         let expected = expected_score(1500.0, 1500.0);
         assert!((expected - 0.5).abs() < 1e-9);
     }
+
+    #[test]
+    fn elo_difference_matches_known_records() {
+        let even = elo_difference(5, 0, 5).unwrap();
+        assert!(even.elo.abs() < 1e-9);
+        assert!((even.los - 0.5).abs() < 1e-9);
+        assert!(even.error_margin > 200.0);
+
+        let sweep = elo_difference(10, 0, 0).unwrap();
+        assert!(sweep.elo > 800.0);
+        assert!(sweep.los > 0.99);
+
+        let edge = elo_difference(60, 20, 40).unwrap();
+        assert!((edge.elo - 58.4).abs() < 0.5, "{edge}");
+        assert!((edge.los - 0.9772).abs() < 0.001, "{edge}");
+        assert_eq!(edge.to_string(), "+58 ±58 Elo, LOS 98%");
+
+        assert!(elo_difference(0, 0, 0).is_none());
+    }
 }
@@ -1,7 +1,9 @@
 mod models;
 mod rating;
 mod scheduler;
+mod sprt;
 
 pub use models::*;
 pub use rating::*;
 pub use scheduler::*;
+pub use sprt::*;
@@ -105,7 +105,10 @@ impl StabilityTracker {
         *self.pair_counts.entry(pair.engine_b).or_insert(0) += 1;
         self.total_pairs += 1;
 
-        if self.total_pairs % self.config.checkpoint_interval_pairs == 0 {
+        if self
+            .total_pairs
+            .is_multiple_of(self.config.checkpoint_interval_pairs)
+        {
             self.checkpoints.push(build_checkpoint(
                 self.total_pairs,
                 ratings,
@@ -41,38 +41,35 @@ pub fn default_rating_entry(engine_id: EngineId, config: PairEloConfig) -> Leade
     }
 }
 
+/// Rates the pair's games one at a time, each against the ratings the
+/// previous game left, so a long match moves ratings the same as the same
+/// games spread over several shorter ones.
 pub fn apply_match_pair(
     existing_entries: &HashMap<EngineId, LeaderboardEntry>,
     pair: &MatchPair,
     config: PairEloConfig,
 ) -> PairRatingUpdate {
-    let engine_a_current = existing_entries
+    let mut engine_a = existing_entries
         .get(&pair.engine_a)
         .cloned()
         .unwrap_or_else(|| default_rating_entry(pair.engine_a, config));
-    let engine_b_current = existing_entries
+    let mut engine_b = existing_entries
         .get(&pair.engine_b)
         .cloned()
         .unwrap_or_else(|| default_rating_entry(pair.engine_b, config));
 
-    let engine_a_score = pair.score_for_engine_a();
-    let expected_a = 2.0 * expected_score(engine_a_current.rating, engine_b_current.rating);
-    let rating_delta = config.k_factor * (engine_a_score - expected_a);
-
-    let mut engine_a = engine_a_current;
-    let mut engine_b = engine_b_current;
-    engine_a.rating += rating_delta;
-    engine_b.rating -= rating_delta;
-
     for game in &pair.games {
-        engine_a.games_played += 1;
-        engine_b.games_played += 1;
-
         let a_score = if game.white_version_id == pair.engine_a {
             game.result.white_score()
         } else {
             game.result.black_score()
         };
+        let rating_delta =
+            config.k_factor * (a_score - expected_score(engine_a.rating, engine_b.rating));
+        engine_a.rating += rating_delta;
+        engine_b.rating -= rating_delta;
+        engine_a.games_played += 1;
+        engine_b.games_played += 1;
 
         match a_score {
             score if (score - 1.0).abs() < f64::EPSILON => {
@@ -144,4 +141,65 @@ mod tests {
         assert_eq!(update.engine_b.games_played, 2);
         assert!(update.engine_a.rating > update.engine_b.rating);
     }
+
+    fn sweep(a: EngineId, b: EngineId, games: usize) -> MatchPair {
+        MatchPair {
+            engine_a: a,
+            engine_b: b,
+            games: (0..games)
+                .map(|index| {
+                    if index % 2 == 0 {
+                        game(a, b, GameResult::WhiteWin)
+                    } else {
+                        game(b, a, GameResult::BlackWin)
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn a_single_win_between_equals_moves_half_the_k_factor() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let config = PairEloConfig::default();
+
+        let update = apply_match_pair(&HashMap::new(), &sweep(a, b, 1), config);
+
+        assert!((update.engine_a.rating - config.k_factor / 2.0).abs() < 1e-9);
+        assert!((update.engine_b.rating + config.k_factor / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn a_sweep_gains_less_for_every_further_win() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let config = PairEloConfig::default();
+
+        let update = apply_match_pair(&HashMap::new(), &sweep(a, b, 10), config);
+
+        // Ten equal-rated wins would be 10 * K / 2; each later win is
+        // expected a little more, so it earns a little less.
+        let gain = update.engine_a.rating;
+        assert!(gain > 8.0 * config.k_factor / 2.0, "{gain}");
+        assert!(gain < 10.0 * config.k_factor / 2.0, "{gain}");
+        assert!((update.engine_a.rating + update.engine_b.rating).abs() < 1e-9);
+        assert_eq!((update.engine_a.wins, update.engine_b.losses), (10, 10));
+    }
+
+    #[test]
+    fn split_matches_rate_the_same_as_one_long_match() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let config = PairEloConfig::default();
+
+        let whole = apply_match_pair(&HashMap::new(), &sweep(a, b, 10), config);
+        let first = apply_match_pair(&HashMap::new(), &sweep(a, b, 5), config);
+        let entries = HashMap::from([(a, first.engine_a), (b, first.engine_b)]);
+        let split = apply_match_pair(&entries, &sweep(a, b, 5), config);
+
+        assert!((whole.engine_a.rating - split.engine_a.rating).abs() < 1e-9);
+        assert!((whole.engine_b.rating - split.engine_b.rating).abs() < 1e-9);
+        assert_eq!(whole.engine_a.games_played, split.engine_a.games_played);
+    }
 }
@@ -67,7 +67,7 @@ mod tests {
         let c = Uuid::new_v4();
         let mut scheduler = RoundRobinScheduler::new(&[a, b, c], 1);
 
-        let seen = vec![
+        let seen = [
             scheduler.next_pair().unwrap(),
             scheduler.next_pair().unwrap(),
             scheduler.next_pair().unwrap(),
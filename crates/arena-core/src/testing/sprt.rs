@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+use crate::expected_score;
+
+use super::{EngineId, MatchPair};
+
+/// Hypotheses and error rates for a sequential probability ratio test:
+/// H0 is "engine A is `elo0` stronger", H1 is "engine A is `elo1` stronger".
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SprtConfig {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+impl Default for SprtConfig {
+    fn default() -> Self {
+        Self {
+            elo0: 0.0,
+            elo1: 5.0,
+            alpha: 0.05,
+            beta: 0.05,
+        }
+    }
+}
+
+impl SprtConfig {
+    pub fn lower_bound(&self) -> f64 {
+        (self.beta / (1.0 - self.alpha)).ln()
+    }
+
+    pub fn upper_bound(&self) -> f64 {
+        ((1.0 - self.beta) / self.alpha).ln()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SprtResult {
+    /// `Some(true)` once H1 is accepted, `Some(false)` once H0 is accepted.
+    pub accepted: Option<bool>,
+    pub games_played: u32,
+    pub llr: f64,
+}
+
+/// Tracks engine A's W/D/L against engine B and evaluates the trinomial
+/// log-likelihood ratio after every game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SprtTracker {
+    config: SprtConfig,
+    engine_a: EngineId,
+    wins: u32,
+    draws: u32,
+    losses: u32,
+}
+
+impl SprtTracker {
+    pub fn new(config: SprtConfig, engine_a: EngineId) -> Self {
+        Self {
+            config,
+            engine_a,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        }
+    }
+
+    pub fn observe_score(&mut self, score: f64) {
+        match score {
+            score if (score - 1.0).abs() < f64::EPSILON => self.wins += 1,
+            score if (score - 0.5).abs() < f64::EPSILON => self.draws += 1,
+            _ => self.losses += 1,
+        }
+    }
+
+    pub fn observe_pair(&mut self, pair: &MatchPair) {
+        for game in &pair.games {
+            let score = if game.white_version_id == self.engine_a {
+                game.result.white_score()
+            } else if game.black_version_id == self.engine_a {
+                game.result.black_score()
+            } else {
+                continue;
+            };
+            self.observe_score(score);
+        }
+    }
+
+    pub fn llr(&self) -> f64 {
+        if self.wins + self.draws + self.losses == 0 {
+            return 0.0;
+        }
+
+        // Empty outcome buckets get half a game so the variance never collapses.
+        let regularize = |count: u32| if count == 0 { 0.5 } else { f64::from(count) };
+        let (wins, draws, losses) = (
+            regularize(self.wins),
+            regularize(self.draws),
+            regularize(self.losses),
+        );
+        let games = wins + draws + losses;
+        let score = (wins + 0.5 * draws) / games;
+        let variance = (wins + 0.25 * draws) / games - score * score;
+        let score0 = expected_score(self.config.elo0, 0.0);
+        let score1 = expected_score(self.config.elo1, 0.0);
+        games * (score1 - score0) * (2.0 * score - score0 - score1) / (2.0 * variance)
+    }
+
+    pub fn result(&self) -> SprtResult {
+        let llr = self.llr();
+        let accepted = if llr >= self.config.upper_bound() {
+            Some(true)
+        } else if llr <= self.config.lower_bound() {
+            Some(false)
+        } else {
+            None
+        };
+        SprtResult {
+            accepted,
+            games_played: self.wins + self.draws + self.losses,
+            llr,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn run_until_decided(score: impl Fn(u32) -> f64) -> SprtResult {
+        let mut tracker = SprtTracker::new(SprtConfig::default(), Uuid::new_v4());
+        for game in 0..100_000 {
+            tracker.observe_score(score(game));
+            let result = tracker.result();
+            if result.accepted.is_some() {
+                return result;
+            }
+        }
+        panic!("SPRT did not terminate");
+    }
+
+    #[test]
+    fn all_wins_accept_h1_quickly() {
+        let result = run_until_decided(|_| 1.0);
+
+        assert_eq!(result.accepted, Some(true));
+        assert!(result.games_played <= 30, "{result:?}");
+    }
+
+    #[test]
+    fn all_draws_accept_h0() {
+        let result = run_until_decided(|_| 0.5);
+
+        assert_eq!(result.accepted, Some(false));
+        assert!((100..400).contains(&result.games_played), "{result:?}");
+    }
+
+    #[test]
+    fn balanced_results_lean_towards_h0() {
+        let result = run_until_decided(|game| match game % 4 {
+            0 => 1.0,
+            1 => 0.0,
+            _ => 0.5,
+        });
+
+        assert_eq!(result.accepted, Some(false));
+        assert!(result.llr <= SprtConfig::default().lower_bound());
+    }
+}
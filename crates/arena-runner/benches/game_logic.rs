@@ -0,0 +1,33 @@
+//! Criterion benchmarks for the per-move game bookkeeping: `cargo bench -p
+//! arena-runner --bench game_logic`.
+
+use std::hint::black_box;
+
+use arena_runner::insufficient_material;
+use cozy_chess::Board;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+/// Guards the draw check run after every move of every arena game: the
+/// early return with pawns on the board, a bare-kings draw, and the
+/// same-coloured-bishops scan.
+fn material(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insufficient_material");
+    group.throughput(Throughput::Elements(1));
+    for (name, fen) in [
+        (
+            "startpos",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        ),
+        ("bare_kings", "8/8/4k3/8/8/3K4/8/8 w - - 0 1"),
+        ("same_colour_bishops", "4k3/8/8/2b5/8/2B5/8/4K3 w - - 0 1"),
+    ] {
+        let board: Board = fen.parse().expect("valid benchmark FEN");
+        group.bench_with_input(BenchmarkId::from_parameter(name), &board, |b, board| {
+            b.iter(|| insufficient_material(black_box(board)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, material);
+criterion_main!(benches);
@@ -1,9 +1,78 @@
-use anyhow::Result;
-use arena_core::{AgentVersion, GameLogEntry, Variant};
+use anyhow::{Result, bail};
+use arena_core::{AgentVersion, AnalysisLine, EngineScore, GameLogEntry, Variant};
 use async_trait::async_trait;
-use cozy_chess::Board;
+use cozy_chess::{Board, Color};
+use tokio::sync::watch;
 
-use crate::uci::UciAgentAdapter;
+use crate::{game_logic::calculate_move_budget, uci::UciAgentAdapter};
+
+/// Budget for a single engine search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchLimit {
+    MoveTime(u64),
+    /// Both remaining clocks, so the engine budgets its own time.
+    Clock {
+        white_ms: u64,
+        black_ms: u64,
+        increment_ms: u64,
+    },
+    Depth(u32),
+    Nodes(u64),
+    /// Search until stopped, for analysis.
+    Infinite,
+}
+
+impl SearchLimit {
+    /// Fixed movetime for adapters that cannot manage a clock themselves.
+    pub fn movetime_ms(self, side: Color) -> Option<u64> {
+        match self {
+            Self::MoveTime(movetime_ms) => Some(movetime_ms),
+            Self::Clock {
+                white_ms,
+                black_ms,
+                increment_ms,
+            } => Some(calculate_move_budget(
+                if side == Color::White {
+                    white_ms
+                } else {
+                    black_ms
+                },
+                increment_ms,
+            )),
+            Self::Depth(_) | Self::Nodes(_) | Self::Infinite => None,
+        }
+    }
+}
+
+/// The move an engine chose and the score it reported for it, from the side
+/// to move's point of view.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchOutcome {
+    pub best_move: String,
+    pub score: Option<EngineScore>,
+    /// Depth, selective depth and node count from the engine's last `info`
+    /// line reporting them; the depth is the last iteration the engine
+    /// finished, and the selective depth the deepest ply it reached.
+    pub depth: Option<u32>,
+    pub seldepth: Option<u32>,
+    pub nodes: Option<u64>,
+}
+
+/// What an engine has reported so far in a running search, from the side to
+/// move's point of view.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchProgress {
+    pub depth: Option<u32>,
+    pub seldepth: Option<u32>,
+    pub score: Option<EngineScore>,
+    pub nodes: Option<u64>,
+    pub nps: Option<u64>,
+    /// Principal variation in UCI notation.
+    pub pv: Vec<String>,
+    /// The latest line reported for each `multipv` rank, best first. Empty
+    /// until the engine reports a line with both a score and moves.
+    pub lines: Vec<AnalysisLine>,
+}
 
 /// Low-level engine adapter used by the server-owned match runtime.
 ///
@@ -22,6 +91,48 @@ pub trait AgentAdapter: Send {
         movetime_ms: u64,
         logs: &mut Vec<GameLogEntry>,
     ) -> Result<String>;
+    /// Search the position for `movetime_ms` and return the engine's score
+    /// from White's point of view, if it reported one.
+    async fn analyse(
+        &mut self,
+        _board: &Board,
+        _start_fen: &str,
+        _moves: &[String],
+        _movetime_ms: u64,
+        _logs: &mut Vec<GameLogEntry>,
+    ) -> Result<Option<EngineScore>> {
+        Ok(None)
+    }
+    /// Search under `limit` and return the engine's move with its own score.
+    async fn search(
+        &mut self,
+        board: &Board,
+        start_fen: &str,
+        moves: &[String],
+        limit: SearchLimit,
+        logs: &mut Vec<GameLogEntry>,
+    ) -> Result<SearchOutcome> {
+        let Some(movetime_ms) = limit.movetime_ms(board.side_to_move()) else {
+            bail!("this adapter only supports timed searches");
+        };
+        let best_move = self
+            .choose_move(board, start_fen, moves, movetime_ms, logs)
+            .await?;
+        Ok(SearchOutcome {
+            best_move,
+            ..SearchOutcome::default()
+        })
+    }
+
+    /// Publish the [`SearchProgress`] of later `search` calls to `progress`,
+    /// replacing any earlier sender. Adapters without progress reports ignore it.
+    fn watch_progress(&mut self, _progress: watch::Sender<SearchProgress>) {}
+
+    /// Abort an in-flight `choose_move` whose future was dropped, e.g. when the
+    /// runtime's clock fired first, so a late reply cannot leak into later turns.
+    async fn stop(&mut self, _logs: &mut Vec<GameLogEntry>) -> Result<()> {
+        Ok(())
+    }
     async fn shutdown(&mut self, logs: &mut Vec<GameLogEntry>) -> Result<()>;
 }
 
@@ -30,5 +141,21 @@ pub trait AgentAdapter: Send {
 /// This factory starts no authoritative match runtime; callers are responsible
 /// for owning lifecycle and game semantics.
 pub fn build_adapter(version: AgentVersion) -> Box<dyn AgentAdapter> {
-    Box::new(UciAgentAdapter::new(version))
+    build_adapter_with_options(version, Vec::new())
+}
+
+/// [`build_adapter`] for an engine that gets these UCI options, as `name` and
+/// `value` pairs, before its first game. They are sent after the version's
+/// own `uci_options`, so they win where both set the same option.
+pub fn build_adapter_with_options(
+    version: AgentVersion,
+    options: Vec<(String, String)>,
+) -> Box<dyn AgentAdapter> {
+    let options = version
+        .uci_options
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .chain(options)
+        .collect();
+    Box::new(UciAgentAdapter::new(version, options))
 }
@@ -1,7 +1,12 @@
 use std::collections::HashMap;
 
-use arena_core::{GameResult, GameTermination, OpeningPosition, Variant};
-use cozy_chess::{Board, Color, GameStatus, Piece};
+use anyhow::{Result, anyhow};
+
+use arena_core::{
+    AdjudicationConfig, EngineScore, GameResult, GameTermination, OpeningPosition, Variant,
+    move_to_san,
+};
+use cozy_chess::{BitBoard, Board, Color, GameStatus, Move, Piece, util};
 
 pub fn starting_board(
     variant: Variant,
@@ -27,6 +32,116 @@ pub fn fen_for_variant(board: &Board, variant: Variant) -> String {
     }
 }
 
+/// A game from its start position: the position now, the moves that led to
+/// it and how often each position occurred, so every game loop applies the
+/// same rules for how a game ends. Moves can be taken back exactly.
+#[derive(Debug, Clone)]
+pub struct Game {
+    board: Board,
+    history: Vec<(Board, Move)>,
+    repetitions: HashMap<u64, u8>,
+}
+
+impl Game {
+    pub fn new(start: Board) -> Self {
+        let repetitions = HashMap::from([(start.hash_without_ep(), 1)]);
+        Self {
+            board: start,
+            history: Vec::new(),
+            repetitions,
+        }
+    }
+
+    pub fn startpos() -> Self {
+        Self::new(Board::startpos())
+    }
+
+    pub fn from_fen(fen: &str, chess960: bool) -> Result<Self> {
+        Board::from_fen(fen, chess960)
+            .map(Self::new)
+            .map_err(|err| anyhow!("invalid FEN {fen}: {err:?}"))
+    }
+
+    /// The current position.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn start(&self) -> &Board {
+        self.history.first().map_or(&self.board, |(board, _)| board)
+    }
+
+    pub fn ply_count(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> + '_ {
+        self.history.iter().map(|&(_, mv)| mv)
+    }
+
+    /// The moves in UCI notation, castling written the way each position's
+    /// castling mode expects.
+    pub fn moves_uci(&self) -> Vec<String> {
+        self.history
+            .iter()
+            .map(|(board, mv)| util::display_uci_move(board, *mv).to_string())
+            .collect()
+    }
+
+    /// How often each position, keyed by `Board::hash_without_ep`, occurred.
+    pub fn repetitions(&self) -> &HashMap<u64, u8> {
+        &self.repetitions
+    }
+
+    /// How often the current position occurred, this time included.
+    pub fn occurrences(&self) -> u8 {
+        self.repetitions[&self.board.hash_without_ep()]
+    }
+
+    pub fn play(&mut self, mv: Move) -> Result<()> {
+        let before = self.board.clone();
+        self.board
+            .try_play(mv)
+            .map_err(|_| anyhow!("illegal move {mv} in {before}"))?;
+        self.history.push((before, mv));
+        *self
+            .repetitions
+            .entry(self.board.hash_without_ep())
+            .or_insert(0) += 1;
+        Ok(())
+    }
+
+    pub fn play_uci(&mut self, move_uci: &str) -> Result<Move> {
+        let mv = util::parse_uci_move(&self.board, move_uci)
+            .map_err(|err| anyhow!("invalid move {move_uci}: {err:?}"))?;
+        self.play(mv)?;
+        Ok(mv)
+    }
+
+    /// Takes back the last move, restoring the position before it exactly.
+    pub fn undo(&mut self) -> Option<Move> {
+        let (before, mv) = self.history.pop()?;
+        let hash = self.board.hash_without_ep();
+        if let Some(count) = self.repetitions.get_mut(&hash) {
+            *count -= 1;
+            if *count == 0 {
+                self.repetitions.remove(&hash);
+            }
+        }
+        self.board = before;
+        Some(mv)
+    }
+
+    /// How the game ended, if it has: checkmate, the fifty-move rule,
+    /// threefold repetition, insufficient material, then stalemate.
+    pub fn outcome(&self) -> Option<(GameResult, GameTermination)> {
+        classify_position(&self.board, &self.repetitions).or_else(|| {
+            (self.board.status() != GameStatus::Ongoing)
+                .then(|| classify_terminal_board(&self.board))
+        })
+    }
+}
+
 pub fn calculate_move_budget(remaining_ms: u64, increment_ms: u64) -> u64 {
     let base = (remaining_ms / 30).max(50);
     base.saturating_add(increment_ms).min(remaining_ms.max(50))
@@ -36,6 +151,11 @@ pub fn classify_position(
     board: &Board,
     repetitions: &HashMap<u64, u8>,
 ) -> Option<(GameResult, GameTermination)> {
+    // Checkmate outranks every draw claim, including a mate on the 100th halfmove.
+    if board.status() == GameStatus::Won {
+        return Some(classify_terminal_board(board));
+    }
+
     if board.halfmove_clock() >= 100 {
         return Some((GameResult::Draw, GameTermination::FiftyMoveRule));
     }
@@ -70,18 +190,74 @@ pub fn classify_terminal_board(board: &Board) -> (GameResult, GameTermination) {
     }
 }
 
+/// Score adjudication for a game whose plies so far were reported with
+/// `white_scores`, oldest first and from White's point of view, and whose
+/// next move is move `fullmove_number`. Every score in the window must be
+/// known, so a ply without a reported score restarts both streaks.
+pub fn adjudicate(
+    config: &AdjudicationConfig,
+    white_scores: &[Option<EngineScore>],
+    fullmove_number: u16,
+) -> Option<(GameResult, GameTermination)> {
+    if let Some(resign) = config.resign
+        && let Some(scores) = recent_centipawns(white_scores, resign.move_count)
+    {
+        if scores.iter().all(|score| *score >= resign.score_cp) {
+            return Some((GameResult::WhiteWin, GameTermination::Adjudication));
+        }
+        if scores.iter().all(|score| *score <= -resign.score_cp) {
+            return Some((GameResult::BlackWin, GameTermination::Adjudication));
+        }
+    }
+
+    if let Some(draw) = config.draw
+        && fullmove_number >= draw.min_move_number
+        && let Some(scores) = recent_centipawns(white_scores, draw.move_count)
+        && scores.iter().all(|score| score.abs() <= draw.score_cp)
+    {
+        return Some((GameResult::Draw, GameTermination::Adjudication));
+    }
+
+    None
+}
+
+/// The last `move_count` moves of both sides as centipawns, with mates
+/// beyond any resign threshold.
+fn recent_centipawns(white_scores: &[Option<EngineScore>], move_count: u16) -> Option<Vec<i32>> {
+    let plies = usize::from(move_count) * 2;
+    if plies == 0 || white_scores.len() < plies {
+        return None;
+    }
+    white_scores[white_scores.len() - plies..]
+        .iter()
+        .map(|score| {
+            score.map(|score| match score {
+                EngineScore::Centipawns(value) => value,
+                EngineScore::Mate(moves) if moves > 0 => 100_000,
+                EngineScore::Mate(_) => -100_000,
+            })
+        })
+        .collect()
+}
+
 pub fn insufficient_material(board: &Board) -> bool {
     let major_minor = [Piece::Queen, Piece::Rook, Piece::Pawn];
     if major_minor
         .iter()
-        .any(|piece| (board.pieces(*piece)).len() > 0)
+        .any(|piece| !board.pieces(*piece).is_empty())
     {
         return false;
     }
 
-    let bishops = (board.pieces(Piece::Bishop)).len();
+    let bishops = board.pieces(Piece::Bishop);
     let knights = (board.pieces(Piece::Knight)).len();
-    bishops + knights <= 1
+    if bishops.len() + knights <= 1 {
+        return true;
+    }
+
+    // Any number of bishops confined to one square colour cannot force mate.
+    knights == 0
+        && (bishops.is_subset(BitBoard::DARK_SQUARES) || bishops.is_subset(BitBoard::LIGHT_SQUARES))
 }
 
 pub fn pgn_from_moves(
@@ -90,25 +266,51 @@ pub fn pgn_from_moves(
     start_fen: &str,
     moves: &[String],
     result: GameResult,
+    termination: GameTermination,
 ) -> String {
     let result_token = match result {
         GameResult::WhiteWin => "1-0",
         GameResult::BlackWin => "0-1",
         GameResult::Draw => "1/2-1/2",
     };
+    let mut board = Board::from_fen(start_fen, variant.is_chess960()).ok();
+    // Number moves from the start position, so an opening with Black to
+    // move begins with `N...` as the FEN tag requires.
+    let (first_move_number, black_first) = board.as_ref().map_or((1, false), |board| {
+        (
+            board.fullmove_number() as usize,
+            board.side_to_move() == Color::Black,
+        )
+    });
     let mut movetext = String::new();
     for (index, mv) in moves.iter().enumerate() {
-        if index % 2 == 0 {
-            let move_number = index / 2 + 1;
+        let ply = index + usize::from(black_first);
+        let move_number = first_move_number + ply / 2;
+        if ply % 2 == 0 {
             movetext.push_str(&format!("{move_number}. "));
+        } else if index == 0 {
+            movetext.push_str(&format!("{move_number}... "));
+        }
+        // Fall back to the raw UCI token if the history cannot be replayed.
+        let san = board.as_mut().and_then(|board| {
+            let parsed = cozy_chess::util::parse_uci_move(board, mv).ok()?;
+            let san = move_to_san(board, parsed);
+            board.try_play(parsed).ok()?;
+            Some(san)
+        });
+        if san.is_none() {
+            board = None;
         }
-        movetext.push_str(mv);
+        movetext.push_str(san.as_deref().unwrap_or(mv));
         movetext.push(' ');
     }
     movetext.push_str(result_token);
 
+    let termination_tag = termination_text(result, termination)
+        .map(|text| format!("[Termination \"{text}\"]\n"))
+        .unwrap_or_default();
     format!(
-        "[Event \"{}\"]\n[Site \"Rust Chess Arena\"]\n[Variant \"{}\"]\n[FEN \"{}\"]\n[Result \"{}\"]\n\n{}",
+        "[Event \"{}\"]\n[Site \"Rust Chess Arena\"]\n[Variant \"{}\"]\n[SetUp \"1\"]\n[FEN \"{}\"]\n[Result \"{}\"]\n{}\n{}",
         event_name,
         match variant {
             Variant::Standard => "Standard",
@@ -116,6 +318,284 @@ pub fn pgn_from_moves(
         },
         start_fen,
         result_token,
+        termination_tag,
         movetext.trim()
     )
 }
+
+/// Human-readable reason the game ended, such as "Draw by threefold
+/// repetition", or `None` when the reason was not recorded.
+pub fn termination_text(result: GameResult, termination: GameTermination) -> Option<String> {
+    let reason = match termination {
+        GameTermination::Checkmate => "checkmate",
+        GameTermination::Stalemate => "stalemate",
+        GameTermination::FiftyMoveRule => "the fifty-move rule",
+        GameTermination::Repetition => "threefold repetition",
+        GameTermination::InsufficientMaterial => "insufficient material",
+        GameTermination::Timeout => "timeout",
+        GameTermination::Resignation => "resignation",
+        GameTermination::IllegalMove => "illegal move",
+        GameTermination::MoveLimit => "move limit",
+        GameTermination::Adjudication => "adjudication",
+        GameTermination::EngineFailure => "engine failure",
+        GameTermination::Unknown => return None,
+    };
+    let outcome = match result {
+        GameResult::WhiteWin => "White wins",
+        GameResult::BlackWin => "Black wins",
+        GameResult::Draw => "Draw",
+    };
+    Some(format!("{outcome} by {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use arena_core::{DrawAdjudication, ResignAdjudication};
+
+    use super::*;
+
+    #[test]
+    fn checkmate_on_the_hundredth_halfmove_is_not_a_fifty_move_draw() {
+        let board: Board = "R5k1/5ppp/8/8/8/8/8/6K1 b - - 100 80".parse().unwrap();
+
+        assert_eq!(
+            classify_position(&board, &HashMap::new()),
+            Some((GameResult::WhiteWin, GameTermination::Checkmate))
+        );
+    }
+
+    #[test]
+    fn third_occurrence_is_a_repetition_draw() {
+        let board = Board::default();
+        let repetitions = HashMap::from([(board.hash_without_ep(), 3)]);
+
+        assert_eq!(
+            classify_position(&board, &repetitions),
+            Some((GameResult::Draw, GameTermination::Repetition))
+        );
+    }
+
+    fn scripted(fen: &str, moves: &[&str]) -> Game {
+        let mut game = Game::from_fen(fen, false).unwrap();
+        for (index, uci) in moves.iter().enumerate() {
+            assert_eq!(game.outcome(), None, "ended before move {index} in {fen}");
+            game.play_uci(uci).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn scripted_games_end_the_way_the_rules_say() {
+        let startpos = Board::default().to_string();
+        let knight_dance = ["g1f3", "g8f6", "f3g1", "f6g8"].repeat(2);
+        let cases = [
+            (
+                startpos.as_str(),
+                &["f2f3", "e7e5", "g2g4", "d8h4"][..],
+                (GameResult::BlackWin, GameTermination::Checkmate),
+            ),
+            (
+                "7k/8/8/6Q1/8/8/8/K7 w - - 0 1",
+                &["g5g6"],
+                (GameResult::Draw, GameTermination::Stalemate),
+            ),
+            (
+                "4k3/8/8/8/8/8/R7/4K3 w - - 99 80",
+                &["a2a3"],
+                (GameResult::Draw, GameTermination::FiftyMoveRule),
+            ),
+            (
+                startpos.as_str(),
+                &knight_dance,
+                (GameResult::Draw, GameTermination::Repetition),
+            ),
+            (
+                "4k3/8/8/8/8/8/3r4/3BK3 w - - 0 1",
+                &["e1d2"],
+                (GameResult::Draw, GameTermination::InsufficientMaterial),
+            ),
+        ];
+
+        for (fen, moves, expected) in cases {
+            assert_eq!(scripted(fen, moves).outcome(), Some(expected), "{fen}");
+        }
+    }
+
+    #[test]
+    fn undo_restores_every_earlier_position_exactly() {
+        let fen = "r3k2r/8/8/3pP3/8/8/1p6/R3K2R w KQkq d6 0 1";
+        let mut game = Game::from_fen(fen, false).unwrap();
+        let mut boards = vec![game.board().clone()];
+        // En passant, both castlings and a capturing promotion.
+        for uci in ["e5d6", "e8c8", "e1g1", "b2a1q"] {
+            game.play_uci(uci).unwrap();
+            boards.push(game.board().clone());
+        }
+        assert_eq!(game.moves_uci(), ["e5d6", "e8c8", "e1g1", "b2a1q"]);
+
+        while let Some(board) = boards.pop() {
+            assert_eq!(game.board(), &board);
+            assert_eq!(game.board().to_string(), board.to_string());
+            assert_eq!(game.ply_count(), boards.len());
+            game.undo();
+        }
+        assert_eq!(game.undo(), None);
+        assert_eq!(game.start().to_string(), fen);
+        assert_eq!(
+            game.repetitions(),
+            &HashMap::from([(game.board().hash_without_ep(), 1)])
+        );
+    }
+
+    #[test]
+    fn pgn_movetext_uses_san() {
+        let moves = ["e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "g8f6", "e1g1"].map(String::from);
+        let pgn = pgn_from_moves(
+            "test",
+            Variant::Standard,
+            &Board::default().to_string(),
+            &moves,
+            GameResult::Draw,
+            GameTermination::Unknown,
+        );
+
+        assert!(
+            pgn.ends_with("1. e4 e5 2. Nf3 Nc6 3. Bc4 Nf6 4. O-O 1/2-1/2"),
+            "{pgn}"
+        );
+        assert!(!pgn.contains("[Termination"), "{pgn}");
+    }
+
+    #[test]
+    fn pgn_from_an_opening_tags_the_position_and_numbers_from_it() {
+        let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1";
+        let moves = ["c7c5", "g1f3", "d7d6"].map(String::from);
+        let pgn = pgn_from_moves(
+            "test",
+            Variant::Standard,
+            fen,
+            &moves,
+            GameResult::Draw,
+            GameTermination::Unknown,
+        );
+
+        assert!(
+            pgn.contains(&format!("[SetUp \"1\"]\n[FEN \"{fen}\"]")),
+            "{pgn}"
+        );
+        assert!(pgn.ends_with("1... c5 2. Nf3 d6 1/2-1/2"), "{pgn}");
+    }
+
+    #[test]
+    fn pgn_records_why_the_game_ended() {
+        let moves: Vec<String> = ["g1f3", "g8f6", "f3g1", "f6g8"]
+            .repeat(2)
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let pgn = pgn_from_moves(
+            "test",
+            Variant::Standard,
+            &Board::default().to_string(),
+            &moves,
+            GameResult::Draw,
+            GameTermination::Repetition,
+        );
+
+        assert!(
+            pgn.contains(
+                "[Result \"1/2-1/2\"]\n[Termination \"Draw by threefold repetition\"]\n\n1. Nf3"
+            ),
+            "{pgn}"
+        );
+    }
+
+    #[test]
+    fn each_draw_reason_is_classified_from_its_position() {
+        let fifty_moves: Board = "4k3/8/8/8/8/8/R7/4K3 w - - 100 90".parse().unwrap();
+        let bare_kings: Board = "4k3/8/8/8/8/8/8/4K3 w - - 0 1".parse().unwrap();
+        let stalemate: Board = "7k/5Q2/6K1/8/8/8/8/8 b - - 0 1".parse().unwrap();
+
+        assert_eq!(
+            classify_position(&fifty_moves, &HashMap::new()),
+            Some((GameResult::Draw, GameTermination::FiftyMoveRule))
+        );
+        assert_eq!(
+            classify_position(&bare_kings, &HashMap::new()),
+            Some((GameResult::Draw, GameTermination::InsufficientMaterial))
+        );
+        assert_eq!(classify_position(&stalemate, &HashMap::new()), None);
+        assert_eq!(
+            classify_terminal_board(&stalemate),
+            (GameResult::Draw, GameTermination::Stalemate)
+        );
+        assert_eq!(
+            termination_text(GameResult::Draw, GameTermination::FiftyMoveRule).as_deref(),
+            Some("Draw by the fifty-move rule")
+        );
+    }
+
+    #[test]
+    fn move_budget_is_a_thirtieth_of_the_clock_plus_increment_capped_by_the_clock() {
+        assert_eq!(calculate_move_budget(60_000, 0), 2_000);
+        assert_eq!(calculate_move_budget(60_000, 1_000), 3_000);
+        assert_eq!(calculate_move_budget(600, 1_000), 600);
+        assert_eq!(calculate_move_budget(0, 0), 50);
+    }
+
+    #[test]
+    fn resign_adjudication_needs_both_engines_to_agree_for_the_whole_window() {
+        let config = AdjudicationConfig {
+            resign: Some(ResignAdjudication::default()),
+            draw: None,
+        };
+        let winning = Some(EngineScore::Centipawns(-900));
+        let mut scores = vec![winning; 7];
+
+        assert_eq!(adjudicate(&config, &scores, 20), None);
+        scores.push(Some(EngineScore::Mate(-3)));
+        assert_eq!(
+            adjudicate(&config, &scores, 20),
+            Some((GameResult::BlackWin, GameTermination::Adjudication))
+        );
+        scores[1] = None;
+        assert_eq!(adjudicate(&config, &scores, 20), None);
+        scores.extend([winning; 2]);
+        assert_eq!(
+            adjudicate(&config, &scores, 21),
+            Some((GameResult::BlackWin, GameTermination::Adjudication))
+        );
+        scores.push(Some(EngineScore::Centipawns(-300)));
+        assert_eq!(adjudicate(&config, &scores, 21), None);
+    }
+
+    #[test]
+    fn draw_adjudication_waits_for_the_minimum_move_number() {
+        let config = AdjudicationConfig {
+            resign: Some(ResignAdjudication::default()),
+            draw: Some(DrawAdjudication::default()),
+        };
+        let scores = vec![Some(EngineScore::Centipawns(5)); 24];
+
+        assert_eq!(adjudicate(&config, &scores, 39), None);
+        assert_eq!(
+            adjudicate(&config, &scores, 40),
+            Some((GameResult::Draw, GameTermination::Adjudication))
+        );
+        assert_eq!(
+            adjudicate(&AdjudicationConfig::default(), &scores, 40),
+            None
+        );
+    }
+
+    #[test]
+    fn same_coloured_bishops_are_insufficient_material() {
+        let same_colour: Board = "4k3/8/8/2b5/8/2B5/8/4K3 w - - 0 1".parse().unwrap();
+        let opposite_colour: Board = "4k3/8/8/3b4/8/2B5/8/4K3 w - - 0 1".parse().unwrap();
+        let bishop_and_knight: Board = "4k3/8/8/8/8/8/2BN4/4K3 w - - 0 1".parse().unwrap();
+
+        assert!(insufficient_material(&same_colour));
+        assert!(!insufficient_material(&opposite_colour));
+        assert!(!insufficient_material(&bishop_and_knight));
+    }
+}
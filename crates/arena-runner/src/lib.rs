@@ -7,10 +7,13 @@ pub mod adapter;
 mod game_logic;
 mod uci;
 
-pub use adapter::{AgentAdapter, build_adapter};
+pub use adapter::{
+    AgentAdapter, SearchLimit, SearchOutcome, SearchProgress, build_adapter,
+    build_adapter_with_options,
+};
 pub use game_logic::{
-    calculate_move_budget, classify_position, classify_terminal_board, fen_for_variant,
-    insufficient_material, pgn_from_moves, starting_board,
+    Game, adjudicate, calculate_move_budget, classify_position, classify_terminal_board,
+    fen_for_variant, insufficient_material, pgn_from_moves, starting_board, termination_text,
 };
 
 #[cfg(test)]
@@ -1,28 +1,39 @@
 use std::{path::PathBuf, process::Stdio, time::Duration};
 
 use anyhow::{Context, Result, anyhow};
-use arena_core::{AgentVersion, GameLogEntry, Variant};
+use arena_core::{AgentVersion, AnalysisLine, EngineScore, GameLogEntry, Variant};
 use async_trait::async_trait;
-use cozy_chess::Board;
+use cozy_chess::{Board, Color};
 use tokio::{
     io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
     process::{Child, ChildStdin, ChildStdout, Command},
+    sync::watch,
     time::timeout,
 };
 use tracing::warn;
 
-use crate::adapter::AgentAdapter;
+use crate::adapter::{AgentAdapter, SearchLimit, SearchOutcome, SearchProgress};
+
+/// How long a stopped engine gets to flush its pending `bestmove`.
+const STOP_GRACE: Duration = Duration::from_secs(1);
+/// How long a depth- or node-limited search may stay silent before the engine is presumed hung.
+const UNTIMED_SEARCH_TIMEOUT: Duration = Duration::from_secs(300);
 
 pub(crate) struct UciAgentAdapter {
     version: AgentVersion,
+    /// `setoption` name and value pairs sent during the handshake.
+    options: Vec<(String, String)>,
     session: Option<UciSession>,
+    progress: Option<watch::Sender<SearchProgress>>,
 }
 
 impl UciAgentAdapter {
-    pub(crate) fn new(version: AgentVersion) -> Self {
+    pub(crate) fn new(version: AgentVersion, options: Vec<(String, String)>) -> Self {
         Self {
             version,
+            options,
             session: None,
+            progress: None,
         }
     }
 }
@@ -31,7 +42,7 @@ impl UciAgentAdapter {
 impl AgentAdapter for UciAgentAdapter {
     async fn prepare(&mut self, variant: Variant, logs: &mut Vec<GameLogEntry>) -> Result<()> {
         let mut session = UciSession::spawn(&self.version).await?;
-        session.handshake(variant, logs).await?;
+        session.handshake(variant, &self.options, logs).await?;
         self.session = Some(session);
         Ok(())
     }
@@ -55,7 +66,72 @@ impl AgentAdapter for UciAgentAdapter {
         self.session
             .as_mut()
             .ok_or_else(|| anyhow!("session not prepared"))?
-            .best_move(board, start_fen, moves, movetime_ms, logs)
+            .search(
+                board,
+                start_fen,
+                moves,
+                SearchLimit::MoveTime(movetime_ms),
+                None,
+                logs,
+            )
+            .await
+            .map(|outcome| outcome.best_move)
+    }
+
+    async fn analyse(
+        &mut self,
+        board: &Board,
+        start_fen: &str,
+        moves: &[String],
+        movetime_ms: u64,
+        logs: &mut Vec<GameLogEntry>,
+    ) -> Result<Option<EngineScore>> {
+        let SearchOutcome { score, .. } = self
+            .session
+            .as_mut()
+            .ok_or_else(|| anyhow!("session not prepared"))?
+            .search(
+                board,
+                start_fen,
+                moves,
+                SearchLimit::MoveTime(movetime_ms),
+                None,
+                logs,
+            )
+            .await?;
+        Ok(score.map(|score| {
+            if board.side_to_move() == Color::White {
+                score
+            } else {
+                score.flipped()
+            }
+        }))
+    }
+
+    async fn search(
+        &mut self,
+        board: &Board,
+        start_fen: &str,
+        moves: &[String],
+        limit: SearchLimit,
+        logs: &mut Vec<GameLogEntry>,
+    ) -> Result<SearchOutcome> {
+        self.session
+            .as_mut()
+            .ok_or_else(|| anyhow!("session not prepared"))?
+            .search(board, start_fen, moves, limit, self.progress.as_ref(), logs)
+            .await
+    }
+
+    fn watch_progress(&mut self, progress: watch::Sender<SearchProgress>) {
+        self.progress = Some(progress);
+    }
+
+    async fn stop(&mut self, logs: &mut Vec<GameLogEntry>) -> Result<()> {
+        self.session
+            .as_mut()
+            .ok_or_else(|| anyhow!("session not prepared"))?
+            .stop(logs)
             .await
     }
 
@@ -99,13 +175,22 @@ impl UciSession {
         })
     }
 
-    async fn handshake(&mut self, variant: Variant, logs: &mut Vec<GameLogEntry>) -> Result<()> {
+    async fn handshake(
+        &mut self,
+        variant: Variant,
+        options: &[(String, String)],
+        logs: &mut Vec<GameLogEntry>,
+    ) -> Result<()> {
         self.send("uci", logs).await?;
         self.read_until("uciok", logs).await?;
         if variant.is_chess960() {
             self.send("setoption name UCI_Chess960 value true", logs)
                 .await?;
         }
+        for (name, value) in options {
+            self.send(&format!("setoption name {name} value {value}"), logs)
+                .await?;
+        }
         self.send("isready", logs).await?;
         self.read_until("readyok", logs).await?;
         Ok(())
@@ -118,30 +203,55 @@ impl UciSession {
         Ok(())
     }
 
-    async fn best_move(
+    /// Run `go` under `limit` and return the `bestmove` together with the last
+    /// score, depth and node count the engine reported, with the score from
+    /// the side to move's point of view. Each `info` line that changes them
+    /// is also sent to `progress`.
+    async fn search(
         &mut self,
-        _board: &Board,
+        board: &Board,
         start_fen: &str,
         moves: &[String],
-        movetime_ms: u64,
+        limit: SearchLimit,
+        progress: Option<&watch::Sender<SearchProgress>>,
         logs: &mut Vec<GameLogEntry>,
-    ) -> Result<String> {
+    ) -> Result<SearchOutcome> {
         let position = if moves.is_empty() {
             format!("position fen {start_fen}")
         } else {
             format!("position fen {start_fen} moves {}", moves.join(" "))
         };
         self.send(&position, logs).await?;
-        self.send(&format!("go movetime {movetime_ms}"), logs)
-            .await?;
+        let (go, wait) = go_command(limit, board.side_to_move());
+        self.send(&go, logs).await?;
 
+        let mut reported = SearchProgress::default();
         loop {
-            let line = self
-                .read_line(Duration::from_millis(movetime_ms + 2_000), logs)
-                .await?;
+            let line = self.read_line(wait, logs).await?;
             if let Some(bestmove) = line.strip_prefix("bestmove ") {
                 let token = bestmove.split_whitespace().next().unwrap_or("0000");
-                return Ok(token.to_string());
+                return Ok(SearchOutcome {
+                    best_move: token.to_string(),
+                    score: reported.score,
+                    depth: reported.depth,
+                    seldepth: reported.seldepth,
+                    nodes: reported.nodes,
+                });
+            }
+            if update_progress(&mut reported, &line)
+                && let Some(progress) = progress
+            {
+                progress.send_replace(reported.clone());
+            }
+        }
+    }
+
+    async fn stop(&mut self, logs: &mut Vec<GameLogEntry>) -> Result<()> {
+        self.send("stop", logs).await?;
+        loop {
+            let line = self.read_line(STOP_GRACE, logs).await?;
+            if line.starts_with("bestmove") {
+                return Ok(());
             }
         }
     }
@@ -190,3 +300,289 @@ impl UciSession {
         Ok(line)
     }
 }
+
+/// The `go` command for `limit` and how long to wait between lines of its
+/// reply before giving up on the engine.
+fn go_command(limit: SearchLimit, side: Color) -> (String, Duration) {
+    match limit {
+        SearchLimit::MoveTime(movetime_ms) => (
+            format!("go movetime {movetime_ms}"),
+            Duration::from_millis(movetime_ms + 2_000),
+        ),
+        SearchLimit::Clock {
+            white_ms,
+            black_ms,
+            increment_ms,
+        } => {
+            let remaining_ms = if side == Color::White {
+                white_ms
+            } else {
+                black_ms
+            };
+            (
+                format!(
+                    "go wtime {white_ms} btime {black_ms} winc {increment_ms} binc {increment_ms}"
+                ),
+                Duration::from_millis(remaining_ms + 2_000),
+            )
+        }
+        SearchLimit::Depth(depth) => (format!("go depth {depth}"), UNTIMED_SEARCH_TIMEOUT),
+        SearchLimit::Nodes(nodes) => (format!("go nodes {nodes}"), UNTIMED_SEARCH_TIMEOUT),
+        SearchLimit::Infinite => ("go infinite".to_string(), UNTIMED_SEARCH_TIMEOUT),
+    }
+}
+
+/// The `score cp <n>` or `score mate <n>` of a UCI `info` line. Secondary
+/// `multipv` lines are skipped so the score always belongs to the best move,
+/// and so are `lowerbound`/`upperbound` scores, which are not evaluations.
+fn parse_info_score(line: &str) -> Option<EngineScore> {
+    let mut tokens = line.strip_prefix("info ")?.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if token == "multipv" && tokens.next()? != "1" {
+            return None;
+        }
+        if token == "score" {
+            let kind = tokens.next()?;
+            let value = tokens.next()?.parse().ok()?;
+            if matches!(tokens.next(), Some("lowerbound" | "upperbound")) {
+                return None;
+            }
+            return match kind {
+                "cp" => Some(EngineScore::Centipawns(value)),
+                "mate" => Some(EngineScore::Mate(value)),
+                _ => None,
+            };
+        }
+    }
+    None
+}
+
+/// The value after `name` in a UCI `info` line, such as `depth` or `nodes`,
+/// skipping secondary `multipv` lines like [`parse_info_score`].
+fn parse_info_counter(line: &str, name: &str) -> Option<u64> {
+    let tokens: Vec<_> = line.strip_prefix("info ")?.split_whitespace().collect();
+    if tokens
+        .windows(2)
+        .any(|window| window[0] == "multipv" && window[1] != "1")
+    {
+        return None;
+    }
+    tokens
+        .windows(2)
+        .find(|window| window[0] == name)
+        .and_then(|window| window[1].parse().ok())
+}
+
+/// The moves after `pv` in a UCI `info` line, skipping secondary `multipv`
+/// lines like [`parse_info_score`].
+fn parse_info_pv(line: &str) -> Option<Vec<String>> {
+    let tokens: Vec<_> = line.strip_prefix("info ")?.split_whitespace().collect();
+    if tokens
+        .windows(2)
+        .any(|window| window[0] == "multipv" && window[1] != "1")
+    {
+        return None;
+    }
+    let start = tokens.iter().position(|token| *token == "pv")? + 1;
+    Some(
+        tokens[start..]
+            .iter()
+            .map(|token| token.to_string())
+            .collect(),
+    )
+}
+
+/// The `multipv` rank, counted from 1, and the scored line of a UCI `info`
+/// line that carries both an exact score and a `pv`. Lines without `multipv`
+/// rank 1.
+fn parse_info_line(line: &str) -> Option<(usize, AnalysisLine)> {
+    let tokens: Vec<_> = line.strip_prefix("info ")?.split_whitespace().collect();
+    let rank = match tokens.iter().position(|token| *token == "multipv") {
+        Some(index) => tokens.get(index + 1)?.parse().ok()?,
+        None => 1,
+    };
+    let start = tokens.iter().position(|token| *token == "pv")? + 1;
+    let scored = tokens.iter().position(|token| *token == "score")?;
+    let value = tokens.get(scored + 2)?.parse().ok()?;
+    if matches!(tokens.get(scored + 3), Some(&("lowerbound" | "upperbound"))) {
+        return None;
+    }
+    let score = match *tokens.get(scored + 1)? {
+        "cp" => EngineScore::Centipawns(value),
+        "mate" => EngineScore::Mate(value),
+        _ => return None,
+    };
+    Some((
+        rank,
+        AnalysisLine {
+            score,
+            moves_uci: tokens[start..]
+                .iter()
+                .map(|token| token.to_string())
+                .collect(),
+        },
+    ))
+}
+
+/// Fold the main line's values from a UCI `info` line into `progress`,
+/// returning whether anything changed. Every ranked line also lands in
+/// `progress.lines`; a rank more than one past the known lines is skipped.
+fn update_progress(progress: &mut SearchProgress, line: &str) -> bool {
+    let before = progress.clone();
+    progress.score = parse_info_score(line).or(progress.score);
+    progress.depth = parse_info_counter(line, "depth")
+        .map(|depth| depth.min(u64::from(u32::MAX)) as u32)
+        .or(progress.depth);
+    progress.seldepth = parse_info_counter(line, "seldepth")
+        .map(|seldepth| seldepth.min(u64::from(u32::MAX)) as u32)
+        .or(progress.seldepth);
+    progress.nodes = parse_info_counter(line, "nodes").or(progress.nodes);
+    progress.nps = parse_info_counter(line, "nps").or(progress.nps);
+    if let Some(pv) = parse_info_pv(line) {
+        progress.pv = pv;
+    }
+    match parse_info_line(line) {
+        Some((rank, line)) if rank >= 1 && rank <= progress.lines.len() => {
+            progress.lines[rank - 1] = line;
+        }
+        Some((rank, line)) if rank == progress.lines.len() + 1 => progress.lines.push(line),
+        _ => {}
+    }
+    *progress != before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_searches_send_both_clocks_and_wait_on_the_movers() {
+        let limit = SearchLimit::Clock {
+            white_ms: 60_000,
+            black_ms: 30_000,
+            increment_ms: 500,
+        };
+
+        let (go, wait) = go_command(limit, Color::Black);
+        assert_eq!(go, "go wtime 60000 btime 30000 winc 500 binc 500");
+        assert_eq!(wait, Duration::from_millis(32_000));
+        assert_eq!(limit.movetime_ms(Color::White), Some(2_500));
+        assert_eq!(SearchLimit::Depth(4).movetime_ms(Color::White), None);
+    }
+
+    #[test]
+    fn parse_info_score_reads_centipawns_and_mates() {
+        assert_eq!(
+            parse_info_score("info depth 9 seldepth 14 score cp -35 nodes 1200 pv e2e4"),
+            Some(EngineScore::Centipawns(-35))
+        );
+        assert_eq!(
+            parse_info_score("info depth 5 score mate 3"),
+            Some(EngineScore::Mate(3))
+        );
+        assert_eq!(
+            parse_info_score("info depth 12 score cp 20 lowerbound"),
+            None
+        );
+        assert_eq!(
+            parse_info_score("info depth 12 score cp -80 upperbound pv e2e4"),
+            None
+        );
+        assert_eq!(
+            parse_info_score("info depth 6 multipv 1 score cp 40 pv d2d4"),
+            Some(EngineScore::Centipawns(40))
+        );
+        assert_eq!(
+            parse_info_score("info depth 6 multipv 2 score cp 15 pv c2c4"),
+            None
+        );
+        assert_eq!(parse_info_score("info string hello"), None);
+        assert_eq!(parse_info_score("bestmove e2e4"), None);
+    }
+
+    #[test]
+    fn parse_info_counter_reads_depth_and_nodes_of_the_main_line() {
+        let line = "info depth 9 seldepth 14 score cp -35 nodes 1200 pv e2e4";
+        assert_eq!(parse_info_counter(line, "depth"), Some(9));
+        assert_eq!(parse_info_counter(line, "nodes"), Some(1_200));
+        assert_eq!(parse_info_counter("info nodes 5000 time 20", "depth"), None);
+        assert_eq!(
+            parse_info_counter("info depth 6 multipv 2 score cp 15 nodes 99", "nodes"),
+            None
+        );
+        assert_eq!(parse_info_counter("bestmove e2e4", "depth"), None);
+    }
+
+    #[test]
+    fn progress_keeps_the_latest_main_line_values() {
+        let mut progress = SearchProgress::default();
+
+        assert!(update_progress(
+            &mut progress,
+            "info depth 7 seldepth 12 score cp 31 nodes 9000 nps 450000 pv e2e4 e7e5 g1f3"
+        ));
+        assert!(update_progress(
+            &mut progress,
+            "info depth 7 multipv 2 score cp 12 nodes 9000 pv d2d4"
+        ));
+        assert!(!update_progress(&mut progress, "info string thinking"));
+        // A fail-high bound from an aspiration re-search is not a score.
+        assert!(!update_progress(
+            &mut progress,
+            "info depth 7 score cp 95 lowerbound"
+        ));
+        assert!(update_progress(
+            &mut progress,
+            "info nodes 12000 nps 480000"
+        ));
+        assert_eq!(
+            progress,
+            SearchProgress {
+                depth: Some(7),
+                seldepth: Some(12),
+                score: Some(EngineScore::Centipawns(31)),
+                nodes: Some(12_000),
+                nps: Some(480_000),
+                pv: vec!["e2e4".into(), "e7e5".into(), "g1f3".into()],
+                lines: vec![
+                    AnalysisLine {
+                        score: EngineScore::Centipawns(31),
+                        moves_uci: vec!["e2e4".into(), "e7e5".into(), "g1f3".into()],
+                    },
+                    AnalysisLine {
+                        score: EngineScore::Centipawns(12),
+                        moves_uci: vec!["d2d4".into()],
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn later_multipv_lines_replace_their_rank_only() {
+        let mut progress = SearchProgress::default();
+        for line in [
+            "info depth 4 multipv 1 score cp 20 pv e2e4",
+            "info depth 4 multipv 2 score cp 10 pv d2d4",
+            "info depth 4 multipv 4 score cp 5 pv c2c4",
+            "info depth 5 multipv 1 score mate 3 pv g1f3 b8c6",
+        ] {
+            update_progress(&mut progress, line);
+        }
+
+        assert_eq!(
+            progress.lines,
+            [
+                AnalysisLine {
+                    score: EngineScore::Mate(3),
+                    moves_uci: vec!["g1f3".into(), "b8c6".into()],
+                },
+                AnalysisLine {
+                    score: EngineScore::Centipawns(10),
+                    moves_uci: vec!["d2d4".into()],
+                },
+            ]
+        );
+        assert_eq!(go_command(SearchLimit::Infinite, Color::White).0, "go infinite");
+    }
+}
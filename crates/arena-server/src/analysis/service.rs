@@ -0,0 +1,403 @@
+use arena_core::{AgentVersion, AnalysisLine, EngineScore, PositionAnalysis, Variant};
+use arena_runner::{
+    AgentAdapter, SearchLimit, SearchProgress, build_adapter, build_adapter_with_options,
+};
+use axum::extract::ws::{Message, WebSocket};
+use cozy_chess::Color;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use uuid::Uuid;
+
+use crate::{
+    ApiError,
+    gameplay::parse_saved_board,
+    state::AppState,
+    storage::{get_agent_version, list_agent_versions},
+};
+
+const DEFAULT_ANALYSIS_MOVETIME_MS: u64 = 300;
+const MAX_ANALYSIS_MOVETIME_MS: u64 = 2_000;
+
+/// Run a short engine search on `fen` and report its evaluation from White's
+/// point of view. The reply echoes `fen` so clients can drop stale results.
+/// Without an explicit engine, the newest active engine that supports the
+/// variant is used.
+pub(crate) async fn analyse_position(
+    state: &AppState,
+    variant: Variant,
+    fen: &str,
+    engine_version_id: Option<Uuid>,
+    movetime_ms: Option<u64>,
+) -> Result<PositionAnalysis, ApiError> {
+    let (board, normalized_fen) = parse_saved_board(variant, fen)
+        .map_err(|err| ApiError::BadRequest(format!("invalid FEN: {err}")))?;
+    let engine = match engine_version_id {
+        Some(id) => get_agent_version(&state.db, id).await?,
+        None => default_analysis_engine(state, variant).await?,
+    };
+    let engine_version_id = engine.id;
+    let movetime_ms = movetime_ms
+        .unwrap_or(DEFAULT_ANALYSIS_MOVETIME_MS)
+        .clamp(1, MAX_ANALYSIS_MOVETIME_MS);
+
+    let mut logs = Vec::new();
+    let mut adapter = build_adapter(engine);
+    adapter.prepare(variant, &mut logs).await?;
+    adapter.begin_game(&mut logs).await?;
+    let score = adapter
+        .analyse(&board, &normalized_fen, &[], movetime_ms, &mut logs)
+        .await;
+    adapter.shutdown(&mut logs).await.ok();
+
+    Ok(PositionAnalysis {
+        fen: fen.to_string(),
+        engine_version_id,
+        score: score?,
+        depth: None,
+        lines: Vec::new(),
+    })
+}
+
+async fn default_analysis_engine(
+    state: &AppState,
+    variant: Variant,
+) -> Result<AgentVersion, ApiError> {
+    list_agent_versions(&state.db, None)
+        .await?
+        .into_iter()
+        .find(|version| version.active && version.capabilities.supports_variant(variant))
+        .ok_or_else(|| ApiError::NotFound("no engine available for analysis".to_string()))
+}
+
+/// Most ranked lines a live analysis reports.
+const MAX_ANALYSIS_LINES: usize = 5;
+
+/// A position for live analysis to search until the next request.
+#[derive(Debug, Clone, Deserialize)]
+struct LiveAnalysisRequest {
+    fen: String,
+    variant: Variant,
+    engine_version_id: Option<Uuid>,
+    multipv: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "message_type", rename_all = "snake_case")]
+enum AnalysisWsClientMessage {
+    Start(LiveAnalysisRequest),
+    Stop,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "message_type", rename_all = "snake_case")]
+enum AnalysisWsServerMessage {
+    Analysis(PositionAnalysis),
+    Error { error: String },
+}
+
+/// One engine process kept across the positions of a live analysis, so
+/// stepping through a game does not restart it.
+struct AnalysisSession {
+    engine_version_id: Uuid,
+    variant: Variant,
+    multipv: usize,
+    adapter: Box<dyn AgentAdapter>,
+    progress: watch::Receiver<SearchProgress>,
+}
+
+impl AnalysisSession {
+    /// `current` when it already runs the requested engine, variant and line
+    /// count, otherwise a freshly started engine.
+    async fn open(
+        state: &AppState,
+        current: Option<Self>,
+        request: &LiveAnalysisRequest,
+    ) -> Result<Self, ApiError> {
+        let engine = match request.engine_version_id {
+            Some(id) => get_agent_version(&state.db, id).await?,
+            None => default_analysis_engine(state, request.variant).await?,
+        };
+        let multipv = request.multipv.unwrap_or(1).clamp(1, MAX_ANALYSIS_LINES);
+        if let Some(current) = current {
+            if current.engine_version_id == engine.id
+                && current.variant == request.variant
+                && current.multipv == multipv
+            {
+                return Ok(current);
+            }
+            current.shutdown().await;
+        }
+
+        let engine_version_id = engine.id;
+        let (sender, progress) = watch::channel(SearchProgress::default());
+        let mut adapter =
+            build_adapter_with_options(engine, vec![("MultiPV".to_string(), multipv.to_string())]);
+        adapter.watch_progress(sender);
+        let mut logs = Vec::new();
+        adapter.prepare(request.variant, &mut logs).await?;
+        adapter.begin_game(&mut logs).await?;
+        Ok(Self {
+            engine_version_id,
+            variant: request.variant,
+            multipv,
+            adapter,
+            progress,
+        })
+    }
+
+    async fn shutdown(mut self) {
+        self.adapter.shutdown(&mut Vec::new()).await.ok();
+    }
+}
+
+/// Serve one live analysis socket. Each `start` message replaces the
+/// position being searched with `go infinite`, and every change in the
+/// engine's report is sent back as a [`PositionAnalysis`] from White's point
+/// of view. `stop` idles the engine; closing the socket shuts it down.
+pub(crate) async fn serve_live_analysis(state: AppState, mut socket: WebSocket) {
+    let mut session: Option<AnalysisSession> = None;
+    let mut pending = None;
+    loop {
+        let message = match pending.take() {
+            Some(message) => message,
+            None => match next_client_message(&mut socket).await {
+                Some(message) => message,
+                None => break,
+            },
+        };
+        let AnalysisWsClientMessage::Start(request) = message else {
+            continue;
+        };
+        let result = match AnalysisSession::open(&state, session.take(), &request).await {
+            Ok(opened) => {
+                analyse_until_interrupted(session.insert(opened), &request, &mut socket).await
+            }
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(Some(message)) => pending = Some(message),
+            Ok(None) => break,
+            Err(err) => {
+                // A failed engine is not reused for the next position.
+                if let Some(failed) = session.take() {
+                    failed.shutdown().await;
+                }
+                let error = AnalysisWsServerMessage::Error {
+                    error: err.to_string(),
+                };
+                if send_analysis_message(&mut socket, &error).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+    if let Some(session) = session {
+        session.shutdown().await;
+    }
+}
+
+/// Search `request` until the client sends its next message, which is
+/// returned, or closes the socket.
+async fn analyse_until_interrupted(
+    session: &mut AnalysisSession,
+    request: &LiveAnalysisRequest,
+    socket: &mut WebSocket,
+) -> Result<Option<AnalysisWsClientMessage>, ApiError> {
+    let (board, normalized_fen) = parse_saved_board(request.variant, &request.fen)
+        .map_err(|err| ApiError::BadRequest(format!("invalid FEN: {err}")))?;
+    let white_to_move = board.side_to_move() == Color::White;
+    let AnalysisSession {
+        engine_version_id,
+        adapter,
+        progress,
+        ..
+    } = session;
+    // Whatever the previous position reported is stale.
+    progress.borrow_and_update();
+
+    let mut logs = Vec::new();
+    let mut searching = true;
+    let interrupt = {
+        let search = adapter.search(
+            &board,
+            &normalized_fen,
+            &[],
+            SearchLimit::Infinite,
+            &mut logs,
+        );
+        tokio::pin!(search);
+        loop {
+            tokio::select! {
+                result = &mut search => {
+                    // The engine ended the search itself, e.g. on a forced
+                    // mate; its last report stands until the next message.
+                    result?;
+                    searching = false;
+                    break None;
+                }
+                Ok(()) = progress.changed() => {
+                    let analysis = live_analysis(
+                        &request.fen,
+                        *engine_version_id,
+                        white_to_move,
+                        &progress.borrow_and_update(),
+                    );
+                    let message = AnalysisWsServerMessage::Analysis(analysis);
+                    if send_analysis_message(socket, &message).await.is_err() {
+                        break Some(None);
+                    }
+                }
+                message = socket.recv() => {
+                    match client_message(message) {
+                        ClientMessage::Message(message) => break Some(Some(message)),
+                        ClientMessage::Closed => break Some(None),
+                        ClientMessage::Malformed => {
+                            let error = AnalysisWsServerMessage::Error {
+                                error: "Malformed analysis websocket message".to_string(),
+                            };
+                            let _ = send_analysis_message(socket, &error).await;
+                        }
+                        ClientMessage::Ignored => {}
+                    }
+                }
+            }
+        }
+    };
+
+    if searching {
+        adapter.stop(&mut logs).await?;
+    }
+    match interrupt {
+        Some(next) => Ok(next),
+        None => {
+            if progress.has_changed().unwrap_or(false) {
+                let analysis = live_analysis(
+                    &request.fen,
+                    *engine_version_id,
+                    white_to_move,
+                    &progress.borrow_and_update(),
+                );
+                let message = AnalysisWsServerMessage::Analysis(analysis);
+                if send_analysis_message(socket, &message).await.is_err() {
+                    return Ok(None);
+                }
+            }
+            Ok(next_client_message(socket).await)
+        }
+    }
+}
+
+/// `progress`, reported from the side to move's point of view, as an
+/// analysis of `fen` from White's.
+fn live_analysis(
+    fen: &str,
+    engine_version_id: Uuid,
+    white_to_move: bool,
+    progress: &SearchProgress,
+) -> PositionAnalysis {
+    let white_view = |score: EngineScore| {
+        if white_to_move {
+            score
+        } else {
+            score.flipped()
+        }
+    };
+    PositionAnalysis {
+        fen: fen.to_string(),
+        engine_version_id,
+        score: progress.score.map(white_view),
+        depth: progress.depth,
+        lines: progress
+            .lines
+            .iter()
+            .map(|line| AnalysisLine {
+                score: white_view(line.score),
+                moves_uci: line.moves_uci.clone(),
+            })
+            .collect(),
+    }
+}
+
+enum ClientMessage {
+    Message(AnalysisWsClientMessage),
+    Malformed,
+    Ignored,
+    Closed,
+}
+
+fn client_message(message: Option<Result<Message, axum::Error>>) -> ClientMessage {
+    match message {
+        Some(Ok(Message::Text(text))) => serde_json::from_str(&text)
+            .map(ClientMessage::Message)
+            .unwrap_or(ClientMessage::Malformed),
+        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => ClientMessage::Closed,
+        Some(Ok(_)) => ClientMessage::Ignored,
+    }
+}
+
+/// The next well-formed message from an idle socket, or `None` once it closes.
+async fn next_client_message(socket: &mut WebSocket) -> Option<AnalysisWsClientMessage> {
+    loop {
+        match client_message(socket.recv().await) {
+            ClientMessage::Message(message) => return Some(message),
+            ClientMessage::Closed => return None,
+            ClientMessage::Malformed => {
+                let error = AnalysisWsServerMessage::Error {
+                    error: "Malformed analysis websocket message".to_string(),
+                };
+                send_analysis_message(socket, &error).await.ok()?;
+            }
+            ClientMessage::Ignored => {}
+        }
+    }
+}
+
+async fn send_analysis_message(
+    socket: &mut WebSocket,
+    message: &AnalysisWsServerMessage,
+) -> Result<(), axum::Error> {
+    socket
+        .send(Message::Text(
+            serde_json::to_string(message)
+                .expect("message should serialize")
+                .into(),
+        ))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn live_analysis_scores_every_line_from_whites_side() {
+        let progress = SearchProgress {
+            depth: Some(12),
+            score: Some(EngineScore::Centipawns(40)),
+            lines: vec![
+                AnalysisLine {
+                    score: EngineScore::Centipawns(40),
+                    moves_uci: vec!["e7e5".into()],
+                },
+                AnalysisLine {
+                    score: EngineScore::Mate(-3),
+                    moves_uci: vec!["f7f6".into()],
+                },
+            ],
+            ..SearchProgress::default()
+        };
+
+        let analysis = live_analysis("black-to-move", Uuid::nil(), false, &progress);
+
+        assert_eq!(analysis.score, Some(EngineScore::Centipawns(-40)));
+        assert_eq!(analysis.depth, Some(12));
+        assert_eq!(
+            analysis
+                .lines
+                .iter()
+                .map(|line| line.score)
+                .collect::<Vec<_>>(),
+            [EngineScore::Centipawns(-40), EngineScore::Mate(3)]
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use arena_core::{PositionAnalysis, Variant};
+use axum::{
+    Json,
+    extract::{State, ws::WebSocketUpgrade},
+    response::IntoResponse,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    ApiError,
+    analysis::service::{analyse_position, serve_live_analysis},
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub(super) struct AnalysePositionRequest {
+    fen: String,
+    variant: Variant,
+    engine_version_id: Option<Uuid>,
+    movetime_ms: Option<u64>,
+}
+
+pub(super) async fn analyse_position_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<AnalysePositionRequest>,
+) -> Result<Json<PositionAnalysis>, ApiError> {
+    Ok(Json(
+        analyse_position(
+            &state,
+            payload.variant,
+            &payload.fen,
+            payload.engine_version_id,
+            payload.movetime_ms,
+        )
+        .await?,
+    ))
+}
+
+pub(super) async fn websocket_analysis_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| serve_live_analysis(state, socket))
+}
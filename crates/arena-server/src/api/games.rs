@@ -16,7 +16,7 @@ use arena_core::{LiveEventEnvelope, LiveMatchSnapshot};
 use crate::{
     ApiError,
     debug::query_service::build_debug_bundle,
-    gameplay::build_replay_frames,
+    gameplay::{build_move_log, build_replay_frames},
     human_games::service::{create_human_game, load_human_player_profile, submit_human_move},
     live::stream_bootstrap::subscribe_live_socket,
     presentation::{
@@ -105,3 +105,15 @@ pub(super) async fn get_game_logs_handler(
     let game = get_game(&state.db, id).await?;
     Ok(Json(json!({ "id": game.id, "logs": game.logs })))
 }
+
+pub(super) async fn get_game_move_log_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl axum::response::IntoResponse, ApiError> {
+    let game = get_game(&state.db, id).await?;
+    let move_log = build_move_log(&game)?;
+    Ok((
+        [(axum::http::header::CONTENT_TYPE, "application/x-ndjson")],
+        move_log,
+    ))
+}
@@ -16,7 +16,7 @@ use arena_core::{LiveEventEnvelope, LiveMatchSnapshot};
 use crate::{
     ApiError,
     debug::query_service::build_debug_bundle,
-    gameplay::build_replay_frames,
+    gameplay::{GameSetup, build_replay_frames},
     human_games::service::{create_human_game, load_human_player_profile, submit_human_move},
     live::stream_bootstrap::subscribe_live_socket,
     presentation::{
@@ -53,6 +53,16 @@ pub(super) struct CreateHumanGameRequest {
     pool_id: Uuid,
     engine_version_id: Uuid,
     human_side: HumanSideChoice,
+    /// Start from this FEN instead of the pool's opening.
+    #[serde(default)]
+    start_fen: Option<String>,
+    /// Continue from the end of this PGN instead of the pool's opening.
+    #[serde(default)]
+    pgn: Option<String>,
+    /// UCI `Skill Level` for the engine, from 0 to 20; games below full
+    /// strength are unrated.
+    #[serde(default)]
+    engine_skill_level: Option<u8>,
 }
 
 pub(super) async fn create_human_game_handler(
@@ -62,14 +72,31 @@ pub(super) async fn create_human_game_handler(
     let human_plays_white = match payload.human_side {
         HumanSideChoice::White => true,
         HumanSideChoice::Black => false,
-        HumanSideChoice::Random => Uuid::new_v4().as_u128() % 2 == 0,
+        HumanSideChoice::Random => Uuid::new_v4().as_u128().is_multiple_of(2),
     };
+    let setup = match (payload.start_fen, payload.pgn) {
+        (Some(_), Some(_)) => {
+            return Err(ApiError::BadRequest(
+                "provide either start_fen or pgn, not both".to_string(),
+            ));
+        }
+        (Some(fen), None) => Some(GameSetup::Fen(fen)),
+        (None, Some(pgn)) => Some(GameSetup::Pgn(pgn)),
+        (None, None) => None,
+    };
+    if payload.engine_skill_level.is_some_and(|level| level > 20) {
+        return Err(ApiError::BadRequest(
+            "engine_skill_level must be between 0 and 20".to_string(),
+        ));
+    }
     let (match_id, tournament_id) = create_human_game(
         &state,
         payload.name,
         payload.pool_id,
         payload.engine_version_id,
         human_plays_white,
+        setup,
+        payload.engine_skill_level,
     )
     .await?;
 
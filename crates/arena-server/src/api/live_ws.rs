@@ -17,7 +17,9 @@ use crate::{
     ApiError,
     debug::query_service::build_debug_bundle,
     gameplay::build_replay_frames,
-    human_games::service::{create_human_game, load_human_player_profile, submit_human_move},
+    human_games::service::{
+        create_human_game, load_human_player_profile, submit_human_move, submit_human_takeback,
+    },
     live::stream_bootstrap::subscribe_live_socket,
     presentation::{
         ApiGameRecord, ApiLeaderboardEntry, ApiMatchSeries, HumanPlayerProfile, ReplayPayload,
@@ -51,6 +53,11 @@ enum LiveWsClientMessage {
         ws_connection_id: Option<Uuid>,
         move_uci: String,
     },
+    Takeback {
+        intent_id: Option<Uuid>,
+        client_action_id: Option<Uuid>,
+        ws_connection_id: Option<Uuid>,
+    },
 }
 
 #[derive(Debug, Serialize)]
@@ -176,12 +183,36 @@ pub(super) async fn handle_live_socket(
                                     }
                                 }
                             }
+                            LiveWsClientMessage::Takeback { intent_id, client_action_id, ws_connection_id } => {
+                                active_ws_connection_id = ws_connection_id.or(active_ws_connection_id);
+                                let intent_id = intent_id.unwrap_or_else(Uuid::new_v4);
+                                match submit_human_takeback(state.clone(), match_id, intent_id).await {
+                                    Ok(ack) => {
+                                        if send_ws_json(
+                                            &mut socket,
+                                            &LiveWsServerMessage::IntentAck {
+                                                match_id,
+                                                intent_id,
+                                                client_action_id,
+                                                ws_connection_id: active_ws_connection_id,
+                                                request_id: Some(request_context.request_id),
+                                                ack,
+                                            }
+                                        ).await.is_err() {
+                                            return;
+                                        }
+                                    }
+                                    Err(err) => {
+                                        let _ = send_ws_error(&mut socket, &err.to_string(), &request_context, client_action_id, active_ws_connection_id).await;
+                                    }
+                                }
+                            }
                         }
                     }
                     Message::Ping(payload) => {
-                        if socket.send(Message::Pong(payload)).await.is_err() {
+                        let Ok(()) = socket.send(Message::Pong(payload)).await else {
                             break;
-                        }
+                        };
                     }
                     Message::Close(_) => break,
                     _ => {}
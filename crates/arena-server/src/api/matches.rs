@@ -57,6 +57,7 @@ pub(super) async fn list_matches_handler(
         .collect::<std::collections::HashMap<_, _>>();
     let games = list_games(&state.db, query.tournament_id, None).await?;
     let checkpoints = list_live_runtime_checkpoints(&state.db, None).await?;
+    let unrated_match_ids = state.human_games.unrated_match_ids().await;
     let game_id_by_match_id = games
         .into_iter()
         .map(|game| (game.match_id, game.id))
@@ -88,10 +89,11 @@ pub(super) async fn list_matches_handler(
                 &version_name_by_id,
                 &human_player,
                 interactive,
+                interactive && unrated_match_ids.contains(&series.id),
             )
         })
         .collect();
-    matches.sort_by(|left, right| right.created_at.cmp(&left.created_at));
+    matches.sort_by_key(|summary| std::cmp::Reverse(summary.created_at));
     Ok(Json(matches))
 }
 
@@ -11,6 +11,7 @@ use crate::{
 };
 
 mod agents;
+mod analysis;
 mod debug;
 mod event_presets;
 mod games;
@@ -40,6 +41,7 @@ mod tests {
         registry::{SetupRegistryCache, sync_setup_registry_if_changed},
         state::{HumanGameStore, TournamentCoordinator},
         storage::{insert_live_runtime_event, upsert_live_runtime_checkpoint},
+        test_fixtures::{STANDARD_FEN, SeededGame, seed_finished_tournament},
     };
     use arena_core::{
         LiveEventEnvelope, LiveResult, LiveRuntimeCheckpoint, LiveStatus, LiveTermination,
@@ -91,6 +93,7 @@ mod tests {
                 "4k3/8/8/8/8/8/8/4K3 b - - 0 1".to_string()
             },
             moves: moves.iter().map(|value| (*value).to_string()).collect(),
+            move_times_ms: Vec::new(),
             white_remaining_ms: 60_000,
             black_remaining_ms: 60_000,
             side_to_move: if moves.is_empty() {
@@ -741,6 +744,292 @@ mod tests {
         assert_eq!(first.get("status").and_then(Value::as_str), Some("failed"));
     }
 
+    #[tokio::test]
+    async fn resuming_a_running_tournament_is_a_conflict() {
+        let state = setup_state().await;
+        let app = crate::build_app(state.clone());
+        let pool = crate::storage::list_pools(&state.db)
+            .await
+            .unwrap()
+            .remove(0);
+        let mut versions = crate::storage::list_agent_versions(&state.db, None)
+            .await
+            .unwrap();
+        let white = versions.remove(0);
+        let black = versions.remove(0);
+        let tournament_id = Uuid::new_v4();
+        crate::storage::insert_tournament(
+            &state.db,
+            &arena_core::Tournament {
+                id: tournament_id,
+                name: "in progress".to_string(),
+                kind: arena_core::TournamentKind::RoundRobin,
+                pool_id: pool.id,
+                participant_version_ids: vec![white.id, black.id],
+                worker_count: 1,
+                games_per_pairing: 1,
+                status: arena_core::TournamentStatus::Running,
+                created_at: Utc::now(),
+                started_at: Some(Utc::now()),
+                completed_at: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri(format!("/api/tournaments/{tournament_id}/resume"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn tournament_stats_summarize_its_finished_games() {
+        use arena_core::{GameResult, GameTermination};
+
+        let state = setup_state().await;
+        let app = crate::build_app(state.clone());
+        let finished = [
+            (GameResult::WhiteWin, GameTermination::Checkmate, 3, 2_000),
+            (GameResult::Draw, GameTermination::Repetition, 8, 6_000),
+            (GameResult::BlackWin, GameTermination::Checkmate, 4, 1_000),
+        ];
+        let (tournament_id, _) = seed_finished_tournament(
+            &state,
+            finished
+                .into_iter()
+                .map(|(result, termination, plies, duration_ms)| SeededGame {
+                    duration_ms,
+                    ..SeededGame::new(result, termination, &vec!["e2e4"; plies])
+                })
+                .collect(),
+        )
+        .await;
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri(format!("/api/tournaments/{tournament_id}/stats"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let payload: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            payload,
+            json!({
+                "games": 3,
+                "white_wins": 1,
+                "black_wins": 1,
+                "draws": 1,
+                "average_plies": 5.0,
+                "average_duration_ms": 3_000,
+                "terminations": { "checkmate": 2, "repetition": 1 },
+            })
+        );
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri(format!("/api/tournaments/{tournament_id}/openings"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let payload: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            payload,
+            json!([{
+                "opening_id": null,
+                "label": null,
+                "start_fen": STANDARD_FEN,
+                "games": 3,
+                "white_wins": 1,
+                "black_wins": 1,
+                "draws": 1,
+                "white_score": 0.5,
+            }])
+        );
+
+        let missing = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri(format!("/api/tournaments/{}/stats", Uuid::new_v4()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn game_move_log_has_a_header_and_one_line_per_move() {
+        let state = setup_state().await;
+        let app = crate::build_app(state.clone());
+        let returned = |ply: u64, depth: u32| {
+            arena_core::GameLogEntry::new(
+                "engine.move_returned",
+                "info",
+                "server.engine_runtime",
+                "engine returned a move",
+            )
+            .with_fields(json!({
+                "ply": ply,
+                "score": { "kind": "centipawns", "value": 20 },
+                "depth": depth,
+                "nodes": 1_000,
+                "elapsed_ms": 15,
+            }))
+        };
+        let games = [vec!["e2e4", "e7e5", "g1f3"], vec!["d2d4", "d7d5"]];
+        let (_, game_ids) = seed_finished_tournament(
+            &state,
+            games
+                .iter()
+                .map(|moves| SeededGame {
+                    logs: (1..=moves.len() as u64)
+                        .map(|ply| returned(ply, ply as u32 + 4))
+                        .collect(),
+                    ..SeededGame::new(
+                        arena_core::GameResult::Draw,
+                        arena_core::GameTermination::Adjudication,
+                        moves,
+                    )
+                })
+                .collect(),
+        )
+        .await;
+
+        let mut total_lines = 0;
+        for (game_id, moves) in game_ids.iter().zip(&games) {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .uri(format!("/api/games/{game_id}/moves"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let lines: Vec<Value> = std::str::from_utf8(&body)
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect();
+            assert_eq!(lines.len(), moves.len() + 1);
+            assert_eq!(lines[0]["game_id"], json!(game_id));
+            assert_eq!(lines[2]["side"], "black");
+            assert_eq!(lines[2]["move_uci"], moves[1]);
+            assert_eq!(lines[2]["depth"], 6);
+            assert_eq!(lines[2]["score"]["value"], 20);
+            total_lines += lines.len();
+        }
+        assert_eq!(total_lines, 5 + 2);
+    }
+
+    #[tokio::test]
+    async fn tournament_training_export_has_one_record_per_ply_labelled_from_the_mover() {
+        let state = setup_state().await;
+        let app = crate::build_app(state.clone());
+        let games = [
+            (
+                vec!["f2f3", "e7e5", "g2g4", "d8h4"],
+                arena_core::GameResult::BlackWin,
+                arena_core::GameTermination::Checkmate,
+            ),
+            (
+                vec!["d2d4", "d7d5", "c2c4"],
+                arena_core::GameResult::Draw,
+                arena_core::GameTermination::MoveLimit,
+            ),
+        ];
+        let (tournament_id, _) = seed_finished_tournament(
+            &state,
+            games
+                .iter()
+                .map(|(moves, result, termination)| SeededGame {
+                    logs: vec![
+                        arena_core::GameLogEntry::new(
+                            "engine.move_returned",
+                            "info",
+                            "server.engine_runtime",
+                            "engine returned a move",
+                        )
+                        .with_fields(json!({
+                            "ply": 2,
+                            "score": { "kind": "centipawns", "value": 35 },
+                        })),
+                    ],
+                    ..SeededGame::new(*result, *termination, moves)
+                })
+                .collect(),
+        )
+        .await;
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri(format!("/api/tournaments/{tournament_id}/training"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let records: Vec<Value> = std::str::from_utf8(&body)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(records.len(), 4 + 3);
+        for game_records in records.chunk_by(|left, right| left["game_id"] == right["game_id"]) {
+            let white_outcome = game_records[0]["outcome"].as_i64().unwrap();
+            for record in game_records {
+                let sign = if record["side_to_move"] == "white" { 1 } else { -1 };
+                assert_eq!(record["outcome"].as_i64().unwrap() * sign, white_outcome);
+                assert_eq!(record["score_perspective"], "side_to_move");
+            }
+            assert_eq!(game_records[1]["score"]["value"], 35);
+            assert!(game_records[0]["score"].is_null());
+        }
+        let mate = records.iter().find(|record| record["move_uci"] == "d8h4").unwrap();
+        assert_eq!(mate["side_to_move"], "black");
+        assert_eq!(mate["outcome"], 1);
+        assert_eq!(mate["adjudicated"], false);
+        let move_limit = records.iter().find(|record| record["move_uci"] == "c2c4").unwrap();
+        assert_eq!(move_limit["outcome"], 0);
+        assert_eq!(move_limit["adjudicated"], true);
+    }
+
     #[tokio::test]
     async fn save_debug_report_writes_repo_local_file() {
         let state = setup_state().await;
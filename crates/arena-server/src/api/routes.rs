@@ -73,6 +73,11 @@ pub(crate) fn router() -> Router<AppState> {
             "/event-presets/{id}/start",
             post(super::event_presets::start_event_preset_handler),
         )
+        .route("/analysis", post(super::analysis::analyse_position_handler))
+        .route(
+            "/analysis/ws",
+            get(super::analysis::websocket_analysis_handler),
+        )
         .route("/duels", post(super::live_duel::create_live_duel_handler))
         .route(
             "/human-games",
@@ -87,10 +92,26 @@ pub(crate) fn router() -> Router<AppState> {
             "/tournaments/{id}",
             get(super::tournaments::get_tournament_handler),
         )
+        .route(
+            "/tournaments/{id}/stats",
+            get(super::tournaments::get_tournament_stats_handler),
+        )
+        .route(
+            "/tournaments/{id}/openings",
+            get(super::tournaments::get_tournament_openings_handler),
+        )
+        .route(
+            "/tournaments/{id}/training",
+            get(super::tournaments::get_tournament_training_handler),
+        )
         .route(
             "/tournaments/{id}/stop",
             post(super::tournaments::stop_tournament_handler),
         )
+        .route(
+            "/tournaments/{id}/resume",
+            post(super::tournaments::resume_tournament_handler),
+        )
         .route("/matches", get(super::matches::list_matches_handler))
         .route("/matches/{id}/live", get(super::matches::get_live_match_handler))
         .route("/live/metrics", get(super::matches::get_live_metrics_handler))
@@ -130,6 +151,7 @@ pub(crate) fn router() -> Router<AppState> {
         .route("/games/{id}", get(super::games::get_game_handler))
         .route("/games/{id}/replay", get(super::games::get_game_replay_handler))
         .route("/games/{id}/logs", get(super::games::get_game_logs_handler))
+        .route("/games/{id}/moves", get(super::games::get_game_move_log_handler))
         .route("/leaderboards", get(super::leaderboards::get_leaderboard_handler))
         .route(
             "/ratings/history",
@@ -16,7 +16,7 @@ use arena_core::{LiveEventEnvelope, LiveMatchSnapshot};
 use crate::{
     ApiError,
     debug::query_service::build_debug_bundle,
-    gameplay::build_replay_frames,
+    gameplay::{build_replay_frames, build_training_records},
     human_games::service::{create_human_game, load_human_player_profile, submit_human_move},
     live::stream_bootstrap::subscribe_live_socket,
     presentation::{
@@ -32,7 +32,7 @@ use crate::{
         list_event_presets, list_games, list_live_runtime_checkpoints, list_match_series,
         list_opening_suites, list_pools, list_recent_request_errors, list_tournaments,
         load_aggregate_leaderboard, load_live_runtime_events_since, load_pool_leaderboard,
-        load_rating_history, update_tournament_status,
+        load_pool_openings, load_rating_history, update_tournament_status,
     },
     tournaments::service::{create_tournament_run, resolve_preset_participants},
 };
@@ -100,6 +100,42 @@ pub(super) async fn get_tournament_handler(
     get_tournament(&state.db, id).await.map(Json)
 }
 
+pub(super) async fn get_tournament_stats_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<arena_core::GameStats>, ApiError> {
+    get_tournament(&state.db, id).await?;
+    let games = list_games(&state.db, Some(id), None).await?;
+    Ok(Json(arena_core::GameStats::from_games(&games)))
+}
+
+/// The tournament's results per start position, labelled from its pool's
+/// opening suite.
+pub(super) async fn get_tournament_openings_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<arena_core::OpeningScore>>, ApiError> {
+    let tournament = get_tournament(&state.db, id).await?;
+    let pool = get_pool(&state.db, tournament.pool_id).await?;
+    let positions = load_pool_openings(&state.db, &pool).await?;
+    let games = list_games(&state.db, Some(id), None).await?;
+    Ok(Json(arena_core::opening_scores(&games, &positions)))
+}
+
+/// Every ply of the tournament's finished games as JSONL training records.
+pub(super) async fn get_tournament_training_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl axum::response::IntoResponse, ApiError> {
+    get_tournament(&state.db, id).await?;
+    let games = list_games(&state.db, Some(id), None).await?;
+    let records = build_training_records(&games)?;
+    Ok((
+        [(axum::http::header::CONTENT_TYPE, "application/x-ndjson")],
+        records,
+    ))
+}
+
 pub(super) async fn stop_tournament_handler(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
@@ -117,4 +153,29 @@ pub(super) async fn stop_tournament_handler(
     )
     .await?;
     Ok(Json(json!({ "stopped": true, "tournament_id": id })))
-}
\ No newline at end of file
+}
+
+/// Continues a stopped, failed or interrupted tournament from its last
+/// finished pair.
+pub(super) async fn resume_tournament_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, ApiError> {
+    let tournament = get_tournament(&state.db, id).await?;
+    match tournament.status {
+        arena_core::TournamentStatus::Running => {
+            return Err(ApiError::Conflict("tournament is already running".to_string()));
+        }
+        arena_core::TournamentStatus::Draft | arena_core::TournamentStatus::Completed => {
+            return Err(ApiError::Conflict(
+                "only a stopped, failed or interrupted tournament can be resumed".to_string(),
+            ));
+        }
+        arena_core::TournamentStatus::Failed | arena_core::TournamentStatus::Stopped => {}
+    }
+    let resumed = state.coordinator.start(state.clone(), id).await?;
+    if !resumed {
+        return Err(ApiError::Conflict("tournament is already running".to_string()));
+    }
+    Ok(Json(json!({ "resumed": true, "tournament_id": id })))
+}
@@ -26,6 +26,7 @@ pub(crate) async fn init_db(db: &SqlitePool) -> Result<()> {
             working_directory TEXT,
             args TEXT NOT NULL,
             env TEXT NOT NULL,
+            uci_options TEXT NOT NULL DEFAULT '{}',
             capabilities TEXT NOT NULL,
             declared_name TEXT,
             tags TEXT NOT NULL,
@@ -184,6 +185,7 @@ pub(crate) async fn init_db(db: &SqlitePool) -> Result<()> {
             start_fen TEXT NOT NULL,
             fen TEXT NOT NULL,
             moves TEXT NOT NULL,
+            move_times_ms TEXT NOT NULL DEFAULT '[]',
             white_remaining_ms INTEGER NOT NULL,
             black_remaining_ms INTEGER NOT NULL,
             side_to_move TEXT NOT NULL,
@@ -222,6 +224,13 @@ pub(crate) async fn init_db(db: &SqlitePool) -> Result<()> {
     ensure_column(db, "agent_versions", "registry_key", "TEXT").await?;
     ensure_column(db, "agent_versions", "active", "INTEGER NOT NULL DEFAULT 1").await?;
     ensure_column(db, "agent_versions", "documentation", "TEXT").await?;
+    ensure_column(
+        db,
+        "agent_versions",
+        "uci_options",
+        "TEXT NOT NULL DEFAULT '{}'",
+    )
+    .await?;
     ensure_column(db, "request_journal", "client_route", "TEXT").await?;
     ensure_column(db, "request_journal", "client_ts", "TEXT").await?;
     ensure_column(
@@ -241,6 +250,13 @@ pub(crate) async fn init_db(db: &SqlitePool) -> Result<()> {
         "TEXT NOT NULL DEFAULT ''",
     )
     .await?;
+    ensure_column(
+        db,
+        "live_runtime_checkpoints",
+        "move_times_ms",
+        "TEXT NOT NULL DEFAULT '[]'",
+    )
+    .await?;
     ensure_foreign_key_schema(db).await?;
     for statement in [
         "CREATE UNIQUE INDEX IF NOT EXISTS idx_agents_registry_key ON agents(registry_key) WHERE registry_key IS NOT NULL",
@@ -375,6 +391,7 @@ async fn ensure_foreign_key_schema(db: &SqlitePool) -> Result<()> {
             working_directory TEXT,
             args TEXT NOT NULL,
             env TEXT NOT NULL,
+            uci_options TEXT NOT NULL DEFAULT '{}',
             capabilities TEXT NOT NULL,
             declared_name TEXT,
             tags TEXT NOT NULL,
@@ -384,8 +401,8 @@ async fn ensure_foreign_key_schema(db: &SqlitePool) -> Result<()> {
             FOREIGN KEY(agent_id) REFERENCES agents(id) ON DELETE CASCADE
         )",
         "INSERT INTO agent_versions_new
-            (id, registry_key, agent_id, version, active, executable_path, working_directory, args, env, capabilities, declared_name, tags, notes, documentation, created_at)
-         SELECT id, registry_key, agent_id, version, COALESCE(active, 1), executable_path, working_directory, args, env, capabilities, declared_name, tags, notes, documentation, created_at
+            (id, registry_key, agent_id, version, active, executable_path, working_directory, args, env, uci_options, capabilities, declared_name, tags, notes, documentation, created_at)
+         SELECT id, registry_key, agent_id, version, COALESCE(active, 1), executable_path, working_directory, args, env, uci_options, capabilities, declared_name, tags, notes, documentation, created_at
          FROM agent_versions",
         "DROP TABLE agent_versions",
         "ALTER TABLE agent_versions_new RENAME TO agent_versions",
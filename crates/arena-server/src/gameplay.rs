@@ -1,6 +1,11 @@
-use arena_core::{AgentVersion, OpeningPosition, Variant};
-use arena_runner::starting_board;
-use cozy_chess::{Board, util};
+use std::collections::HashMap;
+
+use arena_core::{
+    AgentVersion, GameRecord, GameResult, GameTermination, OpeningPosition, Variant, parse_pgn_game,
+};
+use arena_runner::{Game, starting_board};
+use cozy_chess::{Board, GameStatus, util};
+use serde_json::{Value, json};
 
 use crate::ApiError;
 
@@ -10,6 +15,59 @@ pub(crate) struct MatchConfig<'a> {
     pub(crate) opening_seed: Option<u64>,
 }
 
+/// A user-supplied start for a human game instead of the pool's opening.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum GameSetup {
+    /// Start from this position.
+    Fen(String),
+    /// Continue a game from the position after its moves.
+    Pgn(String),
+}
+
+/// A resolved [`GameSetup`]: where the game started, the moves already on the
+/// board, and the game they lead to.
+pub(crate) struct SetupPosition {
+    pub(crate) start_fen: String,
+    pub(crate) moves_uci: Vec<String>,
+    pub(crate) game: Game,
+}
+
+pub(crate) fn resolve_game_setup(
+    variant: Variant,
+    setup: &GameSetup,
+) -> Result<SetupPosition, ApiError> {
+    let (start, start_fen, moves) = match setup {
+        GameSetup::Fen(fen) => {
+            let (board, start_fen) = parse_saved_board(variant, fen.trim())
+                .map_err(|err| ApiError::BadRequest(format!("invalid FEN: {err}")))?;
+            (board, start_fen, Vec::new())
+        }
+        GameSetup::Pgn(pgn) => {
+            let game = parse_pgn_game(pgn, variant.is_chess960())
+                .map_err(|err| ApiError::BadRequest(format!("invalid PGN: {err}")))?;
+            let start_fen = fen_for_variant(&game.start, variant);
+            (game.start, start_fen, game.moves)
+        }
+    };
+
+    let mut game = Game::new(start);
+    for mv in moves {
+        game.play(mv)
+            .map_err(|err| ApiError::BadRequest(format!("invalid PGN: {err}")))?;
+    }
+    if game.board().status() != GameStatus::Ongoing {
+        return Err(ApiError::BadRequest(
+            "the set-up position is already decided".to_string(),
+        ));
+    }
+
+    Ok(SetupPosition {
+        start_fen,
+        moves_uci: game.moves_uci(),
+        game,
+    })
+}
+
 pub(crate) fn ensure_engine_supports_variant(
     version: &AgentVersion,
     variant: Variant,
@@ -98,6 +156,113 @@ pub(crate) fn build_replay_frames(
     Ok(frames)
 }
 
+/// JSONL move log of a finished game: a header line describing the game, then
+/// one line per ply with the mover, the move, the position before it, and the
+/// score, depth, nodes and time its engine reported for it.
+pub(crate) fn build_move_log(game: &GameRecord) -> Result<String, ApiError> {
+    let frames = build_replay_frames(game.variant, &game.start_fen, &game.moves_uci)?;
+    let engine_fields = engine_fields_by_ply(game);
+    let white_to_move = frames[0].split_whitespace().nth(1) != Some("b");
+
+    let mut lines = vec![json!({
+        "game_id": game.id,
+        "white_version_id": game.white_version_id,
+        "black_version_id": game.black_version_id,
+        "variant": game.variant,
+        "start_fen": frames[0],
+        "result": game.result,
+        "termination": game.termination,
+    })];
+    for (index, move_uci) in game.moves_uci.iter().enumerate() {
+        let ply = index as u64 + 1;
+        let mut line = json!({
+            "ply": ply,
+            "side": if (index % 2 == 0) == white_to_move { "white" } else { "black" },
+            "move_uci": move_uci,
+            "fen_before": frames[index],
+        });
+        if let Some(fields) = engine_fields.get(&ply) {
+            for key in ["score", "depth", "nodes", "elapsed_ms"] {
+                line[key] = fields.get(key).cloned().unwrap_or(Value::Null);
+            }
+        }
+        lines.push(line);
+    }
+    Ok(lines.iter().map(|line| format!("{line}\n")).collect())
+}
+
+/// JSONL training records from finished games, one per ply: the position
+/// before the move, the side to move, the move, the score its engine
+/// reported and the game's outcome. Scores and outcomes are both from the
+/// side to move's point of view, and `adjudicated` marks games that ended by
+/// adjudication or the move limit rather than on the board.
+pub(crate) fn build_training_records(games: &[GameRecord]) -> Result<String, ApiError> {
+    let mut records = String::new();
+    for game in games {
+        let frames = build_replay_frames(game.variant, &game.start_fen, &game.moves_uci)?;
+        let engine_fields = engine_fields_by_ply(game);
+        let white_outcome: i8 = match game.result {
+            GameResult::WhiteWin => 1,
+            GameResult::BlackWin => -1,
+            GameResult::Draw => 0,
+        };
+        let adjudicated = matches!(
+            game.termination,
+            GameTermination::Adjudication | GameTermination::MoveLimit
+        );
+        for (index, move_uci) in game.moves_uci.iter().enumerate() {
+            let ply = index as u64 + 1;
+            let white_to_move = frames[index].split_whitespace().nth(1) != Some("b");
+            let record = json!({
+                "game_id": game.id,
+                "ply": ply,
+                "fen": frames[index],
+                "side_to_move": if white_to_move { "white" } else { "black" },
+                "move_uci": move_uci,
+                "score": engine_fields
+                    .get(&ply)
+                    .and_then(|fields| fields.get("score"))
+                    .cloned()
+                    .unwrap_or(Value::Null),
+                "score_perspective": "side_to_move",
+                "outcome": if white_to_move { white_outcome } else { -white_outcome },
+                "termination": game.termination,
+                "adjudicated": adjudicated,
+            });
+            records.push_str(&format!("{record}\n"));
+        }
+    }
+    Ok(records)
+}
+
+/// The fields of each `engine.move_returned` log entry, by the ply it played.
+fn engine_fields_by_ply(game: &GameRecord) -> HashMap<u64, &Value> {
+    game.logs
+        .iter()
+        .filter(|entry| entry.event == "engine.move_returned")
+        .filter_map(|entry| {
+            let fields = entry.fields.as_ref()?;
+            Some((fields.get("ply")?.as_u64()?, fields))
+        })
+        .collect()
+}
+
+/// Replay a saved game's `moves_uci` from its `start_fen`, returning the game
+/// they lead to and the start FEN as [`parse_saved_board`] normalizes it.
+pub(crate) fn replay_saved_game(
+    variant: Variant,
+    start_fen: &str,
+    moves_uci: &[String],
+) -> anyhow::Result<(Game, String)> {
+    let (board, start_fen) = parse_saved_board(variant, start_fen)
+        .map_err(|err| anyhow::anyhow!("invalid saved start FEN: {err}"))?;
+    let mut game = Game::new(board);
+    for move_uci in moves_uci {
+        game.play_uci(move_uci)?;
+    }
+    Ok((game, start_fen))
+}
+
 fn repair_chess960_castling_fen(fen: &str) -> Option<String> {
     let mut parts: Vec<&str> = fen.split_whitespace().collect();
     if parts.len() != 6 || parts[2] == "-" {
@@ -203,6 +368,52 @@ mod tests {
         Board::from_fen(&start_fen, true).unwrap();
     }
 
+    #[test]
+    fn pgn_setup_replays_moves_into_the_current_position() {
+        let setup = resolve_game_setup(
+            Variant::Standard,
+            &GameSetup::Pgn("1. e4 e5 2. Nf3 Nc6 3. Bc4 Nf6 4. O-O *".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            setup.start_fen,
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+        assert_eq!(
+            setup.moves_uci,
+            vec!["e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "g8f6", "e1g1"]
+        );
+        assert_eq!(
+            setup.game.board().to_string(),
+            "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQ1RK1 b kq - 5 4"
+        );
+        assert_eq!(setup.game.repetitions().values().sum::<u8>(), 8);
+    }
+
+    #[test]
+    fn setup_rejects_bad_fens_and_decided_positions() {
+        let invalid = resolve_game_setup(Variant::Standard, &GameSetup::Fen("8/8/8 w - -".into()));
+        assert!(matches!(invalid, Err(ApiError::BadRequest(_))));
+
+        let mated = resolve_game_setup(
+            Variant::Standard,
+            &GameSetup::Fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 1 1".to_string()),
+        );
+        assert!(matches!(mated, Err(ApiError::BadRequest(_))));
+
+        let illegal = resolve_game_setup(Variant::Standard, &GameSetup::Pgn("1. e5 *".into()));
+        assert!(matches!(illegal, Err(ApiError::BadRequest(_))));
+
+        let fen = resolve_game_setup(
+            Variant::Standard,
+            &GameSetup::Fen(" 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1 ".to_string()),
+        )
+        .unwrap();
+        assert_eq!(fen.start_fen, "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+        assert!(fen.moves_uci.is_empty());
+    }
+
     #[test]
     fn replay_repairs_legacy_chess960_castling_rights() {
         let frames = build_replay_frames(
@@ -2,10 +2,10 @@ use std::collections::HashMap;
 
 use anyhow::Result;
 use arena_core::{
-    GameRecord, GameResult, LiveRuntimeCheckpoint, MatchSeries, MatchStatus, Tournament,
-    TournamentKind, TournamentStatus, snapshot_from_entry,
+    AdjudicationConfig, GameRecord, GameResult, LiveRuntimeCheckpoint, MatchSeries, MatchStatus,
+    Tournament, TournamentKind, TournamentStatus,
 };
-use arena_runner::build_adapter;
+use arena_runner::{Game, build_adapter, build_adapter_with_options};
 use chrono::Utc;
 use serde_json::json;
 use sqlx::SqlitePool;
@@ -14,11 +14,13 @@ use uuid::Uuid;
 use crate::{
     ApiError,
     gameplay::{
-        MatchConfig, ensure_engine_supports_variant, parse_saved_board, resolve_start_state,
+        GameSetup, MatchConfig, SetupPosition, ensure_engine_supports_variant, fen_for_variant,
+        replay_saved_game, resolve_game_setup, resolve_start_state,
     },
     match_runtime::{
         logs::{human_runtime_log, push_runtime_log},
         owner::run_match_owner,
+        publish::checkpoint_move_times,
         types::{
             CompletedGameTable, EngineSeatController, HumanGameCommand, HumanGameHandle,
             HumanMoveAck, HumanPlayer, HumanSeatController, MatchRuntime, MatchSeatController,
@@ -26,12 +28,11 @@ use crate::{
         },
     },
     presentation::HumanPlayerProfile,
-    rating::build_pair_rating_update,
     state::{AppState, MoveDebugContext},
     storage::{
         ensure_human_player, get_agent_version, get_match_series, get_pool, get_tournament,
-        insert_human_rating_snapshot, insert_match_series_tx, insert_rating_snapshot,
-        insert_tournament_tx, load_human_profile, load_pool_leaderboard, load_pool_openings,
+        insert_match_series_tx, insert_tournament_tx, load_human_profile, load_pool_openings,
+        record_pair_rating_update,
     },
 };
 
@@ -41,18 +42,31 @@ pub(crate) async fn create_human_game(
     pool_id: Uuid,
     engine_version_id: Uuid,
     human_plays_white: bool,
+    setup: Option<GameSetup>,
+    engine_skill_level: Option<u8>,
 ) -> Result<(Uuid, Uuid), ApiError> {
     let pool = get_pool(&state.db, pool_id).await?;
     let engine_version = get_agent_version(&state.db, engine_version_id).await?;
     ensure_engine_supports_variant(&engine_version, pool.variant)?;
     let human_player = ensure_human_player(&state.db).await?;
-    let openings = load_pool_openings(&state.db, &pool).await?;
-    let opening = openings.first().cloned();
-    let (board, start_fen) = resolve_start_state(MatchConfig {
-        variant: pool.variant,
-        opening: opening.as_ref(),
-        opening_seed: None,
-    })?;
+    let (opening, position) = match setup {
+        Some(setup) => (None, resolve_game_setup(pool.variant, &setup)?),
+        None => {
+            let openings = load_pool_openings(&state.db, &pool).await?;
+            let opening = openings.first().cloned();
+            let (board, start_fen) = resolve_start_state(MatchConfig {
+                variant: pool.variant,
+                opening: opening.as_ref(),
+                opening_seed: None,
+            })?;
+            let position = SetupPosition {
+                start_fen,
+                moves_uci: Vec::new(),
+                game: Game::new(board),
+            };
+            (opening, position)
+        }
+    };
     let match_id = Uuid::new_v4();
     let tournament_id = Uuid::new_v4();
     let created_at = Utc::now();
@@ -94,10 +108,12 @@ pub(crate) async fn create_human_game(
     insert_match_series_tx(&mut tx, &match_series).await?;
     tx.commit().await?;
     let mut logs = Vec::new();
-    let mut engine = build_adapter(engine_version);
+    let engine_options = engine_skill_level
+        .map(|level| vec![("Skill Level".to_string(), level.to_string())])
+        .unwrap_or_default();
+    let mut engine = build_adapter_with_options(engine_version, engine_options);
     engine.prepare(pool.variant, &mut logs).await?;
     engine.begin_game(&mut logs).await?;
-    let initial_hash = board.hash_without_ep();
     let (command_tx, command_rx) = tokio::sync::mpsc::channel(32);
     let human_side = if human_plays_white {
         cozy_chess::Color::White
@@ -131,11 +147,13 @@ pub(crate) async fn create_human_game(
         tournament_id,
         variant: pool.variant,
         time_control: pool.time_control.clone(),
-        start_fen: start_fen.clone(),
-        current_fen: start_fen.clone(),
-        board,
-        repetitions: HashMap::from([(initial_hash, 1)]),
-        move_history: Vec::new(),
+        current_fen: fen_for_variant(position.game.board(), pool.variant),
+        start_fen: position.start_fen,
+        game: position.game,
+        move_times_ms: vec![None; position.moves_uci.len()],
+        move_scores: vec![None; position.moves_uci.len()],
+        adjudication: AdjudicationConfig::default(),
+        move_history: position.moves_uci,
         white_time_left_ms: pool.time_control.initial_ms,
         black_time_left_ms: pool.time_control.initial_ms,
         max_plies: 300,
@@ -153,6 +171,7 @@ pub(crate) async fn create_human_game(
         name,
         match_series: match_series.clone(),
         completed_game_table: CompletedGameTable::Human,
+        rated: engine_skill_level.is_none(),
     };
     let created_log = human_runtime_log(
         &session,
@@ -164,11 +183,18 @@ pub(crate) async fn create_human_game(
         "human_player_id": human_player.id,
         "white_version_id": session.match_series.white_version_id,
         "black_version_id": session.match_series.black_version_id,
+        "engine_skill_level": engine_skill_level,
     }));
     push_runtime_log(&mut runtime.logs, created_log);
     state
         .human_games
-        .insert(match_series.id, HumanGameHandle { command_tx })
+        .insert(
+            match_series.id,
+            HumanGameHandle {
+                command_tx,
+                rated: session.rated,
+            },
+        )
         .await;
     tokio::spawn(run_match_owner(state.clone(), session, runtime, true));
 
@@ -204,20 +230,55 @@ pub(crate) async fn submit_human_move(
         })
         .await
         .map_err(|_| ApiError::Conflict("game owner is unavailable".to_string()))?;
-    match receive_ack
+    human_ack_result(
+        receive_ack
+            .await
+            .map_err(|_| ApiError::Conflict("game owner is unavailable".to_string()))?,
+    )
+}
+
+pub(crate) async fn submit_human_takeback(
+    state: AppState,
+    match_id: Uuid,
+    intent_id: Uuid,
+) -> Result<&'static str, ApiError> {
+    let session = state
+        .human_games
+        .get(match_id)
         .await
-        .map_err(|_| ApiError::Conflict("game owner is unavailable".to_string()))?
-    {
-        HumanMoveAck::Accepted => return Ok("accepted"),
-        HumanMoveAck::RejectedIllegal => {
-            return Err(ApiError::BadRequest("illegal move".to_string()));
-        }
+        .ok_or_else(|| ApiError::NotFound(format!("human game {match_id} not found")))?;
+    let (respond_to, receive_ack) = tokio::sync::oneshot::channel();
+    session
+        .command_tx
+        .send(HumanGameCommand::Takeback {
+            intent_id,
+            respond_to,
+        })
+        .await
+        .map_err(|_| ApiError::Conflict("game owner is unavailable".to_string()))?;
+    human_ack_result(
+        receive_ack
+            .await
+            .map_err(|_| ApiError::Conflict("game owner is unavailable".to_string()))?,
+    )
+}
+
+fn human_ack_result(ack: HumanMoveAck) -> Result<&'static str, ApiError> {
+    match ack {
+        HumanMoveAck::Accepted => Ok("accepted"),
+        HumanMoveAck::RejectedIllegal => Err(ApiError::BadRequest("illegal move".to_string())),
         HumanMoveAck::RejectedNotYourTurn => {
-            return Err(ApiError::Conflict("it is not your turn".to_string()));
+            Err(ApiError::Conflict("it is not your turn".to_string()))
         }
         HumanMoveAck::RejectedGameFinished => {
-            return Err(ApiError::Conflict("game is no longer running".to_string()));
+            Err(ApiError::Conflict("game is no longer running".to_string()))
         }
+        HumanMoveAck::RejectedNothingToTakeBack => Err(ApiError::Conflict(
+            "there is no move of yours to take back".to_string(),
+        )),
+        HumanMoveAck::RejectedRatedGame => Err(ApiError::Conflict(
+            "takebacks are not allowed in rated games".to_string(),
+        )),
     }
 }
 
@@ -241,17 +302,9 @@ pub(crate) async fn restore_human_game(
     } else {
         cozy_chess::Color::Black
     };
-    let (mut board, start_fen) = parse_saved_board(pool.variant, &checkpoint.start_fen)
-        .map_err(|err| ApiError::Conflict(format!("failed to restore start FEN: {err}")))?;
-    let mut repetitions = HashMap::from([(board.hash_without_ep(), 1_u8)]);
-    for uci in &checkpoint.moves {
-        let mv = cozy_chess::util::parse_uci_move(&board, uci)
-            .map_err(|err| ApiError::Conflict(format!("failed to restore move {uci}: {err}")))?;
-        board
-            .try_play(mv)
-            .map_err(|_| ApiError::Conflict(format!("failed to replay restored move {uci}")))?;
-        *repetitions.entry(board.hash_without_ep()).or_insert(0) += 1;
-    }
+    let (game, start_fen) =
+        replay_saved_game(pool.variant, &checkpoint.start_fen, &checkpoint.moves)
+            .map_err(|err| ApiError::Conflict(format!("failed to restore game: {err:#}")))?;
 
     let mut logs = Vec::new();
     let mut engine = build_adapter(engine_version);
@@ -287,9 +340,11 @@ pub(crate) async fn restore_human_game(
         time_control: pool.time_control.clone(),
         start_fen,
         current_fen: checkpoint.fen.clone(),
-        board,
-        repetitions,
+        game,
         move_history: checkpoint.moves.clone(),
+        move_times_ms: checkpoint_move_times(&checkpoint),
+        move_scores: vec![None; checkpoint.moves.len()],
+        adjudication: AdjudicationConfig::default(),
         white_time_left_ms: checkpoint.white_remaining_ms,
         black_time_left_ms: checkpoint.black_remaining_ms,
         max_plies: 300,
@@ -326,6 +381,9 @@ pub(crate) async fn restore_human_game(
                 Some(arena_core::GameTermination::IllegalMove)
             }
             arena_core::LiveTermination::MoveLimit => Some(arena_core::GameTermination::MoveLimit),
+            arena_core::LiveTermination::Adjudication => {
+                Some(arena_core::GameTermination::Adjudication)
+            }
             arena_core::LiveTermination::EngineFailure => {
                 Some(arena_core::GameTermination::EngineFailure)
             }
@@ -341,10 +399,17 @@ pub(crate) async fn restore_human_game(
         name: tournament.name,
         match_series: match_series.clone(),
         completed_game_table: CompletedGameTable::Human,
+        rated: true,
     };
     state
         .human_games
-        .insert(match_series.id, HumanGameHandle { command_tx })
+        .insert(
+            match_series.id,
+            HumanGameHandle {
+                command_tx,
+                rated: session.rated,
+            },
+        )
         .await;
     tokio::spawn(run_match_owner(state.clone(), session, runtime, false));
     Ok(())
@@ -356,26 +421,11 @@ pub(crate) async fn apply_human_pool_rating_update(
     game: &GameRecord,
     human_player: &HumanPlayer,
 ) -> Result<()> {
-    let entries = load_pool_leaderboard(db, pool_id)
-        .await?
-        .into_iter()
-        .map(|entry| (entry.agent_version_id, entry))
-        .collect::<HashMap<_, _>>();
     let pair = arena_core::MatchPair {
         engine_a: game.white_version_id,
         engine_b: game.black_version_id,
         games: vec![game.clone()],
     };
-    let update = build_pair_rating_update(&entries, &pair);
-
-    if game.white_version_id == human_player.id {
-        insert_human_rating_snapshot(db, &snapshot_from_entry(Some(pool_id), &update.engine_a))
-            .await?;
-        insert_rating_snapshot(db, &snapshot_from_entry(Some(pool_id), &update.engine_b)).await?;
-    } else {
-        insert_rating_snapshot(db, &snapshot_from_entry(Some(pool_id), &update.engine_a)).await?;
-        insert_human_rating_snapshot(db, &snapshot_from_entry(Some(pool_id), &update.engine_b))
-            .await?;
-    }
+    record_pair_rating_update(db, pool_id, &pair, Some(human_player.id)).await?;
     Ok(())
 }
@@ -1,4 +1,5 @@
 // Legacy transition module. Do not add new behavior here. Move code into feature/bootstrap/storage/state submodules instead.
+mod analysis;
 mod api;
 mod bootstrap;
 mod debug;
@@ -15,6 +16,8 @@ mod registry_simple_toml;
 mod registry_sync;
 mod state;
 mod storage;
+#[cfg(test)]
+mod test_fixtures;
 mod tournaments;
 
 use axum::{
@@ -163,8 +166,8 @@ mod tests {
         let pools = list_pools(&db).await.unwrap();
         let leaderboard = load_aggregate_leaderboard(&db).await.unwrap();
 
-        assert!(agents.len() >= 1);
-        assert!(versions.len() >= 1);
+        assert!(!agents.is_empty());
+        assert!(!versions.is_empty());
         assert!(
             pools
                 .iter()
@@ -175,7 +178,7 @@ mod tests {
                 .iter()
                 .any(|pool| pool.registry_key.as_deref() == Some("starter-chess960-pool"))
         );
-        assert!(leaderboard.len() >= 1);
+        assert!(!leaderboard.is_empty());
     }
 
     #[tokio::test]
@@ -427,6 +430,7 @@ mod tests {
             start_fen: cozy_chess::Board::default().to_string(),
             fen: "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1".to_string(),
             moves: vec!["e2e4".to_string()],
+            move_times_ms: vec![Some(1_500)],
             white_remaining_ms: 60_000,
             black_remaining_ms: 60_000,
             side_to_move: arena_core::ProtocolLiveSide::Black,
@@ -442,6 +446,7 @@ mod tests {
                 &arena_core::LiveRuntimeCheckpoint {
                     seq: 1,
                     moves: Vec::new(),
+                    move_times_ms: Vec::new(),
                     ..checkpoint.clone()
                 },
             )),
@@ -462,6 +467,7 @@ mod tests {
         let snapshot = state.live_matches.get_snapshot(match_id).await.unwrap();
         assert_eq!(snapshot.seq, 2);
         assert_eq!(snapshot.moves, vec!["e2e4".to_string()]);
+        assert_eq!(snapshot.move_times_ms, vec![Some(1_500)]);
         let crate::live::ReplayResult::Replay(replay) =
             state.live_matches.replay_since(match_id, 0).await.unwrap()
         else {
@@ -588,6 +594,7 @@ mod tests {
                     .to_string(),
                 fen: "4r1k1/pp2rp1p/2pbn2q/Q2p4/3P4/1R1BP2P/PPP2PP1/1R5K w - - 0 29".to_string(),
                 moves: vec!["e1a5".to_string()],
+                move_times_ms: Vec::new(),
                 white_remaining_ms: 22_000,
                 black_remaining_ms: 22_000,
                 side_to_move: arena_core::ProtocolLiveSide::None,
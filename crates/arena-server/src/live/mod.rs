@@ -6,10 +6,11 @@ use std::{
 };
 
 use arena_core::{
-    ClockSyncEvent, GameResult, GameTermination, LiveEventEnvelope, LiveEventType,
-    LiveMatchSnapshot, LiveResult, LiveRuntimeCheckpoint, LiveStatus, MoveCommittedEvent,
-    ProtocolLiveSide,
+    ClockSyncEvent, EngineThinkingEvent, GameResult, GameTermination, LiveEventEnvelope,
+    LiveEventType, LiveMatchSnapshot, LiveResult, LiveRuntimeCheckpoint, LiveStatus,
+    MoveCommittedEvent, ProtocolLiveSide, uci_line_to_san,
 };
+use arena_runner::SearchProgress;
 use tracing::info;
 use uuid::Uuid;
 
@@ -129,6 +130,7 @@ impl LiveMatchStore {
             LiveEventEnvelope::MoveCommitted(_) => "move_committed",
             LiveEventEnvelope::ClockSync(_) => "clock_sync",
             LiveEventEnvelope::GameFinished(_) => "game_finished",
+            LiveEventEnvelope::EngineThinking(_) => "engine_thinking",
         };
         upsert_live_runtime_checkpoint(db, &checkpoint).await?;
         insert_live_runtime_event(db, &event).await?;
@@ -161,6 +163,20 @@ impl LiveMatchStore {
         Ok(())
     }
 
+    /// Send `event` to the match's subscribers only: it leaves the checkpoint
+    /// and the replay buffer alone, for progress nobody needs to catch up on.
+    pub(crate) async fn broadcast(&self, match_id: Uuid, event: LiveEventEnvelope) {
+        let sender = self
+            .entries
+            .read()
+            .await
+            .get(&match_id)
+            .map(|entry| entry.sender.clone());
+        if let Some(sender) = sender {
+            let _ = sender.send(event);
+        }
+    }
+
     pub(crate) async fn publish_transient(
         &self,
         checkpoint: LiveRuntimeCheckpoint,
@@ -230,6 +246,7 @@ pub(crate) fn snapshot_from_checkpoint(checkpoint: &LiveRuntimeCheckpoint) -> Li
         start_fen: checkpoint.start_fen.clone(),
         fen: checkpoint.fen.clone(),
         moves: checkpoint.moves.clone(),
+        move_times_ms: checkpoint.move_times_ms.clone(),
         white_remaining_ms: checkpoint.white_remaining_ms,
         black_remaining_ms: checkpoint.black_remaining_ms,
         side_to_move: checkpoint.side_to_move,
@@ -251,6 +268,7 @@ pub(crate) fn move_committed_from_checkpoint(
         start_fen: checkpoint.start_fen.clone(),
         fen: checkpoint.fen.clone(),
         moves: checkpoint.moves.clone(),
+        move_times_ms: checkpoint.move_times_ms.clone(),
         white_remaining_ms: checkpoint.white_remaining_ms,
         black_remaining_ms: checkpoint.black_remaining_ms,
         side_to_move: checkpoint.side_to_move,
@@ -273,6 +291,32 @@ pub(crate) fn clock_sync_from_checkpoint(checkpoint: &LiveRuntimeCheckpoint) ->
     }
 }
 
+/// The engine to move's `progress` in `board`, searched from the state at
+/// `seq`, with its principal variation in SAN.
+pub(crate) fn engine_thinking_event(
+    match_id: Uuid,
+    seq: u64,
+    board: &cozy_chess::Board,
+    progress: &SearchProgress,
+) -> EngineThinkingEvent {
+    EngineThinkingEvent {
+        protocol_version: LIVE_PROTOCOL_VERSION,
+        event_type: LiveEventType::EngineThinking,
+        match_id,
+        seq,
+        server_now_unix_ms: chrono::Utc::now().timestamp_millis(),
+        side: match board.side_to_move() {
+            cozy_chess::Color::White => ProtocolLiveSide::White,
+            cozy_chess::Color::Black => ProtocolLiveSide::Black,
+        },
+        depth: progress.depth,
+        score: progress.score,
+        nodes: progress.nodes,
+        nps: progress.nps,
+        pv: uci_line_to_san(board, &progress.pv),
+    }
+}
+
 pub(crate) fn game_finished_from_checkpoint(
     checkpoint: &LiveRuntimeCheckpoint,
 ) -> arena_core::GameFinishedEvent {
@@ -288,6 +332,7 @@ pub(crate) fn game_finished_from_checkpoint(
         start_fen: checkpoint.start_fen.clone(),
         fen: checkpoint.fen.clone(),
         moves: checkpoint.moves.clone(),
+        move_times_ms: checkpoint.move_times_ms.clone(),
         white_remaining_ms: checkpoint.white_remaining_ms,
         black_remaining_ms: checkpoint.black_remaining_ms,
         side_to_move: ProtocolLiveSide::None,
@@ -324,6 +369,7 @@ pub(crate) fn live_termination_from_game_termination(
         GameTermination::Resignation => arena_core::LiveTermination::Resignation,
         GameTermination::IllegalMove => arena_core::LiveTermination::IllegalMove,
         GameTermination::MoveLimit => arena_core::LiveTermination::MoveLimit,
+        GameTermination::Adjudication => arena_core::LiveTermination::Adjudication,
         GameTermination::EngineFailure => arena_core::LiveTermination::EngineFailure,
         GameTermination::Unknown => arena_core::LiveTermination::Abort,
     }
@@ -343,6 +389,7 @@ fn event_seq(event: &LiveEventEnvelope) -> u64 {
         LiveEventEnvelope::MoveCommitted(value) => value.seq,
         LiveEventEnvelope::ClockSync(value) => value.seq,
         LiveEventEnvelope::GameFinished(value) => value.seq,
+        LiveEventEnvelope::EngineThinking(value) => value.seq,
     }
 }
 
@@ -382,6 +429,7 @@ mod tests {
                 "4k3/8/8/8/8/8/8/4K3 b - - 0 1".to_string()
             },
             moves: moves.iter().map(|value| (*value).to_string()).collect(),
+            move_times_ms: Vec::new(),
             white_remaining_ms: 60_000,
             black_remaining_ms: 60_000,
             side_to_move: if status == LiveStatus::Running {
@@ -532,6 +580,7 @@ mod tests {
                     "4k3/8/8/8/8/8/8/4K3 b - - 0 1".to_string()
                 },
                 moves: moves.iter().map(|value| (*value).to_string()).collect(),
+                move_times_ms: Vec::new(),
                 white_remaining_ms: 60_000,
                 black_remaining_ms: 60_000,
                 side_to_move: ProtocolLiveSide::White,
@@ -1,15 +1,18 @@
 use anyhow::Result;
 use arena_core::{GameResult, MatchStatus};
-use arena_runner::{AgentAdapter, calculate_move_budget};
+use arena_runner::{AgentAdapter, SearchLimit, SearchOutcome, SearchProgress, adjudicate};
 use chrono::Utc;
+use serde_json::json;
+use tracing::debug;
 
 use crate::{ApiError, gameplay::fen_for_variant, state::AppState};
 
 use super::{
     logs::{match_runtime_log, match_runtime_source, push_runtime_log},
     publish::{
-        CLOCK_SYNC_INTERVAL_MS, elapsed_since_turn_start_ms, emit_match_clock_sync,
-        publish_match_runtime, remaining_turn_time_ms, update_terminal_state,
+        CLOCK_SYNC_INTERVAL_MS, THINKING_INTERVAL_MS, broadcast_engine_thinking,
+        elapsed_since_turn_start_ms, emit_match_clock_sync, publish_match_runtime,
+        remaining_turn_time_ms, update_terminal_state,
     },
     types::{MatchRuntime, MatchSeatController, MatchSession},
 };
@@ -21,7 +24,7 @@ pub(crate) async fn process_engine_turn(
     side: cozy_chess::Color,
 ) -> Result<(), ApiError> {
     let source = match_runtime_source(session);
-    if runtime.status != MatchStatus::Running || runtime.board.side_to_move() != side {
+    if runtime.status != MatchStatus::Running || runtime.game.board().side_to_move() != side {
         return Ok(());
     }
     if runtime.move_history.len() as u16 >= runtime.max_plies {
@@ -32,17 +35,14 @@ pub(crate) async fn process_engine_turn(
         return Ok(());
     }
     let increment_ms = runtime.time_control.increment_ms;
-    let movetime_ms = calculate_move_budget(
-        if side == cozy_chess::Color::White {
-            runtime.white_time_left_ms
-        } else {
-            runtime.black_time_left_ms
-        },
+    let limit = SearchLimit::Clock {
+        white_ms: runtime.white_time_left_ms,
+        black_ms: runtime.black_time_left_ms,
         increment_ms,
-    );
+    };
     let start_fen = runtime.start_fen.clone();
     let move_history = runtime.move_history.clone();
-    let board = runtime.board.clone();
+    let board = runtime.game.board().clone();
     let handled_at = Utc::now();
     let started_log = match_runtime_log(
         session,
@@ -65,14 +65,21 @@ pub(crate) async fn process_engine_turn(
         CLOCK_SYNC_INTERVAL_MS.min(remaining.max(1)),
     ));
     tokio::pin!(sync);
+    // The watch channel keeps only the newest report, so however many `info`
+    // lines arrive between ticks, spectators get one event per tick.
+    let (progress_tx, mut progress_rx) = tokio::sync::watch::channel(SearchProgress::default());
+    adapter.watch_progress(progress_tx);
+    let mut thinking =
+        tokio::time::interval(std::time::Duration::from_millis(THINKING_INTERVAL_MS));
+    thinking.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
     enum EngineTurnOutcome {
-        Move(String),
+        Move(SearchOutcome),
         Timeout,
         Error(anyhow::Error),
     }
 
     let selected = {
-        let choose = adapter.choose_move(&board, &start_fen, &move_history, movetime_ms, &mut logs);
+        let choose = adapter.search(&board, &start_fen, &move_history, limit, &mut logs);
         tokio::pin!(choose);
         loop {
             tokio::select! {
@@ -81,6 +88,13 @@ pub(crate) async fn process_engine_turn(
                     Err(err) => EngineTurnOutcome::Error(err),
                 },
                 _ = &mut timeout => break EngineTurnOutcome::Timeout,
+                _ = thinking.tick() => {
+                    if progress_rx.has_changed().unwrap_or(false) {
+                        let progress = progress_rx.borrow_and_update().clone();
+                        broadcast_engine_thinking(state, session, runtime.seq, &board, &progress)
+                            .await;
+                    }
+                }
                 _ = &mut sync => {
                     emit_match_clock_sync(state, session, runtime, source).await?;
                     let next_delay = CLOCK_SYNC_INTERVAL_MS.min(remaining_turn_time_ms(runtime).max(1));
@@ -89,6 +103,10 @@ pub(crate) async fn process_engine_turn(
             }
         }
     };
+    let stop_error = match selected {
+        EngineTurnOutcome::Timeout => adapter.stop(&mut logs).await.err(),
+        _ => None,
+    };
     restore_engine_adapter(runtime, side, adapter);
     runtime.logs = logs;
     let elapsed_ms = elapsed_since_turn_start_ms(runtime);
@@ -99,7 +117,20 @@ pub(crate) async fn process_engine_turn(
     };
     *clock = clock.saturating_sub(elapsed_ms);
     match selected {
-        EngineTurnOutcome::Move(selected) => {
+        EngineTurnOutcome::Move(SearchOutcome {
+            best_move: selected,
+            score,
+            depth,
+            seldepth,
+            nodes,
+        }) => {
+            let ply = runtime.move_history.len() + 1;
+            let fen_before = fen_for_variant(&board, runtime.variant);
+            debug!(
+                match_id = %session.match_series.id,
+                ply,
+                "{side:?} played {selected} (score {score:?}, depth {depth:?}, seldepth {seldepth:?}, nodes {nodes:?}, {elapsed_ms}ms)"
+            );
             let returned_log = match_runtime_log(
                 session,
                 runtime,
@@ -107,7 +138,16 @@ pub(crate) async fn process_engine_turn(
                 "engine.move_returned",
                 format!("engine returned {selected}"),
             )
-            .with_move_uci(selected.clone());
+            .with_move_uci(selected.clone())
+            .with_fields(json!({
+                "ply": ply,
+                "fen_before": &fen_before,
+                "score": score,
+                "depth": depth,
+                "seldepth": seldepth,
+                "nodes": nodes,
+                "elapsed_ms": elapsed_ms,
+            }));
             push_runtime_log(&mut runtime.logs, returned_log);
             if elapsed_ms >= remaining {
                 runtime.result = Some(if side == cozy_chess::Color::White {
@@ -126,34 +166,24 @@ pub(crate) async fn process_engine_turn(
                 );
                 push_runtime_log(&mut runtime.logs, timeout_log);
             } else if selected == "0000" {
-                runtime.result = Some(GameResult::Draw);
-                runtime.termination = Some(arena_core::GameTermination::EngineFailure);
-                runtime.status = MatchStatus::Completed;
-            } else if let Ok(mv) = cozy_chess::util::parse_uci_move(&runtime.board, &selected) {
-                if runtime.board.try_play(mv).is_err() {
-                    runtime.result = Some(if side == cozy_chess::Color::White {
-                        GameResult::BlackWin
-                    } else {
-                        GameResult::WhiteWin
-                    });
-                    runtime.termination = Some(arena_core::GameTermination::IllegalMove);
-                    runtime.status = MatchStatus::Completed;
+                if let Some((result, termination)) = no_move_outcome(runtime.game.board()) {
+                    runtime.result = Some(result);
+                    runtime.termination = Some(termination);
                 } else {
-                    runtime.move_history.push(selected);
-                    runtime.current_fen = fen_for_variant(&runtime.board, runtime.variant);
-                    let board_hash = runtime.board.hash_without_ep();
-                    *runtime.repetitions.entry(board_hash).or_insert(0) += 1;
-                    if side == cozy_chess::Color::White {
-                        runtime.white_time_left_ms =
-                            runtime.white_time_left_ms.saturating_add(increment_ms);
-                    } else {
-                        runtime.black_time_left_ms =
-                            runtime.black_time_left_ms.saturating_add(increment_ms);
-                    }
-                    runtime.turn_started_server_unix_ms = handled_at.timestamp_millis();
-                    update_terminal_state(runtime);
+                    runtime.result = Some(GameResult::Draw);
+                    runtime.termination = Some(arena_core::GameTermination::EngineFailure);
+                    let failure_log = match_runtime_log(
+                        session,
+                        runtime,
+                        source,
+                        "engine.move_failed",
+                        "engine returned no move in a playable position",
+                    )
+                    .with_fields(json!({ "ply": ply, "fen_before": fen_before }));
+                    push_runtime_log(&mut runtime.logs, failure_log);
                 }
-            } else {
+                runtime.status = MatchStatus::Completed;
+            } else if runtime.game.play_uci(&selected).is_err() {
                 runtime.result = Some(if side == cozy_chess::Color::White {
                     GameResult::BlackWin
                 } else {
@@ -161,6 +191,39 @@ pub(crate) async fn process_engine_turn(
                 });
                 runtime.termination = Some(arena_core::GameTermination::IllegalMove);
                 runtime.status = MatchStatus::Completed;
+                let illegal_log = match_runtime_log(
+                    session,
+                    runtime,
+                    source,
+                    "engine.illegal_move",
+                    format!("engine forfeited with illegal move {selected}"),
+                )
+                .with_move_uci(selected)
+                .with_fields(json!({ "ply": ply, "fen_before": fen_before }));
+                push_runtime_log(&mut runtime.logs, illegal_log);
+            } else {
+                runtime.move_history.push(selected);
+                runtime.move_times_ms.push(Some(elapsed_ms));
+                runtime.move_scores.push(score.map(|score| {
+                    if side == cozy_chess::Color::White {
+                        score
+                    } else {
+                        score.flipped()
+                    }
+                }));
+                runtime.current_fen = fen_for_variant(runtime.game.board(), runtime.variant);
+                if side == cozy_chess::Color::White {
+                    runtime.white_time_left_ms =
+                        runtime.white_time_left_ms.saturating_add(increment_ms);
+                } else {
+                    runtime.black_time_left_ms =
+                        runtime.black_time_left_ms.saturating_add(increment_ms);
+                }
+                runtime.turn_started_server_unix_ms = handled_at.timestamp_millis();
+                update_terminal_state(runtime);
+                if runtime.status == MatchStatus::Running {
+                    adjudicate_by_score(session, runtime, source);
+                }
             }
         }
         EngineTurnOutcome::Timeout => {
@@ -171,6 +234,16 @@ pub(crate) async fn process_engine_turn(
             });
             runtime.termination = Some(arena_core::GameTermination::Timeout);
             runtime.status = MatchStatus::Completed;
+            if let Some(err) = stop_error {
+                let stop_log = match_runtime_log(
+                    session,
+                    runtime,
+                    source,
+                    "engine.stop_failed",
+                    format!("engine did not acknowledge stop: {err}"),
+                );
+                push_runtime_log(&mut runtime.logs, stop_log);
+            }
         }
         EngineTurnOutcome::Error(err) => {
             runtime.result = Some(if side == cozy_chess::Color::White {
@@ -193,6 +266,42 @@ pub(crate) async fn process_engine_turn(
     publish_match_runtime(state, session, runtime, false).await
 }
 
+/// End the game early when the pool's adjudication rules agree with the
+/// scores both engines have been reporting.
+fn adjudicate_by_score(session: &MatchSession, runtime: &mut MatchRuntime, source: &str) {
+    let Some((result, termination)) = adjudicate(
+        &runtime.adjudication,
+        &runtime.move_scores,
+        runtime.game.board().fullmove_number(),
+    ) else {
+        return;
+    };
+    runtime.result = Some(result);
+    runtime.termination = Some(termination);
+    runtime.status = MatchStatus::Completed;
+    let adjudication_log = match_runtime_log(
+        session,
+        runtime,
+        source,
+        "adjudication.applied",
+        match result {
+            GameResult::Draw => "draw adjudicated from engine scores",
+            _ => "loss adjudicated from engine scores",
+        },
+    );
+    push_runtime_log(&mut runtime.logs, adjudication_log);
+}
+
+/// How the game ends when the engine answers with the null move: by the
+/// board itself if it has no legal moves, otherwise `None` because the
+/// engine gave up in a playable position.
+pub(crate) fn no_move_outcome(
+    board: &cozy_chess::Board,
+) -> Option<(GameResult, arena_core::GameTermination)> {
+    (board.status() != cozy_chess::GameStatus::Ongoing)
+        .then(|| arena_runner::classify_terminal_board(board))
+}
+
 fn take_engine_adapter(
     runtime: &mut MatchRuntime,
     side: cozy_chess::Color,
@@ -68,6 +68,7 @@ pub(crate) async fn finalize_match_game(
             &runtime.start_fen,
             &runtime.move_history,
             result,
+            termination,
         ),
         moves_uci: runtime.move_history.clone(),
         white_time_left_ms: runtime.white_time_left_ms,
@@ -90,7 +91,7 @@ pub(crate) async fn finalize_match_game(
         event,
     )
     .await?;
-    if let Some(human_player) = human_player {
+    if let Some(human_player) = human_player.filter(|_| session.rated) {
         apply_human_pool_rating_update(
             &state.db,
             session.match_series.pool_id,
@@ -102,17 +103,55 @@ pub(crate) async fn finalize_match_game(
     Ok(game)
 }
 
+/// Abandon a running game without recording a result, as happens when its
+/// tournament is stopped mid-game. Live viewers receive an aborted finish.
+pub(crate) async fn abort_match_game(
+    state: &AppState,
+    session: MatchSession,
+    mut runtime: MatchRuntime,
+) -> Result<(), ApiError> {
+    shutdown_match_seats(&mut runtime).await;
+    let abort_log = match_runtime_log(
+        &session,
+        &runtime,
+        match_runtime_source(&session),
+        "game.aborted",
+        "game aborted because the tournament was stopped",
+    );
+    push_runtime_log(&mut runtime.logs, abort_log);
+    runtime.seq += 1;
+    runtime.status = MatchStatus::Skipped;
+    let mut checkpoint = match_checkpoint(&session, &runtime);
+    checkpoint.termination = arena_core::LiveTermination::Abort;
+    let event =
+        arena_core::LiveEventEnvelope::GameFinished(game_finished_from_checkpoint(&checkpoint));
+    let mut tx: Transaction<'_, Sqlite> = state.db.begin().await?;
+    update_match_series_status_tx(&mut tx, session.match_series.id, MatchStatus::Skipped).await?;
+    upsert_live_runtime_checkpoint_tx(&mut tx, &checkpoint).await?;
+    insert_live_runtime_event_tx(&mut tx, &event).await?;
+    tx.commit().await?;
+    crate::live::publish_transient_with_metrics(
+        &state.live_matches,
+        Some(&state.live_metrics),
+        checkpoint,
+        event,
+    )
+    .await;
+    Ok(())
+}
+
 pub(crate) async fn shutdown_match_seats(runtime: &mut MatchRuntime) {
     for seat in [&mut runtime.white_seat, &mut runtime.black_seat] {
-        if let MatchSeatController::Engine(engine) = seat {
-            if let Some(mut adapter) = engine.adapter.take() {
-                adapter.shutdown(&mut runtime.logs).await.ok();
-                engine.adapter = Some(adapter);
-            }
+        if let MatchSeatController::Engine(engine) = seat
+            && let Some(mut adapter) = engine.adapter.take()
+        {
+            adapter.shutdown(&mut runtime.logs).await.ok();
+            engine.adapter = Some(adapter);
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn persist_terminal_match_state(
     state: &AppState,
     completed_game_table: CompletedGameTable,
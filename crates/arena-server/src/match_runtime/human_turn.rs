@@ -74,7 +74,7 @@ pub(crate) async fn process_human_move(
         insert_human_ack(runtime, side, intent_id, HumanMoveAck::RejectedGameFinished);
         return HumanMoveAck::RejectedGameFinished;
     }
-    if runtime.board.side_to_move() != side {
+    if runtime.game.board().side_to_move() != side {
         let rejected_log = match_runtime_log(
             session,
             runtime,
@@ -128,7 +128,7 @@ pub(crate) async fn process_human_move(
         insert_human_ack(runtime, side, intent_id, HumanMoveAck::RejectedGameFinished);
         return HumanMoveAck::RejectedGameFinished;
     }
-    let Ok(mv) = cozy_chess::util::parse_uci_move(&runtime.board, &move_uci) else {
+    let Ok(mv) = cozy_chess::util::parse_uci_move(runtime.game.board(), &move_uci) else {
         let rejected_log = match_runtime_log(
             session,
             runtime,
@@ -142,7 +142,7 @@ pub(crate) async fn process_human_move(
         insert_human_ack(runtime, side, intent_id, HumanMoveAck::RejectedIllegal);
         return HumanMoveAck::RejectedIllegal;
     };
-    if runtime.board.try_play(mv).is_err() {
+    if runtime.game.play(mv).is_err() {
         let rejected_log = match_runtime_log(
             session,
             runtime,
@@ -157,9 +157,9 @@ pub(crate) async fn process_human_move(
         return HumanMoveAck::RejectedIllegal;
     }
     runtime.move_history.push(move_uci.clone());
-    runtime.current_fen = fen_for_variant(&runtime.board, runtime.variant);
-    let board_hash = runtime.board.hash_without_ep();
-    *runtime.repetitions.entry(board_hash).or_insert(0) += 1;
+    runtime.move_times_ms.push(Some(elapsed_ms));
+    runtime.move_scores.push(None);
+    runtime.current_fen = fen_for_variant(runtime.game.board(), runtime.variant);
     if side == cozy_chess::Color::White {
         runtime.white_time_left_ms = runtime.white_time_left_ms.saturating_add(increment_ms);
     } else {
@@ -186,6 +186,82 @@ pub(crate) async fn process_human_move(
     HumanMoveAck::Accepted
 }
 
+/// Take back the engine's last reply and the human move before it, returning
+/// the game to the human's previous turn. Time already spent stays spent.
+/// Rated games refuse takebacks, since their result moves the human's rating.
+pub(crate) async fn process_human_takeback(
+    state: &AppState,
+    session: &MatchSession,
+    runtime: &mut MatchRuntime,
+    side: cozy_chess::Color,
+    intent_id: Uuid,
+) -> HumanMoveAck {
+    let source = match_runtime_source(session);
+    let seen_intents = match side {
+        cozy_chess::Color::White => match &runtime.white_seat {
+            MatchSeatController::Human(controller) => &controller.seen_intents,
+            MatchSeatController::Engine(_) => return HumanMoveAck::RejectedNotYourTurn,
+        },
+        cozy_chess::Color::Black => match &runtime.black_seat {
+            MatchSeatController::Human(controller) => &controller.seen_intents,
+            MatchSeatController::Engine(_) => return HumanMoveAck::RejectedNotYourTurn,
+        },
+    };
+    if let Some(previous) = seen_intents.get(&intent_id).copied() {
+        return previous;
+    }
+    if runtime.status != MatchStatus::Running {
+        insert_human_ack(runtime, side, intent_id, HumanMoveAck::RejectedGameFinished);
+        return HumanMoveAck::RejectedGameFinished;
+    }
+    if session.rated {
+        insert_human_ack(runtime, side, intent_id, HumanMoveAck::RejectedRatedGame);
+        return HumanMoveAck::RejectedRatedGame;
+    }
+    if runtime.game.board().side_to_move() != side {
+        insert_human_ack(runtime, side, intent_id, HumanMoveAck::RejectedNotYourTurn);
+        return HumanMoveAck::RejectedNotYourTurn;
+    }
+    if runtime.move_history.len() < 2 {
+        insert_human_ack(
+            runtime,
+            side,
+            intent_id,
+            HumanMoveAck::RejectedNothingToTakeBack,
+        );
+        return HumanMoveAck::RejectedNothingToTakeBack;
+    }
+
+    let kept_plies = runtime.move_history.len() - 2;
+    let handled_at = Utc::now();
+    let elapsed_ms = elapsed_since_turn_start_ms(runtime);
+    let clock = if side == cozy_chess::Color::White {
+        &mut runtime.white_time_left_ms
+    } else {
+        &mut runtime.black_time_left_ms
+    };
+    *clock = clock.saturating_sub(elapsed_ms);
+    let taken_back = runtime.move_history.split_off(kept_plies);
+    runtime.move_times_ms.truncate(kept_plies);
+    runtime.move_scores.truncate(kept_plies);
+    runtime.game.undo();
+    runtime.game.undo();
+    runtime.current_fen = fen_for_variant(runtime.game.board(), runtime.variant);
+    runtime.turn_started_server_unix_ms = handled_at.timestamp_millis();
+    let takeback_log = match_runtime_log(
+        session,
+        runtime,
+        source,
+        "move.taken_back",
+        format!("human took back {}", taken_back.join(" ")),
+    )
+    .with_fields(json!({ "intent_id": intent_id }));
+    push_runtime_log(&mut runtime.logs, takeback_log);
+    let _ = publish_match_runtime(state, session, runtime, true).await;
+    insert_human_ack(runtime, side, intent_id, HumanMoveAck::Accepted);
+    HumanMoveAck::Accepted
+}
+
 fn insert_human_ack(
     runtime: &mut MatchRuntime,
     side: cozy_chess::Color,
@@ -295,6 +371,14 @@ pub(crate) async fn process_human_turn(
                 let _ = respond_to.send(ack);
                 return Ok(());
             }
+            HumanGameCommand::Takeback {
+                intent_id,
+                respond_to,
+            } => {
+                let ack = process_human_takeback(state, session, runtime, side, intent_id).await;
+                let _ = respond_to.send(ack);
+                return Ok(());
+            }
         }
     }
 }
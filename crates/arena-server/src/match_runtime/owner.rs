@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use anyhow::Result;
 use arena_core::{GameRecord, MatchStatus};
 
@@ -5,7 +7,7 @@ use crate::{ApiError, state::AppState};
 
 use super::{
     engine_turn::process_engine_turn,
-    finalize::finalize_match_game,
+    finalize::{abort_match_game, finalize_match_game},
     human_turn::process_human_turn,
     publish::publish_match_runtime,
     types::{MatchRuntime, MatchSeatController, MatchSession},
@@ -38,6 +40,31 @@ pub(crate) async fn run_match_to_completion(
     }
 }
 
+/// Like [`run_match_to_completion`], but checks `stop_flag` before every
+/// turn. A stopped game is aborted and yields `Ok(None)`.
+pub(crate) async fn run_match_until_stopped(
+    state: &AppState,
+    session: MatchSession,
+    mut runtime: MatchRuntime,
+    publish_initial_snapshot: bool,
+    stop_flag: &AtomicBool,
+) -> Result<Option<GameRecord>, ApiError> {
+    if publish_initial_snapshot {
+        publish_match_runtime(state, &session, &mut runtime, true).await?;
+    }
+
+    loop {
+        if runtime.status != MatchStatus::Running {
+            return finalize_match_game(state, session, runtime).await.map(Some);
+        }
+        if stop_flag.load(Ordering::SeqCst) {
+            abort_match_game(state, session, runtime).await?;
+            return Ok(None);
+        }
+        process_active_turn(state, &session, &mut runtime).await?;
+    }
+}
+
 pub(crate) async fn process_active_turn(
     state: &AppState,
     session: &MatchSession,
@@ -1,14 +1,15 @@
 use anyhow::Result;
 use arena_core::{GameResult, LiveRuntimeCheckpoint, MatchStatus};
+use arena_runner::SearchProgress;
 use chrono::Utc;
 
 use crate::{
     ApiError,
     live::{
-        clock_sync_from_checkpoint, live_result_from_game_result, live_status_from_match_status,
-        live_termination_from_game_termination, move_committed_from_checkpoint,
-        publish_transient_with_metrics, publish_with_metrics, side_from_fen,
-        snapshot_from_checkpoint,
+        clock_sync_from_checkpoint, engine_thinking_event, live_result_from_game_result,
+        live_status_from_match_status, live_termination_from_game_termination,
+        move_committed_from_checkpoint, publish_transient_with_metrics, publish_with_metrics,
+        side_from_fen, snapshot_from_checkpoint,
     },
     state::AppState,
 };
@@ -19,6 +20,8 @@ use super::{
 };
 
 pub(crate) const CLOCK_SYNC_INTERVAL_MS: u64 = 1_000;
+/// Engine progress goes out at most this often, however chatty the engine.
+pub(crate) const THINKING_INTERVAL_MS: u64 = 100;
 
 pub(crate) fn match_checkpoint(
     session: &MatchSession,
@@ -40,6 +43,7 @@ pub(crate) fn match_checkpoint(
         start_fen: runtime.start_fen.clone(),
         fen: runtime.current_fen.clone(),
         moves: runtime.move_history.clone(),
+        move_times_ms: runtime.move_times_ms.clone(),
         white_remaining_ms: runtime.white_time_left_ms,
         black_remaining_ms: runtime.black_time_left_ms,
         side_to_move: if runtime.status == MatchStatus::Running {
@@ -52,6 +56,16 @@ pub(crate) fn match_checkpoint(
     }
 }
 
+/// Move times restored from `checkpoint`, padded at the front with `None`
+/// when it was written before move times were recorded.
+pub(crate) fn checkpoint_move_times(checkpoint: &LiveRuntimeCheckpoint) -> Vec<Option<u64>> {
+    let mut times = checkpoint.move_times_ms.clone();
+    times.truncate(checkpoint.moves.len());
+    let missing = checkpoint.moves.len() - times.len();
+    times.splice(0..0, std::iter::repeat_n(None, missing));
+    times
+}
+
 pub(crate) fn elapsed_since_turn_start_ms(runtime: &MatchRuntime) -> u64 {
     Utc::now()
         .timestamp_millis()
@@ -59,7 +73,7 @@ pub(crate) fn elapsed_since_turn_start_ms(runtime: &MatchRuntime) -> u64 {
 }
 
 pub(crate) fn remaining_turn_time_ms(runtime: &MatchRuntime) -> u64 {
-    let remaining = if runtime.board.side_to_move() == cozy_chess::Color::White {
+    let remaining = if runtime.game.board().side_to_move() == cozy_chess::Color::White {
         runtime.white_time_left_ms
     } else {
         runtime.black_time_left_ms
@@ -80,7 +94,7 @@ pub(crate) async fn emit_match_clock_sync(
     if elapsed_ms == 0 {
         return Ok(());
     }
-    if runtime.board.side_to_move() == cozy_chess::Color::White {
+    if runtime.game.board().side_to_move() == cozy_chess::Color::White {
         runtime.white_time_left_ms = runtime.white_time_left_ms.saturating_sub(elapsed_ms);
     } else {
         runtime.black_time_left_ms = runtime.black_time_left_ms.saturating_sub(elapsed_ms);
@@ -106,6 +120,27 @@ pub(crate) async fn emit_match_clock_sync(
     Ok(())
 }
 
+/// Show spectators what the engine to move in `board` is thinking. Broadcast
+/// only: the event carries the `seq` it was searched from, and the next
+/// committed move supersedes it.
+pub(crate) async fn broadcast_engine_thinking(
+    state: &AppState,
+    session: &MatchSession,
+    seq: u64,
+    board: &cozy_chess::Board,
+    progress: &SearchProgress,
+) {
+    let match_id = session.match_series.id;
+    let event = engine_thinking_event(match_id, seq, board, progress);
+    state
+        .live_matches
+        .broadcast(
+            match_id,
+            arena_core::LiveEventEnvelope::EngineThinking(event),
+        )
+        .await;
+}
+
 pub(crate) async fn finalize_timeout(
     state: &AppState,
     session: &MatchSession,
@@ -116,7 +151,7 @@ pub(crate) async fn finalize_timeout(
         return Ok(());
     }
     runtime.result = Some(
-        if runtime.board.side_to_move() == cozy_chess::Color::White {
+        if runtime.game.board().side_to_move() == cozy_chess::Color::White {
             GameResult::BlackWin
         } else {
             GameResult::WhiteWin
@@ -124,7 +159,7 @@ pub(crate) async fn finalize_timeout(
     );
     runtime.termination = Some(arena_core::GameTermination::Timeout);
     runtime.status = MatchStatus::Completed;
-    if runtime.board.side_to_move() == cozy_chess::Color::White {
+    if runtime.game.board().side_to_move() == cozy_chess::Color::White {
         runtime.white_time_left_ms = 0;
     } else {
         runtime.black_time_left_ms = 0;
@@ -174,14 +209,7 @@ pub(crate) async fn publish_match_runtime(
 }
 
 pub(crate) fn update_terminal_state(runtime: &mut MatchRuntime) {
-    if let Some((result, termination)) =
-        arena_runner::classify_position(&runtime.board, &runtime.repetitions)
-    {
-        runtime.result = Some(result);
-        runtime.termination = Some(termination);
-        runtime.status = MatchStatus::Completed;
-    } else if runtime.board.status() != cozy_chess::GameStatus::Ongoing {
-        let (result, termination) = arena_runner::classify_terminal_board(&runtime.board);
+    if let Some((result, termination)) = runtime.game.outcome() {
         runtime.result = Some(result);
         runtime.termination = Some(termination);
         runtime.status = MatchStatus::Completed;
@@ -1,14 +1,17 @@
 use std::collections::HashMap;
 
-use arena_core::{GameResult, MatchSeries, MatchStatus, TimeControl, Variant};
-use arena_runner::AgentAdapter;
+use arena_core::{
+    AdjudicationConfig, EngineScore, GameResult, MatchSeries, MatchStatus, TimeControl, Variant,
+};
+use arena_runner::{AgentAdapter, Game};
 use chrono::{DateTime, Utc};
-use cozy_chess::{Board, Color};
+use cozy_chess::Color;
 use uuid::Uuid;
 
 #[derive(Clone)]
 pub(crate) struct HumanGameHandle {
     pub(crate) command_tx: tokio::sync::mpsc::Sender<HumanGameCommand>,
+    pub(crate) rated: bool,
 }
 
 pub(crate) enum HumanGameCommand {
@@ -19,6 +22,11 @@ pub(crate) enum HumanGameCommand {
         move_uci: String,
         respond_to: tokio::sync::oneshot::Sender<HumanMoveAck>,
     },
+    /// Undo the engine's last reply and the human move before it.
+    Takeback {
+        intent_id: Uuid,
+        respond_to: tokio::sync::oneshot::Sender<HumanMoveAck>,
+    },
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -27,6 +35,8 @@ pub(crate) enum HumanMoveAck {
     RejectedIllegal,
     RejectedNotYourTurn,
     RejectedGameFinished,
+    RejectedNothingToTakeBack,
+    RejectedRatedGame,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -40,6 +50,9 @@ pub(crate) struct MatchSession {
     pub(crate) name: String,
     pub(crate) match_series: MatchSeries,
     pub(crate) completed_game_table: CompletedGameTable,
+    /// Whether a finished human game updates ratings; games against an engine
+    /// playing below full strength do not.
+    pub(crate) rated: bool,
 }
 
 pub(crate) struct MatchRuntime {
@@ -48,9 +61,17 @@ pub(crate) struct MatchRuntime {
     pub(crate) time_control: TimeControl,
     pub(crate) start_fen: String,
     pub(crate) current_fen: String,
-    pub(crate) board: Board,
-    pub(crate) repetitions: HashMap<u64, u8>,
+    /// The position and the moves that led to it; `move_history` keeps
+    /// those moves as the players sent them.
+    pub(crate) game: Game,
     pub(crate) move_history: Vec<String>,
+    /// Thinking time of each move in `move_history`, `None` for moves that
+    /// were not played here.
+    pub(crate) move_times_ms: Vec<Option<u64>>,
+    /// Score each engine reported for its move in `move_history`, from
+    /// White's point of view, `None` for human moves and silent engines.
+    pub(crate) move_scores: Vec<Option<EngineScore>>,
+    pub(crate) adjudication: AdjudicationConfig,
     pub(crate) white_time_left_ms: u64,
     pub(crate) black_time_left_ms: u64,
     pub(crate) max_plies: u16,
@@ -82,7 +103,7 @@ pub(crate) struct HumanSeatController {
 
 impl MatchRuntime {
     pub(crate) fn active_side(&self) -> Color {
-        self.board.side_to_move()
+        self.game.board().side_to_move()
     }
 
     pub(crate) fn has_human_seat(&self) -> bool {
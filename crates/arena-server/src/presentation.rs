@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
 use arena_core::{
-    AgentVersion, GameRecord, GameResult, LeaderboardEntry, LiveRuntimeCheckpoint, LiveStatus,
-    MatchSeries, MatchStatus, TournamentStatus, Variant,
+    AgentVersion, EloEstimate, GameRecord, GameResult, LeaderboardEntry, LiveRuntimeCheckpoint,
+    LiveStatus, MatchSeries, MatchStatus, TournamentStatus, Variant, elo_difference,
 };
 use chrono::{DateTime, Duration, Utc};
 use serde::Serialize;
@@ -41,6 +41,7 @@ pub(crate) struct ApiMatchSeries {
     pub(crate) white_participant: ApiParticipant,
     pub(crate) black_participant: ApiParticipant,
     pub(crate) interactive: bool,
+    pub(crate) takeback_allowed: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -82,6 +83,8 @@ pub(crate) struct ApiLeaderboardEntry {
     pub(crate) wins: u32,
     pub(crate) draws: u32,
     pub(crate) losses: u32,
+    /// Score-based Elo versus the field, with 95% margin and LOS.
+    pub(crate) performance: Option<EloEstimate>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -146,6 +149,7 @@ pub(crate) fn participant_for_id(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn api_match_series(
     series: &MatchSeries,
     status: MatchStatus,
@@ -154,6 +158,7 @@ pub(crate) fn api_match_series(
     version_name_by_id: &HashMap<Uuid, String>,
     human_player: &HumanPlayer,
     interactive: bool,
+    takeback_allowed: bool,
 ) -> ApiMatchSeries {
     ApiMatchSeries {
         id: series.id,
@@ -179,6 +184,7 @@ pub(crate) fn api_match_series(
             human_player,
         ),
         interactive,
+        takeback_allowed,
     }
 }
 
@@ -265,9 +271,7 @@ pub(crate) fn resolve_tournament_status(
     }
 
     if tournament_status == TournamentStatus::Failed
-        || match_statuses
-            .iter()
-            .any(|status| *status == MatchStatus::Failed)
+        || match_statuses.contains(&MatchStatus::Failed)
     {
         return TournamentStatus::Failed;
     }
@@ -323,5 +327,6 @@ pub(crate) fn api_leaderboard_entry(
         wins: entry.wins,
         draws: entry.draws,
         losses: entry.losses,
+        performance: elo_difference(entry.wins, entry.draws, entry.losses),
     }
 }
@@ -63,10 +63,12 @@ mod tests {
     use crate::storage::{
         list_agent_versions, list_agents, list_event_presets, list_opening_suites, list_pools,
     };
-    use arena_core::{FairnessConfig, Variant};
+    use arena_core::{
+        AdjudicationConfig, DrawAdjudication, FairnessConfig, ResignAdjudication, Variant,
+    };
     use chrono::Utc;
     use sqlx::sqlite::SqlitePoolOptions;
-    use std::path::PathBuf;
+    use std::{collections::BTreeMap, path::PathBuf};
     use uuid::Uuid;
 
     #[tokio::test]
@@ -110,10 +112,19 @@ mod tests {
                 .iter()
                 .any(|suite| suite.registry_key.as_deref() == Some("starter-benchmark-suite"))
         );
-        assert!(
-            pools
-                .iter()
-                .any(|pool| pool.registry_key.as_deref() == Some("starter-standard-pool"))
+        let pool = pools
+            .iter()
+            .find(|pool| pool.registry_key.as_deref() == Some("starter-standard-pool"))
+            .unwrap();
+        assert_eq!(
+            pool.fairness.adjudication,
+            AdjudicationConfig {
+                resign: Some(ResignAdjudication::default()),
+                draw: Some(DrawAdjudication {
+                    move_count: 8,
+                    ..DrawAdjudication::default()
+                }),
+            }
         );
         assert!(
             list_event_presets(&db)
@@ -161,6 +172,10 @@ notes = "Updated notes"
 
 [env]
 MODEL_PATH = "models/latest.pt"
+
+[options]
+Threads = "1"
+"Move Overhead" = "50"
 "#,
         )
         .unwrap();
@@ -185,6 +200,13 @@ MODEL_PATH = "models/latest.pt"
             ]
         );
         assert_eq!(updated_version.notes.as_deref(), Some("Updated notes"));
+        assert_eq!(
+            updated_version.uci_options,
+            BTreeMap::from([
+                ("Move Overhead".to_string(), "50".to_string()),
+                ("Threads".to_string(), "1".to_string()),
+            ])
+        );
     }
 
     #[tokio::test]
@@ -212,6 +234,7 @@ MODEL_PATH = "models/latest.pt"
                 swap_colors: true,
                 opening_suite_id: None,
                 opening_seed: None,
+                adjudication: AdjudicationConfig::default(),
             })
             .unwrap(),
         )
@@ -411,6 +434,9 @@ paired_games = true
 swap_colors = true
 opening_suite_key = "starter-benchmark-suite"
 opening_seed = 7
+resign_adjudication = true
+draw_adjudication = true
+draw_move_count = 8
 active = true
 "#,
         )?;
@@ -7,13 +7,13 @@ use std::{
 
 use anyhow::{Context, Result, bail};
 use arena_core::{
-    AgentCapabilities, EventPresetSelectionMode, OpeningSourceKind, TimeControl, TournamentKind,
-    Variant,
+    AdjudicationConfig, AgentCapabilities, DrawAdjudication, EventPresetSelectionMode,
+    OpeningSourceKind, ResignAdjudication, TimeControl, TournamentKind, Variant,
 };
 use serde::Deserialize;
 use serde_json::Value;
 
-use crate::registry_simple_toml::parse_simple_toml;
+use crate::registry_simple_toml::{SimpleToml, parse_simple_toml};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct AgentRegistration {
@@ -33,6 +33,7 @@ pub(crate) struct AgentVersionRegistration {
     pub(crate) working_directory: Option<String>,
     pub(crate) args: Vec<String>,
     pub(crate) env: BTreeMap<String, String>,
+    pub(crate) uci_options: BTreeMap<String, String>,
     pub(crate) capabilities: AgentCapabilities,
     pub(crate) declared_name: Option<String>,
     pub(crate) tags: Vec<String>,
@@ -63,6 +64,7 @@ pub(crate) struct PoolRegistration {
     pub(crate) swap_colors: bool,
     pub(crate) opening_suite_key: Option<String>,
     pub(crate) opening_seed: Option<u64>,
+    pub(crate) adjudication: AdjudicationConfig,
     pub(crate) active: bool,
 }
 
@@ -102,6 +104,7 @@ struct EngineManifest {
     working_directory: Option<String>,
     args: Vec<String>,
     env: BTreeMap<String, String>,
+    uci_options: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -283,14 +286,14 @@ fn load_rust_engines(workspace_root: &Path) -> Result<Vec<EngineManifest>> {
                 )
             })?;
 
-        if let Some(launcher) = arena.launcher.as_deref() {
-            if launcher != "cargo_package" {
-                bail!(
-                    "unsupported launcher {launcher} for Rust engine {} in {}",
-                    package.name,
-                    manifest_path.display()
-                );
-            }
+        if let Some(launcher) = arena.launcher.as_deref()
+            && launcher != "cargo_package"
+        {
+            bail!(
+                "unsupported launcher {launcher} for Rust engine {} in {}",
+                package.name,
+                manifest_path.display()
+            );
         }
 
         engines.push(EngineManifest {
@@ -313,6 +316,7 @@ fn load_rust_engines(workspace_root: &Path) -> Result<Vec<EngineManifest>> {
             working_directory: Some(workspace_dir.clone()),
             args: Vec::new(),
             env: BTreeMap::new(),
+            uci_options: BTreeMap::new(),
         });
     }
 
@@ -405,6 +409,7 @@ fn load_command_engines(workspace_root: &Path) -> Result<Vec<EngineManifest>> {
             working_directory,
             args: document.optional_string_array("args")?,
             env: document.string_map("env")?,
+            uci_options: document.string_map("options")?,
         });
     }
 
@@ -462,6 +467,7 @@ fn split_engine_registrations(
             working_directory: manifest.working_directory,
             args: manifest.args,
             env: manifest.env,
+            uci_options: manifest.uci_options,
             capabilities: AgentCapabilities {
                 supported_variants: manifest.supported_variants,
             },
@@ -539,14 +545,14 @@ fn load_pools(
 
         let opening_suite_key =
             normalize_optional_string(document.optional_string("opening_suite_key")?);
-        if let Some(opening_suite_key) = &opening_suite_key {
-            if !opening_keys.contains(opening_suite_key) {
-                bail!(
-                    "pool {} references unknown opening suite key {}",
-                    path.display(),
-                    opening_suite_key
-                );
-            }
+        if let Some(opening_suite_key) = &opening_suite_key
+            && !opening_keys.contains(opening_suite_key)
+        {
+            bail!(
+                "pool {} references unknown opening suite key {}",
+                path.display(),
+                opening_suite_key
+            );
         }
 
         let opening_seed = document
@@ -574,6 +580,8 @@ fn load_pools(
             swap_colors: document.require_bool("swap_colors")?,
             opening_suite_key,
             opening_seed,
+            adjudication: parse_adjudication(&document)
+                .with_context(|| format!("invalid adjudication in {}", path.display()))?,
             active: document.optional_bool("active")?.unwrap_or(true),
         });
     }
@@ -630,6 +638,60 @@ fn load_event_presets(
     Ok(presets)
 }
 
+/// `resign_adjudication = true` and `draw_adjudication = true` turn the rules
+/// on with their defaults, which the `resign_*` and `draw_*` keys override.
+fn parse_adjudication(document: &SimpleToml) -> Result<AdjudicationConfig> {
+    let resign = if document
+        .optional_bool("resign_adjudication")?
+        .unwrap_or(false)
+    {
+        let defaults = ResignAdjudication::default();
+        Some(ResignAdjudication {
+            score_cp: match document.optional_integer("resign_score_cp")? {
+                Some(value) => value
+                    .try_into()
+                    .context("resign_score_cp is out of range")?,
+                None => defaults.score_cp,
+            },
+            move_count: match document.optional_integer("resign_move_count")? {
+                Some(value) => value
+                    .try_into()
+                    .context("resign_move_count is out of range")?,
+                None => defaults.move_count,
+            },
+        })
+    } else {
+        None
+    };
+    let draw = if document
+        .optional_bool("draw_adjudication")?
+        .unwrap_or(false)
+    {
+        let defaults = DrawAdjudication::default();
+        Some(DrawAdjudication {
+            score_cp: match document.optional_integer("draw_score_cp")? {
+                Some(value) => value.try_into().context("draw_score_cp is out of range")?,
+                None => defaults.score_cp,
+            },
+            move_count: match document.optional_integer("draw_move_count")? {
+                Some(value) => value
+                    .try_into()
+                    .context("draw_move_count is out of range")?,
+                None => defaults.move_count,
+            },
+            min_move_number: match document.optional_integer("draw_min_move_number")? {
+                Some(value) => value
+                    .try_into()
+                    .context("draw_min_move_number is out of range")?,
+                None => defaults.min_move_number,
+            },
+        })
+    } else {
+        None
+    };
+    Ok(AdjudicationConfig { resign, draw })
+}
+
 fn normalize_tags(mut tags: Vec<String>) -> Vec<String> {
     for tag in &mut tags {
         *tag = tag.trim().to_string();
@@ -107,7 +107,13 @@ pub(crate) fn parse_simple_toml(text: &str) -> Result<SimpleToml> {
         let (key, value) = line
             .split_once('=')
             .ok_or_else(|| anyhow!("invalid assignment on line {}", line_idx + 1))?;
-        let key = key.trim().to_string();
+        // UCI option names may contain spaces, so keys may be quoted.
+        let key = key.trim();
+        let key = key
+            .strip_prefix('"')
+            .and_then(|key| key.strip_suffix('"'))
+            .unwrap_or(key)
+            .to_string();
         let value = parse_simple_toml_value(value.trim())
             .with_context(|| format!("invalid value for {key} on line {}", line_idx + 1))?;
 
@@ -114,6 +114,7 @@ async fn sync_agents_and_versions(
                 working_directory: definition.working_directory.clone(),
                 args: definition.args.clone(),
                 env: definition.env.clone(),
+                uci_options: definition.uci_options.clone(),
                 capabilities: definition.capabilities.clone(),
                 declared_name: definition.declared_name.clone(),
                 tags: definition.tags.clone(),
@@ -131,6 +132,7 @@ async fn sync_agents_and_versions(
             || version.working_directory != definition.working_directory
             || version.args != definition.args
             || version.env != definition.env
+            || version.uci_options != definition.uci_options
             || version.capabilities != definition.capabilities
             || version.declared_name != definition.declared_name
             || version.tags != definition.tags
@@ -146,6 +148,7 @@ async fn sync_agents_and_versions(
             version.working_directory = definition.working_directory.clone();
             version.args = definition.args.clone();
             version.env = definition.env.clone();
+            version.uci_options = definition.uci_options.clone();
             version.capabilities = definition.capabilities.clone();
             version.declared_name = definition.declared_name.clone();
             version.tags = definition.tags.clone();
@@ -165,13 +168,13 @@ async fn sync_agents_and_versions(
         .map(|definition| definition.registry_key.as_str())
         .collect::<BTreeSet<_>>();
     for version in existing_versions {
-        if let Some(key) = version.registry_key.as_deref() {
-            if !version_keys.contains(key) {
-                sqlx::query("DELETE FROM agent_versions WHERE id = ?")
-                    .bind(version.id.to_string())
-                    .execute(db)
-                    .await?;
-            }
+        if let Some(key) = version.registry_key.as_deref()
+            && !version_keys.contains(key)
+        {
+            sqlx::query("DELETE FROM agent_versions WHERE id = ?")
+                .bind(version.id.to_string())
+                .execute(db)
+                .await?;
         }
     }
 
@@ -180,13 +183,13 @@ async fn sync_agents_and_versions(
         .map(|definition| definition.registry_key.as_str())
         .collect::<BTreeSet<_>>();
     for agent in existing_agents {
-        if let Some(key) = agent.registry_key.as_deref() {
-            if !agent_keys.contains(key) {
-                sqlx::query("DELETE FROM agents WHERE id = ?")
-                    .bind(agent.id.to_string())
-                    .execute(db)
-                    .await?;
-            }
+        if let Some(key) = agent.registry_key.as_deref()
+            && !agent_keys.contains(key)
+        {
+            sqlx::query("DELETE FROM agents WHERE id = ?")
+                .bind(agent.id.to_string())
+                .execute(db)
+                .await?;
         }
     }
 
@@ -281,6 +284,7 @@ async fn sync_pools(
                 })
                 .transpose()?,
             opening_seed: definition.opening_seed,
+            adjudication: definition.adjudication,
         };
 
         let existing = existing_pools
@@ -1,4 +1,7 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use uuid::Uuid;
 
@@ -18,6 +21,17 @@ impl HumanGameStore {
         self.sessions.read().await.get(&match_id).cloned()
     }
 
+    /// Matches whose live human game takes no rating, so takebacks are allowed.
+    pub(crate) async fn unrated_match_ids(&self) -> HashSet<Uuid> {
+        self.sessions
+            .read()
+            .await
+            .iter()
+            .filter(|(_, handle)| !handle.rated)
+            .map(|(match_id, _)| *match_id)
+            .collect()
+    }
+
     pub(crate) async fn remove(&self, match_id: Uuid) -> Option<HumanGameHandle> {
         self.sessions.write().await.remove(&match_id)
     }
@@ -82,8 +82,8 @@ fn agent_from_row(row: sqlx::sqlite::SqliteRow) -> Result<Agent> {
 pub(crate) async fn insert_agent_version(db: &SqlitePool, version: &AgentVersion) -> Result<()> {
     sqlx::query(
         "INSERT INTO agent_versions (
-            id, registry_key, agent_id, version, active, executable_path, working_directory, args, env, capabilities, declared_name, tags, notes, documentation, created_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            id, registry_key, agent_id, version, active, executable_path, working_directory, args, env, uci_options, capabilities, declared_name, tags, notes, documentation, created_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(version.id.to_string())
     .bind(&version.registry_key)
@@ -94,6 +94,7 @@ pub(crate) async fn insert_agent_version(db: &SqlitePool, version: &AgentVersion
     .bind(&version.working_directory)
     .bind(encode_json(&version.args)?)
     .bind(encode_json(&version.env)?)
+    .bind(encode_json(&version.uci_options)?)
     .bind(encode_json(&version.capabilities)?)
     .bind(&version.declared_name)
     .bind(encode_json(&version.tags)?)
@@ -108,7 +109,7 @@ pub(crate) async fn insert_agent_version(db: &SqlitePool, version: &AgentVersion
 pub(crate) async fn update_agent_version(db: &SqlitePool, version: &AgentVersion) -> Result<()> {
     sqlx::query(
         "UPDATE agent_versions SET
-            registry_key = ?, agent_id = ?, version = ?, active = ?, executable_path = ?, working_directory = ?, args = ?, env = ?, capabilities = ?, declared_name = ?, tags = ?, notes = ?, documentation = ?
+            registry_key = ?, agent_id = ?, version = ?, active = ?, executable_path = ?, working_directory = ?, args = ?, env = ?, uci_options = ?, capabilities = ?, declared_name = ?, tags = ?, notes = ?, documentation = ?
         WHERE id = ?",
     )
     .bind(&version.registry_key)
@@ -119,6 +120,7 @@ pub(crate) async fn update_agent_version(db: &SqlitePool, version: &AgentVersion
     .bind(&version.working_directory)
     .bind(encode_json(&version.args)?)
     .bind(encode_json(&version.env)?)
+    .bind(encode_json(&version.uci_options)?)
     .bind(encode_json(&version.capabilities)?)
     .bind(&version.declared_name)
     .bind(encode_json(&version.tags)?)
@@ -189,6 +191,7 @@ fn agent_version_from_row(row: sqlx::sqlite::SqliteRow) -> Result<AgentVersion>
         working_directory: row.get("working_directory"),
         args: decode_json(&row.get::<String, _>("args"))?,
         env: decode_json(&row.get::<String, _>("env"))?,
+        uci_options: decode_json(&row.get::<String, _>("uci_options"))?,
         capabilities: decode_json(&row.get::<String, _>("capabilities"))?,
         declared_name: row.get("declared_name"),
         tags: decode_json(&row.get::<String, _>("tags"))?,
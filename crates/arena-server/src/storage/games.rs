@@ -93,7 +93,7 @@ pub(crate) async fn list_games(
         .chain(human_rows)
         .map(game_from_row)
         .collect::<Result<Vec<_>>>()?;
-    games.sort_by(|left, right| right.completed_at.cmp(&left.completed_at));
+    games.sort_by_key(|game| std::cmp::Reverse(game.completed_at));
     Ok(games)
 }
 
@@ -39,8 +39,8 @@ where
 {
     sqlx::query(
         "INSERT INTO live_runtime_checkpoints (
-            match_id, seq, status, result, termination, start_fen, fen, moves, white_remaining_ms, black_remaining_ms, side_to_move, turn_started_server_unix_ms, updated_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            match_id, seq, status, result, termination, start_fen, fen, moves, move_times_ms, white_remaining_ms, black_remaining_ms, side_to_move, turn_started_server_unix_ms, updated_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         ON CONFLICT(match_id) DO UPDATE SET
             seq = excluded.seq,
             status = excluded.status,
@@ -49,6 +49,7 @@ where
             start_fen = excluded.start_fen,
             fen = excluded.fen,
             moves = excluded.moves,
+            move_times_ms = excluded.move_times_ms,
             white_remaining_ms = excluded.white_remaining_ms,
             black_remaining_ms = excluded.black_remaining_ms,
             side_to_move = excluded.side_to_move,
@@ -63,6 +64,7 @@ where
     .bind(&checkpoint.start_fen)
     .bind(&checkpoint.fen)
     .bind(encode_json(&checkpoint.moves)?)
+    .bind(encode_json(&checkpoint.move_times_ms)?)
     .bind(checkpoint.white_remaining_ms as i64)
     .bind(checkpoint.black_remaining_ms as i64)
     .bind(encode_json(&checkpoint.side_to_move)?)
@@ -105,6 +107,9 @@ where
         LiveEventEnvelope::GameFinished(value) => {
             (value.match_id, value.seq, LiveEventType::GameFinished)
         }
+        LiveEventEnvelope::EngineThinking(_) => {
+            return Err(anyhow!("engine thinking events are broadcast only"));
+        }
     };
     sqlx::query(
         "INSERT OR REPLACE INTO live_runtime_events (match_id, seq, event_type, payload, created_at)
@@ -177,6 +182,7 @@ fn live_runtime_checkpoint_from_row(row: sqlx::sqlite::SqliteRow) -> Result<Live
         start_fen: row.get("start_fen"),
         fen: row.get("fen"),
         moves: decode_json(&row.get::<String, _>("moves"))?,
+        move_times_ms: decode_json(&row.get::<String, _>("move_times_ms"))?,
         white_remaining_ms: row.get::<i64, _>("white_remaining_ms") as u64,
         black_remaining_ms: row.get::<i64, _>("black_remaining_ms") as u64,
         side_to_move: decode_json(&row.get::<String, _>("side_to_move"))?,
@@ -13,42 +13,101 @@ use crate::{
     db::{as_bool, decode_json, encode_json, parse_ts, ts},
     match_runtime::types::HumanPlayer,
     presentation::HumanPlayerProfile,
-    rating::default_entry,
+    rating::{build_pair_rating_update, default_entry},
     storage::list_agent_versions,
     state::RequestJournalEntry,
 };
+/// Serializes [`record_pair_rating_update`] so every update starts from the
+/// latest stored ratings, however many tournaments and human games share a pool.
+static RATING_UPDATES: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
 pub(crate) async fn insert_rating_snapshot(
     db: &SqlitePool,
     snapshot: &RatingSnapshot,
 ) -> Result<()> {
-    sqlx::query(
-        "INSERT INTO rating_snapshots (
-            id, pool_id, agent_version_id, rating, games_played, wins, draws, losses, created_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
-    )
-    .bind(snapshot.id.to_string())
-    .bind(snapshot.pool_id.map(|id| id.to_string()))
-    .bind(snapshot.agent_version_id.to_string())
-    .bind(snapshot.rating)
-    .bind(snapshot.games_played as i64)
-    .bind(snapshot.wins as i64)
-    .bind(snapshot.draws as i64)
-    .bind(snapshot.losses as i64)
-    .bind(ts(snapshot.created_at))
-    .execute(db)
-    .await?;
-    Ok(())
+    insert_snapshot_with_executor(db, SnapshotTable::Engine, snapshot).await
 }
 
 pub(crate) async fn insert_human_rating_snapshot(
     db: &SqlitePool,
     snapshot: &RatingSnapshot,
 ) -> Result<()> {
-    sqlx::query(
-        "INSERT INTO human_rating_snapshots (
-            id, pool_id, human_player_id, rating, games_played, wins, draws, losses, created_at
+    insert_snapshot_with_executor(db, SnapshotTable::Human, snapshot).await
+}
+
+/// Applies `pair` on top of its two participants' latest ratings in the pool
+/// and stores both new snapshots in one transaction, so a concurrent update
+/// cannot be overwritten from a stale copy and a crash cannot record one side
+/// without the other. `human_player_id` marks the human side, if any.
+pub(crate) async fn record_pair_rating_update(
+    db: &SqlitePool,
+    pool_id: Uuid,
+    pair: &MatchPair,
+    human_player_id: Option<Uuid>,
+) -> Result<PairRatingUpdate> {
+    let table = |participant_id| {
+        if Some(participant_id) == human_player_id {
+            SnapshotTable::Human
+        } else {
+            SnapshotTable::Engine
+        }
+    };
+
+    let _guard = RATING_UPDATES.lock().await;
+    let mut tx = db.begin().await?;
+    let mut entries = HashMap::new();
+    for participant_id in [pair.engine_a, pair.engine_b] {
+        let entry = latest_pool_entry(&mut tx, table(participant_id), pool_id, participant_id)
+            .await?
+            .unwrap_or_else(|| default_entry(participant_id));
+        entries.insert(participant_id, entry);
+    }
+    let update = build_pair_rating_update(&entries, pair);
+    for entry in [&update.engine_a, &update.engine_b] {
+        let snapshot = snapshot_from_entry(Some(pool_id), entry);
+        insert_snapshot_with_executor(&mut *tx, table(entry.agent_version_id), &snapshot).await?;
+    }
+    tx.commit().await?;
+    Ok(update)
+}
+
+#[derive(Clone, Copy)]
+enum SnapshotTable {
+    Engine,
+    Human,
+}
+
+impl SnapshotTable {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Engine => "rating_snapshots",
+            Self::Human => "human_rating_snapshots",
+        }
+    }
+
+    fn participant_column(self) -> &'static str {
+        match self {
+            Self::Engine => "agent_version_id",
+            Self::Human => "human_player_id",
+        }
+    }
+}
+
+async fn insert_snapshot_with_executor<'e, E>(
+    executor: E,
+    table: SnapshotTable,
+    snapshot: &RatingSnapshot,
+) -> Result<()>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query(&format!(
+        "INSERT INTO {} (
+            id, pool_id, {}, rating, games_played, wins, draws, losses, created_at
         ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
-    )
+        table.name(),
+        table.participant_column()
+    ))
     .bind(snapshot.id.to_string())
     .bind(snapshot.pool_id.map(|id| id.to_string()))
     .bind(snapshot.agent_version_id.to_string())
@@ -58,11 +117,41 @@ pub(crate) async fn insert_human_rating_snapshot(
     .bind(snapshot.draws as i64)
     .bind(snapshot.losses as i64)
     .bind(ts(snapshot.created_at))
-    .execute(db)
+    .execute(executor)
     .await?;
     Ok(())
 }
 
+async fn latest_pool_entry(
+    tx: &mut Transaction<'_, Sqlite>,
+    table: SnapshotTable,
+    pool_id: Uuid,
+    participant_id: Uuid,
+) -> Result<Option<LeaderboardEntry>> {
+    let mut query = QueryBuilder::<Sqlite>::new(format!("SELECT * FROM {}", table.name()));
+    push_rating_filters(
+        &mut query,
+        Some(pool_id),
+        Some(participant_id),
+        table.participant_column(),
+    );
+    query.push(" ORDER BY created_at DESC LIMIT 1");
+    let latest = query
+        .build()
+        .fetch_optional(&mut **tx)
+        .await?
+        .map(rating_snapshot_from_row)
+        .transpose()?;
+    Ok(latest.map(|snapshot| LeaderboardEntry {
+        agent_version_id: snapshot.agent_version_id,
+        rating: snapshot.rating,
+        games_played: snapshot.games_played,
+        wins: snapshot.wins,
+        draws: snapshot.draws,
+        losses: snapshot.losses,
+    }))
+}
+
 pub(crate) async fn load_rating_history(
     db: &SqlitePool,
     pool_id: Option<Uuid>,
@@ -83,7 +172,7 @@ pub(crate) async fn load_rating_history(
         .chain(human_rows)
         .map(rating_snapshot_from_row)
         .collect::<Result<Vec<_>>>()?;
-    snapshots.sort_by(|left, right| left.created_at.cmp(&right.created_at));
+    snapshots.sort_by_key(|snapshot| snapshot.created_at);
     Ok(snapshots)
 }
 
@@ -0,0 +1,145 @@
+use arena_core::{
+    BenchmarkPool, GameLogEntry, GameRecord, GameResult, GameTermination, MatchSeries, MatchStatus,
+    Tournament, TournamentKind, TournamentStatus,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    state::AppState,
+    storage::{
+        insert_game, insert_match_series, insert_tournament, list_agent_versions, list_pools,
+    },
+};
+
+pub(crate) const STANDARD_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// A game between `white` and `black` in `pool` that ended in `result`, with
+/// no moves from the standard start position. Tests override the fields they
+/// care about.
+pub(crate) fn finished_game(
+    pool: &BenchmarkPool,
+    white_version_id: Uuid,
+    black_version_id: Uuid,
+    result: GameResult,
+) -> GameRecord {
+    let now = Utc::now();
+    GameRecord {
+        id: Uuid::new_v4(),
+        tournament_id: Uuid::new_v4(),
+        match_id: Uuid::new_v4(),
+        pool_id: pool.id,
+        variant: pool.variant,
+        opening_id: None,
+        white_version_id,
+        black_version_id,
+        result,
+        termination: GameTermination::Checkmate,
+        start_fen: STANDARD_FEN.to_string(),
+        pgn: String::new(),
+        moves_uci: Vec::new(),
+        white_time_left_ms: pool.time_control.initial_ms,
+        black_time_left_ms: pool.time_control.initial_ms,
+        logs: Vec::new(),
+        started_at: now,
+        completed_at: now,
+    }
+}
+
+/// [`finished_game`] as played for `series`.
+pub(crate) fn finished_series_game(
+    pool: &BenchmarkPool,
+    series: &MatchSeries,
+    result: GameResult,
+) -> GameRecord {
+    GameRecord {
+        tournament_id: series.tournament_id,
+        match_id: series.id,
+        opening_id: series.opening_id,
+        ..finished_game(
+            pool,
+            series.white_version_id,
+            series.black_version_id,
+            result,
+        )
+    }
+}
+
+/// A finished game for [`seed_finished_tournament`], played from the
+/// standard start position.
+pub(crate) struct SeededGame {
+    pub(crate) result: GameResult,
+    pub(crate) termination: GameTermination,
+    pub(crate) moves: Vec<&'static str>,
+    pub(crate) duration_ms: i64,
+    pub(crate) logs: Vec<GameLogEntry>,
+}
+
+impl SeededGame {
+    pub(crate) fn new(
+        result: GameResult,
+        termination: GameTermination,
+        moves: &[&'static str],
+    ) -> Self {
+        Self {
+            result,
+            termination,
+            moves: moves.to_vec(),
+            duration_ms: 0,
+            logs: Vec::new(),
+        }
+    }
+}
+
+/// Stores a completed tournament between the first two registry engines,
+/// with one completed series per game, and returns its id and the game ids.
+pub(crate) async fn seed_finished_tournament(
+    state: &AppState,
+    games: Vec<SeededGame>,
+) -> (Uuid, Vec<Uuid>) {
+    let pool = list_pools(&state.db).await.unwrap().remove(0);
+    let mut versions = list_agent_versions(&state.db, None).await.unwrap();
+    let white = versions.remove(0);
+    let black = versions.remove(0);
+    let tournament = Tournament {
+        id: Uuid::new_v4(),
+        name: "finished".to_string(),
+        kind: TournamentKind::RoundRobin,
+        pool_id: pool.id,
+        participant_version_ids: vec![white.id, black.id],
+        worker_count: 1,
+        games_per_pairing: games.len() as u16,
+        status: TournamentStatus::Completed,
+        created_at: Utc::now(),
+        started_at: Some(Utc::now()),
+        completed_at: Some(Utc::now()),
+    };
+    insert_tournament(&state.db, &tournament).await.unwrap();
+    let mut game_ids = Vec::new();
+    for (game_index, seeded) in games.into_iter().enumerate() {
+        let series = MatchSeries {
+            id: Uuid::new_v4(),
+            tournament_id: tournament.id,
+            pool_id: pool.id,
+            round_index: 0,
+            white_version_id: white.id,
+            black_version_id: black.id,
+            opening_id: None,
+            game_index: game_index as u32,
+            status: MatchStatus::Completed,
+            created_at: Utc::now(),
+        };
+        insert_match_series(&state.db, &series).await.unwrap();
+        let game = finished_series_game(&pool, &series, seeded.result);
+        let game = GameRecord {
+            termination: seeded.termination,
+            moves_uci: seeded.moves.iter().map(ToString::to_string).collect(),
+            logs: seeded.logs,
+            completed_at: game.started_at + chrono::Duration::milliseconds(seeded.duration_ms),
+            ..game
+        };
+        insert_game(&state.db, &game).await.unwrap();
+        game_ids.push(game.id);
+    }
+    (tournament.id, game_ids)
+}
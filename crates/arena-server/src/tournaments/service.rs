@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     sync::{
         Arc,
         atomic::{AtomicBool, Ordering},
@@ -10,10 +10,11 @@ use anyhow::{Result, anyhow, bail};
 use arena_core::{
     AgentVersion, EventPreset, EventPresetSelectionMode, GameRecord, LeaderboardEntry,
     LiveRuntimeCheckpoint, MatchSeries, MatchStatus, RoundRobinScheduler, ScheduledPair,
-    StabilityConfig, StabilityTracker, Tournament, TournamentKind, TournamentStatus,
-    snapshot_from_entry,
+    SprtConfig, SprtTracker, StabilityConfig, StabilityTracker, Tournament, TournamentKind,
+    TournamentStatus, Variant, opening_for_pair,
 };
-use arena_runner::build_adapter;
+use arena_runner::{Game, build_adapter};
+use async_trait::async_trait;
 use chrono::Utc;
 use sqlx::SqlitePool;
 use tracing::{info, warn};
@@ -22,22 +23,22 @@ use uuid::Uuid;
 use crate::{
     ApiError,
     gameplay::{
-        MatchConfig, ensure_engine_supports_variant, parse_saved_board, resolve_start_state,
+        MatchConfig, ensure_engine_supports_variant, replay_saved_game, resolve_start_state,
     },
     match_runtime::{
-        owner::run_match_to_completion,
+        owner::{run_match_to_completion, run_match_until_stopped},
+        publish::checkpoint_move_times,
         types::{
             CompletedGameTable, EngineSeatController, MatchRuntime, MatchSeatController,
             MatchSession,
         },
     },
-    rating::build_pair_rating_update,
     state::AppState,
     storage::{
         ensure_leaderboard_seed, get_agent_version, get_match_series, get_pool, get_tournament,
-        insert_match_series, insert_rating_snapshot, insert_tournament, list_agent_versions,
-        list_agent_versions_by_ids, load_pool_openings, update_match_series_status,
-        update_tournament_status,
+        insert_match_series, insert_tournament, list_agent_versions, list_agent_versions_by_ids,
+        list_games, list_match_series, load_pool_openings, record_pair_rating_update,
+        update_match_series_status, update_tournament_status,
     },
 };
 
@@ -45,6 +46,76 @@ pub(crate) async fn run_tournament(
     state: AppState,
     tournament_id: Uuid,
     stop_flag: Arc<AtomicBool>,
+) -> Result<()> {
+    play_tournament(state, tournament_id, stop_flag, &EngineGames).await
+}
+
+/// Plays one game of a tournament pair and returns it once stored, or `None`
+/// when the tournament was stopped before it finished.
+#[async_trait]
+trait PairGamePlayer: Send + Sync {
+    #[allow(clippy::too_many_arguments)]
+    async fn play_game(
+        &self,
+        state: &AppState,
+        pool: &arena_core::BenchmarkPool,
+        white: &AgentVersion,
+        black: &AgentVersion,
+        opening: Option<&arena_core::OpeningPosition>,
+        pair_index: u32,
+        series: MatchSeries,
+        stop_flag: &AtomicBool,
+    ) -> Result<Option<GameRecord>>;
+}
+
+/// Games between the participants' own engines.
+struct EngineGames;
+
+#[async_trait]
+impl PairGamePlayer for EngineGames {
+    async fn play_game(
+        &self,
+        state: &AppState,
+        pool: &arena_core::BenchmarkPool,
+        white: &AgentVersion,
+        black: &AgentVersion,
+        opening: Option<&arena_core::OpeningPosition>,
+        pair_index: u32,
+        series: MatchSeries,
+        stop_flag: &AtomicBool,
+    ) -> Result<Option<GameRecord>> {
+        let runtime = build_engine_runtime(
+            series.tournament_id,
+            pool,
+            white.clone(),
+            black.clone(),
+            opening.cloned(),
+            300,
+            pool.fairness.opening_seed.or(Some(pair_index as u64)),
+        )
+        .await?;
+        let game = play_server_owned_engine_game(
+            state,
+            MatchSession {
+                name: format!("{} vs {}", white.version, black.version),
+                match_series: series,
+                completed_game_table: CompletedGameTable::Engine,
+                rated: true,
+            },
+            runtime,
+            true,
+            stop_flag,
+        )
+        .await?;
+        Ok(game)
+    }
+}
+
+async fn play_tournament(
+    state: AppState,
+    tournament_id: Uuid,
+    stop_flag: Arc<AtomicBool>,
+    player: &dyn PairGamePlayer,
 ) -> Result<()> {
     let tournament = get_tournament(&state.db, tournament_id).await?;
     let pool = get_pool(&state.db, tournament.pool_id).await?;
@@ -61,7 +132,11 @@ pub(crate) async fn run_tournament(
             .collect(),
     );
     let rating_ids: Vec<_> = participant_map.keys().copied().collect();
-    let openings = load_pool_openings(&state.db, &pool).await?;
+    let openings = usable_openings(
+        tournament_id,
+        pool.variant,
+        load_pool_openings(&state.db, &pool).await?,
+    );
     let ratings = Arc::new(tokio::sync::Mutex::new(
         ensure_leaderboard_seed(&state.db, pool.id, &rating_ids).await?,
     ));
@@ -70,7 +145,7 @@ pub(crate) async fn run_tournament(
         &state.db,
         tournament.id,
         TournamentStatus::Running,
-        Some(Utc::now()),
+        tournament.started_at.or(Some(Utc::now())),
         None,
     )
     .await?;
@@ -79,6 +154,31 @@ pub(crate) async fn run_tournament(
     let mut pair_index = 0_u32;
     let mut scheduler = build_scheduler(&tournament);
     let mut stability = StabilityTracker::new(StabilityConfig::default());
+    // Head-to-head tournaments also stop as soon as an SPRT reaches a decision.
+    let mut sprt = match tournament.participant_version_ids.as_slice() {
+        [engine_a, _] => Some(SprtTracker::new(SprtConfig::default(), *engine_a)),
+        _ => None,
+    };
+
+    // A resumed tournament replays its finished pairs into the scheduler and
+    // stopping rules without rating them again, then finishes the pair it was
+    // interrupted in.
+    let progress = load_tournament_progress(&state.db, tournament.id).await?;
+    {
+        let ratings_guard = ratings.lock().await;
+        for pair in &progress.pairs {
+            scheduler.next_pair();
+            stability.observe_pair(pair, &ratings_guard);
+            if let Some(sprt) = sprt.as_mut() {
+                sprt.observe_pair(pair);
+            }
+            pair_index += 1;
+        }
+    }
+    if pair_index > 0 {
+        info!("resuming tournament {tournament_id} after {pair_index} finished pairs");
+    }
+    let mut unfinished = progress.unfinished;
 
     loop {
         if stop_flag.load(Ordering::SeqCst) {
@@ -96,6 +196,18 @@ pub(crate) async fn run_tournament(
             break;
         }
 
+        if let Some(result) = sprt.as_ref().map(SprtTracker::result)
+            && let Some(accepted) = result.accepted
+        {
+            info!(
+                "stopping tournament {tournament_id}: SPRT accepted {} after {} games (llr={:.2})",
+                if accepted { "H1" } else { "H0" },
+                result.games_played,
+                result.llr
+            );
+            break;
+        }
+
         let Some(scheduled_pair) = scheduler.next_pair() else {
             break;
         };
@@ -108,14 +220,11 @@ pub(crate) async fn run_tournament(
             .get(&scheduled_pair.engine_b)
             .cloned()
             .ok_or_else(|| anyhow!("missing second participant"))?;
-        let opening = if openings.is_empty() {
-            None
-        } else {
-            Some(openings[pair_index as usize % openings.len()].clone())
-        };
+        let opening = opening_for_pair(&openings, pair_index).cloned();
 
         match play_engine_match_pair(
             &state,
+            player,
             tournament_id,
             &pool,
             &white,
@@ -123,15 +232,29 @@ pub(crate) async fn run_tournament(
             opening,
             pair_index,
             pool.fairness.paired_games && pool.fairness.swap_colors,
+            unfinished.take(),
+            &stop_flag,
         )
         .await
         {
-            Ok(pair) => {
+            Ok(None) => break,
+            Ok(Some(pair)) => {
                 apply_pool_rating_update(&state.db, pool.id, &pair, &ratings).await?;
                 {
                     let ratings_guard = ratings.lock().await;
                     stability.observe_pair(&pair, &ratings_guard);
                 }
+                if let Some(sprt) = sprt.as_mut() {
+                    sprt.observe_pair(&pair);
+                    let result = sprt.result();
+                    info!(
+                        "tournament {tournament_id} SPRT llr={:.2} [{:.2}, {:.2}] after {} games",
+                        result.llr,
+                        SprtConfig::default().lower_bound(),
+                        SprtConfig::default().upper_bound(),
+                        result.games_played
+                    );
+                }
                 pair_index += 1;
             }
             Err(err) => {
@@ -255,26 +378,146 @@ async fn apply_pool_rating_update(
     pair: &arena_core::MatchPair,
     ratings: &tokio::sync::Mutex<HashMap<Uuid, LeaderboardEntry>>,
 ) -> Result<()> {
-    let (engine_a_snapshot, engine_b_snapshot) = {
-        let mut ratings = ratings.lock().await;
-        let update = build_pair_rating_update(&ratings, pair);
+    // Start from the stored ratings rather than this tournament's copy, which
+    // misses games other tournaments and human games played in the pool.
+    let update = record_pair_rating_update(db, pool_id, pair, None).await?;
+    let mut ratings = ratings.lock().await;
+    ratings.insert(pair.engine_a, update.engine_a);
+    ratings.insert(pair.engine_b, update.engine_b);
+    Ok(())
+}
 
-        ratings.insert(pair.engine_a, update.engine_a.clone());
-        ratings.insert(pair.engine_b, update.engine_b.clone());
+/// Drop suite positions whose FEN does not load for the pool variant so a bad
+/// stored opening is skipped instead of aborting every pair that lands on it.
+fn usable_openings(
+    tournament_id: Uuid,
+    variant: Variant,
+    openings: Vec<arena_core::OpeningPosition>,
+) -> Vec<arena_core::OpeningPosition> {
+    openings
+        .into_iter()
+        .filter(|opening| {
+            let valid = cozy_chess::Board::from_fen(&opening.fen, variant.is_chess960()).is_ok();
+            if !valid {
+                warn!(
+                    "skipping invalid opening {} ({}) in tournament {tournament_id}: {}",
+                    opening.label, opening.id, opening.fen
+                );
+            }
+            valid
+        })
+        .collect()
+}
 
-        (
-            snapshot_from_entry(Some(pool_id), &update.engine_a),
-            snapshot_from_entry(Some(pool_id), &update.engine_b),
-        )
-    };
+/// The pairs a tournament has finished, rebuilt from its stored match series
+/// and games, so a resumed run carries on where an interrupted one stopped.
+#[derive(Debug, Default)]
+struct TournamentProgress {
+    pairs: Vec<arena_core::MatchPair>,
+    unfinished: Option<UnfinishedPair>,
+}
 
-    insert_rating_snapshot(db, &engine_a_snapshot).await?;
-    insert_rating_snapshot(db, &engine_b_snapshot).await?;
-    Ok(())
+/// The pair a tournament was interrupted in: the games it already finished
+/// and the series, in order, it still has to play.
+#[derive(Debug)]
+struct UnfinishedPair {
+    games: Vec<GameRecord>,
+    remaining: Vec<MatchSeries>,
+}
+
+/// Walks the tournament's rounds in order. A game slot counts as played once
+/// any series for it stored a game, since an interrupted slot is replayed
+/// under a fresh series. Pairs are rated as soon as their last game is
+/// stored, so every finished pair here has already been rated.
+async fn load_tournament_progress(
+    db: &SqlitePool,
+    tournament_id: Uuid,
+) -> Result<TournamentProgress> {
+    let mut games: HashMap<Uuid, GameRecord> = list_games(db, Some(tournament_id), None)
+        .await?
+        .into_iter()
+        .map(|game| (game.match_id, game))
+        .collect();
+    let mut rounds: BTreeMap<u32, BTreeMap<u32, Vec<MatchSeries>>> = BTreeMap::new();
+    for series in list_match_series(db, Some(tournament_id)).await? {
+        rounds
+            .entry(series.round_index)
+            .or_default()
+            .entry(series.game_index)
+            .or_default()
+            .push(series);
+    }
+
+    let mut progress = TournamentProgress::default();
+    for (round_index, slots) in rounds {
+        if round_index as usize != progress.pairs.len() {
+            break;
+        }
+        let mut played = Vec::new();
+        let mut remaining = Vec::new();
+        for (_, candidates) in slots {
+            match candidates
+                .iter()
+                .find_map(|series| games.remove(&series.id))
+            {
+                Some(game) => played.push(game),
+                None => remaining.push(candidates[0].clone()),
+            }
+        }
+        if !remaining.is_empty() {
+            progress.unfinished = Some(UnfinishedPair {
+                games: played,
+                remaining,
+            });
+            break;
+        }
+        progress.pairs.push(arena_core::MatchPair {
+            engine_a: played[0].white_version_id,
+            engine_b: played[0].black_version_id,
+            games: played,
+        });
+    }
+    Ok(progress)
+}
+
+/// The series of one pair: `engine_a` plays White first, then the colors
+/// swap if the pool pairs its games.
+fn pair_series(
+    tournament_id: Uuid,
+    pool_id: Uuid,
+    engine_a: Uuid,
+    engine_b: Uuid,
+    opening_id: Option<Uuid>,
+    pair_index: u32,
+    swap_colors: bool,
+) -> Vec<MatchSeries> {
+    let colors = if swap_colors {
+        vec![(engine_a, engine_b), (engine_b, engine_a)]
+    } else {
+        vec![(engine_a, engine_b)]
+    };
+    colors
+        .into_iter()
+        .enumerate()
+        .map(|(offset, (white, black))| MatchSeries {
+            id: Uuid::new_v4(),
+            tournament_id,
+            pool_id,
+            round_index: pair_index,
+            white_version_id: white,
+            black_version_id: black,
+            opening_id,
+            game_index: pair_index.saturating_mul(2).saturating_add(offset as u32),
+            status: MatchStatus::Pending,
+            created_at: Utc::now(),
+        })
+        .collect()
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn play_engine_match_pair(
     state: &AppState,
+    player: &dyn PairGamePlayer,
     tournament_id: Uuid,
     pool: &arena_core::BenchmarkPool,
     engine_a: &AgentVersion,
@@ -282,92 +525,78 @@ async fn play_engine_match_pair(
     opening: Option<arena_core::OpeningPosition>,
     pair_index: u32,
     swap_colors: bool,
-) -> Result<arena_core::MatchPair> {
-    let first_series = MatchSeries {
-        id: Uuid::new_v4(),
-        tournament_id,
-        pool_id: pool.id,
-        round_index: pair_index,
-        white_version_id: engine_a.id,
-        black_version_id: engine_b.id,
-        opening_id: opening.as_ref().map(|value| value.id),
-        game_index: pair_index.saturating_mul(2),
-        status: MatchStatus::Running,
-        created_at: Utc::now(),
+    unfinished: Option<UnfinishedPair>,
+    stop_flag: &AtomicBool,
+) -> Result<Option<arena_core::MatchPair>> {
+    let (mut games, planned) = match unfinished {
+        Some(unfinished) => (unfinished.games, unfinished.remaining),
+        None => (
+            Vec::new(),
+            pair_series(
+                tournament_id,
+                pool.id,
+                engine_a.id,
+                engine_b.id,
+                opening.as_ref().map(|value| value.id),
+                pair_index,
+                swap_colors,
+            ),
+        ),
     };
-    insert_match_series(&state.db, &first_series).await?;
-
-    let second_series = if swap_colors {
+    // Resumed slots get fresh series so the interrupted ones keep their own
+    // live history.
+    let mut series_queue = Vec::with_capacity(planned.len());
+    for (index, planned) in planned.into_iter().enumerate() {
         let series = MatchSeries {
             id: Uuid::new_v4(),
-            tournament_id,
-            pool_id: pool.id,
-            round_index: pair_index,
-            white_version_id: engine_b.id,
-            black_version_id: engine_a.id,
-            opening_id: opening.as_ref().map(|value| value.id),
-            game_index: pair_index.saturating_mul(2).saturating_add(1),
-            status: MatchStatus::Pending,
+            status: if index == 0 {
+                MatchStatus::Running
+            } else {
+                MatchStatus::Pending
+            },
             created_at: Utc::now(),
+            ..planned
         };
         insert_match_series(&state.db, &series).await?;
-        Some(series)
-    } else {
-        None
-    };
-
-    let first_game = play_server_owned_engine_game(
-        state,
-        MatchSession {
-            name: format!("{} vs {}", engine_a.version, engine_b.version),
-            match_series: first_series.clone(),
-            completed_game_table: CompletedGameTable::Engine,
-        },
-        build_engine_runtime(
-            tournament_id,
-            pool,
-            engine_a.clone(),
-            engine_b.clone(),
-            opening.clone(),
-            300,
-            pool.fairness.opening_seed.or(Some(pair_index as u64)),
-        )
-        .await?,
-        true,
-    )
-    .await?;
+        series_queue.push(series);
+    }
 
-    let mut games = vec![first_game];
-    if let Some(second_series) = second_series {
-        update_match_series_status(&state.db, second_series.id, MatchStatus::Running).await?;
-        let second_game = play_server_owned_engine_game(
-            state,
-            MatchSession {
-                name: format!("{} vs {}", engine_b.version, engine_a.version),
-                match_series: second_series,
-                completed_game_table: CompletedGameTable::Engine,
-            },
-            build_engine_runtime(
-                tournament_id,
+    let mut series_queue = series_queue.into_iter();
+    while let Some(series) = series_queue.next() {
+        if series.status != MatchStatus::Running {
+            update_match_series_status(&state.db, series.id, MatchStatus::Running).await?;
+        }
+        let (white, black) = if series.white_version_id == engine_a.id {
+            (engine_a, engine_b)
+        } else {
+            (engine_b, engine_a)
+        };
+        let Some(game) = player
+            .play_game(
+                state,
                 pool,
-                engine_b.clone(),
-                engine_a.clone(),
-                opening,
-                300,
-                pool.fairness.opening_seed.or(Some(pair_index as u64)),
+                white,
+                black,
+                opening.as_ref(),
+                pair_index,
+                series,
+                stop_flag,
             )
-            .await?,
-            true,
-        )
-        .await?;
-        games.push(second_game);
+            .await?
+        else {
+            for skipped in series_queue {
+                update_match_series_status(&state.db, skipped.id, MatchStatus::Skipped).await?;
+            }
+            return Ok(None);
+        };
+        games.push(game);
     }
 
-    Ok(arena_core::MatchPair {
+    Ok(Some(arena_core::MatchPair {
         engine_a: engine_a.id,
         engine_b: engine_b.id,
         games,
-    })
+    }))
 }
 
 async fn build_engine_runtime(
@@ -386,7 +615,6 @@ async fn build_engine_runtime(
         opening: opening.as_ref(),
         opening_seed,
     })?;
-    let initial_hash = board.hash_without_ep();
     let started_at = Utc::now();
     let mut logs = Vec::new();
     let mut white_engine = build_adapter(white);
@@ -401,9 +629,11 @@ async fn build_engine_runtime(
         time_control: pool.time_control.clone(),
         start_fen: start_fen.clone(),
         current_fen: start_fen,
-        board,
-        repetitions: HashMap::from([(initial_hash, 1)]),
+        game: Game::new(board),
         move_history: Vec::new(),
+        move_times_ms: Vec::new(),
+        move_scores: Vec::new(),
+        adjudication: pool.fairness.adjudication,
         white_time_left_ms: pool.time_control.initial_ms,
         black_time_left_ms: pool.time_control.initial_ms,
         max_plies,
@@ -428,8 +658,9 @@ async fn play_server_owned_engine_game(
     session: MatchSession,
     runtime: MatchRuntime,
     publish_initial_snapshot: bool,
-) -> Result<GameRecord, ApiError> {
-    run_match_to_completion(state, session, runtime, publish_initial_snapshot).await
+    stop_flag: &AtomicBool,
+) -> Result<Option<GameRecord>, ApiError> {
+    run_match_until_stopped(state, session, runtime, publish_initial_snapshot, stop_flag).await
 }
 
 pub(crate) async fn restore_engine_game(
@@ -441,17 +672,9 @@ pub(crate) async fn restore_engine_game(
     let tournament = get_tournament(&state.db, match_series.tournament_id).await?;
     let white = get_agent_version(&state.db, match_series.white_version_id).await?;
     let black = get_agent_version(&state.db, match_series.black_version_id).await?;
-    let (mut board, start_fen) = parse_saved_board(pool.variant, &checkpoint.start_fen)
-        .map_err(|err| ApiError::Conflict(format!("failed to restore start FEN: {err}")))?;
-    let mut repetitions = HashMap::from([(board.hash_without_ep(), 1_u8)]);
-    for uci in &checkpoint.moves {
-        let mv = cozy_chess::util::parse_uci_move(&board, uci)
-            .map_err(|err| ApiError::Conflict(format!("failed to restore move {uci}: {err}")))?;
-        board
-            .try_play(mv)
-            .map_err(|_| ApiError::Conflict(format!("failed to replay restored move {uci}")))?;
-        *repetitions.entry(board.hash_without_ep()).or_insert(0) += 1;
-    }
+    let (game, start_fen) =
+        replay_saved_game(pool.variant, &checkpoint.start_fen, &checkpoint.moves)
+            .map_err(|err| ApiError::Conflict(format!("failed to restore game: {err:#}")))?;
     let mut logs = Vec::new();
     let mut white_engine = build_adapter(white);
     let mut black_engine = build_adapter(black);
@@ -465,9 +688,11 @@ pub(crate) async fn restore_engine_game(
         time_control: pool.time_control.clone(),
         start_fen,
         current_fen: checkpoint.fen.clone(),
-        board,
-        repetitions,
+        game,
         move_history: checkpoint.moves.clone(),
+        move_times_ms: checkpoint_move_times(&checkpoint),
+        move_scores: vec![None; checkpoint.moves.len()],
+        adjudication: pool.fairness.adjudication,
         white_time_left_ms: checkpoint.white_remaining_ms,
         black_time_left_ms: checkpoint.black_remaining_ms,
         max_plies: 300,
@@ -489,6 +714,7 @@ pub(crate) async fn restore_engine_game(
         name: tournament.name,
         match_series,
         completed_game_table: CompletedGameTable::Engine,
+        rated: true,
     };
     let state = state.clone();
     tokio::spawn(async move {
@@ -500,31 +726,43 @@ pub(crate) async fn restore_engine_game(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arena_core::{GameLogEntry, TimeControl, Variant};
-    use arena_runner::AgentAdapter;
+    use arena_core::{
+        AdjudicationConfig, DrawAdjudication, EngineScore, GameLogEntry, ResignAdjudication,
+        TimeControl, Variant,
+    };
+    use arena_runner::{AgentAdapter, SearchLimit, SearchOutcome, SearchProgress};
     use async_trait::async_trait;
     use sqlx::sqlite::SqlitePoolOptions;
-    use std::time::Duration;
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
 
     use crate::{
         db::init_db,
         human_games::service::create_human_game,
         match_runtime::{
-            engine_turn::process_engine_turn,
+            engine_turn::{no_move_outcome, process_engine_turn},
             finalize::finalize_match_game,
-            human_turn::process_human_move,
+            human_turn::{process_human_move, process_human_takeback},
             owner::run_match_owner,
+            publish::publish_match_runtime,
             types::{HumanGameHandle, HumanMoveAck, HumanSeatController},
         },
         registry::{SetupRegistryCache, sync_setup_registry_if_changed},
         state::MoveDebugContext,
         state::{HumanGameStore, TournamentCoordinator},
         storage::{ensure_human_player, insert_tournament},
+        test_fixtures::{finished_game, finished_series_game},
     };
 
     struct SleepyAdapter {
         delay_ms: u64,
         move_uci: String,
+        stop_calls: Arc<AtomicUsize>,
     }
 
     #[async_trait]
@@ -553,6 +791,133 @@ mod tests {
             Ok(self.move_uci.clone())
         }
 
+        async fn stop(&mut self, _logs: &mut Vec<GameLogEntry>) -> Result<()> {
+            self.stop_calls.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn shutdown(&mut self, _logs: &mut Vec<GameLogEntry>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Plays a fixed list of moves, one per turn, reporting the same score
+    /// for each.
+    struct ScriptedAdapter {
+        moves: std::vec::IntoIter<&'static str>,
+        score: Option<EngineScore>,
+    }
+
+    #[async_trait]
+    impl AgentAdapter for ScriptedAdapter {
+        async fn prepare(
+            &mut self,
+            _variant: Variant,
+            _logs: &mut Vec<GameLogEntry>,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn begin_game(&mut self, _logs: &mut Vec<GameLogEntry>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn choose_move(
+            &mut self,
+            _board: &cozy_chess::Board,
+            _start_fen: &str,
+            _moves: &[String],
+            _movetime_ms: u64,
+            _logs: &mut Vec<GameLogEntry>,
+        ) -> Result<String> {
+            Ok(self.moves.next().unwrap_or("0000").to_string())
+        }
+
+        async fn search(
+            &mut self,
+            _board: &cozy_chess::Board,
+            _start_fen: &str,
+            _moves: &[String],
+            _limit: SearchLimit,
+            _logs: &mut Vec<GameLogEntry>,
+        ) -> Result<SearchOutcome> {
+            Ok(SearchOutcome {
+                best_move: self.moves.next().unwrap_or("0000").to_string(),
+                score: self.score,
+                depth: Some(1),
+                seldepth: Some(3),
+                nodes: Some(20),
+            })
+        }
+
+        async fn shutdown(&mut self, _logs: &mut Vec<GameLogEntry>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Reports a burst of deepening progress at once, then thinks a while
+    /// before playing e2e4.
+    struct ChattyAdapter {
+        progress: Option<tokio::sync::watch::Sender<SearchProgress>>,
+    }
+
+    #[async_trait]
+    impl AgentAdapter for ChattyAdapter {
+        async fn prepare(
+            &mut self,
+            _variant: Variant,
+            _logs: &mut Vec<GameLogEntry>,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn begin_game(&mut self, _logs: &mut Vec<GameLogEntry>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn choose_move(
+            &mut self,
+            _board: &cozy_chess::Board,
+            _start_fen: &str,
+            _moves: &[String],
+            _movetime_ms: u64,
+            _logs: &mut Vec<GameLogEntry>,
+        ) -> Result<String> {
+            Ok("e2e4".to_string())
+        }
+
+        async fn search(
+            &mut self,
+            _board: &cozy_chess::Board,
+            _start_fen: &str,
+            _moves: &[String],
+            _limit: SearchLimit,
+            _logs: &mut Vec<GameLogEntry>,
+        ) -> Result<SearchOutcome> {
+            if let Some(progress) = &self.progress {
+                for depth in 1..=20 {
+                    progress.send_replace(SearchProgress {
+                        depth: Some(depth),
+                        seldepth: Some(depth + 4),
+                        score: Some(EngineScore::Centipawns(depth as i32)),
+                        nodes: Some(1_000 * u64::from(depth)),
+                        nps: Some(100_000),
+                        pv: vec!["e2e4".into(), "e7e5".into(), "g1f3".into()],
+                        lines: Vec::new(),
+                    });
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            Ok(SearchOutcome {
+                best_move: "e2e4".to_string(),
+                ..SearchOutcome::default()
+            })
+        }
+
+        fn watch_progress(&mut self, progress: tokio::sync::watch::Sender<SearchProgress>) {
+            self.progress = Some(progress);
+        }
+
         async fn shutdown(&mut self, _logs: &mut Vec<GameLogEntry>) -> Result<()> {
             Ok(())
         }
@@ -643,6 +1008,7 @@ mod tests {
             name: "test".to_string(),
             match_series: match_series.clone(),
             completed_game_table: CompletedGameTable::Human,
+            rated: true,
         };
         let board = cozy_chess::Board::default();
         let human_seat = MatchSeatController::Human(HumanSeatController {
@@ -654,6 +1020,7 @@ mod tests {
             adapter: Some(Box::new(SleepyAdapter {
                 delay_ms: 0,
                 move_uci: "e2e4".to_string(),
+                stop_calls: Arc::default(),
             })),
         });
         let (white_seat, black_seat) = if human_plays_white {
@@ -670,9 +1037,11 @@ mod tests {
             },
             start_fen: board.to_string(),
             current_fen: board.to_string(),
-            board,
-            repetitions: HashMap::from([(cozy_chess::Board::default().hash_without_ep(), 1)]),
+            game: Game::new(board),
             move_history: Vec::new(),
+            move_times_ms: Vec::new(),
+            move_scores: Vec::new(),
+            adjudication: AdjudicationConfig::default(),
             white_time_left_ms: 50,
             black_time_left_ms: 50,
             max_plies: 300,
@@ -686,7 +1055,10 @@ mod tests {
             termination: None,
             status: MatchStatus::Running,
         };
-        let handle = HumanGameHandle { command_tx };
+        let handle = HumanGameHandle {
+            command_tx,
+            rated: session.rated,
+        };
         state.human_games.insert(match_series.id, handle).await;
         (session, runtime)
     }
@@ -732,16 +1104,307 @@ mod tests {
         assert_eq!(snapshot.termination, arena_core::LiveTermination::Timeout);
     }
 
+    #[tokio::test]
+    async fn shuffling_engines_end_in_a_repetition_draw() {
+        let state = test_state().await;
+        let (session, mut runtime) =
+            session_and_runtime(&state, cozy_chess::Color::White, false).await;
+        runtime.white_time_left_ms = 10_000;
+        runtime.black_time_left_ms = 10_000;
+        runtime.white_seat = MatchSeatController::Engine(EngineSeatController {
+            adapter: Some(Box::new(ScriptedAdapter {
+                moves: ["g1f3", "f3g1"].repeat(4).into_iter(),
+                score: None,
+            })),
+        });
+        runtime.black_seat = MatchSeatController::Engine(EngineSeatController {
+            adapter: Some(Box::new(ScriptedAdapter {
+                moves: ["g8f6", "f6g8"].repeat(4).into_iter(),
+                score: None,
+            })),
+        });
+
+        while runtime.status == MatchStatus::Running {
+            let side = runtime.game.board().side_to_move();
+            process_engine_turn(&state, &session, &mut runtime, side)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(runtime.move_history.len(), 8);
+        assert_eq!(runtime.move_times_ms.len(), 8);
+        assert!(runtime.move_times_ms.iter().all(Option::is_some));
+        let returned: Vec<_> = runtime
+            .logs
+            .iter()
+            .filter(|entry| entry.event == "engine.move_returned")
+            .filter_map(|entry| entry.fields.as_ref())
+            .collect();
+        assert_eq!(returned.len(), 8);
+        assert_eq!(returned[1]["ply"], 2);
+        assert_eq!(
+            returned[1]["fen_before"],
+            "rnbqkbnr/pppppppp/8/8/8/5N2/PPPPPPPP/RNBQKB1R b KQkq - 1 1"
+        );
+        assert_eq!(returned[1]["depth"], 1);
+        assert_eq!(returned[1]["seldepth"], 3);
+        assert_eq!(returned[1]["nodes"], 20);
+        assert_eq!(runtime.result, Some(arena_core::GameResult::Draw));
+        assert_eq!(
+            runtime.termination,
+            Some(arena_core::GameTermination::Repetition)
+        );
+    }
+
+    #[test]
+    fn null_move_ends_a_finished_board_by_its_own_result() {
+        let mated: cozy_chess::Board =
+            "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3"
+                .parse()
+                .unwrap();
+        let stalemated: cozy_chess::Board = "7k/5Q2/6K1/8/8/8/8/8 b - - 0 1".parse().unwrap();
+
+        assert_eq!(
+            no_move_outcome(&mated),
+            Some((
+                arena_core::GameResult::BlackWin,
+                arena_core::GameTermination::Checkmate
+            ))
+        );
+        assert_eq!(
+            no_move_outcome(&stalemated),
+            Some((
+                arena_core::GameResult::Draw,
+                arena_core::GameTermination::Stalemate
+            ))
+        );
+        assert_eq!(no_move_outcome(&cozy_chess::Board::default()), None);
+    }
+
+    #[tokio::test]
+    async fn engine_without_a_move_in_a_playable_position_is_reported() {
+        let state = test_state().await;
+        let (session, mut runtime) =
+            session_and_runtime(&state, cozy_chess::Color::White, false).await;
+        runtime.white_time_left_ms = 10_000;
+        runtime.white_seat = MatchSeatController::Engine(EngineSeatController {
+            adapter: Some(Box::new(ScriptedAdapter {
+                moves: Vec::new().into_iter(),
+                score: None,
+            })),
+        });
+
+        process_engine_turn(&state, &session, &mut runtime, cozy_chess::Color::White)
+            .await
+            .unwrap();
+
+        assert_eq!(runtime.status, MatchStatus::Completed);
+        assert_eq!(
+            runtime.termination,
+            Some(arena_core::GameTermination::EngineFailure)
+        );
+        assert!(runtime.move_history.is_empty());
+        assert!(
+            runtime
+                .logs
+                .iter()
+                .any(|entry| entry.event == "engine.move_failed")
+        );
+    }
+
+    #[tokio::test]
+    async fn an_illegal_engine_move_forfeits_the_game() {
+        let state = test_state().await;
+        let (session, mut runtime) =
+            session_and_runtime(&state, cozy_chess::Color::White, false).await;
+        runtime.white_time_left_ms = 10_000;
+        runtime.white_seat = MatchSeatController::Engine(EngineSeatController {
+            adapter: Some(Box::new(ScriptedAdapter {
+                moves: vec!["a1a1"].into_iter(),
+                score: None,
+            })),
+        });
+        let fen_before = runtime.current_fen.clone();
+
+        process_engine_turn(&state, &session, &mut runtime, cozy_chess::Color::White)
+            .await
+            .unwrap();
+
+        assert_eq!(runtime.status, MatchStatus::Completed);
+        assert_eq!(runtime.result, Some(arena_core::GameResult::BlackWin));
+        assert_eq!(
+            runtime.termination,
+            Some(arena_core::GameTermination::IllegalMove)
+        );
+        assert!(runtime.move_history.is_empty());
+        let entry = runtime
+            .logs
+            .iter()
+            .find(|entry| entry.event == "engine.illegal_move")
+            .unwrap();
+        assert_eq!(entry.move_uci.as_deref(), Some("a1a1"));
+        assert_eq!(
+            entry.fields.as_ref().unwrap()["fen_before"],
+            fen_before.as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn agreed_engine_scores_adjudicate_the_game() {
+        let state = test_state().await;
+        let drawn = DrawAdjudication {
+            score_cp: 10,
+            move_count: 2,
+            min_move_number: 1,
+        };
+        for (adjudication, white_score, expected, plies) in [
+            (
+                AdjudicationConfig {
+                    resign: Some(ResignAdjudication::default()),
+                    draw: None,
+                },
+                -900,
+                arena_core::GameResult::BlackWin,
+                8,
+            ),
+            (
+                AdjudicationConfig {
+                    resign: Some(ResignAdjudication::default()),
+                    draw: Some(drawn),
+                },
+                3,
+                arena_core::GameResult::Draw,
+                4,
+            ),
+        ] {
+            let (session, mut runtime) =
+                session_and_runtime(&state, cozy_chess::Color::White, false).await;
+            runtime.white_time_left_ms = 10_000;
+            runtime.black_time_left_ms = 10_000;
+            runtime.adjudication = adjudication;
+            runtime.white_seat = MatchSeatController::Engine(EngineSeatController {
+                adapter: Some(Box::new(ScriptedAdapter {
+                    moves: Vec::from(["e2e4", "d2d4", "g1f3", "b1c3", "f1c4"]).into_iter(),
+                    score: Some(EngineScore::Centipawns(white_score)),
+                })),
+            });
+            runtime.black_seat = MatchSeatController::Engine(EngineSeatController {
+                adapter: Some(Box::new(ScriptedAdapter {
+                    moves: Vec::from(["e7e5", "d7d5", "g8f6", "b8c6", "f8c5"]).into_iter(),
+                    score: Some(EngineScore::Centipawns(-white_score)),
+                })),
+            });
+
+            while runtime.status == MatchStatus::Running {
+                let side = runtime.game.board().side_to_move();
+                process_engine_turn(&state, &session, &mut runtime, side)
+                    .await
+                    .unwrap();
+            }
+
+            assert_eq!(runtime.move_history.len(), plies);
+            assert_eq!(runtime.result, Some(expected));
+            assert_eq!(
+                runtime.termination,
+                Some(arena_core::GameTermination::Adjudication)
+            );
+            assert!(
+                runtime
+                    .logs
+                    .iter()
+                    .any(|entry| entry.event == "adjudication.applied")
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn engine_progress_bursts_coalesce_into_one_thinking_event() {
+        let state = test_state().await;
+        let (session, mut runtime) =
+            session_and_runtime(&state, cozy_chess::Color::White, false).await;
+        runtime.white_time_left_ms = 10_000;
+        runtime.white_seat = MatchSeatController::Engine(EngineSeatController {
+            adapter: Some(Box::new(ChattyAdapter { progress: None })),
+        });
+        insert_match_series(&state.db, &session.match_series)
+            .await
+            .unwrap();
+        publish_match_runtime(&state, &session, &mut runtime, true)
+            .await
+            .unwrap();
+        let (_, mut events) = state
+            .live_matches
+            .subscribe(session.match_series.id)
+            .await
+            .unwrap();
+
+        process_engine_turn(&state, &session, &mut runtime, cozy_chess::Color::White)
+            .await
+            .unwrap();
+
+        let mut thinking = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let arena_core::LiveEventEnvelope::EngineThinking(event) = event {
+                thinking.push(event);
+            }
+        }
+        assert_eq!(thinking.len(), 1, "{thinking:?}");
+        assert_eq!(thinking[0].depth, Some(20));
+        assert_eq!(thinking[0].nodes, Some(20_000));
+        assert_eq!(thinking[0].pv, ["e4", "e5", "Nf3"]);
+        assert_eq!(thinking[0].side, arena_core::ProtocolLiveSide::White);
+        assert_eq!(thinking[0].seq, 1);
+        assert_eq!(runtime.move_history, ["e2e4"]);
+    }
+
+    #[tokio::test]
+    async fn stopped_tournament_aborts_game_between_moves() {
+        let state = test_state().await;
+        let (session, mut runtime) =
+            session_and_runtime(&state, cozy_chess::Color::White, false).await;
+        runtime.white_time_left_ms = 10_000;
+        runtime.black_time_left_ms = 10_000;
+        runtime.white_seat = MatchSeatController::Engine(EngineSeatController {
+            adapter: Some(Box::new(ScriptedAdapter {
+                moves: Vec::from(["e2e4"]).into_iter(),
+                score: None,
+            })),
+        });
+        insert_match_series(&state.db, &session.match_series)
+            .await
+            .unwrap();
+        let stop_flag = AtomicBool::new(true);
+
+        let game = run_match_until_stopped(&state, session.clone(), runtime, false, &stop_flag)
+            .await
+            .unwrap();
+
+        assert!(game.is_none());
+        let match_series = get_match_series(&state.db, session.match_series.id)
+            .await
+            .unwrap();
+        assert_eq!(match_series.status, MatchStatus::Skipped);
+        let snapshot = state
+            .live_matches
+            .get_snapshot(session.match_series.id)
+            .await
+            .unwrap();
+        assert_eq!(snapshot.status, arena_core::LiveStatus::Aborted);
+        assert_eq!(snapshot.termination, arena_core::LiveTermination::Abort);
+    }
+
     #[tokio::test]
     async fn engine_turn_times_out_when_adapter_responds_too_late() {
         let state = test_state().await;
         let (session, mut runtime) =
             session_and_runtime(&state, cozy_chess::Color::White, false).await;
         runtime.white_time_left_ms = 20;
+        let stop_calls = Arc::new(AtomicUsize::new(0));
         runtime.white_seat = MatchSeatController::Engine(EngineSeatController {
             adapter: Some(Box::new(SleepyAdapter {
                 delay_ms: 40,
                 move_uci: "e2e4".to_string(),
+                stop_calls: stop_calls.clone(),
             })),
         });
 
@@ -754,6 +1417,10 @@ mod tests {
             runtime.termination,
             Some(arena_core::GameTermination::Timeout)
         );
+        assert_eq!(stop_calls.load(Ordering::SeqCst), 1);
+        // The search was dropped when the flag fell, so its move never lands.
+        assert!(runtime.move_history.is_empty());
+        assert_eq!(runtime.game.ply_count(), 0);
         finalize_match_game(&state, session.clone(), runtime)
             .await
             .unwrap();
@@ -766,6 +1433,166 @@ mod tests {
         assert_eq!(snapshot.termination, arena_core::LiveTermination::Timeout);
     }
 
+    #[tokio::test]
+    async fn engine_move_that_lands_after_the_flag_fell_is_not_played() {
+        let state = test_state().await;
+        let (session, mut runtime) =
+            session_and_runtime(&state, cozy_chess::Color::White, false).await;
+        runtime.white_time_left_ms = 50;
+        // The clock ran out before the search reported back, so the move
+        // arrives after the flag fell rather than racing the timeout.
+        runtime.turn_started_server_unix_ms -= 100;
+        runtime.white_seat = MatchSeatController::Engine(EngineSeatController {
+            adapter: Some(Box::new(ScriptedAdapter {
+                moves: Vec::from(["e2e4"]).into_iter(),
+                score: None,
+            })),
+        });
+
+        process_engine_turn(&state, &session, &mut runtime, cozy_chess::Color::White)
+            .await
+            .unwrap();
+
+        assert_eq!(runtime.result, Some(arena_core::GameResult::BlackWin));
+        assert_eq!(
+            runtime.termination,
+            Some(arena_core::GameTermination::Timeout)
+        );
+        assert!(runtime.move_history.is_empty());
+        assert_eq!(runtime.game.ply_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn finished_game_takes_no_further_engine_turns() {
+        let state = test_state().await;
+        let (session, mut runtime) =
+            session_and_runtime(&state, cozy_chess::Color::White, false).await;
+        runtime.white_time_left_ms = 10_000;
+        runtime.result = Some(arena_core::GameResult::BlackWin);
+        runtime.termination = Some(arena_core::GameTermination::Timeout);
+        runtime.status = MatchStatus::Completed;
+        runtime.white_seat = MatchSeatController::Engine(EngineSeatController {
+            adapter: Some(Box::new(ScriptedAdapter {
+                moves: Vec::from(["e2e4"]).into_iter(),
+                score: None,
+            })),
+        });
+
+        process_engine_turn(&state, &session, &mut runtime, cozy_chess::Color::White)
+            .await
+            .unwrap();
+
+        assert_eq!(runtime.result, Some(arena_core::GameResult::BlackWin));
+        assert_eq!(
+            runtime.termination,
+            Some(arena_core::GameTermination::Timeout)
+        );
+        assert!(runtime.move_history.is_empty());
+        assert_eq!(runtime.game.ply_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn human_takeback_restores_captures_castling_en_passant_and_promotions() {
+        let cases = [
+            ("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1", ["e4d5", "e8e7"]),
+            ("r3k3/8/8/8/8/8/8/R3K2R w KQq - 0 1", ["e1g1", "e8c8"]),
+            ("4k3/8/8/8/3p4/8/4P3/4K3 w - - 0 1", ["e2e4", "d4e3"]),
+            ("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1", ["b7b8q", "e8d7"]),
+        ];
+        let state = test_state().await;
+
+        for (fen, moves) in cases {
+            let (mut session, mut runtime) =
+                session_and_runtime(&state, cozy_chess::Color::Black, true).await;
+            session.rated = false;
+            let start: cozy_chess::Board = fen.parse().unwrap();
+            let mut game = Game::new(start.clone());
+            for uci in moves {
+                game.play_uci(uci).unwrap();
+            }
+            runtime.start_fen = fen.to_string();
+            runtime.current_fen = game.board().to_string();
+            runtime.game = game;
+            runtime.move_history = moves.map(str::to_string).to_vec();
+            runtime.move_times_ms = vec![Some(1_200), Some(800)];
+            runtime.white_time_left_ms = 10_000;
+
+            let ack = process_human_takeback(
+                &state,
+                &session,
+                &mut runtime,
+                cozy_chess::Color::White,
+                Uuid::new_v4(),
+            )
+            .await;
+
+            assert!(matches!(ack, HumanMoveAck::Accepted), "{fen}");
+            assert_eq!(runtime.game.board(), &start, "{fen}");
+            assert_eq!(runtime.current_fen, fen);
+            assert!(runtime.move_history.is_empty());
+            assert!(runtime.move_times_ms.is_empty());
+            assert_eq!(
+                runtime.game.repetitions(),
+                &HashMap::from([(start.hash_without_ep(), 1)])
+            );
+            assert_eq!(runtime.status, MatchStatus::Running);
+            let snapshot = state
+                .live_matches
+                .get_snapshot(session.match_series.id)
+                .await
+                .unwrap();
+            assert_eq!(snapshot.fen, fen);
+            assert!(snapshot.moves.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn human_takeback_needs_a_human_move_to_undo() {
+        let state = test_state().await;
+        let (mut session, mut runtime) =
+            session_and_runtime(&state, cozy_chess::Color::White, false).await;
+        session.rated = false;
+        runtime.game.play_uci("e2e4").unwrap();
+        runtime.move_history = vec!["e2e4".to_string()];
+
+        let ack = process_human_takeback(
+            &state,
+            &session,
+            &mut runtime,
+            cozy_chess::Color::Black,
+            Uuid::new_v4(),
+        )
+        .await;
+
+        assert!(matches!(ack, HumanMoveAck::RejectedNothingToTakeBack));
+        assert_eq!(runtime.move_history, ["e2e4"]);
+    }
+
+    #[tokio::test]
+    async fn human_takeback_is_refused_in_rated_games() {
+        let state = test_state().await;
+        let (session, mut runtime) =
+            session_and_runtime(&state, cozy_chess::Color::Black, true).await;
+        assert!(session.rated);
+        for uci in ["e2e4", "e7e5"] {
+            runtime.game.play_uci(uci).unwrap();
+        }
+        runtime.move_history = vec!["e2e4".to_string(), "e7e5".to_string()];
+
+        let ack = process_human_takeback(
+            &state,
+            &session,
+            &mut runtime,
+            cozy_chess::Color::White,
+            Uuid::new_v4(),
+        )
+        .await;
+
+        assert!(matches!(ack, HumanMoveAck::RejectedRatedGame));
+        assert_eq!(runtime.move_history, ["e2e4", "e7e5"]);
+        assert_eq!(runtime.game.ply_count(), 2);
+    }
+
     #[tokio::test]
     async fn human_owner_times_out_without_submitted_move() {
         let state = test_state().await;
@@ -822,6 +1649,8 @@ mod tests {
             pool.id,
             engine_version.id,
             true,
+            None,
+            None,
         )
         .await
         .unwrap();
@@ -832,4 +1661,271 @@ mod tests {
         assert_eq!(series.white_version_id, human_player.id);
         assert_eq!(series.black_version_id, engine_version.id);
     }
+
+    #[tokio::test]
+    async fn create_human_game_continues_from_a_pgn_setup() {
+        let state = test_state().await;
+        let pool = crate::storage::list_pools(&state.db)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|pool| pool.variant == arena_core::Variant::Standard)
+            .unwrap();
+        let engine_version = list_agent_versions(&state.db, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        let (match_id, _tournament_id) = create_human_game(
+            &state,
+            "from pgn".to_string(),
+            pool.id,
+            engine_version.id,
+            false,
+            Some(crate::gameplay::GameSetup::Pgn(
+                "1. e4 e5 2. Nf3 *".to_string(),
+            )),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(1);
+        let snapshot = loop {
+            if let Some(snapshot) = state.live_matches.get_snapshot(match_id).await {
+                break snapshot;
+            }
+            assert!(tokio::time::Instant::now() < deadline);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        assert_eq!(
+            snapshot.start_fen,
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+        assert_eq!(snapshot.moves, vec!["e2e4", "e7e5", "g1f3"]);
+        assert_eq!(snapshot.move_times_ms, vec![None; 3]);
+        assert_eq!(
+            snapshot.fen,
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"
+        );
+        let series = crate::storage::get_match_series(&state.db, match_id)
+            .await
+            .unwrap();
+        assert_eq!(series.opening_id, None);
+    }
+
+    #[tokio::test]
+    async fn tournament_rating_updates_keep_concurrent_human_game_results() {
+        let state = test_state().await;
+        let pool = crate::storage::list_pools(&state.db)
+            .await
+            .unwrap()
+            .remove(0);
+        let versions = list_agent_versions(&state.db, None).await.unwrap();
+        let (shared, opponent) = (versions[0].id, versions[1].id);
+        let human = ensure_human_player(&state.db).await.unwrap();
+        let game = |white, black, result| finished_game(&pool, white, black, result);
+
+        // The tournament seeds its ratings before the human game finishes.
+        let ratings = tokio::sync::Mutex::new(
+            ensure_leaderboard_seed(&state.db, pool.id, &[shared, opponent])
+                .await
+                .unwrap(),
+        );
+        let human_game = game(human.id, shared, arena_core::GameResult::BlackWin);
+        crate::human_games::service::apply_human_pool_rating_update(
+            &state.db,
+            pool.id,
+            &human_game,
+            &human,
+        )
+        .await
+        .unwrap();
+        let pair = arena_core::MatchPair {
+            engine_a: shared,
+            engine_b: opponent,
+            games: vec![game(shared, opponent, arena_core::GameResult::WhiteWin)],
+        };
+        apply_pool_rating_update(&state.db, pool.id, &pair, &ratings)
+            .await
+            .unwrap();
+
+        let leaderboard = crate::storage::load_pool_leaderboard(&state.db, pool.id)
+            .await
+            .unwrap();
+        let entry = |id| {
+            leaderboard
+                .iter()
+                .find(|entry| entry.agent_version_id == id)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(entry(shared).games_played, 2);
+        assert_eq!(entry(shared).wins, 2);
+        assert_eq!(entry(opponent).losses, 1);
+        assert_eq!(entry(human.id).losses, 1);
+        assert_eq!(ratings.lock().await[&shared], entry(shared));
+    }
+
+    /// Stores each game with the next scripted result instead of playing
+    /// it, and stops the tournament when the script runs out, as if it was
+    /// interrupted mid-game.
+    struct ScriptedGames {
+        results: std::sync::Mutex<std::collections::VecDeque<arena_core::GameResult>>,
+    }
+
+    impl ScriptedGames {
+        fn new(results: &[arena_core::GameResult]) -> Self {
+            Self {
+                results: std::sync::Mutex::new(results.iter().copied().collect()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl PairGamePlayer for ScriptedGames {
+        async fn play_game(
+            &self,
+            state: &AppState,
+            pool: &arena_core::BenchmarkPool,
+            _white: &AgentVersion,
+            _black: &AgentVersion,
+            _opening: Option<&arena_core::OpeningPosition>,
+            _pair_index: u32,
+            series: MatchSeries,
+            stop_flag: &std::sync::atomic::AtomicBool,
+        ) -> Result<Option<GameRecord>> {
+            let Some(result) = self.results.lock().unwrap().pop_front() else {
+                stop_flag.store(true, Ordering::SeqCst);
+                return Ok(None);
+            };
+            let game = finished_series_game(pool, &series, result);
+            crate::storage::insert_game(&state.db, &game).await?;
+            Ok(Some(game))
+        }
+    }
+
+    #[tokio::test]
+    async fn resumed_tournament_matches_an_uninterrupted_run() {
+        use arena_core::GameResult::{BlackWin, Draw, WhiteWin};
+        let results = [WhiteWin, Draw, BlackWin, WhiteWin, Draw, Draw];
+
+        let mut runs = Vec::new();
+        for interrupted_after in [None, Some(3)] {
+            let state = test_state().await;
+            let pool = crate::storage::list_pools(&state.db)
+                .await
+                .unwrap()
+                .remove(0);
+            let versions = list_agent_versions(&state.db, None).await.unwrap();
+            let tournament = Tournament {
+                id: Uuid::new_v4(),
+                name: "gauntlet".to_string(),
+                kind: TournamentKind::RoundRobin,
+                pool_id: pool.id,
+                participant_version_ids: vec![versions[0].id, versions[1].id],
+                worker_count: 1,
+                games_per_pairing: 3,
+                status: TournamentStatus::Running,
+                created_at: Utc::now(),
+                started_at: Some(Utc::now()),
+                completed_at: None,
+            };
+            insert_tournament(&state.db, &tournament).await.unwrap();
+
+            if let Some(played) = interrupted_after {
+                play_tournament(
+                    state.clone(),
+                    tournament.id,
+                    Arc::default(),
+                    &ScriptedGames::new(&results[..played]),
+                )
+                .await
+                .unwrap();
+                let stopped = get_tournament(&state.db, tournament.id).await.unwrap();
+                assert_eq!(stopped.status, TournamentStatus::Stopped);
+                let progress = load_tournament_progress(&state.db, tournament.id)
+                    .await
+                    .unwrap();
+                let unfinished = progress.unfinished.unwrap();
+                assert_eq!(progress.pairs.len(), 1);
+                assert_eq!(unfinished.games.len(), 1);
+                assert_eq!(unfinished.remaining.len(), 1);
+                assert_eq!(unfinished.remaining[0].white_version_id, versions[1].id);
+                play_tournament(
+                    state.clone(),
+                    tournament.id,
+                    Arc::default(),
+                    &ScriptedGames::new(&results[played..]),
+                )
+                .await
+                .unwrap();
+            } else {
+                play_tournament(
+                    state.clone(),
+                    tournament.id,
+                    Arc::default(),
+                    &ScriptedGames::new(&results),
+                )
+                .await
+                .unwrap();
+            }
+            // Pairings repeat until a stopping rule fires, so both runs stop
+            // when the script runs out at the first game of a fourth pair.
+            let finished = get_tournament(&state.db, tournament.id).await.unwrap();
+            assert_eq!(finished.status, TournamentStatus::Stopped);
+
+            let progress = load_tournament_progress(&state.db, tournament.id)
+                .await
+                .unwrap();
+            assert_eq!(progress.pairs.len(), 3);
+            assert!(
+                progress
+                    .unfinished
+                    .is_some_and(|pair| pair.games.is_empty())
+            );
+            // Registry ids differ between databases, so compare by seat.
+            let seat = |id: Uuid| {
+                tournament
+                    .participant_version_ids
+                    .iter()
+                    .position(|participant| *participant == id)
+            };
+            let games: Vec<_> = progress
+                .pairs
+                .iter()
+                .flat_map(|pair| &pair.games)
+                .map(|game| {
+                    (
+                        seat(game.white_version_id),
+                        seat(game.black_version_id),
+                        game.result,
+                    )
+                })
+                .collect();
+            let mut leaderboard: Vec<_> = crate::storage::load_pool_leaderboard(&state.db, pool.id)
+                .await
+                .unwrap()
+                .into_iter()
+                .filter_map(|entry| {
+                    Some((
+                        seat(entry.agent_version_id)?,
+                        entry.rating.to_bits(),
+                        entry.games_played,
+                        entry.wins,
+                        entry.draws,
+                        entry.losses,
+                    ))
+                })
+                .collect();
+            leaderboard.sort();
+            runs.push((games, leaderboard));
+        }
+
+        assert_eq!(runs[0].0.len(), 6);
+        assert_eq!(runs[0].1[0].2, 6);
+        assert_eq!(runs[0], runs[1]);
+    }
 }
@@ -0,0 +1,31 @@
+//! Embeds the short hash of the commit being built, so engines can tell
+//! builds apart in their UCI `id name`.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=CLASSICAL_ENGINE_GIT_HASH={hash}");
+
+    // A missing path would rebuild every time, so only existing ones are
+    // watched; outside a checkout the hash stays `unknown`.
+    println!("cargo:rerun-if-changed=build.rs");
+    for path in [
+        "../../.git/HEAD",
+        "../../.git/refs",
+        "../../.git/packed-refs",
+    ] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+}
@@ -0,0 +1,314 @@
+//! Endgame knowledge the general evaluation lacks: an exact KPK bitbase and
+//! mop-up scoring for a lone king against a queen or a rook.
+
+use std::sync::OnceLock;
+
+use cozy_chess::{Board, Color, Piece, Square};
+
+use crate::eval::piece_index;
+
+/// Base score for a won endgame, far above any material balance and far
+/// below the mate range, so search still prefers a real mate.
+const KNOWN_WIN: i32 = 10_000;
+
+/// Positions in the bitbase: side to move, pawn on files a-d and ranks 2-7,
+/// and both kings.
+const KPK_POSITIONS: usize = 2 * 24 * 64 * 64;
+
+/// Result-bits used while generating the bitbase; a position's value is the
+/// union of its children's, as in Stockfish's `bitbase.cpp`.
+const INVALID: u8 = 0;
+const UNKNOWN: u8 = 1;
+const DRAW: u8 = 2;
+const WIN: u8 = 4;
+
+/// Side-to-move score for the endgames handled here, or `None` to fall back
+/// to the regular evaluation.
+pub(crate) fn evaluate(board: &Board, eg_value: &[i32; 6]) -> Option<i32> {
+    let strong = [Color::White, Color::Black]
+        .into_iter()
+        .find(|&color| board.colors(!color).len() == 1 && board.colors(color).len() == 2)?;
+    let strong_king = board.king(strong);
+    let weak_king = board.king(!strong);
+    let (piece, square) = [Piece::Pawn, Piece::Queen, Piece::Rook]
+        .into_iter()
+        .find_map(|piece| Some((piece, board.colored_pieces(strong, piece).next_square()?)))?;
+
+    let score = if piece == Piece::Pawn {
+        let pawn = square;
+        if !kpk_is_win(strong, strong_king, pawn, weak_king, board.side_to_move()) {
+            return Some(0);
+        }
+        // Pushing the pawn is progress, and promoting scores higher still.
+        KNOWN_WIN + 20 * pawn.relative_to(strong).rank() as i32
+    } else {
+        KNOWN_WIN + eg_value[piece_index(piece)] + mop_up(strong_king, weak_king)
+    };
+    Some(if board.side_to_move() == strong {
+        score
+    } else {
+        -score
+    })
+}
+
+/// Whether the side with the pawn wins with best play.
+pub(crate) fn kpk_is_win(
+    strong: Color,
+    strong_king: Square,
+    pawn: Square,
+    weak_king: Square,
+    side_to_move: Color,
+) -> bool {
+    // Normalize to White holding a pawn on files a-d.
+    let normalize = |square: Square| {
+        let square = square.relative_to(strong);
+        if pawn.file() as usize >= 4 {
+            square.flip_file()
+        } else {
+            square
+        }
+    };
+    let index = kpk_index(
+        side_to_move == strong,
+        normalize(strong_king) as usize,
+        normalize(weak_king) as usize,
+        normalize(pawn) as usize,
+    );
+    let bits = kpk_bitbase();
+    bits[index / 64] & (1 << (index % 64)) != 0
+}
+
+/// Reward driving the lone king to the edge and bringing ours next to it.
+fn mop_up(strong_king: Square, weak_king: Square) -> i32 {
+    20 * center_distance(weak_king as usize)
+        + 10 * (7 - distance(strong_king as usize, weak_king as usize))
+}
+
+fn kpk_bitbase() -> &'static [u64] {
+    static BITBASE: OnceLock<Vec<u64>> = OnceLock::new();
+    BITBASE.get_or_init(generate_kpk)
+}
+
+fn kpk_index(white_to_move: bool, white_king: usize, black_king: usize, pawn: usize) -> usize {
+    let pawn_index = (pawn >> 3) - 1 + 6 * (pawn & 7);
+    usize::from(white_to_move) + 2 * (white_king + 64 * (black_king + 64 * pawn_index))
+}
+
+fn generate_kpk() -> Vec<u64> {
+    let mut values = vec![INVALID; KPK_POSITIONS];
+    for pawn in (0..64).filter(|square| square & 7 < 4 && (1..7).contains(&(square >> 3))) {
+        for white_king in 0..64 {
+            for black_king in 0..64 {
+                for white_to_move in [false, true] {
+                    values[kpk_index(white_to_move, white_king, black_king, pawn)] =
+                        initial_value(white_to_move, white_king, black_king, pawn);
+                }
+            }
+        }
+    }
+
+    // Retrograde passes until nothing changes; whatever stays unknown is a draw.
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for index in 0..KPK_POSITIONS {
+            if values[index] != UNKNOWN {
+                continue;
+            }
+            let (white_to_move, white_king, black_king, pawn) = kpk_position(index);
+            let value = classify(&values, white_to_move, white_king, black_king, pawn);
+            if value != UNKNOWN {
+                values[index] = value;
+                changed = true;
+            }
+        }
+    }
+
+    let mut bits = vec![0; KPK_POSITIONS / 64];
+    for (index, value) in values.into_iter().enumerate() {
+        if value == WIN {
+            bits[index / 64] |= 1 << (index % 64);
+        }
+    }
+    bits
+}
+
+fn kpk_position(index: usize) -> (bool, usize, usize, usize) {
+    let white_to_move = index & 1 == 1;
+    let white_king = (index >> 1) & 63;
+    let black_king = (index >> 7) & 63;
+    let pawn_index = index >> 13;
+    let pawn = 8 * (pawn_index % 6 + 1) + pawn_index / 6;
+    (white_to_move, white_king, black_king, pawn)
+}
+
+fn initial_value(white_to_move: bool, white_king: usize, black_king: usize, pawn: usize) -> u8 {
+    if distance(white_king, black_king) <= 1
+        || white_king == pawn
+        || black_king == pawn
+        || (white_to_move && pawn_attacks(pawn, black_king))
+    {
+        return INVALID;
+    }
+
+    let promotion = pawn + 8;
+    if white_to_move
+        && pawn >> 3 == 6
+        && white_king != promotion
+        && black_king != promotion
+        && (distance(black_king, promotion) > 1 || distance(white_king, promotion) == 1)
+    {
+        return WIN;
+    }
+
+    if !white_to_move {
+        let stalemated = king_moves(black_king)
+            .all(|square| distance(square, white_king) <= 1 || pawn_attacks(pawn, square));
+        let takes_pawn = distance(black_king, pawn) == 1 && distance(white_king, pawn) > 1;
+        if stalemated || takes_pawn {
+            return DRAW;
+        }
+    }
+    UNKNOWN
+}
+
+/// Combine the children's values: White needs one winning move, Black one
+/// drawing move; illegal children contribute nothing.
+fn classify(
+    values: &[u8],
+    white_to_move: bool,
+    white_king: usize,
+    black_king: usize,
+    pawn: usize,
+) -> u8 {
+    let mut result = INVALID;
+    if white_to_move {
+        for square in king_moves(white_king) {
+            result |= values[kpk_index(false, square, black_king, pawn)];
+        }
+        let push = pawn + 8;
+        if pawn >> 3 < 6 && push != white_king && push != black_king {
+            result |= values[kpk_index(false, white_king, black_king, push)];
+            let double = push + 8;
+            if pawn >> 3 == 1 && double != white_king && double != black_king {
+                result |= values[kpk_index(false, white_king, black_king, double)];
+            }
+        }
+        if result & WIN != 0 {
+            WIN
+        } else if result & UNKNOWN != 0 {
+            UNKNOWN
+        } else {
+            DRAW
+        }
+    } else {
+        for square in king_moves(black_king) {
+            result |= values[kpk_index(true, white_king, square, pawn)];
+        }
+        if result & DRAW != 0 {
+            DRAW
+        } else if result & UNKNOWN != 0 {
+            UNKNOWN
+        } else {
+            WIN
+        }
+    }
+}
+
+fn king_moves(square: usize) -> impl Iterator<Item = usize> {
+    let (file, rank) = ((square & 7) as isize, (square >> 3) as isize);
+    [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)]
+        .into_iter()
+        .map(move |(df, dr)| (file + df, rank + dr))
+        .filter(|&(file, rank)| (0..8).contains(&file) && (0..8).contains(&rank))
+        .map(|(file, rank)| (rank * 8 + file) as usize)
+}
+
+fn pawn_attacks(pawn: usize, square: usize) -> bool {
+    square >> 3 == (pawn >> 3) + 1 && (square & 7).abs_diff(pawn & 7) == 1
+}
+
+fn distance(a: usize, b: usize) -> i32 {
+    (a & 7).abs_diff(b & 7).max((a >> 3).abs_diff(b >> 3)) as i32
+}
+
+fn center_distance(square: usize) -> i32 {
+    let from_center = |coord: usize| if coord < 4 { 3 - coord } else { coord - 4 };
+    (from_center(square & 7) + from_center(square >> 3)) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probe(fen: &str) -> bool {
+        let board: Board = fen.parse().unwrap();
+        let strong = if board.colored_pieces(Color::White, Piece::Pawn).is_empty() {
+            Color::Black
+        } else {
+            Color::White
+        };
+        let pawn = board
+            .colored_pieces(strong, Piece::Pawn)
+            .next_square()
+            .unwrap();
+        kpk_is_win(
+            strong,
+            board.king(strong),
+            pawn,
+            board.king(!strong),
+            board.side_to_move(),
+        )
+    }
+
+    #[test]
+    fn kpk_knows_the_classic_wins() {
+        for fen in [
+            // King on the sixth in front of the pawn wins whoever moves.
+            "4k3/8/4K3/4P3/8/8/8/8 w - - 0 1",
+            "4k3/8/4K3/4P3/8/8/8/8 b - - 0 1",
+            // The defending king is outside the square of the pawn.
+            "7k/8/8/8/P7/8/8/7K w - - 0 1",
+            // The same win for Black, mirrored.
+            "8/8/8/8/4p3/4k3/8/4K3 b - - 0 1",
+        ] {
+            assert!(probe(fen), "{fen}");
+        }
+    }
+
+    #[test]
+    fn kpk_knows_the_classic_draws() {
+        for fen in [
+            // Rook pawn with the defending king on the promotion square.
+            "k7/8/8/8/P7/8/1K6/8 w - - 0 1",
+            // Stalemate.
+            "4k3/4P3/4K3/8/8/8/8/8 b - - 0 1",
+            // The pawn is lost at once.
+            "8/8/8/8/8/3k4/4P3/7K b - - 0 1",
+        ] {
+            assert!(!probe(fen), "{fen}");
+        }
+    }
+
+    #[test]
+    fn drawn_kpk_scores_zero_and_won_kpk_scores_for_the_pawn_side() {
+        let eg_value = [100, 300, 300, 500, 900, 0];
+        let won: Board = "4k3/8/4K3/4P3/8/8/8/8 b - - 0 1".parse().unwrap();
+        let drawn: Board = "k7/8/8/8/P7/8/1K6/8 w - - 0 1".parse().unwrap();
+
+        assert!(evaluate(&won, &eg_value).unwrap() < -KNOWN_WIN);
+        assert_eq!(evaluate(&drawn, &eg_value), Some(0));
+    }
+
+    #[test]
+    fn mop_up_prefers_the_lone_king_in_the_corner() {
+        let eg_value = [100, 300, 300, 500, 900, 0];
+        let cornered: Board = "7k/8/6K1/8/8/8/8/1Q6 w - - 0 1".parse().unwrap();
+        let centered: Board = "8/8/8/4k3/8/8/8/1Q2K3 w - - 0 1".parse().unwrap();
+
+        let cornered = evaluate(&cornered, &eg_value).unwrap();
+        let centered = evaluate(&centered, &eg_value).unwrap();
+        assert!(centered > KNOWN_WIN);
+        assert!(cornered > centered);
+    }
+}
@@ -0,0 +1,655 @@
+//! Tapered middlegame/endgame evaluation. Every tunable term, piece-square
+//! tables included, is read from [`EvalWeights`].
+
+use cozy_chess::{
+    BitBoard, Board, Color, File, Piece, Rank, Square, get_bishop_moves, get_king_moves,
+    get_knight_moves, get_pawn_attacks, get_rook_moves,
+};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::endgame;
+
+const PHASE_MAX: i32 = 24;
+const PHASE_VALUE: [i32; 6] = [0, 1, 1, 2, 4, 0];
+
+/// Evaluation terms a tuning profile can change, in centipawns. Arrays are
+/// indexed pawn, knight, bishop, rook, queen, king.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvalWeights {
+    pub mg_value: [i32; 6],
+    pub eg_value: [i32; 6],
+    pub mobility_mg: [i32; 6],
+    pub mobility_eg: [i32; 6],
+    pub bishop_pair_mg: i32,
+    pub bishop_pair_eg: i32,
+    pub doubled_pawn_mg: i32,
+    pub doubled_pawn_eg: i32,
+    pub isolated_pawn_mg: i32,
+    pub isolated_pawn_eg: i32,
+    pub connected_pawn_mg: i32,
+    pub connected_pawn_eg: i32,
+    pub passed_pawn_base_mg: i32,
+    pub passed_pawn_rank_mg: i32,
+    pub passed_pawn_base_eg: i32,
+    pub passed_pawn_rank_eg: i32,
+    pub king_shield_front_mg: i32,
+    pub king_shield_front_eg: i32,
+    pub king_shield_missing_mg: i32,
+    pub king_shield_second_mg: i32,
+    /// Per file next to or under the king with no pawns at all.
+    pub king_open_file_mg: i32,
+    /// Per file next to or under the king with only enemy pawns.
+    pub king_semi_open_file_mg: i32,
+    pub king_attack_scale_mg: i32,
+    pub king_attack_scale_eg_divisor: i32,
+    pub mg_pst: PieceSquareTables,
+    pub eg_pst: PieceSquareTables,
+}
+
+/// A bonus per piece and square, indexed `[rank][file]` with ranks counted
+/// from the piece owner's side of the board.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PieceSquareTables {
+    pub pawn: [[i32; 8]; 8],
+    pub knight: [[i32; 8]; 8],
+    pub bishop: [[i32; 8]; 8],
+    pub rook: [[i32; 8]; 8],
+    pub queen: [[i32; 8]; 8],
+    pub king: [[i32; 8]; 8],
+}
+
+impl PieceSquareTables {
+    fn value(&self, piece: Piece, square: Square, color: Color) -> i32 {
+        let table = match piece {
+            Piece::Pawn => &self.pawn,
+            Piece::Knight => &self.knight,
+            Piece::Bishop => &self.bishop,
+            Piece::Rook => &self.rook,
+            Piece::Queen => &self.queen,
+            Piece::King => &self.king,
+        };
+        table[relative_rank(square, color) as usize][square.file() as usize]
+    }
+}
+
+impl EvalWeights {
+    /// The hand-chosen values the handcrafted engine has always used.
+    pub const HANDCRAFTED: Self = Self {
+        mg_value: [82, 337, 365, 477, 1025, 0],
+        eg_value: [94, 281, 297, 512, 936, 0],
+        mobility_mg: [0, 4, 5, 2, 1, 0],
+        mobility_eg: [0, 4, 5, 4, 2, 0],
+        bishop_pair_mg: 30,
+        bishop_pair_eg: 42,
+        doubled_pawn_mg: -11,
+        doubled_pawn_eg: -16,
+        isolated_pawn_mg: -14,
+        isolated_pawn_eg: -12,
+        connected_pawn_mg: 6,
+        connected_pawn_eg: 8,
+        passed_pawn_base_mg: 12,
+        passed_pawn_rank_mg: 6,
+        passed_pawn_base_eg: 24,
+        passed_pawn_rank_eg: 10,
+        king_shield_front_mg: 14,
+        king_shield_front_eg: 4,
+        king_shield_missing_mg: -12,
+        king_shield_second_mg: 6,
+        king_open_file_mg: -20,
+        king_semi_open_file_mg: -10,
+        king_attack_scale_mg: 1,
+        king_attack_scale_eg_divisor: 4,
+        mg_pst: PieceSquareTables {
+            pawn: [
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [98, 134, 61, 95, 68, 126, 34, -11],
+                [-6, 7, 26, 31, 65, 56, 25, -20],
+                [-14, 13, 6, 21, 23, 12, 17, -23],
+                [-27, -2, -5, 12, 17, 6, 10, -25],
+                [-26, -4, -4, -10, 3, 3, 33, -12],
+                [-35, -1, -20, -23, -15, 24, 38, -22],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+            ],
+            knight: [
+                [-167, -89, -34, -49, 61, -97, -15, -107],
+                [-73, -41, 72, 36, 23, 62, 7, -17],
+                [-47, 60, 37, 65, 84, 129, 73, 44],
+                [-9, 17, 19, 53, 37, 69, 18, 22],
+                [-13, 4, 16, 13, 28, 19, 21, -8],
+                [-23, -9, 12, 10, 19, 17, 25, -16],
+                [-29, -53, -12, -3, -1, 18, -14, -19],
+                [-105, -21, -58, -33, -17, -28, -19, -23],
+            ],
+            bishop: [
+                [-29, 4, -82, -37, -25, -42, 7, -8],
+                [-26, 16, -18, -13, 30, 59, 18, -47],
+                [-16, 37, 43, 40, 35, 50, 37, -2],
+                [-4, 5, 19, 50, 37, 37, 7, -2],
+                [-6, 13, 13, 26, 34, 12, 10, 4],
+                [0, 15, 15, 15, 14, 27, 18, 10],
+                [4, 15, 16, 0, 7, 21, 33, 1],
+                [-33, -3, -14, -21, -13, -12, -39, -21],
+            ],
+            rook: [
+                [32, 42, 32, 51, 63, 9, 31, 43],
+                [27, 32, 58, 62, 80, 67, 26, 44],
+                [-5, 19, 26, 36, 17, 45, 61, 16],
+                [-24, -11, 7, 26, 24, 35, -8, -20],
+                [-36, -26, -12, -1, 9, -7, 6, -23],
+                [-45, -25, -16, -17, 3, 0, -5, -33],
+                [-44, -16, -20, -9, -1, 11, -6, -71],
+                [-19, -13, 1, 17, 16, 7, -37, -26],
+            ],
+            queen: [
+                [-28, 0, 29, 12, 59, 44, 43, 45],
+                [-24, -39, -5, 1, -16, 57, 28, 54],
+                [-13, -17, 7, 8, 29, 56, 47, 57],
+                [-27, -27, -16, -16, -1, 17, -2, 1],
+                [-9, -26, -9, -10, -2, -4, 3, -3],
+                [-14, 2, -11, -2, -5, 2, 14, 5],
+                [-35, -8, 11, 2, 8, 15, -3, 1],
+                [-1, -18, -9, 10, -15, -25, -31, -50],
+            ],
+            king: [
+                [-65, 23, 16, -15, -56, -34, 2, 13],
+                [29, -1, -20, -7, -8, -4, -38, -29],
+                [-9, 24, 2, -16, -20, 6, 22, -22],
+                [-17, -20, -12, -27, -30, -25, -14, -36],
+                [-49, -1, -27, -39, -46, -44, -33, -51],
+                [-14, -14, -22, -46, -44, -30, -15, -27],
+                [1, 7, -8, -64, -43, -16, 9, 8],
+                [-15, 36, 12, -54, 8, -28, 24, 14],
+            ],
+        },
+        eg_pst: PieceSquareTables {
+            pawn: [
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [178, 173, 158, 134, 147, 132, 165, 187],
+                [94, 100, 85, 67, 56, 53, 82, 84],
+                [32, 24, 13, 5, -2, 4, 17, 17],
+                [13, 9, -3, -7, -7, -8, 3, -1],
+                [4, 7, -6, 1, 0, -5, -1, -8],
+                [13, 8, 8, 10, 13, 0, 2, -7],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+            ],
+            knight: [
+                [-58, -38, -13, -28, -31, -27, -63, -99],
+                [-25, -8, -25, -2, -9, -25, -24, -52],
+                [-24, -20, 10, 9, -1, -9, -19, -41],
+                [-17, 3, 22, 22, 22, 11, 8, -18],
+                [-18, -6, 16, 25, 16, 17, 4, -18],
+                [-23, -3, -1, 15, 10, -3, -20, -22],
+                [-42, -20, -10, -5, -2, -20, -23, -44],
+                [-29, -51, -23, -15, -22, -18, -50, -64],
+            ],
+            bishop: [
+                [-14, -21, -11, -8, -7, -9, -17, -24],
+                [-8, -4, 7, -12, -3, -13, -4, -14],
+                [2, -8, 0, -1, -2, 6, 0, 4],
+                [-3, 9, 12, 9, 14, 10, 3, 2],
+                [-6, 3, 13, 19, 7, 10, -3, -9],
+                [-12, -3, 8, 10, 13, 3, -7, -15],
+                [-14, -18, -7, -1, 4, -9, -15, -27],
+                [-23, -9, -23, -5, -9, -16, -5, -17],
+            ],
+            rook: [
+                [13, 10, 18, 15, 12, 12, 8, 5],
+                [11, 13, 13, 11, -3, 3, 8, 3],
+                [7, 7, 7, 5, 4, -3, -5, -3],
+                [4, 3, 13, 1, 2, 1, -1, 2],
+                [3, 5, 8, 4, -5, -6, -8, -11],
+                [-4, 0, -5, -1, -7, -12, -8, -16],
+                [-6, -6, 0, 2, -9, -9, -11, -3],
+                [-9, 2, 3, -1, -5, -13, 4, -20],
+            ],
+            queen: [
+                [-9, 22, 22, 27, 27, 19, 10, 20],
+                [-17, 20, 32, 41, 58, 25, 30, 0],
+                [-20, 6, 9, 49, 47, 35, 19, 9],
+                [3, 22, 24, 45, 57, 40, 57, 36],
+                [-18, 28, 19, 47, 31, 34, 39, 23],
+                [-16, -27, 15, 6, 9, 17, 10, 5],
+                [-22, -23, -30, -16, -16, -23, -36, -32],
+                [-33, -28, -22, -43, -5, -32, -20, -41],
+            ],
+            king: [
+                [-74, -35, -18, -18, -11, 15, 4, -17],
+                [-12, 17, 14, 17, 17, 38, 23, 11],
+                [10, 17, 23, 15, 20, 45, 44, 13],
+                [-8, 22, 24, 27, 26, 33, 26, 3],
+                [-18, -4, 21, 24, 27, 23, 9, -11],
+                [-19, -3, 11, 21, 23, 16, 7, -9],
+                [-27, -11, 4, 13, 14, 4, -5, -17],
+                [-53, -34, -21, -11, -28, -14, -24, -43],
+            ],
+        },
+    };
+
+    /// Reads a profile saved as JSON, as the tuner writes it.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("invalid weights in {}", path.display()))
+    }
+}
+
+/// Static evaluation in centipawns from the side to move's point of view.
+pub fn evaluate(board: &Board, weights: &EvalWeights) -> i32 {
+    if let Some(score) = endgame::evaluate(board, &weights.eg_value) {
+        return score;
+    }
+    let phase = game_phase(board);
+    let (white_mg, white_eg) = score_side(board, Color::White, weights);
+    let (black_mg, black_eg) = score_side(board, Color::Black, weights);
+    let mg_score = white_mg - black_mg;
+    let eg_score = white_eg - black_eg;
+    let blended = (mg_score * phase + eg_score * (PHASE_MAX - phase)) / PHASE_MAX;
+    if board.side_to_move() == Color::White {
+        blended
+    } else {
+        -blended
+    }
+}
+
+fn score_side(board: &Board, color: Color, weights: &EvalWeights) -> (i32, i32) {
+    let mut mg = 0;
+    let mut eg = 0;
+    let occupied = board.colors(Color::White) | board.colors(Color::Black);
+    let own = board.colors(color);
+
+    for piece in Piece::ALL {
+        for square in board.colored_pieces(color, piece) {
+            let piece_idx = piece_index(piece);
+            mg += weights.mg_value[piece_idx] + weights.mg_pst.value(piece, square, color);
+            eg += weights.eg_value[piece_idx] + weights.eg_pst.value(piece, square, color);
+
+            let mobility = mobility_for_piece(color, piece, square, occupied, own, weights);
+            mg += mobility.0;
+            eg += mobility.1;
+        }
+    }
+
+    let pawn_terms = pawn_structure(board, color, weights);
+    mg += pawn_terms.0;
+    eg += pawn_terms.1;
+
+    let king_terms = king_safety(board, color, weights);
+    mg += king_terms.0;
+    eg += king_terms.1;
+
+    if board.colored_pieces(color, Piece::Bishop).len() >= 2 {
+        mg += weights.bishop_pair_mg;
+        eg += weights.bishop_pair_eg;
+    }
+
+    (mg, eg)
+}
+
+fn pawn_structure(board: &Board, color: Color, weights: &EvalWeights) -> (i32, i32) {
+    let pawns = board.colored_pieces(color, Piece::Pawn);
+    let enemy_pawns = board.colored_pieces(!color, Piece::Pawn);
+    let mut file_counts = [0_u8; 8];
+    let mut mg = 0;
+    let mut eg = 0;
+
+    for pawn in pawns {
+        file_counts[pawn.file() as usize] += 1;
+    }
+
+    for pawn in pawns {
+        let file = pawn.file() as usize;
+        let rank = relative_rank(pawn, color);
+
+        if file_counts[file] > 1 {
+            mg += weights.doubled_pawn_mg;
+            eg += weights.doubled_pawn_eg;
+        }
+
+        let has_left = file > 0 && file_counts[file - 1] > 0;
+        let has_right = file < 7 && file_counts[file + 1] > 0;
+        if !has_left && !has_right {
+            mg += weights.isolated_pawn_mg;
+            eg += weights.isolated_pawn_eg;
+        }
+        if has_left || has_right {
+            mg += weights.connected_pawn_mg;
+            eg += weights.connected_pawn_eg;
+        }
+
+        if is_passed_pawn(pawn, color, enemy_pawns) {
+            mg += weights.passed_pawn_base_mg + rank * weights.passed_pawn_rank_mg;
+            eg += weights.passed_pawn_base_eg + rank * weights.passed_pawn_rank_eg;
+        }
+    }
+
+    (mg, eg)
+}
+
+fn king_safety(board: &Board, color: Color, weights: &EvalWeights) -> (i32, i32) {
+    let king = board.king(color);
+    let zone = get_king_moves(king) | king.bitboard();
+    let mut mg = 0;
+    let mut eg = 0;
+
+    let rank_step = if color == Color::White { 1_i32 } else { -1_i32 };
+    let king_rank = king.rank() as i32;
+    let king_file = king.file() as i32;
+    let pawns = board.colored_pieces(color, Piece::Pawn);
+    let enemy_pawns = board.colored_pieces(!color, Piece::Pawn);
+
+    for file_delta in -1..=1 {
+        let file = king_file + file_delta;
+        if !(0..=7).contains(&file) {
+            continue;
+        }
+
+        let file_mask = File::index(file as usize).bitboard();
+        if (pawns & file_mask).is_empty() {
+            mg += if (enemy_pawns & file_mask).is_empty() {
+                weights.king_open_file_mg
+            } else {
+                weights.king_semi_open_file_mg
+            };
+        }
+
+        let front_rank = king_rank + rank_step;
+        let second_rank = king_rank + rank_step * 2;
+        let front_square = square_from_coords(file as usize, front_rank);
+        let second_square = square_from_coords(file as usize, second_rank);
+
+        let front_has_pawn = front_square.map(|sq| pawns.has(sq)).unwrap_or(false);
+        let second_has_pawn = second_square.map(|sq| pawns.has(sq)).unwrap_or(false);
+
+        if front_has_pawn {
+            mg += weights.king_shield_front_mg;
+            eg += weights.king_shield_front_eg;
+        } else {
+            mg += weights.king_shield_missing_mg;
+        }
+
+        if second_has_pawn {
+            mg += weights.king_shield_second_mg;
+        }
+    }
+
+    let pressure = enemy_attack_pressure(board, !color, zone);
+    mg -= pressure * weights.king_attack_scale_mg;
+    eg -= pressure / weights.king_attack_scale_eg_divisor.max(1);
+
+    (mg, eg)
+}
+
+fn enemy_attack_pressure(board: &Board, attacker: Color, zone: BitBoard) -> i32 {
+    let occupied = board.colors(Color::White) | board.colors(Color::Black);
+    let mut pressure = 0;
+
+    for square in board.colored_pieces(attacker, Piece::Pawn) {
+        if !(get_pawn_attacks(square, attacker) & zone).is_empty() {
+            pressure += 6;
+        }
+    }
+    for square in board.colored_pieces(attacker, Piece::Knight) {
+        if !(get_knight_moves(square) & zone).is_empty() {
+            pressure += 14;
+        }
+    }
+    for square in board.colored_pieces(attacker, Piece::Bishop) {
+        if !(get_bishop_moves(square, occupied) & zone).is_empty() {
+            pressure += 16;
+        }
+    }
+    for square in board.colored_pieces(attacker, Piece::Rook) {
+        if !(get_rook_moves(square, occupied) & zone).is_empty() {
+            pressure += 22;
+        }
+    }
+    for square in board.colored_pieces(attacker, Piece::Queen) {
+        let queen_attacks = get_bishop_moves(square, occupied) | get_rook_moves(square, occupied);
+        if !(queen_attacks & zone).is_empty() {
+            pressure += 30;
+        }
+    }
+
+    pressure
+}
+
+fn mobility_for_piece(
+    color: Color,
+    piece: Piece,
+    square: Square,
+    occupied: BitBoard,
+    own: BitBoard,
+    weights: &EvalWeights,
+) -> (i32, i32) {
+    let attacks = match piece {
+        Piece::Knight => get_knight_moves(square),
+        Piece::Bishop => get_bishop_moves(square, occupied),
+        Piece::Rook => get_rook_moves(square, occupied),
+        Piece::Queen => get_bishop_moves(square, occupied) | get_rook_moves(square, occupied),
+        Piece::King => get_king_moves(square),
+        Piece::Pawn => get_pawn_attacks(square, color),
+    } - own;
+
+    let count = attacks.len() as i32;
+    let piece_idx = piece_index(piece);
+    (
+        count * weights.mobility_mg[piece_idx],
+        count * weights.mobility_eg[piece_idx],
+    )
+}
+
+fn game_phase(board: &Board) -> i32 {
+    let mut phase = 0;
+    for piece in Piece::ALL {
+        let piece_idx = piece_index(piece);
+        phase += PHASE_VALUE[piece_idx] * board.pieces(piece).len() as i32;
+    }
+    phase.clamp(0, PHASE_MAX)
+}
+
+fn is_passed_pawn(square: Square, color: Color, enemy_pawns: BitBoard) -> bool {
+    for enemy in enemy_pawns {
+        let file_distance = (enemy.file() as i32 - square.file() as i32).abs();
+        if file_distance > 1 {
+            continue;
+        }
+        let ahead = match color {
+            Color::White => (enemy.rank() as i32) > square.rank() as i32,
+            Color::Black => (enemy.rank() as i32) < square.rank() as i32,
+        };
+        if ahead {
+            return false;
+        }
+    }
+    true
+}
+
+fn relative_rank(square: Square, color: Color) -> i32 {
+    match color {
+        Color::White => square.rank() as i32,
+        Color::Black => 7 - square.rank() as i32,
+    }
+}
+
+fn square_from_coords(file: usize, rank: i32) -> Option<Square> {
+    if !(0..=7).contains(&rank) {
+        return None;
+    }
+    Some(Square::new(File::index(file), Rank::index(rank as usize)))
+}
+
+pub(crate) fn piece_index(piece: Piece) -> usize {
+    match piece {
+        Piece::Pawn => 0,
+        Piece::Knight => 1,
+        Piece::Bishop => 2,
+        Piece::Rook => 3,
+        Piece::Queen => 4,
+        Piece::King => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The handcrafted weights with every pawn-structure and king-safety
+    /// term off, so a test can switch on exactly one.
+    fn without_structure_terms() -> EvalWeights {
+        EvalWeights {
+            doubled_pawn_mg: 0,
+            doubled_pawn_eg: 0,
+            isolated_pawn_mg: 0,
+            isolated_pawn_eg: 0,
+            connected_pawn_mg: 0,
+            connected_pawn_eg: 0,
+            passed_pawn_base_mg: 0,
+            passed_pawn_rank_mg: 0,
+            passed_pawn_base_eg: 0,
+            passed_pawn_rank_eg: 0,
+            king_shield_front_mg: 0,
+            king_shield_front_eg: 0,
+            king_shield_missing_mg: 0,
+            king_shield_second_mg: 0,
+            king_open_file_mg: 0,
+            king_semi_open_file_mg: 0,
+            king_attack_scale_mg: 0,
+            ..EvalWeights::HANDCRAFTED
+        }
+    }
+
+    /// White's (mg, eg) change in `fen` from switching on one term.
+    fn term_delta(fen: &str, enable: impl Fn(&mut EvalWeights)) -> (i32, i32) {
+        let board: Board = fen.parse().unwrap();
+        let base = without_structure_terms();
+        let mut weights = base.clone();
+        enable(&mut weights);
+        let (mg, eg) = score_side(&board, Color::White, &weights);
+        let (base_mg, base_eg) = score_side(&board, Color::White, &base);
+        (mg - base_mg, eg - base_eg)
+    }
+
+    /// The same position with the colors swapped and the board mirrored.
+    fn color_flipped(fen: &str) -> String {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        let swap_case = |text: &str| -> String {
+            text.chars()
+                .map(|c| {
+                    if c.is_ascii_uppercase() {
+                        c.to_ascii_lowercase()
+                    } else {
+                        c.to_ascii_uppercase()
+                    }
+                })
+                .collect()
+        };
+        let placement: Vec<&str> = fields[0].split('/').rev().collect();
+        let side = if fields[1] == "w" { "b" } else { "w" };
+        let mut castling: Vec<char> = swap_case(fields[2]).chars().collect();
+        castling.sort_by_key(|c| (c.is_ascii_lowercase(), *c != 'K' && *c != 'k'));
+        let en_passant = fields[3]
+            .replace('3', "x")
+            .replace('6', "3")
+            .replace('x', "6");
+        format!(
+            "{} {side} {} {en_passant} {} {}",
+            swap_case(&placement.join("/")),
+            castling.into_iter().collect::<String>(),
+            fields[4],
+            fields[5]
+        )
+    }
+
+    #[test]
+    fn doubled_pawns_count_each_pawn_on_the_file() {
+        let fen = "4k3/8/8/8/8/4P3/4P3/4K3 w - - 0 1";
+        let delta = term_delta(fen, |weights| {
+            weights.doubled_pawn_mg = -11;
+            weights.doubled_pawn_eg = -16;
+        });
+        assert_eq!(delta, (-22, -32));
+    }
+
+    #[test]
+    fn isolated_pawns_have_no_friendly_pawn_on_either_neighbouring_file() {
+        let fen = "4k3/8/8/8/8/8/P3PP2/4K3 w - - 0 1";
+        let delta = term_delta(fen, |weights| {
+            weights.isolated_pawn_mg = -14;
+            weights.isolated_pawn_eg = -12;
+        });
+        assert_eq!(delta, (-14, -12));
+    }
+
+    #[test]
+    fn passed_pawns_grow_with_their_rank() {
+        // The a-pawn is blocked in front by the a7 pawn; the e-pawn is free.
+        let fen = "4k3/p7/8/4P3/8/8/P7/4K3 w - - 0 1";
+        let delta = term_delta(fen, |weights| {
+            weights.passed_pawn_base_mg = 12;
+            weights.passed_pawn_rank_mg = 6;
+            weights.passed_pawn_base_eg = 24;
+            weights.passed_pawn_rank_eg = 10;
+        });
+        assert_eq!(delta, (12 + 4 * 6, 24 + 4 * 10));
+    }
+
+    #[test]
+    fn king_shield_counts_the_pawns_in_front_of_the_castled_king() {
+        let fen = "6k1/5ppp/8/8/8/8/5P1P/6K1 w - - 0 1";
+        let delta = term_delta(fen, |weights| {
+            weights.king_shield_front_mg = 14;
+            weights.king_shield_front_eg = 4;
+            weights.king_shield_missing_mg = -12;
+        });
+        assert_eq!(delta, (2 * 14 - 12, 2 * 4));
+    }
+
+    #[test]
+    fn open_and_semi_open_files_toward_the_king_cost_middlegame_score() {
+        let open = "6k1/5p1p/8/8/8/8/5P1P/6K1 w - - 0 1";
+        let semi_open = "6k1/5ppp/8/8/8/8/5P1P/6K1 w - - 0 1";
+        let enable = |weights: &mut EvalWeights| {
+            weights.king_open_file_mg = -20;
+            weights.king_semi_open_file_mg = -10;
+        };
+        assert_eq!(term_delta(open, enable), (-20, 0));
+        assert_eq!(term_delta(semi_open, enable), (-10, 0));
+        assert_eq!(
+            term_delta("6k1/5ppp/8/8/8/8/5PPP/6K1 w - - 0 1", enable),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn symmetric_positions_evaluate_to_zero_and_mirrors_negate() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r1bqkb1r/pppp1ppp/2n2n2/4p3/4P3/2N2N2/PPPP1PPP/R1BQKB1R w KQkq - 4 4",
+            "r4rk1/pp3ppp/2n5/3p4/3P4/2N5/PP3PPP/R4RK1 b - - 0 1",
+            "6k1/5p1p/8/8/8/8/5P1P/6K1 w - - 0 1",
+        ] {
+            let board: Board = fen.parse().unwrap();
+            assert_eq!(evaluate(&board, &EvalWeights::HANDCRAFTED), 0, "{fen}");
+        }
+
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+            "6k1/5p2/6p1/8/8/8/5PPP/1r4K1 w - - 0 1",
+        ] {
+            let board: Board = fen.parse().unwrap();
+            let mirrored: Board = color_flipped(fen).parse().unwrap();
+            assert_eq!(
+                evaluate(&board, &EvalWeights::HANDCRAFTED),
+                evaluate(&mirrored, &EvalWeights::HANDCRAFTED),
+                "{fen}"
+            );
+        }
+    }
+}
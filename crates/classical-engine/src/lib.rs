@@ -0,0 +1,2297 @@
+//! The classical alpha-beta engine behind `handcrafted-alpha-beta` and
+//! `auto-tuned-classical`: iterative deepening with aspiration windows, PVS
+//! with null-move pruning and late move reductions, quiescence, a
+//! transposition table and a tapered evaluation driven by [`EvalWeights`].
+//! With `Threads` above 1 it searches Lazy SMP style, and results then vary
+//! from run to run.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, anyhow};
+use arena_core::{Variant, gives_check};
+use cozy_chess::{Board, Color, GameStatus, Move, Piece, Square};
+use engine_sdk::{
+    PvLine, SearchContext, SearchInfo, SearchReport, SearchReporter, StopSignal, UciEngine,
+    UciOption, capture_moves, engine_score, is_mate_score, legal_moves, score_from_tt, score_to_tt,
+};
+
+mod endgame;
+mod eval;
+mod skill;
+mod tt;
+
+pub use eval::{EvalWeights, PieceSquareTables, evaluate};
+use skill::{CANDIDATE_LINES, Skill};
+use tt::{Bound, TranspositionEntry, TranspositionTable};
+
+const MAX_DEPTH: i32 = 32;
+const MAX_PLY: usize = 96;
+const MATE_SCORE: i32 = 30_000;
+const DRAW_SCORE: i32 = 0;
+/// Bound of the full search window, beyond any score.
+const INFINITY: i32 = i32::MAX / 4;
+const TIME_CHECK_INTERVAL: u64 = 64;
+/// Nodes between progress reports inside a long iteration.
+const PROGRESS_INTERVAL: u64 = 1 << 20;
+/// Null-move pruning needs this much depth left, and skips `R` plies of it.
+const NULL_MOVE_MIN_DEPTH: i32 = 3;
+const NULL_MOVE_REDUCTION: i32 = 2;
+/// Late move reductions apply from this depth to quiet moves ordered after
+/// the first few.
+const LMR_MIN_DEPTH: i32 = 3;
+const LMR_FULL_DEPTH_MOVES: usize = 3;
+/// Quiet checks are tried after the killers but before every other quiet
+/// move, whose history scores stay below this.
+const QUIET_CHECK_BONUS: i32 = 100_000;
+/// Cap on a history score; every new search halves the table, so moves that
+/// stopped cutting off fade out.
+const MAX_HISTORY: i32 = 50_000;
+/// Iterations from this depth search a window this wide on each side of the
+/// previous score. Each fail doubles it, and past the cap the re-search
+/// falls back to the full window.
+const ASPIRATION_MIN_DEPTH: i32 = 4;
+const ASPIRATION_WINDOW: i32 = 50;
+const ASPIRATION_MAX_WINDOW: i32 = 500;
+const MAX_THREADS: usize = 64;
+/// Bound of the `Contempt` option, well clear of mate scores.
+const MAX_CONTEMPT: i32 = 500;
+
+/// Crate version and the commit the engine was built from, such as
+/// `0.1.0-4f2a9c1`, appended to the engine's `id name`.
+pub const BUILD_VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    "-",
+    env!("CLASSICAL_ENGINE_GIT_HASH")
+);
+
+const OPTIONS: &[UciOption] = &[
+    UciOption::check("NullMove", true),
+    UciOption::check("LateMoveReductions", true),
+    UciOption::check("AspirationWindows", true),
+    UciOption::check("HistoryHeuristic", true),
+    UciOption::check("CounterMoves", true),
+    UciOption::spin(
+        "Skill Level",
+        skill::MAX_SKILL_LEVEL as i64,
+        0,
+        skill::MAX_SKILL_LEVEL as i64,
+    ),
+    UciOption::check("UCI_LimitStrength", false),
+    UciOption::spin(
+        "UCI_Elo",
+        skill::MAX_ELO as i64,
+        skill::MIN_ELO as i64,
+        skill::MAX_ELO as i64,
+    ),
+    UciOption::spin("Seed", 0, 0, i32::MAX as i64),
+    UciOption::spin("Threads", 1, 1, MAX_THREADS as i64),
+    UciOption::spin("Contempt", 0, -(MAX_CONTEMPT as i64), MAX_CONTEMPT as i64),
+    UciOption::string("EvalFile", ""),
+    UciOption::button("Clear Hash"),
+    UciOption::string("NameOverride", ""),
+];
+
+/// Iterative-deepening PVS engine shared by the classical arena engines,
+/// which differ only in their name and evaluation weights.
+#[derive(Clone)]
+pub struct ClassicalEngine {
+    name: &'static str,
+    /// The `id name`: `name` and [`BUILD_VERSION`], unless `NameOverride`
+    /// pins it to a name that stays the same across builds.
+    id_name: String,
+    weights: EvalWeights,
+    /// The weights the engine was built with, restored by an empty
+    /// `EvalFile`.
+    builtin_weights: EvalWeights,
+    /// Shared with the helper threads, which keep their own killers and
+    /// history.
+    tt: Arc<TranspositionTable>,
+    killer_moves: [[Option<Move>; 2]; MAX_PLY],
+    history: [[[i32; 64]; 64]; 2],
+    /// The quiet move that last refuted each previous move, by its squares.
+    counter_moves: [[Option<Move>; 64]; 64],
+    last_search: Option<SearchInfo>,
+    /// Aspiration re-searches the main thread needed on the last move.
+    last_researches: u64,
+    /// Search toggles, from the `NullMove`, `LateMoveReductions`,
+    /// `AspirationWindows`, `HistoryHeuristic` and `CounterMoves` options, so
+    /// the plain search stays available for comparisons.
+    null_move: bool,
+    late_move_reductions: bool,
+    aspiration_windows: bool,
+    history_heuristic: bool,
+    counter_move_heuristic: bool,
+    skill: Skill,
+    /// Search threads, from the `Threads` option; one keeps searches
+    /// reproducible.
+    threads: usize,
+    /// Centipawns a draw costs the side the engine is searching for, from
+    /// the `Contempt` option; positive values play on against weaker
+    /// opposition instead of repeating.
+    contempt: i32,
+}
+
+impl ClassicalEngine {
+    pub fn new(name: &'static str, weights: EvalWeights) -> Self {
+        Self {
+            name,
+            id_name: format!("{name} {BUILD_VERSION}"),
+            builtin_weights: weights.clone(),
+            weights,
+            tt: Arc::default(),
+            killer_moves: [[None; 2]; MAX_PLY],
+            history: [[[0; 64]; 64]; 2],
+            counter_moves: [[None; 64]; 64],
+            last_search: None,
+            last_researches: 0,
+            null_move: true,
+            late_move_reductions: true,
+            aspiration_windows: true,
+            history_heuristic: true,
+            counter_move_heuristic: true,
+            skill: Skill::default(),
+            threads: 1,
+            contempt: 0,
+        }
+    }
+
+    /// How many times the last move's iterations had to widen their
+    /// aspiration window and search again, for tuning the window size.
+    pub fn aspiration_researches(&self) -> u64 {
+        self.last_researches
+    }
+
+    /// Positions stored in the transposition table.
+    pub fn hash_entries(&self) -> usize {
+        self.tt.len()
+    }
+
+    /// Forgets everything earlier searches learned, as a fresh engine would
+    /// start, while keeping the options.
+    fn clear_search_state(&mut self) {
+        self.tt = Arc::default();
+        self.killer_moves = [[None; 2]; MAX_PLY];
+        self.history = [[[0; 64]; 64]; 2];
+        self.counter_moves = [[None; 64]; 64];
+    }
+
+    /// Halves every history score, so a new search still starts from the
+    /// last one's ordering but lets its own cutoffs take over.
+    fn age_history(&mut self) {
+        for entry in self.history.iter_mut().flatten().flatten() {
+            *entry /= 2;
+        }
+    }
+}
+
+impl UciEngine for ClassicalEngine {
+    fn name(&self) -> &str {
+        &self.id_name
+    }
+
+    /// Forgets everything learned in the last game, so a new game, or a
+    /// `bench` position, searches the same way every time.
+    fn new_game(&mut self, _variant: Variant) {
+        self.clear_search_state();
+    }
+
+    fn choose_move(&mut self, board: &Board, legal: &[Move], ctx: SearchContext) -> Result<Move> {
+        let limited = self.skill.is_limited();
+        let movetime_ms = if limited {
+            ctx.movetime_ms.min(self.skill.max_movetime_ms())
+        } else {
+            ctx.movetime_ms
+        };
+        let budget_ms = search_budget_ms(movetime_ms);
+        let started = Instant::now();
+        let deadline = started + Duration::from_millis(budget_ms);
+        // On a clock, an iteration started past half the budget rarely
+        // finishes, so the rest of the allocation is saved for later moves.
+        let soft_deadline = match ctx.clock {
+            Some(_) => started + Duration::from_millis(budget_ms / 2),
+            None => deadline,
+        };
+        self.age_history();
+        let mut repetition = HashMap::<u64, u8>::new();
+        for hash in ctx.position_history_hashes {
+            *repetition.entry(hash).or_insert(0) += 1;
+        }
+
+        let mut searcher = Searcher {
+            engine: self,
+            started,
+            deadline,
+            node_limit: ctx.max_nodes.unwrap_or(u64::MAX),
+            stop_signal: ctx.stop,
+            stopped: false,
+            node_count: 0,
+            depth: 0,
+            seldepth: 0,
+            reporter: ctx.reporter,
+            repetition,
+            in_null_move: false,
+            previous_moves: vec![None; MAX_PLY + 1],
+            pv: vec![Vec::new(); MAX_PLY + 1],
+            researches: 0,
+            root_side: board.side_to_move(),
+        };
+
+        let mut best_move = legal[0];
+        let mut best_line = vec![best_move];
+        let mut best_score = i32::MIN / 4;
+        let mut last_info = None;
+        // Below full strength the move is picked among the best few lines.
+        let shown_lines = ctx.multipv.clamp(1, legal.len());
+        let multipv = if limited {
+            shown_lines.max(CANDIDATE_LINES).min(legal.len())
+        } else {
+            shown_lines
+        };
+        let mut root_lines = Vec::new();
+
+        let mut max_depth = ctx
+            .max_depth
+            .map_or(MAX_DEPTH, |depth| (depth as i32).clamp(1, MAX_DEPTH));
+        if limited {
+            max_depth = max_depth.min(searcher.engine.skill.max_depth());
+        }
+        // Helpers only feed the shared table; the main thread's result is the
+        // one played. Strength-limited play stays on one thread, since the
+        // extra depth would undo the limit.
+        let helpers_done = StopSignal::default();
+        let helper_count = if limited {
+            0
+        } else {
+            searcher.engine.threads - 1
+        };
+        let helpers: Vec<_> = (0..helper_count)
+            .map(|index| {
+                let helper = Helper {
+                    engine: searcher.engine.clone(),
+                    board: board.clone(),
+                    legal: legal.to_vec(),
+                    first_depth: 1 + (index % 2) as i32,
+                    max_depth,
+                    deadline,
+                    repetition: searcher.repetition.clone(),
+                    stop: helpers_done.clone(),
+                };
+                thread::spawn(move || helper.run())
+            })
+            .collect();
+
+        let mut previous_score = None;
+        for depth in 1..=max_depth {
+            if Instant::now() >= soft_deadline
+                || searcher.node_count >= searcher.node_limit
+                || searcher.stop_signal.is_stopped()
+            {
+                break;
+            }
+
+            searcher.depth = depth as u32;
+            if let Some((line, score)) =
+                searcher.search_root_aspirated(board, legal, depth, previous_score)
+            {
+                previous_score = Some(score);
+                best_move = line[0];
+                best_score = score;
+
+                if multipv > 1 && !searcher.stopped {
+                    let lines =
+                        searcher.search_root_lines(board, legal, depth, &line, score, multipv);
+                    if !searcher.stopped {
+                        root_lines = lines;
+                    }
+                }
+                best_line = line;
+                let shown = &root_lines[..shown_lines.min(root_lines.len())];
+                let info = searcher.iteration_info(best_score, &best_line, shown);
+                searcher
+                    .reporter
+                    .report(SearchReport::Iteration(info.clone()));
+                last_info = Some(info);
+            }
+
+            if searcher.stopped || is_mate_score(best_score, MATE_SCORE) {
+                break;
+            }
+        }
+
+        helpers_done.stop();
+        let mut helper_nodes = 0;
+        for helper in helpers {
+            helper_nodes += helper
+                .join()
+                .map_err(|_| anyhow!("search helper thread panicked"))?;
+        }
+
+        if limited && root_lines.len() > 1 {
+            let scores: Vec<i32> = root_lines.iter().map(|(_, score)| *score).collect();
+            let picked = searcher.engine.skill.pick(&scores);
+            best_move = root_lines[picked].0[0];
+        }
+
+        // The final report repeats the last iteration with the totals.
+        let (seldepth, nodes, time_ms) = (
+            searcher.seldepth as u32,
+            searcher.node_count + helper_nodes,
+            searcher.elapsed_ms(),
+        );
+        self.last_researches = searcher.researches;
+        self.last_search = last_info.map(|info| SearchInfo {
+            seldepth,
+            nodes,
+            time_ms,
+            ..info
+        });
+        Ok(best_move)
+    }
+
+    fn search_info(&self) -> Option<SearchInfo> {
+        self.last_search.clone()
+    }
+
+    fn options(&self) -> &'static [UciOption] {
+        OPTIONS
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let enabled = value.eq_ignore_ascii_case("true");
+        if name.eq_ignore_ascii_case("NullMove") {
+            self.null_move = enabled;
+        } else if name.eq_ignore_ascii_case("LateMoveReductions") {
+            self.late_move_reductions = enabled;
+        } else if name.eq_ignore_ascii_case("AspirationWindows") {
+            self.aspiration_windows = enabled;
+        } else if name.eq_ignore_ascii_case("HistoryHeuristic") {
+            self.history_heuristic = enabled;
+        } else if name.eq_ignore_ascii_case("CounterMoves") {
+            self.counter_move_heuristic = enabled;
+        } else if name.eq_ignore_ascii_case("Skill Level") {
+            self.skill
+                .set_level(value.parse().unwrap_or(skill::MAX_SKILL_LEVEL));
+        } else if name.eq_ignore_ascii_case("UCI_LimitStrength") {
+            self.skill.set_limit_strength(enabled);
+        } else if name.eq_ignore_ascii_case("UCI_Elo") {
+            self.skill.set_elo(value.parse().unwrap_or(skill::MAX_ELO));
+        } else if name.eq_ignore_ascii_case("Seed") {
+            self.skill.set_seed(value.parse().unwrap_or_default());
+        } else if name.eq_ignore_ascii_case("Threads") {
+            self.threads = value.parse().unwrap_or(1).clamp(1, MAX_THREADS);
+        } else if name.eq_ignore_ascii_case("Contempt") {
+            self.contempt = value
+                .parse::<i32>()
+                .unwrap_or_default()
+                .clamp(-MAX_CONTEMPT, MAX_CONTEMPT);
+        } else if name.eq_ignore_ascii_case("EvalFile") {
+            // Table scores came from the old weights, so they go too.
+            self.weights = match value.trim() {
+                "" | "<empty>" => self.builtin_weights.clone(),
+                path => EvalWeights::load(path).map_err(|err| format!("{err:#}"))?,
+            };
+            self.tt = Arc::default();
+        } else if name.eq_ignore_ascii_case("Clear Hash") {
+            self.clear_search_state();
+        } else if name.eq_ignore_ascii_case("NameOverride") {
+            self.id_name = match value.trim() {
+                "" | "<empty>" => format!("{} {BUILD_VERSION}", self.name),
+                name => name.to_string(),
+            };
+        }
+        Ok(())
+    }
+}
+
+/// A Lazy SMP helper thread: it searches the same root as the main thread
+/// through the shared table, odd helpers a ply ahead, so the threads spread
+/// over different depths and orderings.
+struct Helper {
+    engine: ClassicalEngine,
+    board: Board,
+    legal: Vec<Move>,
+    first_depth: i32,
+    max_depth: i32,
+    deadline: Instant,
+    repetition: HashMap<u64, u8>,
+    stop: StopSignal,
+}
+
+impl Helper {
+    /// Search until the main thread finishes; returns the nodes searched.
+    fn run(mut self) -> u64 {
+        let mut searcher = Searcher {
+            engine: &mut self.engine,
+            started: Instant::now(),
+            deadline: self.deadline,
+            node_limit: u64::MAX,
+            stop_signal: self.stop,
+            stopped: false,
+            node_count: 0,
+            depth: 0,
+            seldepth: 0,
+            reporter: SearchReporter::default(),
+            repetition: self.repetition,
+            in_null_move: false,
+            previous_moves: vec![None; MAX_PLY + 1],
+            pv: vec![Vec::new(); MAX_PLY + 1],
+            researches: 0,
+            root_side: self.board.side_to_move(),
+        };
+        let mut previous_score = None;
+        for depth in self.first_depth..=self.max_depth {
+            searcher.depth = depth as u32;
+            let result =
+                searcher.search_root_aspirated(&self.board, &self.legal, depth, previous_score);
+            previous_score = result.map(|(_, score)| score);
+            if searcher.stopped {
+                break;
+            }
+        }
+        searcher.node_count
+    }
+}
+
+struct Searcher<'a> {
+    engine: &'a mut ClassicalEngine,
+    started: Instant,
+    deadline: Instant,
+    /// Stop once `node_count` reaches this, for `go nodes`.
+    node_limit: u64,
+    stop_signal: StopSignal,
+    stopped: bool,
+    node_count: u64,
+    /// Iteration in progress and the deepest ply reached so far, for reports.
+    depth: u32,
+    seldepth: usize,
+    reporter: SearchReporter,
+    repetition: HashMap<u64, u8>,
+    /// Set while searching below a null move, so null moves never nest.
+    in_null_move: bool,
+    /// `previous_moves[ply]` is the move that led to the node at `ply`, or
+    /// `None` at the root and after a null move.
+    previous_moves: Vec<Option<Move>>,
+    /// Triangular principal variation table: `pv[ply]` is the best line found
+    /// from the node at `ply`.
+    pv: Vec<Vec<Move>>,
+    /// Aspiration windows that failed and were searched again.
+    researches: u64,
+    /// The side to move at the root, which contempt is applied for.
+    root_side: Color,
+}
+
+impl Searcher<'_> {
+    /// Search `depth` in a narrow window around the previous iteration's
+    /// score, widening it until the score lands inside. Shallow iterations,
+    /// mate scores and a disabled `AspirationWindows` use the full window.
+    ///
+    /// A search stopped while failing low is dropped: its best move is only
+    /// known to be worse than the window, so the caller keeps the last
+    /// completed iteration's move instead.
+    fn search_root_aspirated(
+        &mut self,
+        board: &Board,
+        legal: &[Move],
+        depth: i32,
+        previous_score: Option<i32>,
+    ) -> Option<(Vec<Move>, i32)> {
+        let previous = previous_score.filter(|&score| {
+            self.engine.aspiration_windows
+                && depth >= ASPIRATION_MIN_DEPTH
+                && !is_mate_score(score, MATE_SCORE)
+        });
+        let Some(previous) = previous else {
+            return self.search_root(board, legal, depth, -INFINITY, INFINITY);
+        };
+
+        let mut delta = ASPIRATION_WINDOW;
+        let (mut alpha, mut beta) = (previous - delta, previous + delta);
+        loop {
+            let (line, score) = self.search_root(board, legal, depth, alpha, beta)?;
+            if self.stopped {
+                return (score > alpha).then_some((line, score));
+            }
+            if score <= alpha {
+                alpha = score - delta;
+            } else if score >= beta {
+                beta = score + delta;
+            } else {
+                return Some((line, score));
+            }
+            self.researches += 1;
+            delta *= 2;
+            if delta > ASPIRATION_MAX_WINDOW {
+                (alpha, beta) = (-INFINITY, INFINITY);
+            }
+        }
+    }
+
+    /// Search the root moves in `legal` within `alpha..beta` and return the
+    /// principal variation, starting with the best move, and its score. A
+    /// score outside the window is only a bound: the search stops at the
+    /// first move that fails high.
+    fn search_root(
+        &mut self,
+        board: &Board,
+        legal: &[Move],
+        depth: i32,
+        mut alpha: i32,
+        beta: i32,
+    ) -> Option<(Vec<Move>, i32)> {
+        let tt_move = self
+            .engine
+            .tt
+            .get(board.hash())
+            .and_then(|entry| entry.best_move);
+        let ordered = self.order_moves(board, legal.to_vec(), tt_move, 0);
+        let mut best_move = None;
+        let mut best_score = -INFINITY;
+        let window_alpha = alpha;
+
+        for (index, mv) in ordered.into_iter().enumerate() {
+            if self.should_stop() {
+                return best_move.map(|_| (self.pv[0].clone(), best_score));
+            }
+
+            let mut next = board.clone();
+            next.play(mv);
+            self.push_repetition(next.hash());
+            self.previous_moves[1] = Some(mv);
+
+            let mut score = if index == 0 {
+                -self.pvs(&next, depth - 1, 1, -beta, -alpha)
+            } else {
+                let scout = -self.pvs(&next, depth - 1, 1, -alpha - 1, -alpha);
+                if scout > alpha && scout < beta {
+                    -self.pvs(&next, depth - 1, 1, -beta, -alpha)
+                } else {
+                    scout
+                }
+            };
+
+            self.pop_repetition(next.hash());
+
+            if self.stopped {
+                return best_move.map(|_| (self.pv[0].clone(), best_score));
+            }
+
+            score = score.clamp(-MATE_SCORE, MATE_SCORE);
+            if score > best_score {
+                best_score = score;
+                best_move = Some(mv);
+                self.update_pv(0, mv);
+            }
+            alpha = alpha.max(score);
+            if score >= beta {
+                break;
+            }
+        }
+
+        let best_move = best_move?;
+        // A fail-low's best move is no better than the others, so the entry
+        // from the last completed iteration keeps ordering the re-search.
+        if best_score > window_alpha {
+            self.engine.tt.insert(
+                board.hash(),
+                TranspositionEntry {
+                    depth,
+                    score: best_score,
+                    bound: if best_score >= beta {
+                        Bound::Lower
+                    } else {
+                        Bound::Exact
+                    },
+                    best_move: Some(best_move),
+                },
+            );
+        }
+        Some((self.pv[0].clone(), best_score))
+    }
+
+    /// The `count` best root lines at `depth`, best first: `best` followed by
+    /// the winners of re-searches that exclude every root move already listed.
+    /// The root table entry is restored afterwards so move ordering still
+    /// starts from `best` at the next depth.
+    fn search_root_lines(
+        &mut self,
+        board: &Board,
+        legal: &[Move],
+        depth: i32,
+        best: &[Move],
+        best_score: i32,
+        count: usize,
+    ) -> Vec<(Vec<Move>, i32)> {
+        let root_entry = self.engine.tt.get(board.hash());
+        let mut lines = vec![(best.to_vec(), best_score)];
+        let mut remaining: Vec<Move> = legal.iter().copied().filter(|mv| *mv != best[0]).collect();
+
+        while lines.len() < count && !remaining.is_empty() {
+            let Some((line, score)) =
+                self.search_root(board, &remaining, depth, -INFINITY, INFINITY)
+            else {
+                break;
+            };
+            if self.stopped {
+                break;
+            }
+            remaining.retain(|candidate| *candidate != line[0]);
+            lines.push((line, score));
+        }
+
+        if let Some(entry) = root_entry {
+            self.engine.tt.insert(board.hash(), entry);
+        }
+        lines
+    }
+
+    fn pvs(&mut self, board: &Board, mut depth: i32, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        self.pv[ply].clear();
+        self.seldepth = self.seldepth.max(ply);
+        if self.should_stop() {
+            return DRAW_SCORE;
+        }
+
+        let hash = board.hash();
+        if self.is_repetition(hash) {
+            return self.draw_score(board);
+        }
+
+        match board.status() {
+            GameStatus::Won => return -MATE_SCORE + ply as i32,
+            GameStatus::Drawn => return self.draw_score(board),
+            GameStatus::Ongoing => {}
+        }
+
+        // Check extension: a side in check gets one more ply to find its
+        // evasions, unless the line already runs past twice the iteration depth.
+        let in_check = !board.checkers().is_empty();
+        if in_check && ply as i32 + depth < 2 * self.depth as i32 {
+            depth += 1;
+        }
+
+        if depth <= 0 {
+            return self.quiescence(board, ply, alpha, beta);
+        }
+
+        let original_alpha = alpha;
+        let tt_entry = self.engine.tt.get(hash);
+        if let Some(entry) = tt_entry
+            && entry.depth >= depth
+        {
+            let score = score_from_tt(entry.score, ply, MATE_SCORE);
+            match entry.bound {
+                Bound::Exact => return score,
+                Bound::Lower => alpha = alpha.max(score),
+                Bound::Upper => {}
+            }
+            if matches!(entry.bound, Bound::Upper) && score <= alpha {
+                return score;
+            }
+            if alpha >= beta {
+                return score;
+            }
+        }
+
+        if let Some(score) = self.null_move_cutoff(board, depth, ply, alpha, beta, in_check) {
+            return score;
+        }
+
+        let moves = legal_moves(board);
+        if moves.is_empty() {
+            return if !in_check {
+                self.draw_score(board)
+            } else {
+                -MATE_SCORE + ply as i32
+            };
+        }
+
+        let tt_move = tt_entry.and_then(|entry| entry.best_move);
+        let ordered = self.order_moves(board, moves, tt_move, ply);
+        let mut best_move = None;
+        let mut best_score = i32::MIN / 4;
+
+        for (index, mv) in ordered.into_iter().enumerate() {
+            if self.should_stop() {
+                return DRAW_SCORE;
+            }
+
+            let mut next = board.clone();
+            next.play(mv);
+            self.push_repetition(next.hash());
+            self.previous_moves[ply + 1] = Some(mv);
+
+            let score = if index == 0 {
+                -self.pvs(&next, depth - 1, ply + 1, -beta, -alpha)
+            } else {
+                let reduction = self.late_move_reduction(board, &next, mv, depth, index, ply);
+                let mut scout =
+                    -self.pvs(&next, depth - 1 - reduction, ply + 1, -alpha - 1, -alpha);
+                if reduction > 0 && scout > alpha {
+                    scout = -self.pvs(&next, depth - 1, ply + 1, -alpha - 1, -alpha);
+                }
+                if scout > alpha && scout < beta {
+                    -self.pvs(&next, depth - 1, ply + 1, -beta, -alpha)
+                } else {
+                    scout
+                }
+            };
+
+            self.pop_repetition(next.hash());
+
+            if self.stopped {
+                return DRAW_SCORE;
+            }
+
+            if score > best_score {
+                best_score = score;
+                best_move = Some(mv);
+            }
+            if score > alpha {
+                alpha = score;
+                self.update_pv(ply, mv);
+            }
+            if alpha >= beta {
+                if is_quiet(board, mv) {
+                    self.store_killer(ply, mv);
+                    self.bump_history(board.side_to_move(), mv, depth);
+                    self.store_counter_move(ply, mv);
+                }
+                break;
+            }
+        }
+
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.engine.tt.insert(
+            hash,
+            TranspositionEntry {
+                depth,
+                score: score_to_tt(best_score, ply, MATE_SCORE),
+                bound,
+                best_move,
+            },
+        );
+        best_score
+    }
+
+    /// Null-move pruning: if passing still fails high on a reduced search,
+    /// a real move would too. Skipped in check, in PV nodes, and with only
+    /// pawns left for the mover, where passing may be the best move there is.
+    fn null_move_cutoff(
+        &mut self,
+        board: &Board,
+        depth: i32,
+        ply: usize,
+        alpha: i32,
+        beta: i32,
+        in_check: bool,
+    ) -> Option<i32> {
+        if !self.engine.null_move
+            || self.in_null_move
+            || in_check
+            || depth < NULL_MOVE_MIN_DEPTH
+            || beta - alpha > 1
+            || is_mate_score(beta, MATE_SCORE)
+            || !has_non_pawn_material(board, board.side_to_move())
+            || self.evaluate(board) < beta
+        {
+            return None;
+        }
+        let passed = board.null_move()?;
+
+        let reduction = NULL_MOVE_REDUCTION + i32::from(depth >= 6);
+        self.in_null_move = true;
+        self.previous_moves[ply + 1] = None;
+        let score = -self.pvs(&passed, depth - 1 - reduction, ply + 1, -beta, -beta + 1);
+        self.in_null_move = false;
+
+        // A mate found after passing is not proven for the real moves.
+        (!self.stopped && score >= beta).then_some(beta)
+    }
+
+    /// Plies to cut from the scout search of the `index`th ordered move:
+    /// late quiet moves rarely matter, and one that beats alpha anyway is
+    /// searched again at full depth.
+    fn late_move_reduction(
+        &self,
+        board: &Board,
+        next: &Board,
+        mv: Move,
+        depth: i32,
+        index: usize,
+        ply: usize,
+    ) -> i32 {
+        let is_killer = self.engine.killer_moves[ply.min(MAX_PLY - 1)].contains(&Some(mv));
+        if !self.engine.late_move_reductions
+            || depth < LMR_MIN_DEPTH
+            || index < LMR_FULL_DEPTH_MOVES
+            || is_killer
+            || !is_quiet(board, mv)
+            || !board.checkers().is_empty()
+            || !next.checkers().is_empty()
+        {
+            return 0;
+        }
+        if depth >= 6 && index >= 2 * LMR_FULL_DEPTH_MOVES {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn quiescence(&mut self, board: &Board, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        self.seldepth = self.seldepth.max(ply);
+        if self.should_stop() {
+            return DRAW_SCORE;
+        }
+
+        let hash = board.hash();
+        if self.is_repetition(hash) {
+            return self.draw_score(board);
+        }
+
+        // In check there is no safe stand-pat: every evasion has to be searched.
+        let in_check = !board.checkers().is_empty();
+        if !in_check {
+            let stand_pat = self.evaluate(board);
+            if stand_pat >= beta {
+                return beta;
+            }
+            alpha = alpha.max(stand_pat);
+        }
+
+        let moves = if in_check {
+            legal_moves(board)
+        } else {
+            capture_moves(board)
+        };
+        if in_check && moves.is_empty() {
+            return -MATE_SCORE + ply as i32;
+        }
+        let ordered = self.order_moves(board, moves, None, ply);
+
+        for mv in ordered {
+            if self.should_stop() {
+                return alpha;
+            }
+
+            let mut next = board.clone();
+            next.play(mv);
+            self.push_repetition(next.hash());
+            let score = -self.quiescence(&next, ply + 1, -beta, -alpha);
+            self.pop_repetition(next.hash());
+
+            if self.stopped {
+                return DRAW_SCORE;
+            }
+
+            if score >= beta {
+                return beta;
+            }
+            alpha = alpha.max(score);
+        }
+
+        alpha
+    }
+
+    fn order_moves(
+        &self,
+        board: &Board,
+        mut moves: Vec<Move>,
+        tt_move: Option<Move>,
+        ply: usize,
+    ) -> Vec<Move> {
+        moves.sort_by_cached_key(|mv| -self.move_score(board, *mv, tt_move, ply));
+        moves
+    }
+
+    fn move_score(&self, board: &Board, mv: Move, tt_move: Option<Move>, ply: usize) -> i32 {
+        if tt_move == Some(mv) {
+            return 2_000_000;
+        }
+
+        let attacker = board.piece_on(mv.from).unwrap_or(Piece::Pawn);
+        let victim = captured_piece(board, mv);
+        let promotion_bonus = mv.promotion.map(piece_value).unwrap_or(0);
+
+        if let Some(victim) = victim {
+            return 1_000_000 + piece_value(victim) * 16 - piece_value(attacker) + promotion_bonus;
+        }
+
+        if self.engine.killer_moves[ply.min(MAX_PLY - 1)][0] == Some(mv) {
+            return 900_000;
+        }
+        if self.engine.killer_moves[ply.min(MAX_PLY - 1)][1] == Some(mv) {
+            return 850_000;
+        }
+        if self.counter_move(ply) == Some(mv) {
+            return 800_000;
+        }
+
+        let history = if self.engine.history_heuristic {
+            self.engine.history[color_index(board.side_to_move())][square_index(mv.from)]
+                [square_index(mv.to)]
+        } else {
+            0
+        };
+        let check_bonus = if gives_check(board, mv) {
+            QUIET_CHECK_BONUS
+        } else {
+            0
+        };
+        history + promotion_bonus + check_bonus
+    }
+
+    fn store_killer(&mut self, ply: usize, mv: Move) {
+        let killers = &mut self.engine.killer_moves[ply.min(MAX_PLY - 1)];
+        if killers[0] != Some(mv) {
+            killers[1] = killers[0];
+            killers[0] = Some(mv);
+        }
+    }
+
+    fn bump_history(&mut self, color: Color, mv: Move, depth: i32) {
+        if !self.engine.history_heuristic {
+            return;
+        }
+        let entry = &mut self.engine.history[color_index(color)][square_index(mv.from)]
+            [square_index(mv.to)];
+        *entry = (*entry + depth * depth).min(MAX_HISTORY);
+    }
+
+    /// The stored reply to the move that led to the node at `ply`.
+    fn counter_move(&self, ply: usize) -> Option<Move> {
+        if !self.engine.counter_move_heuristic {
+            return None;
+        }
+        let previous = self.previous_moves.get(ply).copied().flatten()?;
+        self.engine.counter_moves[square_index(previous.from)][square_index(previous.to)]
+    }
+
+    fn store_counter_move(&mut self, ply: usize, mv: Move) {
+        if !self.engine.counter_move_heuristic {
+            return;
+        }
+        if let Some(previous) = self.previous_moves.get(ply).copied().flatten() {
+            self.engine.counter_moves[square_index(previous.from)][square_index(previous.to)] =
+                Some(mv);
+        }
+    }
+
+    /// Make `mv` followed by the child's line the principal variation at `ply`.
+    fn update_pv(&mut self, ply: usize, mv: Move) {
+        let (head, tail) = self.pv.split_at_mut(ply + 1);
+        let line = &mut head[ply];
+        line.clear();
+        line.push(mv);
+        line.extend_from_slice(&tail[0]);
+    }
+
+    /// Static evaluation, with the skill level's noise below full strength.
+    fn evaluate(&self, board: &Board) -> i32 {
+        let score = evaluate(board, &self.engine.weights);
+        if self.engine.skill.is_limited() {
+            score + self.engine.skill.eval_noise(board.hash())
+        } else {
+            score
+        }
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+
+    /// Report for the iteration that just settled on `best_line`, with the
+    /// MultiPV `root_lines` while they still agree with it.
+    fn iteration_info(
+        &self,
+        score: i32,
+        best_line: &[Move],
+        root_lines: &[(Vec<Move>, i32)],
+    ) -> SearchInfo {
+        let lines = if root_lines.first().map(|(line, _)| line[0]) == best_line.first().copied() {
+            root_lines.to_vec()
+        } else {
+            vec![(best_line.to_vec(), score)]
+        };
+        SearchInfo {
+            depth: self.depth,
+            seldepth: self.seldepth as u32,
+            score: engine_score(score, MATE_SCORE),
+            nodes: self.node_count,
+            time_ms: self.elapsed_ms(),
+            lines: lines
+                .into_iter()
+                .map(|(moves, score)| PvLine {
+                    score: engine_score(score, MATE_SCORE),
+                    moves,
+                })
+                .collect(),
+        }
+    }
+
+    fn should_stop(&mut self) -> bool {
+        self.node_count += 1;
+        if self.node_count.is_multiple_of(PROGRESS_INTERVAL) {
+            self.reporter.report(SearchReport::Progress {
+                depth: self.depth,
+                nodes: self.node_count,
+                time_ms: self.elapsed_ms(),
+            });
+        }
+        if self.node_count >= self.node_limit
+            || (self.node_count.is_multiple_of(TIME_CHECK_INTERVAL)
+                && (Instant::now() >= self.deadline || self.stop_signal.is_stopped()))
+        {
+            self.stopped = true;
+        }
+        self.stopped
+    }
+
+    /// The score of a drawn position for its side to move: minus the
+    /// contempt for the root side and plus it for the opponent, so every
+    /// ply of the negamax sees the draw the same way the root does.
+    fn draw_score(&self, board: &Board) -> i32 {
+        if board.side_to_move() == self.root_side {
+            DRAW_SCORE - self.engine.contempt
+        } else {
+            DRAW_SCORE + self.engine.contempt
+        }
+    }
+
+    /// A position seen before, in the game or earlier on this search path,
+    /// scores as a draw: if repeating is good for one side it can repeat
+    /// again, so there is no need to wait for the third occurrence.
+    fn is_repetition(&self, hash: u64) -> bool {
+        self.repetition.get(&hash).copied().unwrap_or_default() >= 2
+    }
+
+    fn push_repetition(&mut self, hash: u64) {
+        *self.repetition.entry(hash).or_insert(0) += 1;
+    }
+
+    fn pop_repetition(&mut self, hash: u64) {
+        if let Some(count) = self.repetition.get_mut(&hash) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                self.repetition.remove(&hash);
+            }
+        }
+    }
+}
+
+/// How long to search when given `movetime_ms`. Short allocations still get
+/// a 20 ms floor, but never more time than was given, so a nearly empty clock
+/// is not overrun.
+fn search_budget_ms(movetime_ms: u64) -> u64 {
+    let safety_margin = movetime_ms.min(30);
+    movetime_ms
+        .saturating_sub(safety_margin)
+        .max(20)
+        .min(movetime_ms)
+}
+
+fn piece_value(piece: Piece) -> i32 {
+    match piece {
+        Piece::Pawn => 100,
+        Piece::Knight => 320,
+        Piece::Bishop => 330,
+        Piece::Rook => 500,
+        Piece::Queen => 900,
+        Piece::King => 0,
+    }
+}
+
+fn square_index(square: Square) -> usize {
+    square as usize
+}
+
+fn color_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+fn has_non_pawn_material(board: &Board, color: Color) -> bool {
+    let pawns_and_king = board.pieces(Piece::Pawn) | board.pieces(Piece::King);
+    !(board.colors(color) - pawns_and_king).is_empty()
+}
+
+fn is_quiet(board: &Board, mv: Move) -> bool {
+    captured_piece(board, mv).is_none() && mv.promotion.is_none()
+}
+
+/// The piece removed by `mv`, counting en passant and ignoring Chess960
+/// castling, which is encoded as the king capturing its own rook.
+fn captured_piece(board: &Board, mv: Move) -> Option<Piece> {
+    if board.color_on(mv.to) == Some(!board.side_to_move()) {
+        return board.piece_on(mv.to);
+    }
+    let is_en_passant =
+        board.piece_on(mv.from) == Some(Piece::Pawn) && mv.from.file() != mv.to.file();
+    is_en_passant.then_some(Piece::Pawn)
+}
+
+#[cfg(test)]
+mod tests {
+    use arena_core::EngineScore;
+    use engine_sdk::SearchClock;
+
+    use super::*;
+
+    fn engine() -> ClassicalEngine {
+        ClassicalEngine::new("test", EvalWeights::HANDCRAFTED)
+    }
+
+    fn searcher(engine: &mut ClassicalEngine) -> Searcher<'_> {
+        Searcher {
+            engine,
+            started: Instant::now(),
+            deadline: Instant::now() + Duration::from_secs(60),
+            node_limit: u64::MAX,
+            stop_signal: StopSignal::default(),
+            stopped: false,
+            node_count: 0,
+            depth: 0,
+            seldepth: 0,
+            reporter: SearchReporter::default(),
+            repetition: HashMap::new(),
+            in_null_move: false,
+            previous_moves: vec![None; MAX_PLY + 1],
+            pv: vec![Vec::new(); MAX_PLY + 1],
+            researches: 0,
+            root_side: Color::White,
+        }
+    }
+
+    fn fixed_depth_context(board: &Board, depth: u32) -> SearchContext {
+        SearchContext {
+            movetime_ms: 60_000,
+            max_depth: Some(depth),
+            max_nodes: None,
+            multipv: 1,
+            clock: None,
+            variant: Variant::Standard,
+            position_history_hashes: vec![board.hash()],
+            stop: StopSignal::default(),
+            reporter: SearchReporter::default(),
+        }
+    }
+
+    /// Fields of an `option name <name> type <kind> [default ..] [min ..]
+    /// [max ..] [var ..]*` line, with the `var`s joined by `|`.
+    fn parse_option_line(line: &str) -> Option<HashMap<&'static str, String>> {
+        let rest = line.strip_prefix("option name ")?;
+        let (name, rest) = rest.split_once(" type ")?;
+        let mut tokens = rest.split(' ');
+        let mut fields = HashMap::from([("name", name.to_string())]);
+        fields.insert("type", tokens.next()?.to_string());
+        while let Some(key) = tokens.next() {
+            let key = ["default", "min", "max", "var"]
+                .into_iter()
+                .find(|known| *known == key)?;
+            let value = tokens.next()?.to_string();
+            fields
+                .entry(key)
+                .and_modify(|vars| *vars = format!("{vars}|{value}"))
+                .or_insert(value);
+        }
+        Some(fields)
+    }
+
+    #[test]
+    fn bench_signature_is_deterministic() {
+        let signature = |engine: &mut ClassicalEngine| -> u64 {
+            engine_sdk::bench(engine, 4)
+                .unwrap()
+                .iter()
+                .map(|position| position.nodes)
+                .sum()
+        };
+
+        // Update this when a change is meant to alter the search.
+        let mut engine = engine();
+        assert_eq!(signature(&mut engine), 359_422);
+        // Earlier searches leave nothing behind that changes the next run.
+        assert_eq!(signature(&mut engine), 359_422);
+    }
+
+    #[test]
+    fn uci_advertises_every_declared_option_in_valid_syntax() {
+        let lines = engine_sdk::option_lines(&engine());
+        let options: Vec<_> = lines
+            .iter()
+            .map(|line| parse_option_line(line).unwrap_or_else(|| panic!("{line}")))
+            .collect();
+
+        let names: Vec<&str> = options
+            .iter()
+            .map(|option| option["name"].as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "UCI_Chess960",
+                "MultiPV",
+                "Ponder",
+                "NullMove",
+                "LateMoveReductions",
+                "AspirationWindows",
+                "HistoryHeuristic",
+                "CounterMoves",
+                "Skill Level",
+                "UCI_LimitStrength",
+                "UCI_Elo",
+                "Seed",
+                "Threads",
+                "Contempt",
+                "EvalFile",
+                "Clear Hash",
+                "NameOverride",
+            ]
+        );
+        for option in &options {
+            match option["type"].as_str() {
+                "check" => assert!(["true", "false"].contains(&option["default"].as_str())),
+                "spin" => {
+                    let [default, min, max] =
+                        ["default", "min", "max"].map(|key| option[key].parse::<i64>().unwrap());
+                    assert!(min <= default && default <= max, "{option:?}");
+                }
+                "string" => assert_eq!(option["default"], "<empty>"),
+                "button" => assert!(!option.contains_key("default"), "{option:?}"),
+                other => panic!("unexpected option type {other}"),
+            }
+        }
+        let threads = &options[names.iter().position(|name| *name == "Threads").unwrap()];
+        assert_eq!(
+            (threads["min"].as_str(), threads["max"].as_str()),
+            ("1", "64")
+        );
+    }
+
+    #[test]
+    fn quiescence_does_not_count_defended_piece_as_free_capture() {
+        let board: Board = "4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1".parse().unwrap();
+        let mut engine = engine();
+        let score = searcher(&mut engine).quiescence(&board, 0, -MATE_SCORE, MATE_SCORE);
+
+        assert_eq!(score, evaluate(&board, &EvalWeights::HANDCRAFTED));
+    }
+
+    #[test]
+    fn quiescence_sees_undefended_capture() {
+        let board: Board = "4k3/8/8/3p4/8/8/8/3QK3 w - - 0 1".parse().unwrap();
+        let mut engine = engine();
+        let score = searcher(&mut engine).quiescence(&board, 0, -MATE_SCORE, MATE_SCORE);
+
+        assert!(score > evaluate(&board, &EvalWeights::HANDCRAFTED) + 50);
+    }
+
+    #[test]
+    fn eval_file_swaps_the_weights_and_an_empty_path_restores_them() {
+        let mut tuned = EvalWeights::HANDCRAFTED;
+        tuned.eg_value[0] = 140;
+        let path = std::env::temp_dir().join(format!("eval-file-{}.json", std::process::id()));
+        std::fs::write(&path, serde_json::to_string(&tuned).unwrap()).unwrap();
+        let mut engine = engine();
+
+        engine
+            .set_option("EvalFile", path.to_str().unwrap())
+            .unwrap();
+        assert_eq!(engine.weights, tuned);
+        let err = engine.set_option("EvalFile", "/no/such/weights.json");
+        assert!(err.unwrap_err().contains("failed to read"));
+        assert_eq!(engine.weights, tuned);
+        engine.set_option("EvalFile", "").unwrap();
+        assert_eq!(engine.weights, EvalWeights::HANDCRAFTED);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn id_name_carries_the_build_version_unless_overridden() {
+        let mut engine = engine();
+        let (name, build) = engine.name().split_once(' ').unwrap();
+        assert_eq!(name, "test");
+        let (version, hash) = build.split_once('-').unwrap();
+        assert_eq!(version, env!("CARGO_PKG_VERSION"));
+        assert!(
+            hash == "unknown" || hash.chars().all(|c| c.is_ascii_hexdigit()),
+            "{hash}"
+        );
+
+        let mut other = ClassicalEngine::new("test", EvalWeights::HANDCRAFTED);
+        engine
+            .set_option("NameOverride", "Classical stable")
+            .unwrap();
+        other
+            .set_option("NameOverride", "Classical nightly")
+            .unwrap();
+        assert_eq!(engine.name(), "Classical stable");
+        assert_ne!(engine.name(), other.name());
+        engine.set_option("NameOverride", "").unwrap();
+        assert_eq!(engine.name(), format!("test {BUILD_VERSION}"));
+    }
+
+    #[test]
+    fn move_ordering_puts_tt_move_then_mvv_lva_captures_then_killers() {
+        let board: Board = "4k3/8/8/2q1r3/3P4/8/8/R6K w - - 0 1".parse().unwrap();
+        let tt_move: Move = "a1a2".parse().unwrap();
+        let killer: Move = "a1a7".parse().unwrap();
+        let mut engine = engine();
+        engine.killer_moves[3][0] = Some(killer);
+        let ordered =
+            searcher(&mut engine).order_moves(&board, legal_moves(&board), Some(tt_move), 3);
+        let ordered: Vec<_> = ordered.iter().take(4).map(ToString::to_string).collect();
+
+        assert_eq!(ordered, ["a1a2", "d4c5", "d4e5", "a1a7"]);
+    }
+
+    #[test]
+    fn move_ordering_puts_the_counter_move_then_history_ahead_of_other_quiets() {
+        let board: Board = "7k/8/8/8/8/8/8/1N2K3 w - - 0 1".parse().unwrap();
+        let previous: Move = "g8h8".parse().unwrap();
+        let counter: Move = "e1f1".parse().unwrap();
+        let mut engine = engine();
+        engine.counter_moves[square_index(previous.from)][square_index(previous.to)] =
+            Some(counter);
+        let mut searcher = searcher(&mut engine);
+        searcher.bump_history(Color::White, "b1c3".parse().unwrap(), 4);
+        searcher.previous_moves[1] = Some(previous);
+
+        let ordered = searcher.order_moves(&board, legal_moves(&board), None, 1);
+        let ordered: Vec<_> = ordered.iter().take(2).map(ToString::to_string).collect();
+        assert_eq!(ordered, ["e1f1", "b1c3"]);
+
+        // Without the toggles both fall back among the unscored quiet moves.
+        searcher
+            .engine
+            .set_option("HistoryHeuristic", "false")
+            .unwrap();
+        searcher.engine.set_option("CounterMoves", "false").unwrap();
+        let scores: Vec<_> = legal_moves(&board)
+            .into_iter()
+            .map(|mv| searcher.move_score(&board, mv, None, 1))
+            .collect();
+        assert!(scores.iter().all(|score| *score == 0), "{scores:?}");
+    }
+
+    #[test]
+    fn history_grows_by_depth_squared_up_to_the_cap_and_halves_each_search() {
+        let mv: Move = "b1c3".parse().unwrap();
+        let (from, to) = (square_index(mv.from), square_index(mv.to));
+        let mut engine = engine();
+        let mut searcher = searcher(&mut engine);
+        searcher.bump_history(Color::White, mv, 3);
+        searcher.bump_history(Color::White, mv, 5);
+        assert_eq!(searcher.engine.history[0][from][to], 34);
+        assert_eq!(searcher.engine.history[1][from][to], 0);
+        for _ in 0..100 {
+            searcher.bump_history(Color::White, mv, 30);
+        }
+        assert_eq!(searcher.engine.history[0][from][to], MAX_HISTORY);
+
+        let board = Board::startpos();
+        engine
+            .choose_move(&board, &legal_moves(&board), fixed_depth_context(&board, 1))
+            .unwrap();
+        // A depth-one search has no quiet cutoffs to add back.
+        assert_eq!(engine.history[0][from][to], MAX_HISTORY / 2);
+
+        engine.new_game(Variant::Standard);
+        assert_eq!(engine.history[0][from][to], 0);
+    }
+
+    #[test]
+    fn en_passant_is_a_capture_and_chess960_castling_is_not() {
+        let board: Board = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2".parse().unwrap();
+        assert_eq!(
+            captured_piece(&board, "e5d6".parse().unwrap()),
+            Some(Piece::Pawn)
+        );
+        assert!(!is_quiet(&board, "e5d6".parse().unwrap()));
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K2R w H - 0 1", true).unwrap();
+        assert_eq!(captured_piece(&board, "e1h1".parse().unwrap()), None);
+        assert!(is_quiet(&board, "e1h1".parse().unwrap()));
+    }
+
+    #[test]
+    fn a_tiny_movetime_still_returns_a_legal_move_on_time() {
+        let board: Board = "r1b2rk1/2q1bppp/p2ppn2/1p6/3BPP2/2N2B2/PPPQ2PP/2KR3R w - - 0 14"
+            .parse()
+            .unwrap();
+        let legal = legal_moves(&board);
+        let mut engine = engine();
+        let started = Instant::now();
+        let mv = engine
+            .choose_move(
+                &board,
+                &legal,
+                SearchContext {
+                    movetime_ms: 5,
+                    max_depth: None,
+                    max_nodes: None,
+                    multipv: 1,
+                    clock: None,
+                    variant: Variant::Standard,
+                    position_history_hashes: vec![board.hash()],
+                    stop: StopSignal::default(),
+                    reporter: SearchReporter::default(),
+                },
+            )
+            .unwrap();
+
+        // Loose enough for a loaded test machine; a search that ignored the
+        // movetime would keep deepening this middlegame far longer.
+        assert!(
+            started.elapsed() < Duration::from_millis(250),
+            "{:?}",
+            started.elapsed()
+        );
+        assert!(legal.contains(&mv));
+        // Only a finished iteration is ever reported.
+        if let Some(info) = engine.search_info() {
+            assert!(info.depth >= 1);
+        }
+    }
+
+    #[test]
+    fn stop_signal_interrupts_a_long_search() {
+        let board: Board = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+            .parse()
+            .unwrap();
+        let legal = legal_moves(&board);
+        let stop = StopSignal::default();
+        let ctx = SearchContext {
+            movetime_ms: 60_000,
+            max_depth: None,
+            max_nodes: None,
+            multipv: 1,
+            clock: None,
+            variant: Variant::Standard,
+            position_history_hashes: vec![board.hash()],
+            stop: stop.clone(),
+            reporter: SearchReporter::default(),
+        };
+        let search_legal = legal.clone();
+        let search = std::thread::spawn(move || engine().choose_move(&board, &search_legal, ctx));
+
+        std::thread::sleep(Duration::from_millis(50));
+        let stopped_at = Instant::now();
+        stop.stop();
+        let mv = search.join().unwrap().unwrap();
+
+        assert!(stopped_at.elapsed() < Duration::from_millis(500));
+        assert!(legal.contains(&mv));
+    }
+
+    #[test]
+    fn node_limit_stops_the_search_near_the_budget() {
+        let board: Board = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+            .parse()
+            .unwrap();
+        let legal = legal_moves(&board);
+        for max_nodes in [0, 1, 10_000] {
+            let mut engine = engine();
+            let mv = engine
+                .choose_move(
+                    &board,
+                    &legal,
+                    SearchContext {
+                        movetime_ms: 60_000,
+                        max_depth: None,
+                        max_nodes: Some(max_nodes),
+                        multipv: 1,
+                        clock: None,
+                        variant: Variant::Standard,
+                        position_history_hashes: vec![board.hash()],
+                        stop: StopSignal::default(),
+                        reporter: SearchReporter::default(),
+                    },
+                )
+                .unwrap();
+
+            assert!(legal.contains(&mv));
+            if max_nodes == 10_000 {
+                let info = engine.search_info().unwrap();
+                assert!(
+                    (max_nodes..max_nodes + 64).contains(&info.nodes),
+                    "{}",
+                    info.nodes
+                );
+                assert_eq!(info.lines[0].moves[0], mv);
+            }
+        }
+    }
+
+    #[test]
+    fn a_starved_search_reports_the_depth_it_completed() {
+        let board = Board::default();
+        let legal = legal_moves(&board);
+        let mut engine = engine();
+        engine
+            .choose_move(
+                &board,
+                &legal,
+                SearchContext {
+                    movetime_ms: 60_000,
+                    max_depth: Some(8),
+                    max_nodes: Some(100),
+                    multipv: 1,
+                    clock: None,
+                    variant: Variant::Standard,
+                    position_history_hashes: vec![board.hash()],
+                    stop: StopSignal::default(),
+                    reporter: SearchReporter::default(),
+                },
+            )
+            .unwrap();
+
+        let info = engine.search_info().unwrap();
+        assert_eq!(info.depth, 1);
+        assert!(info.seldepth >= 1);
+    }
+
+    #[test]
+    fn reports_every_iteration_in_order_while_searching() {
+        let board = Board::default();
+        let legal = legal_moves(&board);
+        let (reporter, reports) = SearchReporter::channel();
+        let mut engine = engine();
+        engine
+            .choose_move(
+                &board,
+                &legal,
+                SearchContext {
+                    movetime_ms: 60_000,
+                    max_depth: Some(6),
+                    max_nodes: None,
+                    multipv: 1,
+                    clock: None,
+                    variant: Variant::Standard,
+                    position_history_hashes: vec![board.hash()],
+                    stop: StopSignal::default(),
+                    reporter,
+                },
+            )
+            .unwrap();
+
+        let iterations: Vec<SearchInfo> = reports
+            .try_iter()
+            .filter_map(|report| match report {
+                SearchReport::Iteration(info) => Some(info),
+                SearchReport::Progress { .. } => None,
+            })
+            .collect();
+        let depths: Vec<u32> = iterations.iter().map(|info| info.depth).collect();
+        assert_eq!(depths, [1, 2, 3, 4, 5, 6]);
+        assert!(
+            iterations.windows(2).all(|pair| {
+                pair[0].nodes < pair[1].nodes && pair[0].time_ms <= pair[1].time_ms
+            })
+        );
+        assert!(iterations.iter().all(|info| info.seldepth >= info.depth));
+        let last = engine.search_info().unwrap();
+        assert_eq!(last.depth, 6);
+        assert_eq!(last.lines, iterations[5].lines);
+        assert!(last.nodes >= iterations[5].nodes);
+    }
+
+    #[test]
+    fn avoids_repeating_a_position_from_earlier_in_the_game() {
+        let board: Board = "4k3/8/8/8/8/8/8/Q3K3 w - - 0 1".parse().unwrap();
+        let legal = legal_moves(&board);
+        let search = |history: Vec<u64>| {
+            let mut engine = engine();
+            let mv = engine
+                .choose_move(
+                    &board,
+                    &legal,
+                    SearchContext {
+                        movetime_ms: 60_000,
+                        max_depth: Some(4),
+                        max_nodes: None,
+                        multipv: 1,
+                        clock: None,
+                        variant: Variant::Standard,
+                        position_history_hashes: history,
+                        stop: StopSignal::default(),
+                        reporter: SearchReporter::default(),
+                    },
+                )
+                .unwrap();
+            (mv, engine.search_info().unwrap().score)
+        };
+
+        let (preferred, _) = search(vec![board.hash()]);
+        let mut repeated = board.clone();
+        repeated.play(preferred);
+        let (mv, score) = search(vec![repeated.hash(), board.hash()]);
+
+        assert_ne!(mv, preferred);
+        assert!(
+            matches!(score, EngineScore::Centipawns(cp) if cp > 500)
+                || matches!(score, EngineScore::Mate(moves) if moves > 0),
+            "{score:?}"
+        );
+    }
+
+    #[test]
+    fn contempt_plays_on_a_pawn_down_instead_of_repeating() {
+        // A pawn down, White can repeat with Kh1 or play on.
+        let board: Board = "6k1/5ppp/8/8/8/8/5PP1/6K1 w - - 0 1".parse().unwrap();
+        let repeat: Move = "g1h1".parse().unwrap();
+        let mut repeated = board.clone();
+        repeated.play(repeat);
+        let legal = legal_moves(&board);
+        let search = |contempt: &str| {
+            let mut engine = engine();
+            engine.set_option("Contempt", contempt).unwrap();
+            let mut ctx = fixed_depth_context(&board, 4);
+            ctx.position_history_hashes = vec![repeated.hash(), board.hash()];
+            let mv = engine.choose_move(&board, &legal, ctx).unwrap();
+            (mv, engine.search_info().unwrap().score)
+        };
+
+        assert_eq!(search("0"), (repeat, EngineScore::Centipawns(0)));
+        let (mv, score) = search("300");
+        assert_ne!(mv, repeat);
+        assert!(
+            matches!(score, EngineScore::Centipawns(cp) if (-300..0).contains(&cp)),
+            "{score:?}"
+        );
+    }
+
+    #[test]
+    fn pruning_searches_fewer_nodes_at_a_fixed_depth() {
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        ];
+        for fen in fens {
+            let board: Board = fen.parse().unwrap();
+            let legal = legal_moves(&board);
+            let nodes = |pruning: &str| {
+                let mut engine = engine();
+                engine.set_option("NullMove", pruning).unwrap();
+                engine.set_option("LateMoveReductions", pruning).unwrap();
+                engine
+                    .choose_move(
+                        &board,
+                        &legal,
+                        SearchContext {
+                            movetime_ms: 60_000,
+                            max_depth: Some(7),
+                            max_nodes: None,
+                            multipv: 1,
+                            clock: None,
+                            variant: Variant::Standard,
+                            position_history_hashes: vec![board.hash()],
+                            stop: StopSignal::default(),
+                            reporter: SearchReporter::default(),
+                        },
+                    )
+                    .unwrap();
+                engine.search_info().unwrap().nodes
+            };
+
+            let (pruned, plain) = (nodes("true"), nodes("false"));
+            assert!(pruned * 2 < plain, "{fen}: {pruned} vs {plain}");
+        }
+    }
+
+    #[test]
+    fn clear_hash_empties_the_table_and_searches_like_a_cold_engine() {
+        let board: Board =
+            "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10"
+                .parse()
+                .unwrap();
+        let legal = legal_moves(&board);
+        let nodes = |engine: &mut ClassicalEngine| {
+            engine
+                .choose_move(
+                    &board,
+                    &legal,
+                    SearchContext {
+                        movetime_ms: 60_000,
+                        max_depth: Some(5),
+                        max_nodes: None,
+                        multipv: 1,
+                        clock: None,
+                        variant: Variant::Standard,
+                        position_history_hashes: vec![board.hash()],
+                        stop: StopSignal::default(),
+                        reporter: SearchReporter::default(),
+                    },
+                )
+                .unwrap();
+            engine.search_info().unwrap().nodes
+        };
+
+        let mut engine = engine();
+        let cold = nodes(&mut engine);
+        assert!(engine.hash_entries() > 0);
+        let warm = nodes(&mut engine);
+        assert!(warm < cold, "{warm} vs {cold}");
+
+        engine.set_option("Clear Hash", "").unwrap();
+        assert_eq!(engine.hash_entries(), 0);
+        assert_eq!(nodes(&mut engine), cold);
+    }
+
+    /// Best move, score and nodes of a fixed-depth search with aspiration
+    /// windows on or off.
+    fn fixed_depth_search(fen: &str, depth: u32, aspiration: &str) -> (Move, i32, u64) {
+        let board: Board = fen.parse().unwrap();
+        let mut engine = engine();
+        engine.set_option("AspirationWindows", aspiration).unwrap();
+        let mv = engine
+            .choose_move(
+                &board,
+                &legal_moves(&board),
+                SearchContext {
+                    movetime_ms: 60_000,
+                    max_depth: Some(depth),
+                    max_nodes: None,
+                    multipv: 1,
+                    clock: None,
+                    variant: Variant::Standard,
+                    position_history_hashes: vec![board.hash()],
+                    stop: StopSignal::default(),
+                    reporter: SearchReporter::default(),
+                },
+            )
+            .unwrap();
+        let info = engine.search_info().unwrap();
+        let score = match info.score {
+            EngineScore::Centipawns(cp) => cp,
+            EngineScore::Mate(moves) => MATE_SCORE * moves.signum(),
+        };
+        (mv, score, info.nodes)
+    }
+
+    #[test]
+    fn aspiration_windows_keep_fixed_depth_results() {
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+            "r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N1PN2/PP2BPPP/R2QKB1R w KQ - 1 8",
+            "r2q1rk1/pp1nbppp/2p1pn2/3p4/2PP1B2/2N1PN2/PPQ2PPP/R3KB1R w KQ - 3 9",
+            "rnbqkb1r/pp3ppp/4pn2/2pp4/3P4/2P1PN2/PP3PPP/RNBQKB1R w KQkq - 0 5",
+            "r1bqk2r/pppp1ppp/2n2n2/2b1p3/2B1P3/3P1N2/PPP2PPP/RNBQK2R w KQkq - 1 5",
+            "2r3k1/pp3ppp/2n1p3/3pP3/3P4/P1r2N2/5PPP/R1R3K1 w - - 0 22",
+            "r4rk1/pp3ppp/2n5/2bq4/8/2P2N2/PP3PPP/R1BQ1RK1 b - - 0 13",
+            "6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1",
+            "8/8/4k3/8/4P3/4K3/8/8 w - - 0 1",
+            "8/5pk1/6p1/3R4/8/6P1/5PK1/3r4 w - - 0 40",
+            "r1b2rk1/2q1bppp/p2ppn2/1p6/3BPP2/2N2B2/PPPQ2PP/2KR3R w - - 0 14",
+            "3r1rk1/p4ppp/1qp1p3/2b5/4P3/1BP2Q2/PP3PPP/3R1RK1 b - - 0 18",
+            "8/8/1p3k2/p1p5/P1P3K1/1P6/8/8 w - - 0 50",
+            "4rrk1/pbq2ppp/1p2p3/2p5/2PPn3/P1B1PN2/4QPPP/R4RK1 w - - 0 17",
+        ];
+        for fen in fens {
+            let (narrow_move, narrow_score, _) = fixed_depth_search(fen, 5, "true");
+            let (full_move, full_score, _) = fixed_depth_search(fen, 5, "false");
+            // Bounds stored during a narrow window can nudge later table
+            // cutoffs, so the score may drift a little, but never the move.
+            assert_eq!(narrow_move, full_move, "{fen}");
+            assert!(
+                (narrow_score - full_score).abs() <= 20,
+                "{fen}: {narrow_score} vs {full_score}"
+            );
+        }
+    }
+
+    #[test]
+    fn aspiration_windows_search_fewer_nodes_in_the_middlegame() {
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+            "r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N1PN2/PP2BPPP/R2QKB1R w KQ - 1 8",
+        ];
+        let nodes = |aspiration: &str| -> u64 {
+            fens.iter()
+                .map(|fen| fixed_depth_search(fen, 6, aspiration).2)
+                .sum()
+        };
+
+        let (narrow, full) = (nodes("true"), nodes("false"));
+        assert!(narrow < full, "{narrow} vs {full}");
+    }
+
+    #[test]
+    fn a_search_stopped_while_failing_low_keeps_the_previous_move() {
+        let board = Board::startpos();
+        let legal = legal_moves(&board);
+        let (mut completed, mut stopped) = (engine(), engine());
+
+        // A previous score far above the real one fails low at once.
+        let mut search = searcher(&mut completed);
+        let (_, score) = search
+            .search_root_aspirated(&board, &legal, 4, Some(2_000))
+            .unwrap();
+        assert!(score.abs() < 200);
+        assert!(search.researches > 0);
+
+        let mut search = searcher(&mut stopped);
+        search.node_limit = 50;
+        assert_eq!(
+            search.search_root_aspirated(&board, &legal, 4, Some(2_000)),
+            None
+        );
+    }
+
+    #[test]
+    fn null_move_is_skipped_with_only_pawns_left() {
+        let board: Board = "8/8/4k3/8/4P3/4K3/8/8 w - - 0 1".parse().unwrap();
+        assert!(!has_non_pawn_material(&board, Color::White));
+        let board: Board = "8/8/4k3/8/4P3/4K3/8/6N1 w - - 0 1".parse().unwrap();
+        assert!(has_non_pawn_material(&board, Color::White));
+        assert!(!has_non_pawn_material(&board, Color::Black));
+    }
+
+    #[test]
+    fn finds_mate_in_one() {
+        let board: Board = "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1".parse().unwrap();
+        let legal = legal_moves(&board);
+        let mut engine = engine();
+        let mv = engine
+            .choose_move(
+                &board,
+                &legal,
+                SearchContext {
+                    movetime_ms: 200,
+                    max_depth: None,
+                    max_nodes: None,
+                    multipv: 1,
+                    clock: None,
+                    variant: Variant::Standard,
+                    position_history_hashes: vec![board.hash()],
+                    stop: StopSignal::default(),
+                    reporter: SearchReporter::default(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(mv.to_string(), "a1a8");
+    }
+
+    #[test]
+    fn check_extensions_find_a_mate_in_three_at_depth_three() {
+        // 1. Nh6+ Kh8 2. Qg8+ Rxg8 3. Nf7#: every black move answers a check.
+        let board: Board = "5rk1/5Npp/8/8/2Q5/8/8/6K1 w - - 0 1".parse().unwrap();
+        let legal = legal_moves(&board);
+        let mut engine = engine();
+        let mv = engine
+            .choose_move(
+                &board,
+                &legal,
+                SearchContext {
+                    movetime_ms: 60_000,
+                    max_depth: Some(3),
+                    max_nodes: None,
+                    multipv: 1,
+                    clock: None,
+                    variant: Variant::Standard,
+                    position_history_hashes: vec![board.hash()],
+                    stop: StopSignal::default(),
+                    reporter: SearchReporter::default(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(mv.to_string(), "f7h6");
+        assert_eq!(
+            engine.search_info().map(|info| info.score),
+            Some(EngineScore::Mate(3))
+        );
+    }
+
+    #[test]
+    fn converts_king_and_pawn_against_king_within_fifty_moves() {
+        let mut board: Board = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1".parse().unwrap();
+        let mut history = vec![board.hash()];
+        let mut engine = engine();
+        for _ in 0..100 {
+            if board.status() != GameStatus::Ongoing {
+                break;
+            }
+            let legal = legal_moves(&board);
+            let mv = engine
+                .choose_move(
+                    &board,
+                    &legal,
+                    SearchContext {
+                        movetime_ms: 60_000,
+                        max_depth: Some(6),
+                        max_nodes: None,
+                        multipv: 1,
+                        clock: None,
+                        variant: Variant::Standard,
+                        position_history_hashes: history.clone(),
+                        stop: StopSignal::default(),
+                        reporter: SearchReporter::default(),
+                    },
+                )
+                .unwrap();
+            board.play(mv);
+            history.push(board.hash());
+        }
+
+        assert_eq!(board.status(), GameStatus::Won, "{board}");
+        assert_eq!(board.side_to_move(), Color::Black);
+    }
+
+    #[test]
+    fn check_extensions_stay_bounded_when_both_sides_can_keep_checking() {
+        let board: Board = "6k1/5p1p/6p1/8/8/8/qr3PPP/3Q2K1 w - - 0 1".parse().unwrap();
+        let legal = legal_moves(&board);
+        let mut engine = engine();
+        engine
+            .choose_move(
+                &board,
+                &legal,
+                SearchContext {
+                    movetime_ms: 60_000,
+                    max_depth: Some(6),
+                    max_nodes: None,
+                    multipv: 1,
+                    clock: None,
+                    variant: Variant::Standard,
+                    position_history_hashes: vec![board.hash()],
+                    stop: StopSignal::default(),
+                    reporter: SearchReporter::default(),
+                },
+            )
+            .unwrap();
+
+        let info = engine.search_info().unwrap();
+        assert_eq!(info.depth, 6);
+        assert!(info.nodes < 500_000, "{}", info.nodes);
+    }
+
+    #[test]
+    fn clock_searches_finish_within_their_allocation() {
+        let board = Board::default();
+        let legal = legal_moves(&board);
+        let clock = SearchClock {
+            wtime_ms: Some(3_000),
+            btime_ms: Some(3_000),
+            winc_ms: 0,
+            binc_ms: 0,
+            movestogo: None,
+        };
+        let movetime_ms = clock.allocate_move_time_ms(Color::White).unwrap();
+        let mut engine = engine();
+        let started = Instant::now();
+        let mv = engine
+            .choose_move(
+                &board,
+                &legal,
+                SearchContext {
+                    movetime_ms,
+                    max_depth: None,
+                    max_nodes: None,
+                    multipv: 1,
+                    clock: Some(clock),
+                    variant: Variant::Standard,
+                    position_history_hashes: vec![board.hash()],
+                    stop: StopSignal::default(),
+                    reporter: SearchReporter::default(),
+                },
+            )
+            .unwrap();
+
+        assert!(legal.contains(&mv));
+        assert!(
+            started.elapsed() < Duration::from_millis(movetime_ms),
+            "{:?} of {movetime_ms}ms",
+            started.elapsed()
+        );
+    }
+
+    #[test]
+    fn a_nearly_empty_clock_moves_before_it_runs_out() {
+        let board: Board = "r1b2rk1/2q1bppp/p2ppn2/1p6/3BPP2/2N2B2/PPPQ2PP/2KR3R w - - 0 14"
+            .parse()
+            .unwrap();
+        let legal = legal_moves(&board);
+        // `go wtime 10 btime 10`
+        let clock = SearchClock {
+            wtime_ms: Some(10),
+            btime_ms: Some(10),
+            winc_ms: 0,
+            binc_ms: 0,
+            movestogo: None,
+        };
+        let movetime_ms = clock.allocate_move_time_ms(Color::White).unwrap();
+        assert!(search_budget_ms(movetime_ms) < 10, "{movetime_ms}");
+        let mut engine = engine();
+        let started = Instant::now();
+        let mv = engine
+            .choose_move(
+                &board,
+                &legal,
+                SearchContext {
+                    movetime_ms,
+                    max_depth: None,
+                    max_nodes: None,
+                    multipv: 1,
+                    clock: Some(clock),
+                    variant: Variant::Standard,
+                    position_history_hashes: vec![board.hash()],
+                    stop: StopSignal::default(),
+                    reporter: SearchReporter::default(),
+                },
+            )
+            .unwrap();
+
+        assert!(legal.contains(&mv));
+        assert!(
+            started.elapsed() < Duration::from_millis(250),
+            "{:?}",
+            started.elapsed()
+        );
+    }
+
+    #[test]
+    fn helper_threads_add_their_nodes_to_the_search() {
+        let board: Board = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+            .parse()
+            .unwrap();
+        let legal = legal_moves(&board);
+        let search = |threads: &str| {
+            let mut engine = engine();
+            engine.set_option("Threads", threads).unwrap();
+            let mv = engine
+                .choose_move(
+                    &board,
+                    &legal,
+                    SearchContext {
+                        movetime_ms: 300,
+                        max_depth: None,
+                        max_nodes: None,
+                        multipv: 1,
+                        clock: None,
+                        variant: Variant::Standard,
+                        position_history_hashes: vec![board.hash()],
+                        stop: StopSignal::default(),
+                        reporter: SearchReporter::default(),
+                    },
+                )
+                .unwrap();
+            assert!(legal.contains(&mv), "{threads} threads played {mv}");
+            engine.search_info().unwrap().nodes
+        };
+
+        let single = search("1");
+        let shared = search("4");
+        // Only a machine with spare cores searches more in the same time.
+        if thread::available_parallelism().map_or(1, usize::from) >= 4 {
+            assert!(
+                shared > single,
+                "{shared} nodes on 4 threads, {single} on 1"
+            );
+        }
+    }
+
+    #[test]
+    fn plays_the_fastest_mate_and_reports_its_distance() {
+        // 1. Rg7 Kb8 2. Rf8# is the only mate in two; Rf8+ and Rg8+ let the king out.
+        let board: Board = "k7/8/8/8/8/8/6R1/5R1K w - - 0 1".parse().unwrap();
+        let legal = legal_moves(&board);
+        let mut engine = engine();
+        let mv = engine
+            .choose_move(
+                &board,
+                &legal,
+                SearchContext {
+                    movetime_ms: 2_000,
+                    max_depth: None,
+                    max_nodes: None,
+                    multipv: 1,
+                    clock: None,
+                    variant: Variant::Standard,
+                    position_history_hashes: vec![board.hash()],
+                    stop: StopSignal::default(),
+                    reporter: SearchReporter::default(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            engine.search_info().map(|info| info.score),
+            Some(EngineScore::Mate(2))
+        );
+        let mut after = board.clone();
+        after.play(mv);
+        for reply in legal_moves(&after) {
+            let mut next = after.clone();
+            next.play(reply);
+            assert!(
+                legal_moves(&next).into_iter().any(|mate| {
+                    let mut mated = next.clone();
+                    mated.play(mate);
+                    mated.status() == GameStatus::Won
+                }),
+                "{mv} allows {reply} without a mate in one"
+            );
+        }
+    }
+
+    #[test]
+    fn multipv_ranks_the_winning_capture_first() {
+        // Only Rxd5 wins the hanging queen.
+        let board: Board = "4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1".parse().unwrap();
+        let legal = legal_moves(&board);
+        let mut engine = engine();
+        let mv = engine
+            .choose_move(
+                &board,
+                &legal,
+                SearchContext {
+                    movetime_ms: 2_000,
+                    max_depth: Some(4),
+                    max_nodes: None,
+                    multipv: 3,
+                    clock: None,
+                    variant: Variant::Standard,
+                    position_history_hashes: vec![board.hash()],
+                    stop: StopSignal::default(),
+                    reporter: SearchReporter::default(),
+                },
+            )
+            .unwrap();
+
+        let info = engine.search_info().unwrap();
+        let roots: Vec<Move> = info.lines.iter().map(|line| line.moves[0]).collect();
+        assert_eq!(roots.len(), 3);
+        assert_eq!(roots[0], "d1d5".parse().unwrap());
+        assert_eq!(roots[0], mv);
+        assert!(roots[1] != roots[0] && roots[2] != roots[0] && roots[1] != roots[2]);
+        assert_eq!(info.lines[0].score, info.score);
+    }
+
+    #[test]
+    fn low_skill_levels_miss_tactics_that_full_strength_finds() {
+        // One clearly best move each: free material, a fork, or a mate.
+        let tactics = [
+            ("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1", "d1d5"),
+            ("4k3/8/8/3n4/8/8/8/3RK3 w - - 0 1", "d1d5"),
+            ("4k3/8/8/8/3b4/4P3/8/4K3 w - - 0 1", "e3d4"),
+            ("4k3/8/8/8/8/2n5/8/2Q1K3 w - - 0 1", "c1c3"),
+            ("4k3/8/8/8/8/8/r7/R3K3 w - - 0 1", "a1a2"),
+            ("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8"),
+            ("r3k3/8/8/1N6/8/8/8/4K3 w - - 0 1", "b5c7"),
+            ("4k3/8/8/8/8/8/1q6/B3K3 w - - 0 1", "a1b2"),
+            ("4k3/8/8/2p5/3P4/8/8/4K3 w - - 0 1", "d4c5"),
+            ("4k3/8/8/8/8/5r2/8/3BK3 w - - 0 1", "d1f3"),
+        ];
+        let best_move_rate = |level: &str| {
+            let mut found = 0;
+            for seed in 0..5 {
+                let mut engine = engine();
+                engine.set_option("Skill Level", level).unwrap();
+                engine.set_option("Seed", &seed.to_string()).unwrap();
+                for (fen, best) in tactics {
+                    let board: Board = fen.parse().unwrap();
+                    let legal = legal_moves(&board);
+                    let mv = engine
+                        .choose_move(
+                            &board,
+                            &legal,
+                            SearchContext {
+                                movetime_ms: 2_000,
+                                max_depth: Some(4),
+                                max_nodes: None,
+                                multipv: 1,
+                                clock: None,
+                                variant: Variant::Standard,
+                                position_history_hashes: vec![board.hash()],
+                                stop: StopSignal::default(),
+                                reporter: SearchReporter::default(),
+                            },
+                        )
+                        .unwrap();
+                    found += usize::from(mv.to_string() == best);
+                }
+            }
+            found
+        };
+
+        let full_strength = best_move_rate("20");
+        let weakest = best_move_rate("0");
+        assert_eq!(full_strength, 50);
+        assert!(weakest <= 40, "level 0 found {weakest} of 50");
+    }
+
+    #[test]
+    fn principal_variation_replays_from_the_root_and_starts_with_the_best_move() {
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4",
+            "4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        ];
+        // A fixed depth, then a 20ms clock that stops the search mid-iteration.
+        let limits = [
+            (Some(1), 2_000),
+            (Some(3), 2_000),
+            (Some(5), 5_000),
+            (None, 20),
+        ];
+        for fen in fens {
+            let board: Board = fen.parse().unwrap();
+            let legal = legal_moves(&board);
+            for (max_depth, movetime_ms) in limits {
+                let mut engine = engine();
+                let mv = engine
+                    .choose_move(
+                        &board,
+                        &legal,
+                        SearchContext {
+                            movetime_ms,
+                            max_depth,
+                            max_nodes: None,
+                            multipv: 1,
+                            clock: None,
+                            variant: Variant::Standard,
+                            position_history_hashes: vec![board.hash()],
+                            stop: StopSignal::default(),
+                            reporter: SearchReporter::default(),
+                        },
+                    )
+                    .unwrap();
+
+                let info = engine.search_info().unwrap();
+                let pv = &info.lines[0].moves;
+                assert_eq!(pv.first(), Some(&mv), "{fen} {max_depth:?}");
+                if let Some(depth) = max_depth {
+                    assert!(pv.len() <= depth as usize);
+                }
+                let mut position = board.clone();
+                for pv_move in pv {
+                    assert!(position.is_legal(*pv_move), "{fen}: {pv_move} in {pv:?}");
+                    position.play(*pv_move);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Strength limiting for casual play, from the `Skill Level` option or its
+//! `UCI_LimitStrength`/`UCI_Elo` equivalent. Below full strength the search
+//! is capped, the evaluation gets bounded noise, and the move is picked from
+//! the best few root lines, favoring close alternatives.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) const MAX_SKILL_LEVEL: u8 = 20;
+pub(crate) const MIN_ELO: u32 = 800;
+pub(crate) const MAX_ELO: u32 = 2400;
+/// Root lines searched to choose among below full strength.
+pub(crate) const CANDIDATE_LINES: usize = 4;
+/// Evaluation noise at level 0, shrinking linearly to none at full strength.
+const MAX_NOISE_CP: i32 = 160;
+const MOVETIME_MS_PER_LEVEL: u64 = 100;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Skill {
+    level: u8,
+    limit_strength: bool,
+    elo: u32,
+    /// Seeds both the evaluation noise and the move picks, from the `Seed`
+    /// option or the clock, so a fixed seed replays the same games.
+    seed: u64,
+    rng: u64,
+}
+
+impl Default for Skill {
+    fn default() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        Self {
+            level: MAX_SKILL_LEVEL,
+            limit_strength: false,
+            elo: MAX_ELO,
+            seed,
+            rng: seed,
+        }
+    }
+}
+
+impl Skill {
+    pub(crate) fn set_level(&mut self, level: u8) {
+        self.level = level.min(MAX_SKILL_LEVEL);
+    }
+
+    pub(crate) fn set_limit_strength(&mut self, enabled: bool) {
+        self.limit_strength = enabled;
+    }
+
+    pub(crate) fn set_elo(&mut self, elo: u32) {
+        self.elo = elo.clamp(MIN_ELO, MAX_ELO);
+    }
+
+    pub(crate) fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = seed;
+    }
+
+    /// Level in effect: the one mapped from `UCI_Elo` while strength is
+    /// limited, otherwise `Skill Level`.
+    pub(crate) fn level(&self) -> u8 {
+        if self.limit_strength {
+            elo_to_level(self.elo)
+        } else {
+            self.level
+        }
+    }
+
+    pub(crate) fn is_limited(&self) -> bool {
+        self.level() < MAX_SKILL_LEVEL
+    }
+
+    pub(crate) fn max_depth(&self) -> i32 {
+        i32::from(self.level()) + 1
+    }
+
+    pub(crate) fn max_movetime_ms(&self) -> u64 {
+        MOVETIME_MS_PER_LEVEL * (u64::from(self.level()) + 1)
+    }
+
+    /// Noise added to the static evaluation of the position with `hash`. It
+    /// depends only on the position and the seed, so transposition table
+    /// entries stay consistent within a search.
+    pub(crate) fn eval_noise(&self, hash: u64) -> i32 {
+        let amplitude =
+            MAX_NOISE_CP * i32::from(MAX_SKILL_LEVEL - self.level()) / i32::from(MAX_SKILL_LEVEL);
+        if amplitude == 0 {
+            return 0;
+        }
+        let spread = (2 * amplitude + 1) as u64;
+        (splitmix64(hash ^ self.seed) % spread) as i32 - amplitude
+    }
+
+    /// Index of the root line to play from `lines`, sorted best first.
+    /// Stockfish's rule: every line gets a random bonus, and weaker levels
+    /// get a larger share of the gap to the best score added back, so the
+    /// closer an alternative is, the likelier it is picked.
+    pub(crate) fn pick(&mut self, lines: &[i32]) -> usize {
+        let (Some(&top), Some(&last)) = (lines.first(), lines.last()) else {
+            return 0;
+        };
+        let weakness = 120 - 2 * i32::from(self.level());
+        let delta = (top - last).min(200);
+        let mut best = (i32::MIN, 0);
+        for (index, &score) in lines.iter().enumerate() {
+            self.rng = splitmix64(self.rng);
+            let noise = (self.rng % weakness as u64) as i32;
+            let push = (weakness * (top - score) + delta * noise) / 128;
+            if score + push > best.0 {
+                best = (score + push, index);
+            }
+        }
+        best.1
+    }
+}
+
+/// Linear map from `MIN_ELO..=MAX_ELO` onto skill levels 0 to 20.
+pub(crate) fn elo_to_level(elo: u32) -> u8 {
+    let elo = elo.clamp(MIN_ELO, MAX_ELO);
+    ((elo - MIN_ELO) * u32::from(MAX_SKILL_LEVEL) / (MAX_ELO - MIN_ELO)) as u8
+}
+
+fn splitmix64(state: u64) -> u64 {
+    let mut z = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elo_maps_onto_the_full_level_range() {
+        assert_eq!(elo_to_level(0), 0);
+        assert_eq!(elo_to_level(MIN_ELO), 0);
+        assert_eq!(elo_to_level(1600), 10);
+        assert_eq!(elo_to_level(MAX_ELO), MAX_SKILL_LEVEL);
+
+        let mut skill = Skill::default();
+        assert!(!skill.is_limited());
+        skill.set_elo(1200);
+        assert!(!skill.is_limited());
+        skill.set_limit_strength(true);
+        assert_eq!(skill.level(), 5);
+    }
+
+    #[test]
+    fn noise_is_bounded_and_vanishes_at_full_strength() {
+        let mut skill = Skill::default();
+        skill.set_seed(7);
+        assert!((0..1_000).all(|hash| skill.eval_noise(hash) == 0));
+
+        skill.set_level(0);
+        let noise: Vec<i32> = (0..1_000).map(|hash| skill.eval_noise(hash)).collect();
+        assert!(noise.iter().all(|value| value.abs() <= MAX_NOISE_CP));
+        assert!(noise.iter().any(|value| value.abs() > MAX_NOISE_CP / 2));
+        assert_eq!(skill.eval_noise(42), skill.eval_noise(42));
+    }
+
+    #[test]
+    fn weaker_levels_pick_close_alternatives_more_often() {
+        let alternatives = |level| {
+            let mut skill = Skill::default();
+            skill.set_seed(1);
+            skill.set_level(level);
+            (0..1_000)
+                .filter(|_| skill.pick(&[50, 20, 0, -300]) != 0)
+                .count()
+        };
+
+        assert!(alternatives(0) > alternatives(10));
+        assert!(alternatives(10) > alternatives(19));
+        assert!(alternatives(0) > 100);
+    }
+}
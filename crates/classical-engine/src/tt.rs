@@ -0,0 +1,62 @@
+//! Transposition table shared by the search threads. Entries are spread over
+//! independently locked shards, so threads rarely wait on each other.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use cozy_chess::Move;
+
+const SHARDS: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TranspositionEntry {
+    pub(crate) depth: i32,
+    pub(crate) score: i32,
+    pub(crate) bound: Bound,
+    pub(crate) best_move: Option<Move>,
+}
+
+pub(crate) struct TranspositionTable {
+    shards: Vec<Mutex<HashMap<u64, TranspositionEntry>>>,
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self {
+            shards: (0..SHARDS).map(|_| Mutex::default()).collect(),
+        }
+    }
+}
+
+impl TranspositionTable {
+    pub(crate) fn get(&self, hash: u64) -> Option<TranspositionEntry> {
+        self.shard(hash).get(&hash).copied()
+    }
+
+    pub(crate) fn insert(&self, hash: u64, entry: TranspositionEntry) {
+        self.shard(hash).insert(hash, entry);
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        (0..SHARDS)
+            .map(|index| self.shard(index as u64).len())
+            .sum()
+    }
+
+    fn shard(&self, hash: u64) -> MutexGuard<'_, HashMap<u64, TranspositionEntry>> {
+        // An entry is written in one go, so a thread that panicked holding
+        // the lock cannot have left its shard half-updated.
+        self.shards[hash as usize % SHARDS]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,96 @@
+//! Criterion benchmarks for move generation and perft: `cargo bench -p
+//! engine-sdk --bench movegen`, narrowed with a filter such as `-- movegen`
+//! or `-- perft`. Benchmark names stay fixed so `--save-baseline` and
+//! `--baseline` compare runs across commits; the `perft_bench` example is
+//! still the tool for one-off timings and flamegraphs.
+
+use std::hint::black_box;
+
+use cozy_chess::{Board, Move};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use engine_sdk::perft;
+
+/// The standard perft positions, named after the Chess Programming Wiki's
+/// list: the opening, a middlegame dense with castling and pins, a sparse
+/// endgame, and a position full of promotions and checks.
+const POSITIONS: &[(&str, &str)] = &[
+    (
+        "startpos",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    ),
+    (
+        "kiwipete",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    ),
+    ("endgame", "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1"),
+    (
+        "promotions",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    ),
+];
+
+fn boards() -> impl Iterator<Item = (&'static str, Board)> {
+    POSITIONS
+        .iter()
+        .map(|(name, fen)| (*name, fen.parse().expect("valid benchmark FEN")))
+}
+
+fn legal_moves(board: &Board) -> Vec<Move> {
+    let mut moves = Vec::new();
+    board.generate_moves(|piece_moves| {
+        moves.extend(piece_moves);
+        false
+    });
+    moves
+}
+
+/// Guards legal move generation, the cost paid at every search node, and
+/// playing each move on a copy of the board, since cozy-chess has no unmake.
+fn movegen(c: &mut Criterion) {
+    let mut group = c.benchmark_group("movegen");
+    for (name, board) in boards() {
+        group.throughput(Throughput::Elements(1));
+        group.bench_with_input(BenchmarkId::new("legal_moves", name), &board, |b, board| {
+            b.iter(|| legal_moves(black_box(board)).len())
+        });
+
+        let moves = legal_moves(&board);
+        group.throughput(Throughput::Elements(moves.len() as u64));
+        group.bench_with_input(BenchmarkId::new("play", name), &board, |b, board| {
+            b.iter(|| {
+                for mv in &moves {
+                    let mut next = board.clone();
+                    next.play_unchecked(*mv);
+                    black_box(&next);
+                }
+            })
+        });
+
+        group.throughput(Throughput::Elements(1));
+        group.bench_with_input(BenchmarkId::new("hash", name), &board, |b, board| {
+            b.iter(|| black_box(board).hash())
+        });
+    }
+    group.finish();
+}
+
+/// Guards the bulk-counting perft as a whole, reported in nodes per second.
+fn perft_nodes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("perft");
+    group.sample_size(10);
+    for (name, depth) in [("startpos", 4), ("kiwipete", 3)] {
+        let board: Board = POSITIONS
+            .iter()
+            .find(|(candidate, _)| *candidate == name)
+            .map(|(_, fen)| fen.parse().expect("valid benchmark FEN"))
+            .expect("named benchmark position");
+        group.throughput(Throughput::Elements(perft(&board, depth)));
+        group.bench_with_input(BenchmarkId::new(name, depth), &board, |b, board| {
+            b.iter(|| perft(black_box(board), depth))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, movegen, perft_nodes);
+criterion_main!(benches);
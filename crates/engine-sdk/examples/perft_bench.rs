@@ -0,0 +1,71 @@
+//! Times perft on a position: `cargo run --release -p engine-sdk --example
+//! perft_bench -- [--depth N] [--divide] [--compare] [FEN]`.
+//!
+//! `--compare` also times the naive make-every-move perft on the same
+//! position so the bulk-counting speedup can be measured.
+
+use std::{
+    env,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, anyhow, bail};
+use cozy_chess::Board;
+use engine_sdk::{format_divide, perft, perft_divide, perft_naive};
+
+const USAGE: &str = "usage: perft_bench [--depth N] [--divide] [--compare] [FEN]";
+
+fn main() -> Result<()> {
+    let mut depth = 5;
+    let mut divide = false;
+    let mut compare = false;
+    let mut fen = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--depth" => {
+                depth = args
+                    .next()
+                    .ok_or_else(|| anyhow!("--depth needs a value\n{USAGE}"))?
+                    .parse()?;
+            }
+            "--divide" => divide = true,
+            "--compare" => compare = true,
+            other if other.starts_with("--") => bail!("unknown argument {other}\n{USAGE}"),
+            other => fen = Some(other.to_string()),
+        }
+    }
+
+    let board = match fen {
+        Some(fen) => Board::from_fen(&fen, false).map_err(|err| anyhow!("invalid FEN: {err:?}"))?,
+        None => Board::default(),
+    };
+
+    let started = Instant::now();
+    let nodes = if divide {
+        let table = perft_divide(&board, depth);
+        println!("{}", format_divide(&board, &table));
+        table.iter().map(|(_, nodes)| nodes).sum()
+    } else {
+        perft(&board, depth)
+    };
+    report("perft", depth, nodes, started.elapsed());
+
+    if compare {
+        let started = Instant::now();
+        let naive_nodes = perft_naive(&board, depth);
+        report("naive", depth, naive_nodes, started.elapsed());
+        if naive_nodes != nodes {
+            bail!("node counts differ: {nodes} vs {naive_nodes}");
+        }
+    }
+    Ok(())
+}
+
+fn report(label: &str, depth: u32, nodes: u64, elapsed: Duration) {
+    println!(
+        "{label} depth {depth}: {nodes} nodes in {elapsed:.2?} ({:.0} nodes/s)",
+        nodes as f64 / elapsed.as_secs_f64()
+    );
+}
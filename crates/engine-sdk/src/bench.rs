@@ -0,0 +1,139 @@
+//! Stockfish-style `bench`: fixed-depth searches over a fixed set of
+//! positions. A deterministic engine always searches the same nodes, so the
+//! total works as a signature that changes only when the search does.
+
+use std::{fmt::Write, time::Instant};
+
+use anyhow::{Result, anyhow};
+use arena_core::Variant;
+use cozy_chess::Board;
+
+use crate::{
+    INFINITE_MOVETIME_MS, SearchContext, SearchReporter, StopSignal, UciEngine, legal_moves,
+};
+
+/// Depth searched by a bare `bench`.
+pub const DEFAULT_BENCH_DEPTH: u32 = 8;
+
+/// The perft suite, a few quiet middlegames, tactics and endgames.
+pub const BENCH_FENS: &[&str] = &[
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+    "r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N1PN2/PP2BPPP/R2QKB1R w KQ - 1 8",
+    "r1b2rk1/2q1bppp/p2ppn2/1p6/3BPP2/2N2B2/PPPQ2PP/2KR3R w - - 0 14",
+    "4rrk1/pbq2ppp/1p2p3/2p5/2PPn3/P1B1PN2/4QPPP/R4RK1 w - - 0 17",
+    "r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4",
+    "6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1",
+    "3r1rk1/p4ppp/1qp1p3/2b5/4P3/1BP2Q2/PP3PPP/3R1RK1 b - - 0 18",
+    "8/5pk1/6p1/3R4/8/6P1/5PK1/3r4 w - - 0 40",
+    "8/8/1p3k2/p1p5/P1P3K1/1P6/8/8 w - - 0 50",
+    "4k3/8/4K3/4P3/8/8/8/8 w - - 0 1",
+];
+
+/// Nodes and time of one bench search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchPosition {
+    pub fen: &'static str,
+    pub nodes: u64,
+    pub time_ms: u64,
+}
+
+/// Searches every [`BENCH_FENS`] position to `depth`, each from a fresh
+/// game so earlier positions leave nothing behind.
+pub fn bench<E: UciEngine + ?Sized>(engine: &mut E, depth: u32) -> Result<Vec<BenchPosition>> {
+    BENCH_FENS
+        .iter()
+        .map(|fen| {
+            let board: Board = fen
+                .parse()
+                .map_err(|err| anyhow!("invalid bench FEN {fen}: {err:?}"))?;
+            engine.new_game(Variant::Standard);
+            let started = Instant::now();
+            engine.choose_move(
+                &board,
+                &legal_moves(&board),
+                SearchContext {
+                    movetime_ms: INFINITE_MOVETIME_MS,
+                    max_depth: Some(depth),
+                    max_nodes: None,
+                    multipv: 1,
+                    clock: None,
+                    variant: Variant::Standard,
+                    position_history_hashes: vec![board.hash()],
+                    stop: StopSignal::default(),
+                    reporter: SearchReporter::default(),
+                },
+            )?;
+            Ok(BenchPosition {
+                fen,
+                nodes: engine.search_info().map_or(0, |info| info.nodes),
+                time_ms: started.elapsed().as_millis() as u64,
+            })
+        })
+        .collect()
+}
+
+/// One line per position, a blank line, then the node total and speed.
+pub fn format_bench(positions: &[BenchPosition]) -> String {
+    let mut table = String::new();
+    for (index, position) in positions.iter().enumerate() {
+        let _ = writeln!(
+            table,
+            "position {}/{}: {} nodes in {} ms ({})",
+            index + 1,
+            positions.len(),
+            position.nodes,
+            position.time_ms,
+            position.fen
+        );
+    }
+    let nodes: u64 = positions.iter().map(|position| position.nodes).sum();
+    let time_ms: u64 = positions.iter().map(|position| position.time_ms).sum();
+    let _ = write!(
+        table,
+        "\nTotal time (ms): {time_ms}\nNodes searched: {nodes}\nNodes/second: {}",
+        nodes * 1_000 / time_ms.max(1)
+    );
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bench_table_ends_with_the_node_total_and_speed() {
+        let positions = [
+            BenchPosition {
+                fen: BENCH_FENS[0],
+                nodes: 1_500,
+                time_ms: 1,
+            },
+            BenchPosition {
+                fen: BENCH_FENS[1],
+                nodes: 2_500,
+                time_ms: 3,
+            },
+        ];
+        let table = format_bench(&positions);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(
+            lines[1],
+            format!("position 2/2: 2500 nodes in 3 ms ({})", BENCH_FENS[1])
+        );
+        assert_eq!(
+            lines[2..],
+            [
+                "",
+                "Total time (ms): 4",
+                "Nodes searched: 4000",
+                "Nodes/second: 1000000"
+            ]
+        );
+    }
+}
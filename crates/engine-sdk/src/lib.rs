@@ -1,18 +1,161 @@
-use std::io::{self, BufRead, Write};
+use std::{
+    io::{self, BufRead, Write},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
 use anyhow::{Result, anyhow, bail};
-use arena_core::Variant;
-use cozy_chess::{Board, Color, Move, Piece, util};
+use arena_core::{EngineScore, Variant, board_from_fen};
+use cozy_chess::{Board, Color, File, Move, Piece, Rank, Square, util};
+
+mod bench;
+mod options;
+mod perft;
+
+pub use bench::{BENCH_FENS, BenchPosition, DEFAULT_BENCH_DEPTH, bench, format_bench};
+pub use options::{UciOption, UciOptionKind, option_lines};
+pub use perft::{format_divide, perft, perft_divide, perft_naive};
+
+/// `go infinite` has no clock, so it searches on a one-day budget until `stop`.
+pub const INFINITE_MOVETIME_MS: u64 = 24 * 60 * 60 * 1_000;
+
+/// Upper bound advertised for the `MultiPV` option.
+pub const MAX_MULTIPV: usize = 32;
 
 #[derive(Debug, Clone)]
 pub struct SearchContext {
     pub movetime_ms: u64,
+    /// Deepest iteration to search, from `go depth`; engines pick their own cap when unset.
+    pub max_depth: Option<u32>,
+    /// Node budget, from `go nodes`; unlike a clock it stops the search at the
+    /// same point on every machine.
+    pub max_nodes: Option<u64>,
+    /// Number of best root moves to report, from the `MultiPV` option.
+    pub multipv: usize,
+    /// The clock `movetime_ms` was allocated from, unset for `go movetime`,
+    /// `go infinite` and searches bounded only by depth or nodes. Engines can
+    /// use it to spend less than the full allocation.
+    pub clock: Option<SearchClock>,
     pub variant: Variant,
     pub position_history_hashes: Vec<u64>,
+    pub stop: StopSignal,
+    pub reporter: SearchReporter,
+}
+
+/// Both clocks and increments from `go wtime .. btime .. winc .. binc ..
+/// movestogo ..`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchClock {
+    pub wtime_ms: Option<u64>,
+    pub btime_ms: Option<u64>,
+    pub winc_ms: u64,
+    pub binc_ms: u64,
+    /// Moves until the next time control; unset in sudden death.
+    pub movestogo: Option<u64>,
+}
+
+impl SearchClock {
+    /// Default per-move budget for `side`: roughly `remaining / movestogo +
+    /// increment`, never more than half the clock, or `None` when `side` has
+    /// no clock.
+    pub fn allocate_move_time_ms(&self, side: Color) -> Option<u64> {
+        let (remaining_ms, increment_ms) = match side {
+            Color::White => (self.wtime_ms?, self.winc_ms),
+            Color::Black => (self.btime_ms?, self.binc_ms),
+        };
+        if remaining_ms < LOW_CLOCK_MS {
+            return Some((remaining_ms / 4).max(1));
+        }
+
+        let moves_to_go = self.movestogo.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+        Some(
+            (remaining_ms / moves_to_go)
+                .saturating_add(increment_ms)
+                .min(remaining_ms / 2),
+        )
+    }
+}
+
+/// Shared flag the UCI loop raises on `stop`/`quit`; engines should poll it
+/// alongside their deadline and return their best move so far.
+#[derive(Debug, Clone, Default)]
+pub struct StopSignal(Arc<AtomicBool>);
+
+impl StopSignal {
+    pub fn stop(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Where engines send [`SearchReport`]s while they search; the UCI loop
+/// prints each as it arrives. Reports go nowhere outside a UCI session.
+#[derive(Debug, Clone, Default)]
+pub struct SearchReporter(Option<mpsc::Sender<SearchReport>>);
+
+impl SearchReporter {
+    /// A reporter whose reports arrive on the returned receiver.
+    pub fn channel() -> (Self, mpsc::Receiver<SearchReport>) {
+        let (sender, receiver) = mpsc::channel();
+        (Self(Some(sender)), receiver)
+    }
+
+    pub fn report(&self, report: SearchReport) {
+        if let Some(sender) = &self.0 {
+            // The session may already be printing `bestmove`; late reports are dropped.
+            let _ = sender.send(report);
+        }
+    }
+}
+
+/// Progress from a running search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchReport {
+    /// A finished iteration, at least one per depth.
+    Iteration(SearchInfo),
+    /// Counters from inside an iteration that is still running.
+    Progress {
+        depth: u32,
+        nodes: u64,
+        time_ms: u64,
+    },
+}
+
+/// Summary of a finished iteration, reported as UCI `info` output as the
+/// search goes and once more for the last one just before `bestmove`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchInfo {
+    pub depth: u32,
+    /// Deepest ply reached, quiescence included.
+    pub seldepth: u32,
+    /// Score from the side to move's point of view.
+    pub score: EngineScore,
+    pub nodes: u64,
+    /// Time since the search started.
+    pub time_ms: u64,
+    /// Best root lines, best first, when the engine reports them. More than
+    /// one line is printed as `multipv` output.
+    pub lines: Vec<PvLine>,
+}
+
+/// One root line of a search: its score and the moves starting with the
+/// root move.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PvLine {
+    pub score: EngineScore,
+    pub moves: Vec<Move>,
 }
 
 pub trait UciEngine {
-    fn name(&self) -> &'static str;
+    fn name(&self) -> &str;
     fn author(&self) -> &'static str {
         "Rust Chess Arena"
     }
@@ -25,6 +168,140 @@ pub trait UciEngine {
         legal_moves: &[Move],
         ctx: SearchContext,
     ) -> Result<Move>;
+
+    fn search_info(&self) -> Option<SearchInfo> {
+        None
+    }
+
+    /// Options to advertise after the built-in ones. A `setoption` for one
+    /// of them only reaches [`UciEngine::set_option`] with a value in its
+    /// declared range.
+    fn options(&self) -> &'static [UciOption] {
+        &[]
+    }
+
+    /// Applies a `setoption` the UCI loop does not handle itself. Names the
+    /// engine did not declare are passed through unchecked; an error is
+    /// reported to the GUI as `info string`.
+    fn set_option(&mut self, _name: &str, _value: &str) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Negamax scores within this many plies of the mate score are forced mates.
+pub const MAX_MATE_PLIES: i32 = 256;
+
+/// Whether `score` is a forced mate, for searches that score a mate found
+/// `ply` plies from the root as `mate_score - ply`.
+pub fn is_mate_score(score: i32, mate_score: i32) -> bool {
+    mate_distance(score, mate_score).is_some()
+}
+
+/// Plies until mate for a mate score: positive when the side to move mates,
+/// negative when it is mated.
+pub fn mate_distance(score: i32, mate_score: i32) -> Option<i32> {
+    let plies_to_mate = mate_score - score.abs();
+    (0..MAX_MATE_PLIES)
+        .contains(&plies_to_mate)
+        .then_some(if score > 0 {
+            plies_to_mate
+        } else {
+            -plies_to_mate
+        })
+}
+
+/// Mate scores count plies from the root, but a transposition table entry can
+/// be reached at any ply. Store them relative to the node instead.
+pub fn score_to_tt(score: i32, ply: usize, mate_score: i32) -> i32 {
+    match mate_distance(score, mate_score) {
+        Some(plies) if plies > 0 => score + ply as i32,
+        Some(_) => score - ply as i32,
+        None => score,
+    }
+}
+
+/// Inverse of [`score_to_tt`] for the ply the entry is probed at.
+pub fn score_from_tt(score: i32, ply: usize, mate_score: i32) -> i32 {
+    match mate_distance(score, mate_score) {
+        Some(plies) if plies > 0 => score - ply as i32,
+        Some(_) => score + ply as i32,
+        None => score,
+    }
+}
+
+/// Convert a negamax score into a UCI score, reporting mates in moves.
+pub fn engine_score(score: i32, mate_score: i32) -> EngineScore {
+    match mate_distance(score, mate_score) {
+        Some(plies) => {
+            let moves = (plies.abs() + 1) / 2;
+            EngineScore::Mate(if plies > 0 { moves } else { -moves })
+        }
+        None => EngineScore::Centipawns(score),
+    }
+}
+
+fn format_search_report(board: &Board, report: &SearchReport, variant: Variant) -> Vec<String> {
+    match report {
+        SearchReport::Iteration(info) => format_search_info(board, info, variant),
+        SearchReport::Progress {
+            depth,
+            nodes,
+            time_ms,
+        } => vec![format!(
+            "info depth {depth} {}",
+            format_counters(*nodes, *time_ms)
+        )],
+    }
+}
+
+fn format_counters(nodes: u64, time_ms: u64) -> String {
+    let nps = nodes.saturating_mul(1_000) / time_ms.max(1);
+    format!("nodes {nodes} nps {nps} time {time_ms}")
+}
+
+fn format_search_info(board: &Board, info: &SearchInfo, variant: Variant) -> Vec<String> {
+    let format_score = |score: EngineScore| match score {
+        EngineScore::Centipawns(value) => format!("score cp {value}"),
+        EngineScore::Mate(moves) => format!("score mate {moves}"),
+    };
+    let format_pv = |moves: &[Move]| {
+        let mut position = board.clone();
+        let mut uci = Vec::with_capacity(moves.len());
+        for mv in moves {
+            if !position.is_legal(*mv) {
+                break;
+            }
+            uci.push(format_uci_move(&position, *mv, variant));
+            position.play_unchecked(*mv);
+        }
+        uci.join(" ")
+    };
+
+    let depth = format!("depth {} seldepth {}", info.depth, info.seldepth);
+    let counters = format_counters(info.nodes, info.time_ms);
+    match info.lines.as_slice() {
+        [] => vec![format!(
+            "info {depth} {} {counters}",
+            format_score(info.score)
+        )],
+        [line] => vec![format!(
+            "info {depth} {} {counters} pv {}",
+            format_score(line.score),
+            format_pv(&line.moves)
+        )],
+        lines => lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                format!(
+                    "info {depth} multipv {} {} {counters} pv {}",
+                    index + 1,
+                    format_score(line.score),
+                    format_pv(&line.moves)
+                )
+            })
+            .collect(),
+    }
 }
 
 pub fn legal_moves(board: &Board) -> Vec<Move> {
@@ -36,6 +313,28 @@ pub fn legal_moves(board: &Board) -> Vec<Move> {
     moves
 }
 
+/// Legal captures, en passant captures and promotions only, for quiescence search.
+pub fn capture_moves(board: &Board) -> Vec<Move> {
+    let side = board.side_to_move();
+    let enemy = board.colors(!side);
+    let mut pawn_targets = enemy | Rank::Eighth.relative_to(side).bitboard();
+    if let Some(file) = board.en_passant() {
+        pawn_targets |= Square::new(file, Rank::Sixth.relative_to(side)).bitboard();
+    }
+
+    let mut moves = Vec::new();
+    board.generate_moves(|mut piece_moves| {
+        piece_moves.to &= if piece_moves.piece == Piece::Pawn {
+            pawn_targets
+        } else {
+            enemy
+        };
+        moves.extend(piece_moves);
+        false
+    });
+    moves
+}
+
 pub fn material_score(board: &Board, color: Color) -> i32 {
     let mut score = 0;
     for piece in [
@@ -60,102 +359,460 @@ pub fn material_score(board: &Board, color: Color) -> i32 {
     score
 }
 
-pub fn run_uci_loop<E: UciEngine>(engine: &mut E) -> Result<()> {
-    let stdin = io::stdin();
-    let mut stdout = io::stdout();
+/// ASCII diagram of `board` for debugging: ranks 8 to 1 with white pieces
+/// in upper case and empty squares as `.`, the file letters, then the side
+/// to move, castling rights, en passant square and halfmove clock.
+pub fn format_board(board: &Board) -> String {
+    let mut diagram = String::new();
+    for &rank in Rank::ALL.iter().rev() {
+        diagram.push(char::from(rank));
+        for &file in &File::ALL {
+            let square = Square::new(file, rank);
+            let symbol = match (board.piece_on(square), board.color_on(square)) {
+                (Some(piece), Some(Color::White)) => char::from(piece).to_ascii_uppercase(),
+                (Some(piece), _) => char::from(piece),
+                _ => '.',
+            };
+            diagram.push(' ');
+            diagram.push(symbol);
+        }
+        diagram.push('\n');
+    }
+    diagram.push_str("  a b c d e f g h\n");
+
+    let fen = board.to_string();
+    let fields: Vec<&str> = fen.split_whitespace().collect();
+    diagram.push_str(&format!(
+        "side {} castling {} ep {} halfmove {}",
+        fields[1], fields[2], fields[3], fields[4]
+    ));
+    diagram
+}
+
+pub fn run_uci_loop<E: UciEngine + Send>(engine: &mut E) -> Result<()> {
+    run_uci_session(engine, io::stdin().lock(), io::stdout())
+}
+
+struct ActiveSearch<'scope> {
+    stop: StopSignal,
+    state: Arc<Mutex<SearchState>>,
+    handle: thread::ScopedJoinHandle<'scope, Result<()>>,
+}
+
+impl ActiveSearch<'_> {
+    fn finish(self) -> Result<()> {
+        self.stop.stop();
+        lock(&self.state).finish();
+        self.handle
+            .join()
+            .map_err(|_| anyhow!("search thread panicked"))?
+    }
+}
+
+/// Runs the UCI protocol over arbitrary streams. Searches run on a worker
+/// thread so `stop`, `ponderhit`, `isready` and `quit` are handled while the
+/// engine is thinking; any other command first ends the active search.
+fn run_uci_session<E, R, W>(engine: &mut E, input: R, output: W) -> Result<()>
+where
+    E: UciEngine + Send,
+    R: BufRead,
+    W: Write + Send,
+{
+    let engine = Mutex::new(engine);
+    let output = Mutex::new(output);
     let mut board = Board::default();
     let mut variant = Variant::Standard;
     let mut position_history_hashes = vec![board.hash()];
+    let mut multipv = 1;
 
-    for line in stdin.lock().lines() {
-        let line = line?;
-        let command = line.trim();
-        if command.is_empty() {
-            continue;
-        }
+    thread::scope(|scope| {
+        let mut active: Option<ActiveSearch<'_>> = None;
 
-        if command == "uci" {
-            writeln!(stdout, "id name {}", engine.name())?;
-            writeln!(stdout, "id author {}", engine.author())?;
-            writeln!(stdout, "option name UCI_Chess960 type check default false")?;
-            writeln!(stdout, "uciok")?;
-            stdout.flush()?;
-            continue;
-        }
+        for line in input.lines() {
+            let line = line?;
+            let command = line.trim();
+            if command.is_empty() {
+                continue;
+            }
 
-        if command == "isready" {
-            writeln!(stdout, "readyok")?;
-            stdout.flush()?;
-            continue;
-        }
+            if command == "isready" {
+                write_line(&output, "readyok")?;
+                continue;
+            }
 
-        if command == "ucinewgame" {
-            board = match variant {
-                Variant::Standard => Board::startpos(),
-                Variant::Chess960 => Board::chess960_startpos(0),
-            };
-            position_history_hashes = vec![board.hash()];
-            engine.new_game(variant);
-            continue;
-        }
+            if command == "stop" {
+                if let Some(search) = active.take() {
+                    search.finish()?;
+                }
+                continue;
+            }
 
-        if let Some(rest) = command.strip_prefix("setoption name UCI_Chess960 value ") {
-            variant = if rest.eq_ignore_ascii_case("true") {
-                Variant::Chess960
-            } else {
-                Variant::Standard
-            };
-            continue;
-        }
+            // The ponder search keeps going, now on the clock the `go ponder`
+            // carried, so everything it found so far still counts.
+            if command == "ponderhit" {
+                let budget_ms = active
+                    .as_ref()
+                    .and_then(|search| lock(&search.state).ponderhit());
+                if let (Some(search), Some(budget_ms)) = (&active, budget_ms) {
+                    let stop = search.stop.clone();
+                    let deadline = Instant::now() + Duration::from_millis(budget_ms);
+                    scope.spawn(move || {
+                        while !stop.is_stopped() && Instant::now() < deadline {
+                            thread::sleep(STOP_POLL_INTERVAL);
+                        }
+                        stop.stop();
+                    });
+                }
+                continue;
+            }
 
-        if let Some(rest) = command.strip_prefix("position ") {
-            let (next_board, next_history_hashes) = parse_position_command(rest, variant)?;
-            board = next_board;
-            position_history_hashes = next_history_hashes;
-            continue;
-        }
+            if let Some(search) = active.take() {
+                search.finish()?;
+            }
+
+            if command == "quit" {
+                break;
+            }
+
+            if command == "uci" {
+                let engine = lock(&engine);
+                write_line(&output, &format!("id name {}", engine.name()))?;
+                write_line(&output, &format!("id author {}", engine.author()))?;
+                for option in option_lines(&**engine) {
+                    write_line(&output, &option)?;
+                }
+                write_line(&output, "uciok")?;
+                continue;
+            }
+
+            if command == "ucinewgame" {
+                board = match variant {
+                    Variant::Standard => Board::startpos(),
+                    Variant::Chess960 => Board::chess960_startpos(0),
+                };
+                position_history_hashes = vec![board.hash()];
+                lock(&engine).new_game(variant);
+                continue;
+            }
+
+            // A value outside the declared range is reported and ignored, so
+            // engines only ever see settings they advertised.
+            if let Some((name, value)) = parse_setoption(command) {
+                let mut engine = lock(&engine);
+                let declared = options::find_option(&**engine, name);
+                if let Some(Err(err)) = declared.map(|option| option.validate(value)) {
+                    write_line(&output, &format!("info string {err}"))?;
+                } else if name.eq_ignore_ascii_case("UCI_Chess960") {
+                    variant = if value.eq_ignore_ascii_case("true") {
+                        Variant::Chess960
+                    } else {
+                        Variant::Standard
+                    };
+                } else if name.eq_ignore_ascii_case("MultiPV") {
+                    multipv = value.parse().unwrap_or(1);
+                } else if name.eq_ignore_ascii_case("Ponder") {
+                    // GUIs only send `go ponder` when this is on; nothing else changes.
+                } else if let Err(err) = engine.set_option(name, value) {
+                    write_line(&output, &format!("info string {err}"))?;
+                } else if declared.is_some() && name.eq_ignore_ascii_case("Clear Hash") {
+                    write_line(&output, "info string hash cleared")?;
+                }
+                continue;
+            }
+
+            // A bad position is reported and ignored rather than ending the
+            // session, so one corrupt move from the GUI costs one search at most.
+            if let Some(rest) = command.strip_prefix("position ") {
+                match parse_position_command(rest, variant) {
+                    Ok((next_board, next_history_hashes)) => {
+                        board = next_board;
+                        position_history_hashes = next_history_hashes;
+                    }
+                    Err(err) => write_line(&output, &format!("info string {err}"))?,
+                }
+                continue;
+            }
+
+            // Nonstandard, as in Stockfish: print the board and its FEN.
+            if command == "d" {
+                for line in format_board(&board).lines() {
+                    write_line(&output, line)?;
+                }
+                write_line(&output, &format!("fen {board}"))?;
+                continue;
+            }
+
+            // Nonstandard, as in Stockfish: `bench [depth]` searches a fixed
+            // set of positions and prints the node total as a signature.
+            if command == "bench" || command.starts_with("bench ") {
+                let depth = command
+                    .split_whitespace()
+                    .nth(1)
+                    .and_then(|depth| depth.parse().ok())
+                    .unwrap_or(DEFAULT_BENCH_DEPTH);
+                match bench(&mut **lock(&engine), depth) {
+                    Ok(positions) => {
+                        for line in format_bench(&positions).lines() {
+                            write_line(&output, line)?;
+                        }
+                    }
+                    Err(err) => write_line(&output, &format!("info string {err}"))?,
+                }
+                continue;
+            }
 
-        if let Some(rest) = command.strip_prefix("go") {
-            let movetime_ms = parse_movetime(rest).unwrap_or(250);
-            let legal = legal_moves(&board);
-            if legal.is_empty() {
-                writeln!(stdout, "bestmove 0000")?;
-                stdout.flush()?;
+            // Nonstandard, as in Stockfish: print the perft divide table.
+            if let Some(rest) = command.strip_prefix("go perft ") {
+                if let Ok(depth) = rest.trim().parse::<u32>() {
+                    let table = format_divide(&board, &perft_divide(&board, depth));
+                    for line in table.lines() {
+                        write_line(&output, line)?;
+                    }
+                }
                 continue;
             }
 
-            let mv = engine.choose_move(
-                &board,
-                &legal,
-                SearchContext {
-                    movetime_ms,
+            if let Some(rest) = command.strip_prefix("go") {
+                let go = parse_go_command(rest);
+                let legal = legal_moves(&board);
+                if legal.is_empty() {
+                    write_line(&output, "bestmove 0000")?;
+                    continue;
+                }
+
+                let stop = StopSignal::default();
+                let state = Arc::new(Mutex::new(SearchState::go(&go, board.side_to_move())));
+                let (reporter, reports) = SearchReporter::channel();
+                let ctx = SearchContext {
+                    movetime_ms: move_budget_ms(&go, board.side_to_move()),
+                    max_depth: go.depth,
+                    max_nodes: go.nodes,
+                    multipv,
+                    clock: managed_clock(&go, board.side_to_move()),
                     variant,
                     position_history_hashes: position_history_hashes.clone(),
-                },
-            )?;
-            if !board.is_legal(mv) {
-                bail!("engine selected illegal move: {:?}", mv);
+                    stop: stop.clone(),
+                    reporter,
+                };
+                let board = board.clone();
+                let (engine, output) = (&engine, &output);
+                let search_state = state.clone();
+                let handle = scope.spawn(move || {
+                    let wait_for_stop = ctx.stop.clone();
+                    let report_board = board.clone();
+                    let printer = scope.spawn(move || -> Result<()> {
+                        for report in reports {
+                            for line in format_search_report(&report_board, &report, variant) {
+                                write_line(output, &line)?;
+                            }
+                        }
+                        Ok(())
+                    });
+                    let (mv, info) = {
+                        let mut engine = lock(engine);
+                        let mv = engine.choose_move(&board, &legal, ctx)?;
+                        (mv, engine.search_info())
+                    };
+                    // `ctx` and its reporter are gone, so the printer drains and exits.
+                    printer
+                        .join()
+                        .map_err(|_| anyhow!("search report printer panicked"))??;
+                    if !board.is_legal(mv) {
+                        bail!("engine selected illegal move: {:?}", mv);
+                    }
+                    if let Some(info) = &info {
+                        for line in format_search_info(&board, info, variant) {
+                            write_line(output, &line)?;
+                        }
+                    }
+                    // UCI forbids reporting before `stop` in infinite/ponder mode.
+                    while (go.infinite || lock(&search_state).is_pondering())
+                        && !wait_for_stop.is_stopped()
+                    {
+                        thread::sleep(STOP_POLL_INTERVAL);
+                    }
+                    let mut bestmove = format!("bestmove {}", format_uci_move(&board, mv, variant));
+                    if let Some(reply) = ponder_move(&board, mv, info.as_ref()) {
+                        let mut after = board.clone();
+                        after.play_unchecked(mv);
+                        bestmove.push_str(&format!(
+                            " ponder {}",
+                            format_uci_move(&after, reply, variant)
+                        ));
+                    }
+                    lock(&search_state).finish();
+                    write_line(output, &bestmove)
+                });
+                active = Some(ActiveSearch {
+                    stop,
+                    state,
+                    handle,
+                });
+                continue;
             }
+        }
+
+        if let Some(search) = active.take() {
+            search.finish()?;
+        }
+        Ok(())
+    })
+}
+
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(5);
 
-            writeln!(stdout, "bestmove {}", format_uci_move(&board, mv, variant))?;
-            stdout.flush()?;
-            continue;
+/// Where the session's search stands. `go ponder` searches the position
+/// after the predicted reply until `ponderhit` turns it into a normal search
+/// on the clock that `go` carried; `stop` or any other command ends it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchState {
+    Idle,
+    Pondering { budget_ms: u64 },
+    Searching,
+}
+
+impl SearchState {
+    fn go(go: &GoCommand, side: Color) -> Self {
+        if go.ponder {
+            let timed = GoCommand {
+                ponder: false,
+                ..*go
+            };
+            Self::Pondering {
+                budget_ms: move_budget_ms(&timed, side),
+            }
+        } else {
+            Self::Searching
         }
+    }
 
-        if command == "quit" {
-            break;
+    /// The budget the search gets from now on, when it was pondering.
+    fn ponderhit(&mut self) -> Option<u64> {
+        match *self {
+            Self::Pondering { budget_ms } => {
+                *self = Self::Searching;
+                Some(budget_ms)
+            }
+            Self::Idle | Self::Searching => None,
         }
     }
 
+    fn finish(&mut self) {
+        *self = Self::Idle;
+    }
+
+    fn is_pondering(self) -> bool {
+        matches!(self, Self::Pondering { .. })
+    }
+}
+
+/// The reply to ponder on: the second move of the principal variation, when
+/// the search reported one that starts with `mv`.
+fn ponder_move(board: &Board, mv: Move, info: Option<&SearchInfo>) -> Option<Move> {
+    let line = info?.lines.first()?;
+    if line.moves.first() != Some(&mv) {
+        return None;
+    }
+    let reply = *line.moves.get(1)?;
+    let mut after = board.clone();
+    after.play_unchecked(mv);
+    after.is_legal(reply).then_some(reply)
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn write_line<W: Write>(output: &Mutex<W>, line: &str) -> Result<()> {
+    let mut output = lock(output);
+    writeln!(output, "{line}")?;
+    output.flush()?;
     Ok(())
 }
 
-fn parse_movetime(command: &str) -> Option<u64> {
+const DEFAULT_MOVETIME_MS: u64 = 250;
+const LOW_CLOCK_MS: u64 = 100;
+const DEFAULT_MOVES_TO_GO: u64 = 30;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct GoCommand {
+    movetime_ms: Option<u64>,
+    clock: SearchClock,
+    depth: Option<u32>,
+    nodes: Option<u64>,
+    /// Set for `go infinite`: search until `stop`.
+    infinite: bool,
+    /// Set for `go ponder`: search until `stop`, or until `ponderhit` and
+    /// then on the clock.
+    ponder: bool,
+}
+
+fn parse_go_command(command: &str) -> GoCommand {
     let tokens: Vec<_> = command.split_whitespace().collect();
-    tokens
-        .windows(2)
-        .find(|window| window[0] == "movetime")
-        .and_then(|window| window[1].parse::<u64>().ok())
+    let value = |name: &str| {
+        tokens
+            .windows(2)
+            .find(|window| window[0] == name)
+            .and_then(|window| window[1].parse::<i64>().ok())
+            // GUIs may report a negative clock once a side has overstepped it.
+            .map(|value| value.max(0) as u64)
+    };
+
+    GoCommand {
+        movetime_ms: value("movetime"),
+        clock: SearchClock {
+            wtime_ms: value("wtime"),
+            btime_ms: value("btime"),
+            winc_ms: value("winc").unwrap_or_default(),
+            binc_ms: value("binc").unwrap_or_default(),
+            movestogo: value("movestogo"),
+        },
+        depth: value("depth").map(|depth| depth.min(u64::from(u32::MAX)) as u32),
+        nodes: value("nodes"),
+        infinite: tokens.contains(&"infinite"),
+        ponder: tokens.contains(&"ponder"),
+    }
+}
+
+/// Per-move search budget: an explicit movetime wins, otherwise the clock's
+/// [`SearchClock::allocate_move_time_ms`].
+fn move_budget_ms(go: &GoCommand, side: Color) -> u64 {
+    if go.infinite || go.ponder {
+        return INFINITE_MOVETIME_MS;
+    }
+    if let Some(movetime_ms) = go.movetime_ms {
+        return movetime_ms;
+    }
+
+    go.clock.allocate_move_time_ms(side).unwrap_or(
+        // A bare `go depth` or `go nodes` is bounded by that, not time.
+        if go.depth.is_some() || go.nodes.is_some() {
+            INFINITE_MOVETIME_MS
+        } else {
+            DEFAULT_MOVETIME_MS
+        },
+    )
+}
+
+/// The clock behind [`move_budget_ms`], when the budget came from one.
+fn managed_clock(go: &GoCommand, side: Color) -> Option<SearchClock> {
+    let from_clock = !go.infinite
+        && !go.ponder
+        && go.movetime_ms.is_none()
+        && go.clock.allocate_move_time_ms(side).is_some();
+    from_clock.then_some(go.clock)
+}
+
+/// Name and value of `setoption name <name> value <value>`; names may contain
+/// spaces, and a button option without a value gets an empty one.
+fn parse_setoption(command: &str) -> Option<(&str, &str)> {
+    let rest = command.strip_prefix("setoption name ")?;
+    Some(match rest.split_once(" value ") {
+        Some((name, value)) => (name.trim(), value.trim()),
+        None => (rest.trim(), ""),
+    })
 }
 
 fn parse_position_command(command: &str, variant: Variant) -> Result<(Board, Vec<u64>)> {
@@ -175,8 +832,7 @@ fn parse_position_command(command: &str, variant: Variant) -> Result<(Board, Vec
             .position(|part| *part == "moves")
             .unwrap_or(parts.len());
         let fen = parts[1..moves_index].join(" ");
-        Board::from_fen(&fen, variant.is_chess960())
-            .map_err(|err| anyhow!("invalid position FEN: {err}"))?
+        board_from_fen(&fen, variant).map_err(|err| anyhow!("invalid position FEN: {err}"))?
     } else {
         bail!("unsupported position command: {command}");
     };
@@ -186,9 +842,13 @@ fn parse_position_command(command: &str, variant: Variant) -> Result<(Board, Vec
         for mv in &parts[moves_index + 1..] {
             let parsed = util::parse_uci_move(&board, mv)
                 .map_err(|err| anyhow!("invalid UCI move: {err}"))?;
-            board
-                .try_play(parsed)
-                .map_err(|err| anyhow!("illegal move in position command: {err}"))?;
+            if !board.is_legal(parsed) {
+                bail!(
+                    "illegal move {mv} in position command: {}",
+                    illegal_move_reason(&board, parsed)
+                );
+            }
+            board.play_unchecked(parsed);
             history_hashes.push(board.hash());
         }
     }
@@ -196,6 +856,43 @@ fn parse_position_command(command: &str, variant: Variant) -> Result<(Board, Vec
     Ok((board, history_hashes))
 }
 
+/// Why `mv` cannot be played, for errors about moves from outside the engine.
+fn illegal_move_reason(board: &Board, mv: Move) -> String {
+    let side = board.side_to_move();
+    match board.color_on(mv.from) {
+        None => format!("no piece on {}", mv.from),
+        Some(color) if color != side => format!(
+            "{} holds a {color:?} piece but {side:?} is to move",
+            mv.from
+        ),
+        Some(_)
+            if board.piece_on(mv.from) == Some(Piece::King) && board.colors(side).has(mv.to) =>
+        {
+            "castling is not available".to_string()
+        }
+        // The squares alone make a legal move, so only the suffix is wrong.
+        Some(_)
+            if mv.promotion.is_some()
+                && board.is_legal(Move {
+                    promotion: None,
+                    ..mv
+                }) =>
+        {
+            format!("{}{} is not a promotion", mv.from, mv.to)
+        }
+        Some(_)
+            if mv.promotion.is_none()
+                && board.is_legal(Move {
+                    promotion: Some(Piece::Queen),
+                    ..mv
+                }) =>
+        {
+            format!("{}{} needs a promotion piece", mv.from, mv.to)
+        }
+        Some(_) => format!("{} cannot move to {}", mv.from, mv.to),
+    }
+}
+
 fn format_uci_move(board: &Board, mv: Move, variant: Variant) -> String {
     if variant.is_chess960() {
         mv.to_string()
@@ -206,8 +903,255 @@ fn format_uci_move(board: &Board, mv: Move, variant: Variant) -> String {
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        io::BufReader,
+        sync::mpsc,
+        time::{Duration, Instant},
+    };
+
     use super::*;
 
+    /// Thinks until told to stop, like an infinite analysis search.
+    struct UntilStoppedEngine;
+
+    impl UciEngine for UntilStoppedEngine {
+        fn name(&self) -> &'static str {
+            "until-stopped"
+        }
+
+        fn choose_move(
+            &mut self,
+            _board: &Board,
+            legal_moves: &[Move],
+            ctx: SearchContext,
+        ) -> Result<Move> {
+            let deadline = Instant::now() + Duration::from_millis(ctx.movetime_ms);
+            while !ctx.stop.is_stopped() && Instant::now() < deadline {
+                thread::sleep(Duration::from_millis(1));
+            }
+            Ok(legal_moves[0])
+        }
+    }
+
+    struct LineSender(mpsc::Sender<String>, Vec<u8>);
+
+    impl Write for LineSender {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.1.extend_from_slice(buf);
+            while let Some(end) = self.1.iter().position(|byte| *byte == b'\n') {
+                let line: Vec<u8> = self.1.drain(..=end).collect();
+                let _ = self
+                    .0
+                    .send(String::from_utf8_lossy(&line).trim().to_string());
+            }
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn spawn_session() -> (
+        io::PipeWriter,
+        mpsc::Receiver<String>,
+        thread::JoinHandle<Result<()>>,
+    ) {
+        let (reader, writer) = io::pipe().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        let session = thread::spawn(move || {
+            run_uci_session(
+                &mut UntilStoppedEngine,
+                BufReader::new(reader),
+                LineSender(sender, Vec::new()),
+            )
+        });
+        (writer, receiver, session)
+    }
+
+    #[test]
+    fn engine_score_reports_mates_in_moves() {
+        assert_eq!(engine_score(35, 30_000), EngineScore::Centipawns(35));
+        assert_eq!(engine_score(-900, 30_000), EngineScore::Centipawns(-900));
+        assert_eq!(engine_score(30_000 - 1, 30_000), EngineScore::Mate(1));
+        assert_eq!(engine_score(30_000 - 3, 30_000), EngineScore::Mate(2));
+        assert_eq!(engine_score(-30_000 + 2, 30_000), EngineScore::Mate(-1));
+        assert_eq!(
+            format_search_info(
+                &Board::default(),
+                &SearchInfo {
+                    depth: 7,
+                    seldepth: 11,
+                    score: EngineScore::Mate(-3),
+                    nodes: 4_096,
+                    time_ms: 0,
+                    lines: Vec::new(),
+                },
+                Variant::Standard,
+            ),
+            vec!["info depth 7 seldepth 11 score mate -3 nodes 4096 nps 4096000 time 0"]
+        );
+    }
+
+    #[test]
+    fn multiple_lines_are_reported_as_multipv() {
+        let board = Board::default();
+        let line = |uci: &str, cp| PvLine {
+            score: EngineScore::Centipawns(cp),
+            moves: vec![util::parse_uci_move(&board, uci).unwrap()],
+        };
+        let info = SearchInfo {
+            depth: 5,
+            seldepth: 8,
+            score: EngineScore::Centipawns(30),
+            nodes: 900,
+            time_ms: 3,
+            lines: vec![line("e2e4", 30), line("d2d4", 25)],
+        };
+
+        assert_eq!(
+            format_search_info(&board, &info, Variant::Standard),
+            vec![
+                "info depth 5 seldepth 8 multipv 1 score cp 30 nodes 900 nps 300000 time 3 pv e2e4",
+                "info depth 5 seldepth 8 multipv 2 score cp 25 nodes 900 nps 300000 time 3 pv d2d4",
+            ]
+        );
+    }
+
+    #[test]
+    fn progress_reports_nps_from_nodes_and_time() {
+        let report = SearchReport::Progress {
+            depth: 9,
+            nodes: 1_234_567,
+            time_ms: 1_500,
+        };
+
+        assert_eq!(
+            format_search_report(&Board::default(), &report, Variant::Standard),
+            vec!["info depth 9 nodes 1234567 nps 823044 time 1500"]
+        );
+    }
+
+    #[test]
+    fn mate_scores_are_stored_relative_to_the_node() {
+        // Mate in 3 plies from the root, found at a node 2 plies deep.
+        let root_score = 30_000 - 3;
+        let stored = score_to_tt(root_score, 2, 30_000);
+        assert_eq!(mate_distance(stored, 30_000), Some(1));
+        // Reached again through a transposition 4 plies deep.
+        assert_eq!(score_from_tt(stored, 4, 30_000), 30_000 - 5);
+
+        let mated = -30_000 + 6;
+        assert_eq!(
+            score_from_tt(score_to_tt(mated, 3, 30_000), 3, 30_000),
+            mated
+        );
+        assert_eq!(mate_distance(mated, 30_000), Some(-6));
+        assert_eq!(score_to_tt(120, 9, 30_000), 120);
+        assert!(!is_mate_score(-2_500, 30_000));
+    }
+
+    #[test]
+    fn board_diagram_shows_the_pieces_and_position_state() {
+        assert_eq!(
+            format_board(&Board::default()),
+            "8 r n b q k b n r\n\
+             7 p p p p p p p p\n\
+             6 . . . . . . . .\n\
+             5 . . . . . . . .\n\
+             4 . . . . . . . .\n\
+             3 . . . . . . . .\n\
+             2 P P P P P P P P\n\
+             1 R N B Q K B N R\n  \
+             a b c d e f g h\n\
+             side w castling KQkq ep - halfmove 0"
+        );
+
+        let kiwipete: Board =
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+                .parse()
+                .unwrap();
+        let diagram = format_board(&kiwipete);
+        let lines: Vec<&str> = diagram.lines().collect();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[0], "8 r . . . k . . r");
+        assert_eq!(lines[3], "5 . . . P N . . .");
+        assert_eq!(lines[5], "3 . . N . . Q . p");
+        assert_eq!(lines[7], "1 R . . . K . . R");
+
+        let en_passant: Board = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2".parse().unwrap();
+        assert!(format_board(&en_passant).ends_with("side w castling - ep d6 halfmove 0"));
+    }
+
+    #[test]
+    fn setoption_splits_the_name_from_the_value() {
+        assert_eq!(
+            parse_setoption("setoption name NullMove value false"),
+            Some(("NullMove", "false"))
+        );
+        assert_eq!(
+            parse_setoption("setoption name Clear Hash"),
+            Some(("Clear Hash", ""))
+        );
+        assert_eq!(parse_setoption("position startpos"), None);
+    }
+
+    #[test]
+    fn position_moves_track_promotions_en_passant_and_castling() {
+        let fen_after = |command: &str| {
+            parse_position_command(command, Variant::Standard)
+                .map(|(board, _)| board.to_string())
+                .map_err(|err| err.to_string())
+        };
+
+        assert_eq!(
+            fen_after("startpos moves e2e4 a7a6 e4e5 d7d5 e5d6").unwrap(),
+            "rnbqkbnr/1pp1pppp/p2P4/8/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 3"
+        );
+        assert_eq!(
+            fen_after("fen r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1 moves e1g1 e8c8").unwrap(),
+            "2kr3r/8/8/8/8/8/8/R4RK1 w - - 2 2"
+        );
+        assert_eq!(
+            fen_after("fen 1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1 moves a7a8n").unwrap(),
+            "Nr2k3/8/8/8/8/8/8/4K3 b - - 0 1"
+        );
+        assert_eq!(
+            fen_after("fen 1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1 moves a7b8q").unwrap(),
+            "1Q2k3/8/8/8/8/8/8/4K3 b - - 0 1"
+        );
+
+        for (command, error) in [
+            (
+                "fen 1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1 moves a7a8",
+                "a7a8 needs a promotion piece",
+            ),
+            ("startpos moves e2e4q", "e2e4 is not a promotion"),
+            (
+                "startpos moves e2e4 a7a6 e4e5 f7f5 e5d6",
+                "e5 cannot move to d6",
+            ),
+        ] {
+            let err = fen_after(command).unwrap_err();
+            assert!(err.contains(error), "{command}: {err}");
+        }
+    }
+
+    #[test]
+    fn chess960_positions_accept_x_fen_castling_rights() {
+        let x_fen = "fen bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w KQkq - 2 9";
+        let shredder = "fen bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9";
+
+        let (board, _) = parse_position_command(x_fen, Variant::Chess960).unwrap();
+        assert_eq!(
+            board,
+            parse_position_command(shredder, Variant::Chess960)
+                .unwrap()
+                .0
+        );
+        assert!(parse_position_command(x_fen, Variant::Standard).is_err());
+    }
+
     #[test]
     fn standard_castling_uses_standard_uci_king_destination() {
         let board: Board = "rnbqkb1r/ppp2ppp/4pn2/3p4/8/5NP1/PPPPPPBP/RNBQK2R w KQkq - 0 4"
@@ -231,4 +1175,356 @@ mod tests {
         assert_eq!(util::display_uci_move(&board, castle).to_string(), "g1g1");
         assert_eq!(format_uci_move(&board, castle, Variant::Chess960), "g1h1");
     }
+
+    #[test]
+    fn stop_ends_infinite_search_with_bestmove() {
+        let (mut input, output, session) = spawn_session();
+        writeln!(input, "position startpos\ngo infinite").unwrap();
+
+        assert!(output.recv_timeout(Duration::from_millis(100)).is_err());
+        writeln!(input, "isready").unwrap();
+        assert_eq!(
+            output.recv_timeout(Duration::from_secs(1)).unwrap(),
+            "readyok"
+        );
+
+        writeln!(input, "stop").unwrap();
+        let bestmove = output.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(bestmove.starts_with("bestmove "), "{bestmove}");
+
+        writeln!(input, "quit").unwrap();
+        session.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn search_state_moves_from_pondering_to_a_timed_search_on_ponderhit() {
+        let go = parse_go_command(" ponder wtime 3000 btime 3000");
+        let mut state = SearchState::go(&go, Color::White);
+        assert_eq!(state, SearchState::Pondering { budget_ms: 100 });
+        assert!(state.is_pondering());
+        assert_eq!(move_budget_ms(&go, Color::White), INFINITE_MOVETIME_MS);
+        assert_eq!(managed_clock(&go, Color::White), None);
+
+        assert_eq!(state.ponderhit(), Some(100));
+        assert_eq!(state, SearchState::Searching);
+        assert_eq!(state.ponderhit(), None);
+        state.finish();
+        assert_eq!(state, SearchState::Idle);
+        assert_eq!(state.ponderhit(), None);
+
+        let mut state = SearchState::go(&parse_go_command(" movetime 50"), Color::White);
+        assert_eq!(state, SearchState::Searching);
+        assert_eq!(state.ponderhit(), None);
+    }
+
+    #[test]
+    fn ponder_move_is_the_legal_second_move_of_the_best_line() {
+        let board = Board::default();
+        let mv: Move = "e2e4".parse().unwrap();
+        let info = |moves: &[&str]| SearchInfo {
+            depth: 2,
+            seldepth: 2,
+            score: EngineScore::Centipawns(20),
+            nodes: 40,
+            time_ms: 1,
+            lines: vec![PvLine {
+                score: EngineScore::Centipawns(20),
+                moves: moves.iter().map(|mv| mv.parse().unwrap()).collect(),
+            }],
+        };
+
+        assert_eq!(
+            ponder_move(&board, mv, Some(&info(&["e2e4", "e7e5"]))),
+            Some("e7e5".parse().unwrap())
+        );
+        assert_eq!(ponder_move(&board, mv, Some(&info(&["e2e4"]))), None);
+        assert_eq!(
+            ponder_move(&board, mv, Some(&info(&["d2d4", "d7d5"]))),
+            None
+        );
+        assert_eq!(
+            ponder_move(&board, mv, Some(&info(&["e2e4", "e2e4"]))),
+            None
+        );
+        assert_eq!(ponder_move(&board, mv, None), None);
+    }
+
+    #[test]
+    fn ponderhit_turns_the_ponder_search_into_a_timed_one() {
+        let (mut input, output, session) = spawn_session();
+        writeln!(input, "uci").unwrap();
+        let options: Vec<String> = (0..6)
+            .map(|_| output.recv_timeout(Duration::from_secs(1)).unwrap())
+            .collect();
+        assert!(options.contains(&"option name Ponder type check default false".to_string()));
+
+        writeln!(
+            input,
+            "setoption name Ponder value true\nposition startpos moves e2e4 e7e5\ngo ponder wtime 3000 btime 3000"
+        )
+        .unwrap();
+        // The 100ms budget only starts on ponderhit.
+        assert!(output.recv_timeout(Duration::from_millis(300)).is_err());
+
+        let hit = Instant::now();
+        writeln!(input, "ponderhit").unwrap();
+        let bestmove = output.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(bestmove.starts_with("bestmove "), "{bestmove}");
+        assert!(
+            hit.elapsed() >= Duration::from_millis(90),
+            "{:?}",
+            hit.elapsed()
+        );
+
+        // A missed prediction: the GUI stops the ponder search and sends the
+        // real position.
+        writeln!(
+            input,
+            "position startpos moves e2e4 e7e5 g1f3 b8c6\ngo ponder wtime 3000 btime 3000"
+        )
+        .unwrap();
+        writeln!(input, "stop").unwrap();
+        let discarded = output.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(discarded.starts_with("bestmove "), "{discarded}");
+        writeln!(
+            input,
+            "position startpos moves e2e4 e7e5 g1f3 g8f6\ngo movetime 20"
+        )
+        .unwrap();
+        let bestmove = output.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(bestmove.starts_with("bestmove "), "{bestmove}");
+
+        writeln!(input, "quit").unwrap();
+        session.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn go_perft_prints_the_divide_table() {
+        let (mut input, output, session) = spawn_session();
+        writeln!(input, "position startpos moves e2e4\ngo perft 1").unwrap();
+        let lines: Vec<String> = (0..22)
+            .map(|_| output.recv_timeout(Duration::from_secs(1)).unwrap())
+            .collect();
+
+        assert_eq!(lines[0], "a7a5: 1");
+        assert_eq!(lines[20], "");
+        assert_eq!(lines[21], "Nodes searched: 20");
+
+        writeln!(input, "quit").unwrap();
+        session.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn bogus_position_moves_are_reported_and_keep_the_previous_position() {
+        let (mut input, output, session) = spawn_session();
+        writeln!(input, "position startpos moves e2e4").unwrap();
+        for (moves, error) in [
+            ("e3e4", "no piece on e3"),
+            ("e2e4 d2d4", "d2 holds a White piece but Black is to move"),
+            ("e1g1", "castling is not available"),
+            ("e2e4 e7e6 e1e3", "e1 cannot move to e3"),
+        ] {
+            writeln!(input, "position startpos moves {moves}").unwrap();
+            let line = output.recv_timeout(Duration::from_secs(1)).unwrap();
+            assert!(
+                line.starts_with("info string") && line.contains(error),
+                "{line}"
+            );
+        }
+        writeln!(input, "d").unwrap();
+        let fen = (0..12)
+            .map(|_| output.recv_timeout(Duration::from_secs(1)).unwrap())
+            .find(|line| line.starts_with("fen "))
+            .unwrap();
+
+        assert_eq!(
+            fen,
+            "fen rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+        writeln!(input, "quit").unwrap();
+        session.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn uci_lists_the_builtin_options_and_setoption_checks_their_ranges() {
+        let (mut input, output, session) = spawn_session();
+        writeln!(input, "uci").unwrap();
+        let reply: Vec<String> = (0..6)
+            .map(|_| output.recv_timeout(Duration::from_secs(1)).unwrap())
+            .collect();
+        assert_eq!(
+            reply[2..],
+            [
+                "option name UCI_Chess960 type check default false",
+                "option name MultiPV type spin default 1 min 1 max 32",
+                "option name Ponder type check default false",
+                "uciok",
+            ]
+        );
+
+        for command in [
+            "setoption name MultiPV value 99",
+            "setoption name uci_chess960 value yes",
+        ] {
+            writeln!(input, "{command}").unwrap();
+            let line = output.recv_timeout(Duration::from_secs(1)).unwrap();
+            assert!(line.starts_with("info string"), "{command}: {line}");
+        }
+        writeln!(input, "setoption name MultiPV value 3\nisready").unwrap();
+        assert_eq!(
+            output.recv_timeout(Duration::from_secs(1)).unwrap(),
+            "readyok"
+        );
+        writeln!(input, "quit").unwrap();
+        session.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn quit_aborts_active_search() {
+        let (mut input, _output, session) = spawn_session();
+        writeln!(input, "position startpos\ngo infinite").unwrap();
+        thread::sleep(Duration::from_millis(20));
+        let started = Instant::now();
+        writeln!(input, "quit").unwrap();
+
+        session.join().unwrap().unwrap();
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn go_movetime_takes_precedence_over_clock() {
+        let go = parse_go_command(" wtime 60000 btime 60000 movetime 700");
+
+        assert_eq!(move_budget_ms(&go, Color::White), 700);
+    }
+
+    #[test]
+    fn go_depth_without_a_clock_searches_until_the_depth_is_reached() {
+        let go = parse_go_command(" depth 6");
+        assert_eq!(go.depth, Some(6));
+        assert_eq!(move_budget_ms(&go, Color::White), INFINITE_MOVETIME_MS);
+
+        let go = parse_go_command(" depth 6 movetime 300");
+        assert_eq!(move_budget_ms(&go, Color::Black), 300);
+    }
+
+    #[test]
+    fn go_nodes_without_a_clock_searches_until_the_node_budget_is_spent() {
+        let go = parse_go_command(" nodes 10000");
+        assert_eq!(go.nodes, Some(10_000));
+        assert_eq!(move_budget_ms(&go, Color::White), INFINITE_MOVETIME_MS);
+    }
+
+    #[test]
+    fn go_clock_budget_uses_side_to_move_clock_and_increment() {
+        let go = parse_go_command(" wtime 60000 btime 30000 winc 1000 binc 500");
+
+        assert_eq!(move_budget_ms(&go, Color::White), 3_000);
+        assert_eq!(move_budget_ms(&go, Color::Black), 1_500);
+    }
+
+    #[test]
+    fn go_clock_budget_respects_movestogo_and_caps_at_half_the_clock() {
+        let go = parse_go_command(" wtime 10000 btime 10000 movestogo 1");
+        assert_eq!(move_budget_ms(&go, Color::White), 5_000);
+
+        let go = parse_go_command(" wtime 2000 btime 2000 winc 5000 binc 5000");
+        assert_eq!(move_budget_ms(&go, Color::Black), 1_000);
+    }
+
+    #[test]
+    fn clock_allocation_covers_sudden_death_increments_and_empty_clocks() {
+        let clock = |wtime_ms, winc_ms, movestogo| SearchClock {
+            wtime_ms,
+            btime_ms: None,
+            winc_ms,
+            binc_ms: 0,
+            movestogo,
+        };
+
+        for (clock, expected) in [
+            (clock(Some(300_000), 0, None), Some(10_000)),
+            (clock(Some(300_000), 2_000, None), Some(12_000)),
+            (clock(Some(300_000), 0, Some(10)), Some(30_000)),
+            (clock(Some(300_000), 0, Some(0)), Some(150_000)),
+            (clock(Some(1_000), 3_000, None), Some(500)),
+            (clock(Some(0), 3_000, None), Some(1)),
+            (clock(Some(99), 0, None), Some(24)),
+            (clock(None, 1_000, None), None),
+        ] {
+            assert_eq!(
+                clock.allocate_move_time_ms(Color::White),
+                expected,
+                "{clock:?}"
+            );
+        }
+        assert_eq!(
+            clock(Some(60_000), 0, None).allocate_move_time_ms(Color::Black),
+            None
+        );
+    }
+
+    #[test]
+    fn only_clock_budgets_hand_the_clock_to_the_engine() {
+        let clock = managed_clock(
+            &parse_go_command(" wtime 60000 btime 30000 binc 500"),
+            Color::Black,
+        );
+        assert_eq!(
+            clock,
+            Some(SearchClock {
+                wtime_ms: Some(60_000),
+                btime_ms: Some(30_000),
+                winc_ms: 0,
+                binc_ms: 500,
+                movestogo: None,
+            })
+        );
+
+        for command in [
+            " wtime 60000 btime 60000 movetime 700",
+            " wtime 60000 infinite",
+            " depth 6",
+            "",
+        ] {
+            assert_eq!(
+                managed_clock(&parse_go_command(command), Color::White),
+                None,
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn go_clock_budget_moves_quickly_on_a_nearly_empty_clock() {
+        let go = parse_go_command(" wtime 80 btime -20 winc 0 binc 0");
+
+        assert_eq!(move_budget_ms(&go, Color::White), 20);
+        assert_eq!(move_budget_ms(&go, Color::Black), 1);
+        assert_eq!(move_budget_ms(&parse_go_command(""), Color::White), 250);
+    }
+
+    #[test]
+    fn capture_moves_include_en_passant_and_promotions_only() {
+        let board: Board = "4k3/1P6/8/3pP3/8/n7/8/R3K3 w - d6 0 2".parse().unwrap();
+        let mut captures: Vec<_> = capture_moves(&board)
+            .into_iter()
+            .map(|mv| mv.to_string())
+            .collect();
+        captures.sort();
+
+        assert_eq!(
+            captures,
+            ["a1a3", "b7b8b", "b7b8n", "b7b8q", "b7b8r", "e5d6"]
+        );
+    }
+
+    #[test]
+    fn position_history_repeats_the_start_hash_after_a_round_trip() {
+        let (board, history_hashes) =
+            parse_position_command("startpos moves g1f3 g8f6 f3g1 f6g8", Variant::Standard)
+                .unwrap();
+        assert_eq!(board.hash(), Board::startpos().hash());
+        assert_eq!(history_hashes.first(), history_hashes.last());
+    }
 }
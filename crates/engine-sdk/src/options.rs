@@ -0,0 +1,199 @@
+//! UCI options: what an engine declares, how it is advertised in reply to
+//! `uci`, and which `setoption` values it accepts.
+
+use std::fmt;
+
+use crate::{MAX_MULTIPV, UciEngine};
+
+/// Options the UCI loop handles itself, advertised before the engine's own.
+pub(crate) const BUILTIN_OPTIONS: &[UciOption] = &[
+    UciOption::check("UCI_Chess960", false),
+    UciOption::spin("MultiPV", 1, 1, MAX_MULTIPV as i64),
+    UciOption::check("Ponder", false),
+];
+
+/// One option as advertised by `option name <name> type <kind> ...`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UciOption {
+    pub name: &'static str,
+    pub kind: UciOptionKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UciOptionKind {
+    Check {
+        default: bool,
+    },
+    Spin {
+        default: i64,
+        min: i64,
+        max: i64,
+    },
+    Combo {
+        default: &'static str,
+        vars: &'static [&'static str],
+    },
+    String {
+        default: &'static str,
+    },
+    Button,
+}
+
+impl UciOption {
+    pub const fn check(name: &'static str, default: bool) -> Self {
+        Self {
+            name,
+            kind: UciOptionKind::Check { default },
+        }
+    }
+
+    pub const fn spin(name: &'static str, default: i64, min: i64, max: i64) -> Self {
+        Self {
+            name,
+            kind: UciOptionKind::Spin { default, min, max },
+        }
+    }
+
+    pub const fn combo(
+        name: &'static str,
+        default: &'static str,
+        vars: &'static [&'static str],
+    ) -> Self {
+        Self {
+            name,
+            kind: UciOptionKind::Combo { default, vars },
+        }
+    }
+
+    pub const fn string(name: &'static str, default: &'static str) -> Self {
+        Self {
+            name,
+            kind: UciOptionKind::String { default },
+        }
+    }
+
+    pub const fn button(name: &'static str) -> Self {
+        Self {
+            name,
+            kind: UciOptionKind::Button,
+        }
+    }
+
+    /// Why `value` is not a valid setting for this option, if it is not.
+    /// Check values and combo vars match without regard to case, like
+    /// option names.
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        let name = self.name;
+        match self.kind {
+            UciOptionKind::Check { .. } => {
+                if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+                    Ok(())
+                } else {
+                    Err(format!("{name} must be true or false, got {value:?}"))
+                }
+            }
+            UciOptionKind::Spin { min, max, .. } => match value.parse::<i64>() {
+                Ok(number) if (min..=max).contains(&number) => Ok(()),
+                _ => Err(format!(
+                    "{name} must be a whole number from {min} to {max}, got {value:?}"
+                )),
+            },
+            UciOptionKind::Combo { vars, .. } => {
+                if vars.iter().any(|var| var.eq_ignore_ascii_case(value)) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "{name} must be one of {}, got {value:?}",
+                        vars.join(", ")
+                    ))
+                }
+            }
+            UciOptionKind::String { .. } | UciOptionKind::Button => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for UciOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "option name {} type ", self.name)?;
+        match self.kind {
+            UciOptionKind::Check { default } => write!(f, "check default {default}"),
+            UciOptionKind::Spin { default, min, max } => {
+                write!(f, "spin default {default} min {min} max {max}")
+            }
+            UciOptionKind::Combo { default, vars } => {
+                write!(f, "combo default {default}")?;
+                vars.iter().try_for_each(|var| write!(f, " var {var}"))
+            }
+            // The protocol spells an empty string default as `<empty>`.
+            UciOptionKind::String { default: "" } => write!(f, "string default <empty>"),
+            UciOptionKind::String { default } => write!(f, "string default {default}"),
+            UciOptionKind::Button => write!(f, "button"),
+        }
+    }
+}
+
+/// The `option` lines sent in reply to `uci`: the loop's own options, then
+/// the engine's.
+pub fn option_lines<E: UciEngine + ?Sized>(engine: &E) -> Vec<String> {
+    BUILTIN_OPTIONS
+        .iter()
+        .chain(engine.options())
+        .map(UciOption::to_string)
+        .collect()
+}
+
+/// The declared option called `name`, compared without regard to case.
+pub(crate) fn find_option<E: UciEngine + ?Sized>(engine: &E, name: &str) -> Option<UciOption> {
+    BUILTIN_OPTIONS
+        .iter()
+        .chain(engine.options())
+        .find(|option| option.name.eq_ignore_ascii_case(name))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_print_in_uci_syntax() {
+        let lines: Vec<String> = [
+            UciOption::check("NullMove", true),
+            UciOption::spin("Threads", 1, 1, 64),
+            UciOption::combo("Style", "Normal", &["Solid", "Normal", "Risky"]),
+            UciOption::string("SyzygyPath", ""),
+            UciOption::button("Clear Hash"),
+        ]
+        .iter()
+        .map(UciOption::to_string)
+        .collect();
+
+        assert_eq!(
+            lines,
+            [
+                "option name NullMove type check default true",
+                "option name Threads type spin default 1 min 1 max 64",
+                "option name Style type combo default Normal var Solid var Normal var Risky",
+                "option name SyzygyPath type string default <empty>",
+                "option name Clear Hash type button",
+            ]
+        );
+    }
+
+    #[test]
+    fn values_outside_the_declared_range_are_rejected() {
+        let threads = UciOption::spin("Threads", 1, 1, 64);
+        assert_eq!(threads.validate("8"), Ok(()));
+        assert!(threads.validate("0").unwrap_err().contains("from 1 to 64"));
+        assert!(threads.validate("many").is_err());
+
+        let null_move = UciOption::check("NullMove", true);
+        assert_eq!(null_move.validate("FALSE"), Ok(()));
+        assert!(null_move.validate("off").is_err());
+
+        let style = UciOption::combo("Style", "Normal", &["Solid", "Normal"]);
+        assert_eq!(style.validate("solid"), Ok(()));
+        assert!(style.validate("Risky").is_err());
+    }
+}
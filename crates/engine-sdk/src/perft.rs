@@ -0,0 +1,168 @@
+//! Perft node counts for checking move generation against published totals.
+
+use std::fmt::Write;
+
+use cozy_chess::{Board, Move, util};
+
+use crate::legal_moves;
+
+/// Number of leaf nodes exactly `depth` plies below `board`.
+///
+/// The last ply is bulk-counted from the generated move sets without playing
+/// any of them, and no level collects its moves into a `Vec`.
+pub fn perft(board: &Board, depth: u32) -> u64 {
+    match depth {
+        0 => 1,
+        1 => {
+            let mut nodes = 0;
+            board.generate_moves(|piece_moves| {
+                nodes += piece_moves.len() as u64;
+                false
+            });
+            nodes
+        }
+        _ => {
+            let mut nodes = 0;
+            board.generate_moves(|piece_moves| {
+                for mv in piece_moves {
+                    let mut next = board.clone();
+                    next.play_unchecked(mv);
+                    nodes += perft(&next, depth - 1);
+                }
+                false
+            });
+            nodes
+        }
+    }
+}
+
+/// Straightforward [`perft`] that plays every move down to the leaves. Kept
+/// as the reference for differential tests and `perft_bench --compare`.
+pub fn perft_naive(board: &Board, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    legal_moves(board)
+        .into_iter()
+        .map(|mv| {
+            let mut next = board.clone();
+            next.play_unchecked(mv);
+            perft_naive(&next, depth - 1)
+        })
+        .sum()
+}
+
+/// [`perft`] split by root move and sorted by UCI move, so a wrong total can
+/// be traced to the root move whose subtree is off.
+pub fn perft_divide(board: &Board, depth: u32) -> Vec<(Move, u64)> {
+    let mut divide: Vec<(Move, u64)> = legal_moves(board)
+        .into_iter()
+        .map(|mv| {
+            let mut next = board.clone();
+            next.play_unchecked(mv);
+            (mv, perft(&next, depth.saturating_sub(1)))
+        })
+        .collect();
+    divide.sort_by_cached_key(|(mv, _)| util::display_uci_move(board, *mv).to_string());
+    divide
+}
+
+/// Stockfish-style divide table: one `move: nodes` line per root move, a
+/// blank line, then the total.
+pub fn format_divide(board: &Board, divide: &[(Move, u64)]) -> String {
+    let mut table = String::new();
+    for (mv, nodes) in divide {
+        let _ = writeln!(table, "{}: {nodes}", util::display_uci_move(board, *mv));
+    }
+    let total: u64 = divide.iter().map(|(_, nodes)| nodes).sum();
+    let _ = write!(table, "\nNodes searched: {total}");
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+    #[test]
+    fn divide_sums_to_the_perft_total() {
+        let startpos = Board::default();
+        let kiwipete: Board = KIWIPETE.parse().unwrap();
+
+        for (board, depth, expected) in [(&startpos, 4, 197_281), (&kiwipete, 3, 97_862)] {
+            let divide = perft_divide(board, depth);
+            assert_eq!(perft(board, depth), expected);
+            assert_eq!(divide.iter().map(|(_, nodes)| nodes).sum::<u64>(), expected);
+        }
+    }
+
+    #[test]
+    fn bulk_counting_matches_the_naive_perft() {
+        for fen in [
+            KIWIPETE,
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        ] {
+            let board: Board = fen.parse().unwrap();
+            for depth in 0..=3 {
+                assert_eq!(
+                    perft(&board, depth),
+                    perft_naive(&board, depth),
+                    "{fen} depth {depth}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn chess960_positions_match_published_totals() {
+        for (fen, expected) in [
+            (
+                "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+                [21, 528, 12_189, 326_672],
+            ),
+            (
+                "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+                [21, 807, 18_002, 667_366],
+            ),
+            (
+                "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+                [20, 479, 10_471, 273_318],
+            ),
+            (
+                "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9",
+                [22, 593, 13_440, 382_958],
+            ),
+            (
+                "1nbbnrkr/p1p1ppp1/3p4/1p3P1p/3Pq2P/8/PPP1P1P1/QNBBNRKR w HFhf - 0 9",
+                [28, 1_120, 31_058, 1_171_749],
+            ),
+        ] {
+            let board = Board::from_fen(fen, true).unwrap();
+            for (depth, expected) in (1..).zip(expected) {
+                assert_eq!(perft(&board, depth), expected, "{fen} depth {depth}");
+            }
+        }
+    }
+
+    #[test]
+    fn startpos_divide_at_depth_two_is_twenty_by_twenty() {
+        let board = Board::default();
+        let divide = perft_divide(&board, 2);
+
+        assert_eq!(divide.len(), 20);
+        assert!(divide.iter().all(|(_, nodes)| *nodes == 20));
+        let moves: Vec<String> = divide
+            .iter()
+            .map(|(mv, _)| util::display_uci_move(&board, *mv).to_string())
+            .collect();
+        assert_eq!(moves.first().map(String::as_str), Some("a2a3"));
+        assert!(moves.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let table = format_divide(&board, &divide);
+        assert!(table.starts_with("a2a3: 20\n"));
+        assert!(table.ends_with("\n\nNodes searched: 400"));
+    }
+}
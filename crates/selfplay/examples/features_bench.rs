@@ -0,0 +1,72 @@
+//! Times feature extraction: `cargo run --release -p selfplay --example
+//! features_bench -- [--iterations N]`.
+//!
+//! Compares a fresh `Vec` per position against one reused buffer over a few
+//! positions from the opening to a promotion race.
+
+use std::{
+    env,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, anyhow, bail};
+use cozy_chess::Board;
+use selfplay::features::{FEATURE_LEN, extract_features_relative, extract_features_relative_into};
+
+const USAGE: &str = "usage: features_bench [--iterations N]";
+
+const POSITIONS: &[&str] = &[
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+];
+
+fn main() -> Result<()> {
+    let mut iterations: usize = 1_000_000;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--iterations" => {
+                iterations = args
+                    .next()
+                    .ok_or_else(|| anyhow!("--iterations needs a value\n{USAGE}"))?
+                    .parse()?;
+            }
+            other => bail!("unknown argument {other}\n{USAGE}"),
+        }
+    }
+
+    let boards: Vec<Board> = POSITIONS
+        .iter()
+        .map(|fen| fen.parse().map_err(|err| anyhow!("invalid FEN: {err:?}")))
+        .collect::<Result<_>>()?;
+
+    let started = Instant::now();
+    for index in 0..iterations {
+        black_box(extract_features_relative(&boards[index % boards.len()]));
+    }
+    let allocating = started.elapsed();
+    report("allocating", iterations, allocating);
+
+    let mut buffer = vec![0.0; FEATURE_LEN];
+    let started = Instant::now();
+    for index in 0..iterations {
+        extract_features_relative_into(&boards[index % boards.len()], &mut buffer);
+        black_box(&buffer);
+    }
+    let buffered = started.elapsed();
+    report("buffered", iterations, buffered);
+
+    println!(
+        "speedup: {:.2}x",
+        allocating.as_secs_f64() / buffered.as_secs_f64().max(f64::EPSILON)
+    );
+    Ok(())
+}
+
+fn report(label: &str, iterations: usize, elapsed: Duration) {
+    let per_call = elapsed.as_nanos() as f64 / iterations.max(1) as f64;
+    println!("{label}: {iterations} positions in {elapsed:.2?} ({per_call:.0} ns each)");
+}
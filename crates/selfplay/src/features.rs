@@ -0,0 +1,396 @@
+//! Position and move encodings shared by the training records.
+//!
+//! The move encoding and every [`FeatureEncoding`] but
+//! [`FeatureEncoding::Planes12`] are relative to the side to move: when Black
+//! is to move the board is mirrored vertically, so "own" pieces always start
+//! on ranks 1-2 and move up the board.
+
+use anyhow::{Result, anyhow};
+use cozy_chess::{Board, Color, Move, Piece, Square};
+use serde::{Deserialize, Serialize};
+
+/// Twelve 8x8 one-hot planes: own pawn, knight, bishop, rook, queen, king,
+/// then the same six for the opponent.
+pub const FEATURE_LEN: usize = 12 * 64;
+
+/// Layout of the feature planes, recorded with the training data so a model
+/// is never fed a layout it was not trained on. Every layout is a stack of
+/// 8x8 planes indexed `plane * 64 + square`, with a1 = 0 .. h8 = 63.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureEncoding {
+    /// White's six piece planes then Black's, never mirrored.
+    Planes12,
+    /// The side to move's six piece planes then the opponent's, mirrored for
+    /// Black as [`extract_features_relative`] does.
+    #[default]
+    Planes12Relative,
+    /// [`Self::Planes12Relative`] followed by six more planes: own castling
+    /// rooks, opponent castling rooks, the en passant file, then constant
+    /// planes for side to move (1 for White), the halfmove clock / 100
+    /// (capped at 1) and earlier occurrences of the position (capped at 2).
+    Planes18Extended,
+}
+
+impl FeatureEncoding {
+    pub const ALL: [Self; 3] = [
+        Self::Planes12,
+        Self::Planes12Relative,
+        Self::Planes18Extended,
+    ];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|encoding| encoding.name() == value)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Planes12 => "planes12",
+            Self::Planes12Relative => "planes12_relative",
+            Self::Planes18Extended => "planes18_extended",
+        }
+    }
+
+    /// Identifier stored in binary file headers.
+    pub fn id(self) -> u32 {
+        match self {
+            Self::Planes12 => 0,
+            Self::Planes12Relative => 1,
+            Self::Planes18Extended => 2,
+        }
+    }
+
+    pub fn from_id(id: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|encoding| encoding.id() == id)
+    }
+
+    pub fn planes(self) -> usize {
+        match self {
+            Self::Planes12 | Self::Planes12Relative => 12,
+            Self::Planes18Extended => 18,
+        }
+    }
+
+    /// `[planes, 8, 8]`, the shape to reshape one record's features into.
+    pub fn shape(self) -> [usize; 3] {
+        [self.planes(), 8, 8]
+    }
+
+    /// Number of features, `planes * 64`.
+    pub fn feature_count(self) -> usize {
+        self.planes() * 64
+    }
+}
+
+/// Encode `board` as `encoding` into `features`, which is cleared first.
+/// `repetitions` counts earlier occurrences of the position in the game and
+/// only shows up in [`FeatureEncoding::Planes18Extended`].
+///
+/// # Panics
+///
+/// If `features` is not `encoding.feature_count()` long.
+pub fn encode(board: &Board, repetitions: u8, encoding: FeatureEncoding, features: &mut [f32]) {
+    assert_eq!(
+        features.len(),
+        encoding.feature_count(),
+        "feature buffer must hold {} values for {}",
+        encoding.feature_count(),
+        encoding.name()
+    );
+    match encoding {
+        FeatureEncoding::Planes12 => {
+            features.fill(0.0);
+            for color in [Color::White, Color::Black] {
+                for piece in Piece::ALL {
+                    let plane = color as usize * 6 + piece as usize;
+                    for square in board.colored_pieces(color, piece) {
+                        features[plane * 64 + square as usize] = 1.0;
+                    }
+                }
+            }
+        }
+        FeatureEncoding::Planes12Relative => extract_features_relative_into(board, features),
+        FeatureEncoding::Planes18Extended => {
+            let (pieces, extra) = features.split_at_mut(FEATURE_LEN);
+            extract_features_relative_into(board, pieces);
+            extra.fill(0.0);
+            let side = board.side_to_move();
+            for (plane, color) in [side, !side].into_iter().enumerate() {
+                let rights = board.castle_rights(color);
+                let back_rank = cozy_chess::Rank::First.relative_to(color);
+                for file in [rights.short, rights.long].into_iter().flatten() {
+                    let rook = relative_square(Square::new(file, back_rank), side);
+                    extra[plane * 64 + rook as usize] = 1.0;
+                }
+            }
+            if let Some(file) = board.en_passant() {
+                for rank in cozy_chess::Rank::ALL {
+                    extra[2 * 64 + Square::new(file, rank) as usize] = 1.0;
+                }
+            }
+            let constants = [
+                if side == Color::White { 1.0 } else { 0.0 },
+                (f32::from(board.halfmove_clock()) / 100.0).min(1.0),
+                f32::from(repetitions.min(2)),
+            ];
+            for (plane, value) in (3..).zip(constants) {
+                extra[plane * 64..(plane + 1) * 64].fill(value);
+            }
+        }
+    }
+}
+
+/// `from * 64 + to`, repeated once per promotion plane (none, knight, bishop,
+/// rook, queen).
+pub const MOVE_ENCODING_LEN: usize = 5 * 64 * 64;
+
+/// One-hot piece planes for `board`, indexed `plane * 64 + square` with
+/// squares numbered a1 = 0 .. h8 = 63 after mirroring for the side to move.
+pub fn extract_features_relative(board: &Board) -> Vec<f32> {
+    let mut features = vec![0.0; FEATURE_LEN];
+    extract_features_relative_into(board, &mut features);
+    features
+}
+
+/// [`extract_features_relative`] into a reusable buffer, which is cleared
+/// first, so batched callers avoid an allocation per position.
+///
+/// # Panics
+///
+/// If `features` is not [`FEATURE_LEN`] long.
+pub fn extract_features_relative_into(board: &Board, features: &mut [f32]) {
+    assert_eq!(
+        features.len(),
+        FEATURE_LEN,
+        "feature buffer must hold {FEATURE_LEN} values"
+    );
+    features.fill(0.0);
+    let side = board.side_to_move();
+    for color in [side, !side] {
+        let color_offset = if color == side { 0 } else { 6 };
+        for piece in Piece::ALL {
+            let plane = color_offset + piece as usize;
+            for square in board.colored_pieces(color, piece) {
+                features[plane * 64 + relative_square(square, side) as usize] = 1.0;
+            }
+        }
+    }
+}
+
+/// Index of `mv` in the policy encoding. Castling is encoded the way
+/// cozy-chess represents it, as the king capturing its own rook.
+pub fn encode_move(board: &Board, mv: Move) -> u32 {
+    let side = board.side_to_move();
+    let from = relative_square(mv.from, side) as u32;
+    let to = relative_square(mv.to, side) as u32;
+    let plane = match mv.promotion {
+        None => 0,
+        Some(Piece::Knight) => 1,
+        Some(Piece::Bishop) => 2,
+        Some(Piece::Rook) => 3,
+        Some(_) => 4,
+    };
+    plane * 4096 + from * 64 + to
+}
+
+/// Inverse of [`encode_move`] for the position the move was encoded in.
+pub fn decode_move(board: &Board, index: u32) -> Result<Move> {
+    if index as usize >= MOVE_ENCODING_LEN {
+        return Err(anyhow!("move index {index} is out of range"));
+    }
+    let side = board.side_to_move();
+    let promotion = match index / 4096 {
+        0 => None,
+        1 => Some(Piece::Knight),
+        2 => Some(Piece::Bishop),
+        3 => Some(Piece::Rook),
+        _ => Some(Piece::Queen),
+    };
+    let from = relative_square(Square::index(((index / 64) % 64) as usize), side);
+    let to = relative_square(Square::index((index % 64) as usize), side);
+    Ok(Move {
+        from,
+        to,
+        promotion,
+    })
+}
+
+fn relative_square(square: Square, side: Color) -> Square {
+    match side {
+        Color::White => square,
+        Color::Black => square.flip_rank(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cozy_chess::util::parse_uci_move;
+
+    use super::*;
+
+    #[test]
+    fn features_are_mirrored_for_the_side_to_move() {
+        let white = Board::startpos();
+        let black: Board = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1"
+            .parse()
+            .unwrap();
+
+        let features = extract_features_relative(&white);
+        assert_eq!(features.len(), FEATURE_LEN);
+        assert_eq!(features.iter().filter(|value| **value == 1.0).count(), 32);
+        assert_eq!(
+            features[Square::E1 as usize + 64 * Piece::King as usize],
+            1.0
+        );
+        assert_eq!(
+            features[(6 + Piece::King as usize) * 64 + Square::E8 as usize],
+            1.0
+        );
+        assert_eq!(extract_features_relative(&black), features);
+    }
+
+    #[test]
+    fn buffered_features_match_a_square_by_square_scan() {
+        let mut buffer = vec![0.0; FEATURE_LEN];
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+        ] {
+            let board: Board = fen.parse().unwrap();
+            let side = board.side_to_move();
+            let mut expected = vec![0.0; FEATURE_LEN];
+            for square in Square::ALL {
+                if let (Some(piece), Some(color)) = (board.piece_on(square), board.color_on(square))
+                {
+                    let plane = piece as usize + if color == side { 0 } else { 6 };
+                    expected[plane * 64 + relative_square(square, side) as usize] = 1.0;
+                }
+            }
+
+            // The buffer still holds the previous position's planes.
+            extract_features_relative_into(&board, &mut buffer);
+            assert_eq!(buffer, expected, "{fen}");
+            assert_eq!(extract_features_relative(&board), expected, "{fen}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "feature buffer must hold")]
+    fn buffered_features_reject_a_short_buffer() {
+        extract_features_relative_into(&Board::startpos(), &mut [0.0; 64]);
+    }
+
+    #[test]
+    fn encodings_report_their_plane_counts_and_names() {
+        let shapes: Vec<_> = FeatureEncoding::ALL
+            .into_iter()
+            .map(FeatureEncoding::shape)
+            .collect();
+        assert_eq!(shapes, [[12, 8, 8], [12, 8, 8], [18, 8, 8]]);
+        assert_eq!(FeatureEncoding::Planes18Extended.feature_count(), 18 * 64);
+        for encoding in FeatureEncoding::ALL {
+            assert_eq!(FeatureEncoding::parse(encoding.name()), Some(encoding));
+            assert_eq!(FeatureEncoding::from_id(encoding.id()), Some(encoding));
+        }
+    }
+
+    #[test]
+    fn absolute_planes_are_not_mirrored_for_black() {
+        let board: Board = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1"
+            .parse()
+            .unwrap();
+        let mut absolute = vec![0.0; FEATURE_LEN];
+        let mut relative = vec![0.0; FEATURE_LEN];
+        encode(&board, 0, FeatureEncoding::Planes12, &mut absolute);
+        encode(&board, 0, FeatureEncoding::Planes12Relative, &mut relative);
+
+        let white_pawn = Piece::Pawn as usize;
+        let black_king = 6 + Piece::King as usize;
+        assert_eq!(absolute[white_pawn * 64 + Square::E4 as usize], 1.0);
+        assert_eq!(absolute[black_king * 64 + Square::E8 as usize], 1.0);
+        // Relative to Black, White's e4 pawn is an opponent pawn on e5.
+        assert_eq!(relative[(6 + white_pawn) * 64 + Square::E5 as usize], 1.0);
+        assert_eq!(relative, extract_features_relative(&board));
+    }
+
+    #[test]
+    fn extended_planes_hold_castling_en_passant_and_game_state() {
+        let plane =
+            |features: &[f32], index: usize| features[index * 64..(index + 1) * 64].to_vec();
+        let squares = |squares: &[Square]| {
+            let mut plane = vec![0.0; 64];
+            for square in squares {
+                plane[*square as usize] = 1.0;
+            }
+            plane
+        };
+        let mut features = vec![0.0; FeatureEncoding::Planes18Extended.feature_count()];
+
+        let white: Board = "r3k2r/8/8/3pP3/8/8/8/R3K2R w KQk d6 0 1".parse().unwrap();
+        encode(&white, 1, FeatureEncoding::Planes18Extended, &mut features);
+        assert_eq!(features[..FEATURE_LEN], extract_features_relative(&white));
+        assert_eq!(plane(&features, 12), squares(&[Square::A1, Square::H1]));
+        assert_eq!(plane(&features, 13), squares(&[Square::H8]));
+        let d_file: Vec<Square> = cozy_chess::Rank::ALL
+            .into_iter()
+            .map(|rank| Square::new(cozy_chess::File::D, rank))
+            .collect();
+        assert_eq!(plane(&features, 14), squares(&d_file));
+        assert_eq!(plane(&features, 15), vec![1.0; 64]);
+        assert_eq!(plane(&features, 16), vec![0.0; 64]);
+        assert_eq!(plane(&features, 17), vec![1.0; 64]);
+
+        // Black's queenside rook is its own a1 rook once mirrored.
+        let black: Board = "r3k2r/8/8/8/8/8/8/R3K2R b Kq - 10 30".parse().unwrap();
+        encode(&black, 5, FeatureEncoding::Planes18Extended, &mut features);
+        assert_eq!(plane(&features, 12), squares(&[Square::A1]));
+        assert_eq!(plane(&features, 13), squares(&[Square::H8]));
+        assert_eq!(plane(&features, 14), vec![0.0; 64]);
+        assert_eq!(plane(&features, 15), vec![0.0; 64]);
+        assert_eq!(plane(&features, 16), vec![0.1; 64]);
+        assert_eq!(plane(&features, 17), vec![2.0; 64]);
+    }
+
+    #[test]
+    fn move_encoding_round_trips_for_both_sides_and_promotions() {
+        let cases = [
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                "e2e4",
+            ),
+            (
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1",
+                "e7e5",
+            ),
+            ("8/1P5k/8/8/8/8/8/K7 w - - 0 1", "b7b8n"),
+            ("8/1P5k/8/8/8/8/8/K7 w - - 0 1", "b7b8q"),
+            ("k7/8/8/8/8/8/6p1/K7 b - - 0 1", "g2g1r"),
+            ("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "e1g1"),
+        ];
+        for (fen, uci) in cases {
+            let board: Board = fen.parse().unwrap();
+            let mv = parse_uci_move(&board, uci).unwrap();
+            let index = encode_move(&board, mv);
+
+            assert!((index as usize) < MOVE_ENCODING_LEN);
+            assert_eq!(decode_move(&board, index).unwrap(), mv, "{uci}");
+        }
+    }
+
+    #[test]
+    fn mirrored_moves_share_an_encoding() {
+        let white = Board::startpos();
+        let black: Board = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            encode_move(&white, parse_uci_move(&white, "g1f3").unwrap()),
+            encode_move(&black, parse_uci_move(&black, "g8f6").unwrap())
+        );
+        assert!(decode_move(&white, MOVE_ENCODING_LEN as u32).is_err());
+    }
+}
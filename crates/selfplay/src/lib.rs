@@ -0,0 +1,16 @@
+//! Self-play training data generation.
+//!
+//! An engine plays itself through the same [`arena_runner::AgentAdapter`] the
+//! arena uses, and every position it searched becomes one [`TrainingRecord`]
+//! labelled with the game's final outcome.
+
+pub mod features;
+mod record;
+mod selfplay;
+
+pub use features::FeatureEncoding;
+pub use record::{
+    BINARY_HEADER_LEN, BINARY_MAGIC, BINARY_VERSION, MATE_SCORE_CP, OutputFormat, RecordWriter,
+    TrainingRecord, binary_record_len, check_binary_header, score_to_cp,
+};
+pub use selfplay::{GameSummary, SelfPlayConfig, run_selfplay};
@@ -0,0 +1,126 @@
+use std::{collections::BTreeMap, env, fs::File, io::BufWriter, time::Instant};
+
+use anyhow::{Context, Result, anyhow, bail};
+use arena_core::{AgentCapabilities, AgentVersion, GameResult};
+use arena_runner::{SearchLimit, build_adapter};
+use chrono::Utc;
+use selfplay::{FeatureEncoding, OutputFormat, RecordWriter, SelfPlayConfig, run_selfplay};
+use uuid::Uuid;
+
+const USAGE: &str = "usage: selfplay --engine <path> --out <file> [--games N] [--depth N | --movetime MS | --nodes N] \
+[--format jsonl|bin] [--encoding planes12|planes12_relative|planes18_extended] [--random-plies N] \
+[--max-plies N] [--seed N]";
+
+struct Args {
+    engine: String,
+    out: String,
+    format: OutputFormat,
+    config: SelfPlayConfig,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = parse_args(env::args().skip(1).collect())?;
+    let file = File::create(&args.out).with_context(|| format!("failed to create {}", args.out))?;
+    let mut writer = RecordWriter::new(args.format, args.config.encoding, BufWriter::new(file))?;
+    let mut engine = build_adapter(engine_version(&args.engine));
+
+    let started = Instant::now();
+    let games = args.config.games;
+    let mut positions = 0;
+    run_selfplay(engine.as_mut(), args.config, &mut writer, |summary| {
+        positions += summary.records;
+        let result = match summary.result {
+            GameResult::WhiteWin => "1-0",
+            GameResult::BlackWin => "0-1",
+            GameResult::Draw => "1/2-1/2",
+        };
+        println!(
+            "game {}/{games}: {result} in {} plies, {} positions ({positions} total, {:.0?} elapsed)",
+            summary.game + 1,
+            summary.plies,
+            summary.records,
+            started.elapsed()
+        );
+    })
+    .await?;
+    writer.finish()?;
+    println!("wrote {positions} positions to {}", args.out);
+    Ok(())
+}
+
+fn parse_args(raw: Vec<String>) -> Result<Args> {
+    let mut engine = None;
+    let mut out = None;
+    let mut format = OutputFormat::Jsonl;
+    let mut config = SelfPlayConfig {
+        games: 10,
+        limit: SearchLimit::Depth(6),
+        random_plies: 8,
+        max_plies: 300,
+        seed: 0,
+        encoding: FeatureEncoding::default(),
+    };
+
+    let mut raw = raw.into_iter();
+    while let Some(flag) = raw.next() {
+        let mut value = || {
+            raw.next()
+                .ok_or_else(|| anyhow!("{flag} needs a value\n{USAGE}"))
+        };
+        match flag.as_str() {
+            "--engine" => engine = Some(value()?),
+            "--out" => out = Some(value()?),
+            "--games" => config.games = value()?.parse()?,
+            "--depth" => config.limit = SearchLimit::Depth(value()?.parse()?),
+            "--movetime" => config.limit = SearchLimit::MoveTime(value()?.parse()?),
+            "--nodes" => config.limit = SearchLimit::Nodes(value()?.parse()?),
+            "--random-plies" => config.random_plies = value()?.parse()?,
+            "--max-plies" => config.max_plies = value()?.parse()?,
+            "--seed" => config.seed = value()?.parse()?,
+            "--encoding" => {
+                let raw_encoding = value()?;
+                config.encoding = FeatureEncoding::parse(&raw_encoding)
+                    .ok_or_else(|| anyhow!("unknown encoding {raw_encoding}\n{USAGE}"))?;
+            }
+            "--format" => {
+                let raw_format = value()?;
+                format = OutputFormat::parse(&raw_format)
+                    .ok_or_else(|| anyhow!("unknown format {raw_format}\n{USAGE}"))?;
+            }
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            other => bail!("unknown argument {other}\n{USAGE}"),
+        }
+    }
+
+    Ok(Args {
+        engine: engine.ok_or_else(|| anyhow!("--engine is required\n{USAGE}"))?,
+        out: out.ok_or_else(|| anyhow!("--out is required\n{USAGE}"))?,
+        format,
+        config,
+    })
+}
+
+fn engine_version(executable_path: &str) -> AgentVersion {
+    AgentVersion {
+        id: Uuid::new_v4(),
+        registry_key: None,
+        agent_id: Uuid::new_v4(),
+        version: "selfplay".to_string(),
+        active: true,
+        executable_path: executable_path.to_string(),
+        working_directory: None,
+        args: Vec::new(),
+        env: BTreeMap::new(),
+        uci_options: BTreeMap::new(),
+        capabilities: AgentCapabilities::default(),
+        declared_name: None,
+        tags: Vec::new(),
+        notes: None,
+        documentation: None,
+        created_at: Utc::now(),
+    }
+}
@@ -0,0 +1,262 @@
+//! Training record layout and the two on-disk formats.
+//!
+//! `jsonl` writes one [`TrainingRecord`] JSON object per line, each naming
+//! its feature encoding.
+//!
+//! `bin` is a flat little-endian file for `numpy.fromfile`: a 20-byte header
+//! (`b"MLCSELF1"`, `u32` format version, `u32` [`FeatureEncoding::id`], `u32`
+//! feature count) followed by fixed-size records of `feature count` x `f32`
+//! features, `u32` move index, `i32` score in centipawns (`i32::MIN` when the
+//! engine reported none) and `i8` outcome.
+
+use std::io::Write;
+
+use anyhow::{Result, bail};
+use arena_core::EngineScore;
+use serde::{Deserialize, Serialize};
+
+use crate::features::FeatureEncoding;
+
+pub const BINARY_MAGIC: &[u8; 8] = b"MLCSELF1";
+pub const BINARY_VERSION: u32 = 2;
+pub const BINARY_HEADER_LEN: usize = 20;
+
+/// Mate scores are stored as centipawns this far from zero, minus the
+/// number of moves to mate.
+pub const MATE_SCORE_CP: i32 = 32_000;
+
+/// One searched position from a self-play game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrainingRecord {
+    pub game: u32,
+    pub ply: u32,
+    pub fen: String,
+    pub encoding: FeatureEncoding,
+    pub features: Vec<f32>,
+    pub move_uci: String,
+    pub move_index: u32,
+    /// Engine score from the side to move's point of view.
+    pub score_cp: Option<i32>,
+    /// Final result from the side to move's point of view: 1 win, 0 draw, -1 loss.
+    pub outcome: i8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Jsonl,
+    Binary,
+}
+
+impl OutputFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "jsonl" => Some(Self::Jsonl),
+            "bin" => Some(Self::Binary),
+            _ => None,
+        }
+    }
+}
+
+pub fn score_to_cp(score: EngineScore) -> i32 {
+    match score {
+        EngineScore::Centipawns(value) => {
+            value.clamp(-(MATE_SCORE_CP - 1000), MATE_SCORE_CP - 1000)
+        }
+        EngineScore::Mate(moves) if moves >= 0 => MATE_SCORE_CP - moves,
+        EngineScore::Mate(moves) => -MATE_SCORE_CP - moves,
+    }
+}
+
+/// Bytes per record in a `bin` file of `encoding` features.
+pub fn binary_record_len(encoding: FeatureEncoding) -> usize {
+    encoding.feature_count() * 4 + 4 + 4 + 1
+}
+
+/// Check a `bin` file's header before loading its records as `expected`
+/// features, failing on anything else rather than misreading the planes.
+pub fn check_binary_header(header: &[u8], expected: FeatureEncoding) -> Result<()> {
+    if header.len() < BINARY_HEADER_LEN || &header[..8] != BINARY_MAGIC {
+        bail!("not a self-play binary file");
+    }
+    let field = |offset: usize| u32::from_le_bytes(header[offset..offset + 4].try_into().unwrap());
+    if field(8) != BINARY_VERSION {
+        bail!(
+            "unsupported self-play format version {}, expected {BINARY_VERSION}",
+            field(8)
+        );
+    }
+    let Some(encoding) = FeatureEncoding::from_id(field(12)) else {
+        bail!("unknown feature encoding id {}", field(12));
+    };
+    if encoding != expected {
+        bail!(
+            "file holds {} features but {} was requested",
+            encoding.name(),
+            expected.name()
+        );
+    }
+    if field(16) as usize != encoding.feature_count() {
+        bail!(
+            "header lists {} features but {} has {}",
+            field(16),
+            encoding.name(),
+            encoding.feature_count()
+        );
+    }
+    Ok(())
+}
+
+pub struct RecordWriter<W: Write> {
+    format: OutputFormat,
+    encoding: FeatureEncoding,
+    out: W,
+}
+
+impl<W: Write> RecordWriter<W> {
+    pub fn new(format: OutputFormat, encoding: FeatureEncoding, mut out: W) -> Result<Self> {
+        if format == OutputFormat::Binary {
+            out.write_all(BINARY_MAGIC)?;
+            out.write_all(&BINARY_VERSION.to_le_bytes())?;
+            out.write_all(&encoding.id().to_le_bytes())?;
+            out.write_all(&(encoding.feature_count() as u32).to_le_bytes())?;
+        }
+        Ok(Self {
+            format,
+            encoding,
+            out,
+        })
+    }
+
+    pub fn write(&mut self, record: &TrainingRecord) -> Result<()> {
+        if record.encoding != self.encoding
+            || record.features.len() != self.encoding.feature_count()
+        {
+            bail!(
+                "record has {} {} features but the file holds {}",
+                record.features.len(),
+                record.encoding.name(),
+                self.encoding.name()
+            );
+        }
+        match self.format {
+            OutputFormat::Jsonl => {
+                serde_json::to_writer(&mut self.out, record)?;
+                self.out.write_all(b"\n")?;
+            }
+            OutputFormat::Binary => {
+                for value in &record.features {
+                    self.out.write_all(&value.to_le_bytes())?;
+                }
+                self.out.write_all(&record.move_index.to_le_bytes())?;
+                self.out
+                    .write_all(&record.score_cp.unwrap_or(i32::MIN).to_le_bytes())?;
+                self.out.write_all(&record.outcome.to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::FEATURE_LEN;
+
+    const RECORD_LEN: usize = FEATURE_LEN * 4 + 4 + 4 + 1;
+
+    fn record() -> TrainingRecord {
+        TrainingRecord {
+            game: 0,
+            ply: 0,
+            fen: "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string(),
+            encoding: FeatureEncoding::Planes12Relative,
+            features: vec![0.5; FEATURE_LEN],
+            move_uci: "e2e4".to_string(),
+            move_index: 796,
+            score_cp: Some(-35),
+            outcome: -1,
+        }
+    }
+
+    #[test]
+    fn binary_output_has_a_header_and_fixed_size_records() {
+        let mut writer = RecordWriter::new(
+            OutputFormat::Binary,
+            FeatureEncoding::Planes12Relative,
+            Vec::new(),
+        )
+        .unwrap();
+        writer.write(&record()).unwrap();
+        writer
+            .write(&TrainingRecord {
+                score_cp: None,
+                ..record()
+            })
+            .unwrap();
+        let bytes = writer.finish().unwrap();
+
+        assert_eq!(bytes.len(), BINARY_HEADER_LEN + 2 * RECORD_LEN);
+        assert_eq!(&bytes[..8], BINARY_MAGIC);
+        assert_eq!(
+            binary_record_len(FeatureEncoding::Planes12Relative),
+            RECORD_LEN
+        );
+        assert_eq!(bytes[12..16], 1u32.to_le_bytes());
+        assert_eq!(bytes[16..20], (FEATURE_LEN as u32).to_le_bytes());
+        let first = &bytes[BINARY_HEADER_LEN..BINARY_HEADER_LEN + RECORD_LEN];
+        let tail = &first[FEATURE_LEN * 4..];
+        assert_eq!(tail[..4], 796u32.to_le_bytes());
+        assert_eq!(tail[4..8], (-35i32).to_le_bytes());
+        assert_eq!(tail[8] as i8, -1);
+        let second = &bytes[BINARY_HEADER_LEN + RECORD_LEN..];
+        assert_eq!(
+            second[FEATURE_LEN * 4 + 4..FEATURE_LEN * 4 + 8],
+            i32::MIN.to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn loading_a_file_as_another_encoding_fails() {
+        let writer = RecordWriter::new(
+            OutputFormat::Binary,
+            FeatureEncoding::Planes18Extended,
+            Vec::new(),
+        )
+        .unwrap();
+        let header = writer.finish().unwrap();
+
+        check_binary_header(&header, FeatureEncoding::Planes18Extended).unwrap();
+        let err = check_binary_header(&header, FeatureEncoding::Planes12Relative).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "file holds planes18_extended features but planes12_relative was requested"
+        );
+        assert!(check_binary_header(b"MLCSELF1", FeatureEncoding::Planes12).is_err());
+    }
+
+    #[test]
+    fn writer_rejects_records_of_another_encoding() {
+        let mut writer =
+            RecordWriter::new(OutputFormat::Jsonl, FeatureEncoding::Planes12, Vec::new()).unwrap();
+
+        assert!(writer.write(&record()).is_err());
+        writer
+            .write(&TrainingRecord {
+                encoding: FeatureEncoding::Planes12,
+                ..record()
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn mate_scores_sort_beyond_every_centipawn_score() {
+        assert_eq!(score_to_cp(EngineScore::Mate(1)), 31_999);
+        assert_eq!(score_to_cp(EngineScore::Mate(-2)), -31_998);
+        assert!(score_to_cp(EngineScore::Mate(40)) > score_to_cp(EngineScore::Centipawns(50_000)));
+    }
+}
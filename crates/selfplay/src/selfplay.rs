@@ -0,0 +1,309 @@
+use std::io::Write;
+
+use anyhow::{Context, Result};
+use arena_core::{GameLogEntry, GameResult, Variant};
+use arena_runner::{AgentAdapter, Game, SearchLimit};
+use cozy_chess::{Board, Color, Move, util};
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::IndexedRandom};
+
+use crate::{
+    features::{FeatureEncoding, encode, encode_move},
+    record::{RecordWriter, TrainingRecord, score_to_cp},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelfPlayConfig {
+    pub games: u32,
+    pub limit: SearchLimit,
+    /// Uniformly random moves played, unrecorded, at the start of each game so
+    /// a deterministic engine does not replay the same game every time.
+    pub random_plies: u32,
+    /// Games still running after this many plies are adjudicated as draws.
+    pub max_plies: u32,
+    pub seed: u64,
+    pub encoding: FeatureEncoding,
+}
+
+/// Summary of one finished self-play game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameSummary {
+    pub game: u32,
+    pub result: GameResult,
+    pub plies: u32,
+    pub records: usize,
+}
+
+/// Play `config.games` games of `engine` against itself and write every
+/// searched position to `writer`, reporting each finished game to `progress`.
+pub async fn run_selfplay<W: Write>(
+    engine: &mut dyn AgentAdapter,
+    config: SelfPlayConfig,
+    writer: &mut RecordWriter<W>,
+    mut progress: impl FnMut(GameSummary),
+) -> Result<()> {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut logs = Vec::new();
+    engine.prepare(Variant::Standard, &mut logs).await?;
+    for game in 0..config.games {
+        let played = play_game(engine, &config, game, &mut rng, &mut logs).await;
+        logs.clear();
+        let (summary, records) = match played {
+            Ok(played) => played,
+            Err(err) => {
+                engine.shutdown(&mut logs).await.ok();
+                return Err(err);
+            }
+        };
+        for record in &records {
+            writer.write(record)?;
+        }
+        progress(summary);
+    }
+    engine.shutdown(&mut logs).await
+}
+
+async fn play_game(
+    engine: &mut dyn AgentAdapter,
+    config: &SelfPlayConfig,
+    game: u32,
+    rng: &mut impl Rng,
+    logs: &mut Vec<GameLogEntry>,
+) -> Result<(GameSummary, Vec<TrainingRecord>)> {
+    engine.begin_game(logs).await?;
+    let mut position = Game::startpos();
+    let start_fen = position.board().to_string();
+    let mut moves = Vec::new();
+    let mut records = Vec::new();
+    let mut sides = Vec::new();
+
+    let result = loop {
+        if let Some((result, _)) = position.outcome() {
+            break result;
+        }
+        let board = position.board().clone();
+        let ply = moves.len() as u32;
+        if ply >= config.max_plies {
+            break GameResult::Draw;
+        }
+
+        let mv = if ply < config.random_plies {
+            let legal = legal_moves(&board);
+            *legal
+                .choose(rng)
+                .context("ongoing position has no legal moves")?
+        } else {
+            let outcome = engine
+                .search(&board, &start_fen, &moves, config.limit, logs)
+                .await?;
+            let mv = util::parse_uci_move(&board, &outcome.best_move)
+                .ok()
+                .filter(|mv| board.is_legal(*mv))
+                .with_context(|| format!("engine played illegal move {}", outcome.best_move))?;
+            let mut features = vec![0.0; config.encoding.feature_count()];
+            encode(
+                &board,
+                position.occurrences() - 1,
+                config.encoding,
+                &mut features,
+            );
+            records.push(TrainingRecord {
+                game,
+                ply,
+                fen: board.to_string(),
+                encoding: config.encoding,
+                features,
+                move_uci: outcome.best_move,
+                move_index: encode_move(&board, mv),
+                score_cp: outcome.score.map(score_to_cp),
+                outcome: 0,
+            });
+            sides.push(board.side_to_move());
+            mv
+        };
+        moves.push(util::display_uci_move(&board, mv).to_string());
+        position.play(mv)?;
+    };
+
+    for (record, side) in records.iter_mut().zip(sides) {
+        record.outcome = outcome_for(result, side);
+    }
+    let summary = GameSummary {
+        game,
+        result,
+        plies: moves.len() as u32,
+        records: records.len(),
+    };
+    Ok((summary, records))
+}
+
+/// `result` from `side`'s point of view: 1 win, 0 draw, -1 loss.
+fn outcome_for(result: GameResult, side: Color) -> i8 {
+    match (result, side) {
+        (GameResult::Draw, _) => 0,
+        (GameResult::WhiteWin, Color::White) | (GameResult::BlackWin, Color::Black) => 1,
+        _ => -1,
+    }
+}
+
+fn legal_moves(board: &Board) -> Vec<Move> {
+    let mut moves = Vec::new();
+    board.generate_moves(|piece_moves| {
+        moves.extend(piece_moves);
+        false
+    });
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use arena_core::EngineScore;
+    use arena_runner::SearchOutcome;
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::{
+        features::FEATURE_LEN,
+        record::{OutputFormat, RecordWriter},
+    };
+
+    /// Plays fool's mate when it can and otherwise the first legal move.
+    struct ScriptedEngine;
+
+    #[async_trait]
+    impl AgentAdapter for ScriptedEngine {
+        async fn prepare(
+            &mut self,
+            _variant: Variant,
+            _logs: &mut Vec<GameLogEntry>,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn begin_game(&mut self, _logs: &mut Vec<GameLogEntry>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn choose_move(
+            &mut self,
+            board: &Board,
+            _start_fen: &str,
+            moves: &[String],
+            _movetime_ms: u64,
+            _logs: &mut Vec<GameLogEntry>,
+        ) -> Result<String> {
+            let scripted = ["f2f3", "e7e5", "g2g4", "d8h4"]
+                .get(moves.len())
+                .filter(|uci| util::parse_uci_move(board, uci).is_ok_and(|mv| board.is_legal(mv)));
+            Ok(match scripted {
+                Some(uci) => uci.to_string(),
+                None => util::display_uci_move(board, legal_moves(board)[0]).to_string(),
+            })
+        }
+
+        async fn search(
+            &mut self,
+            board: &Board,
+            start_fen: &str,
+            moves: &[String],
+            _limit: SearchLimit,
+            logs: &mut Vec<GameLogEntry>,
+        ) -> Result<SearchOutcome> {
+            let best_move = self.choose_move(board, start_fen, moves, 0, logs).await?;
+            Ok(SearchOutcome {
+                best_move,
+                score: Some(EngineScore::Centipawns(moves.len() as i32)),
+                ..SearchOutcome::default()
+            })
+        }
+
+        async fn shutdown(&mut self, _logs: &mut Vec<GameLogEntry>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn config(games: u32, random_plies: u32) -> SelfPlayConfig {
+        SelfPlayConfig {
+            games,
+            limit: SearchLimit::Depth(1),
+            random_plies,
+            max_plies: 60,
+            seed: 7,
+            encoding: FeatureEncoding::Planes12Relative,
+        }
+    }
+
+    #[tokio::test]
+    async fn two_game_run_writes_parseable_records_with_consistent_outcomes() {
+        let mut writer = RecordWriter::new(
+            OutputFormat::Jsonl,
+            FeatureEncoding::Planes12Relative,
+            Vec::new(),
+        )
+        .unwrap();
+        let mut summaries = Vec::new();
+        run_selfplay(&mut ScriptedEngine, config(2, 0), &mut writer, |summary| {
+            summaries.push(summary)
+        })
+        .await
+        .unwrap();
+        let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+        let records: Vec<TrainingRecord> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(summaries.len(), 2);
+        assert!(
+            summaries
+                .iter()
+                .all(|summary| summary.result == GameResult::BlackWin)
+        );
+        assert_eq!(records.len(), 8);
+        for game in 0..2 {
+            let game_records: Vec<_> = records
+                .iter()
+                .filter(|record| record.game == game)
+                .collect();
+            assert_eq!(game_records.len(), 4);
+            for pair in game_records.windows(2) {
+                assert_eq!(pair[1].ply, pair[0].ply + 1);
+                assert_eq!(pair[1].outcome, -pair[0].outcome);
+            }
+            assert_eq!(game_records[0].outcome, -1);
+            assert_eq!(game_records[3].move_uci, "d8h4");
+            assert_eq!(game_records[3].outcome, 1);
+            assert!(
+                game_records
+                    .iter()
+                    .all(|record| record.features.len() == FEATURE_LEN)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn random_opening_plies_are_played_but_not_recorded() {
+        let mut writer = RecordWriter::new(
+            OutputFormat::Jsonl,
+            FeatureEncoding::Planes12Relative,
+            Vec::new(),
+        )
+        .unwrap();
+        let mut summaries = Vec::new();
+        run_selfplay(&mut ScriptedEngine, config(1, 3), &mut writer, |summary| {
+            summaries.push(summary)
+        })
+        .await
+        .unwrap();
+        let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+        let records: Vec<TrainingRecord> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(records.len(), summaries[0].records);
+        assert_eq!(records.first().map(|record| record.ply), Some(3));
+        assert!(summaries[0].plies <= 60);
+        let expected = outcome_for(summaries[0].result, Color::Black);
+        assert_eq!(records[0].outcome, expected);
+    }
+}
@@ -0,0 +1,343 @@
+//! Texel tuning of the classical evaluation.
+//!
+//! Each sample is a position and the result of the game it came from. The
+//! static evaluation is mapped to an expected score with a logistic curve,
+//! and coordinate descent moves one [`EvalWeights`] term at a time while the
+//! mean squared error over the dataset keeps falling.
+
+use anyhow::{Context, Result, anyhow, bail};
+use classical_engine::{EvalWeights, evaluate};
+use cozy_chess::{Board, Color};
+use rayon::prelude::*;
+
+const PIECES: [&str; 5] = ["pawn", "knight", "bishop", "rook", "queen"];
+
+/// One labelled position.
+#[derive(Debug, Clone)]
+pub struct Sample {
+    pub board: Board,
+    /// White's score from the game: 1 win, 0.5 draw, 0 loss.
+    pub result: f64,
+}
+
+/// Reads one sample per line, skipping blanks and `#` comments. A line is
+/// either a FEN followed by White's result, as `1-0`, `1/2-1/2`, `0-1` or a
+/// number from 0 to 1 with or without brackets, or a self-play JSONL record.
+pub fn parse_dataset(text: &str) -> Result<Vec<Sample>> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(index, line)| parse_sample(line).with_context(|| format!("line {}", index + 1)))
+        .collect()
+}
+
+fn parse_sample(line: &str) -> Result<Sample> {
+    let (fen, result) = if line.starts_with('{') {
+        let record: serde_json::Value = serde_json::from_str(line)?;
+        let fen = record["fen"]
+            .as_str()
+            .ok_or_else(|| anyhow!("record has no fen"))?;
+        let outcome = record["outcome"]
+            .as_i64()
+            .ok_or_else(|| anyhow!("record has no outcome"))?;
+        // Self-play outcomes are from the side to move's point of view.
+        let score = (outcome as f64 + 1.0) / 2.0;
+        let white_to_move = fen.split_whitespace().nth(1) != Some("b");
+        let result = if white_to_move { score } else { 1.0 - score };
+        (fen.to_string(), result)
+    } else {
+        let (fen, result) = line
+            .rsplit_once(char::is_whitespace)
+            .ok_or_else(|| anyhow!("expected a FEN and a result"))?;
+        (fen.trim_end().to_string(), parse_result(result)?)
+    };
+    let board = Board::from_fen(&fen, false)
+        .or_else(|_| Board::from_fen(&fen, true))
+        .map_err(|err| anyhow!("invalid FEN {fen}: {err:?}"))?;
+    Ok(Sample { board, result })
+}
+
+fn parse_result(raw: &str) -> Result<f64> {
+    let raw = raw.trim_matches(|c| c == '[' || c == ']' || c == '"' || c == ';');
+    match raw {
+        "1-0" => Ok(1.0),
+        "0-1" => Ok(0.0),
+        "1/2-1/2" => Ok(0.5),
+        _ => match raw.parse::<f64>() {
+            Ok(result) if (0.0..=1.0).contains(&result) => Ok(result),
+            _ => bail!("invalid result {raw}"),
+        },
+    }
+}
+
+/// Expected score for White of a White-relative evaluation in centipawns.
+pub fn expected_score(score_cp: f64, k: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-k * score_cp / 400.0))
+}
+
+/// Mean squared difference between the results and the evaluation's
+/// expected scores, computed in parallel.
+pub fn mean_error(samples: &[Sample], weights: &EvalWeights, k: f64) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let total: f64 = samples
+        .par_iter()
+        .map(|sample| {
+            let score = evaluate(&sample.board, weights);
+            let white_score = if sample.board.side_to_move() == Color::White {
+                score
+            } else {
+                -score
+            };
+            (sample.result - expected_score(white_score as f64, k)).powi(2)
+        })
+        .sum();
+    total / samples.len() as f64
+}
+
+/// The scaling constant that best fits the dataset to `weights` before any
+/// of them change, found by ternary search over a generous range.
+pub fn fit_scale(samples: &[Sample], weights: &EvalWeights) -> f64 {
+    let (mut low, mut high) = (0.05, 5.0);
+    for _ in 0..40 {
+        let left = low + (high - low) / 3.0;
+        let right = high - (high - low) / 3.0;
+        if mean_error(samples, weights, left) <= mean_error(samples, weights, right) {
+            high = right;
+        } else {
+            low = left;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// Every tunable term with its name, such as `eg_value.pawn` or
+/// `mg_pst.knight.e4`, where the square is seen from White's side. King
+/// values are left out because they cancel, and so is the king-pressure
+/// divisor, which is not additive. Pawn cells on the first and last rank are
+/// left out too, since no pawn ever stands there.
+pub fn parameters(weights: &mut EvalWeights) -> Vec<(String, &mut i32)> {
+    let mut parameters = Vec::new();
+    for (name, values) in [
+        ("mg_value", &mut weights.mg_value),
+        ("eg_value", &mut weights.eg_value),
+        ("mobility_mg", &mut weights.mobility_mg),
+        ("mobility_eg", &mut weights.mobility_eg),
+    ] {
+        for (piece, value) in PIECES.iter().zip(values) {
+            parameters.push((format!("{name}.{piece}"), value));
+        }
+    }
+    for (name, value) in [
+        ("bishop_pair_mg", &mut weights.bishop_pair_mg),
+        ("bishop_pair_eg", &mut weights.bishop_pair_eg),
+        ("doubled_pawn_mg", &mut weights.doubled_pawn_mg),
+        ("doubled_pawn_eg", &mut weights.doubled_pawn_eg),
+        ("isolated_pawn_mg", &mut weights.isolated_pawn_mg),
+        ("isolated_pawn_eg", &mut weights.isolated_pawn_eg),
+        ("connected_pawn_mg", &mut weights.connected_pawn_mg),
+        ("connected_pawn_eg", &mut weights.connected_pawn_eg),
+        ("passed_pawn_base_mg", &mut weights.passed_pawn_base_mg),
+        ("passed_pawn_rank_mg", &mut weights.passed_pawn_rank_mg),
+        ("passed_pawn_base_eg", &mut weights.passed_pawn_base_eg),
+        ("passed_pawn_rank_eg", &mut weights.passed_pawn_rank_eg),
+        ("king_shield_front_mg", &mut weights.king_shield_front_mg),
+        ("king_shield_front_eg", &mut weights.king_shield_front_eg),
+        (
+            "king_shield_missing_mg",
+            &mut weights.king_shield_missing_mg,
+        ),
+        ("king_shield_second_mg", &mut weights.king_shield_second_mg),
+        ("king_open_file_mg", &mut weights.king_open_file_mg),
+        (
+            "king_semi_open_file_mg",
+            &mut weights.king_semi_open_file_mg,
+        ),
+        ("king_attack_scale_mg", &mut weights.king_attack_scale_mg),
+    ] {
+        parameters.push((name.to_string(), value));
+    }
+    for (name, tables) in [
+        ("mg_pst", &mut weights.mg_pst),
+        ("eg_pst", &mut weights.eg_pst),
+    ] {
+        for (piece, table) in [
+            ("pawn", &mut tables.pawn),
+            ("knight", &mut tables.knight),
+            ("bishop", &mut tables.bishop),
+            ("rook", &mut tables.rook),
+            ("queen", &mut tables.queen),
+            ("king", &mut tables.king),
+        ] {
+            for (rank, row) in table.iter_mut().enumerate() {
+                if piece == "pawn" && (rank == 0 || rank == 7) {
+                    continue;
+                }
+                for (file, value) in row.iter_mut().enumerate() {
+                    let square = format!("{}{}", char::from(b'a' + file as u8), rank + 1);
+                    parameters.push((format!("{name}.{piece}.{square}"), value));
+                }
+            }
+        }
+    }
+    parameters
+}
+
+/// Which terms to tune: names from [`parameters`], where a prefix such as
+/// `mg_value` or `mg_pst.knight` selects every term under it. Empty tunes
+/// everything.
+#[derive(Debug, Clone, Default)]
+pub struct TuneConfig {
+    pub scale: f64,
+    pub max_passes: u32,
+    pub only: Vec<String>,
+}
+
+impl TuneConfig {
+    fn selects(&self, name: &str) -> bool {
+        self.only.is_empty()
+            || self.only.iter().any(|selected| {
+                name == selected
+                    || name
+                        .strip_prefix(selected.as_str())
+                        .is_some_and(|rest| rest.starts_with('.'))
+            })
+    }
+}
+
+/// Coordinate descent: each pass tries every selected term one centipawn up
+/// and then down, and keeps stepping in a direction for as long as the error
+/// falls. Stops after a pass without improvement or after `max_passes`;
+/// `on_pass` gets the pass number and the error after it.
+pub fn tune(
+    samples: &[Sample],
+    mut weights: EvalWeights,
+    config: &TuneConfig,
+    mut on_pass: impl FnMut(u32, f64),
+) -> EvalWeights {
+    let mut best = mean_error(samples, &weights, config.scale);
+    let names: Vec<String> = parameters(&mut weights)
+        .into_iter()
+        .map(|(name, _)| name)
+        .filter(|name| config.selects(name))
+        .collect();
+    for pass in 1..=config.max_passes {
+        let mut improved = false;
+        for name in &names {
+            for step in [1, -1] {
+                let mut moved = false;
+                loop {
+                    let mut candidate = weights.clone();
+                    if let Some((_, value)) = parameters(&mut candidate)
+                        .into_iter()
+                        .find(|(candidate_name, _)| candidate_name == name)
+                    {
+                        *value += step;
+                    }
+                    let error = mean_error(samples, &candidate, config.scale);
+                    if error >= best {
+                        break;
+                    }
+                    best = error;
+                    weights = candidate;
+                    moved = true;
+                }
+                if moved {
+                    improved = true;
+                    break;
+                }
+            }
+        }
+        on_pass(pass, best);
+        if !improved {
+            break;
+        }
+    }
+    weights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POSITIONS: &[&str] = &[
+        "4k3/pp6/8/8/8/8/PPPP4/4K3 w - - 0 1",
+        "4k3/ppp5/8/8/8/8/1PPPPP2/4K3 b - - 0 1",
+        "r3k3/pppp4/8/8/8/8/PPPPP3/R3K3 w - - 0 1",
+        "rn2k3/ppp5/8/8/8/8/PPPPPP2/RN2K3 w - - 0 1",
+        "q3k3/pppp4/8/8/8/8/PPP5/Q3K3 b - - 0 1",
+        "rnbqk3/ppppp3/8/8/8/8/PPP5/RNBQK3 w - - 0 1",
+        "4k3/p7/8/8/8/8/PPPP4/4K3 w - - 0 1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPP2/RNBQKBNR w KQkq - 0 1",
+        "rnbqkb1r/pppppppp/5n2/8/4N3/8/PPPPPPPP/R1BQKBNR w KQkq - 0 1",
+        "r1bqkbnr/pppppppp/8/4n3/8/2N5/PPPPPPPP/R1BQKBNR b KQkq - 0 1",
+    ];
+
+    #[test]
+    fn dataset_lines_accept_results_in_several_spellings() {
+        let samples = parse_dataset(
+            "# comment\n\
+             4k3/8/8/8/8/8/PPPP4/4K3 w - - 0 1 1-0\n\
+             4k3/8/8/8/8/8/PPPP4/4K3 w - - 0 1 [0.5]\n\
+             4k3/8/8/8/8/8/PPPP4/4K3 w - - 0 1 0-1\n\
+             \n\
+             {\"fen\":\"4k3/8/8/8/8/8/PPPP4/4K3 b - - 0 1\",\"outcome\":-1}\n",
+        )
+        .unwrap();
+
+        let results: Vec<f64> = samples.iter().map(|sample| sample.result).collect();
+        assert_eq!(results, [1.0, 0.5, 0.0, 1.0]);
+        assert!(parse_dataset("4k3/8/8/8/8/8/8/4K3 w - - 0 1 2-0").is_err());
+    }
+
+    #[test]
+    fn tuning_recovers_the_pawn_value_the_results_were_labelled_with() {
+        let mut target = EvalWeights::HANDCRAFTED;
+        target.mg_value[0] = 110;
+        target.eg_value[0] = 125;
+        target.mg_pst.knight[3][4] += 30;
+        let scale = 1.0;
+        let samples: Vec<Sample> = POSITIONS
+            .iter()
+            .map(|fen| {
+                let board: Board = fen.parse().unwrap();
+                let score = evaluate(&board, &target);
+                let white_score = if board.side_to_move() == Color::White {
+                    score
+                } else {
+                    -score
+                };
+                Sample {
+                    board,
+                    result: expected_score(white_score as f64, scale),
+                }
+            })
+            .collect();
+        let config = TuneConfig {
+            scale,
+            max_passes: 20,
+            only: vec![
+                "mg_value.pawn".to_string(),
+                "eg_value.pawn".to_string(),
+                "mg_pst.knight.e4".to_string(),
+            ],
+        };
+
+        let mut passes = 0;
+        let tuned = tune(&samples, EvalWeights::HANDCRAFTED, &config, |_, _| {
+            passes += 1
+        });
+
+        assert!(passes > 1);
+        assert!((tuned.mg_value[0] - 110).abs() <= 2, "{:?}", tuned.mg_value);
+        assert!((tuned.eg_value[0] - 125).abs() <= 2, "{:?}", tuned.eg_value);
+        let knight_e4 = tuned.mg_pst.knight[3][4];
+        assert!(
+            (knight_e4 - target.mg_pst.knight[3][4]).abs() <= 2,
+            "{knight_e4}"
+        );
+        assert_eq!(tuned.mg_value[1..], EvalWeights::HANDCRAFTED.mg_value[1..]);
+    }
+}
@@ -0,0 +1,89 @@
+use std::{env, fs, time::Instant};
+
+use anyhow::{Context, Result, anyhow, bail};
+use classical_engine::EvalWeights;
+use tuner::{TuneConfig, fit_scale, mean_error, parse_dataset, tune};
+
+const USAGE: &str = "usage: tuner --data <file> --out <weights.json> [--start <weights.json>] \
+[--params name,...] [--passes N] [--scale K]";
+
+struct Args {
+    data: String,
+    out: String,
+    start: Option<String>,
+    scale: Option<f64>,
+    config: TuneConfig,
+}
+
+fn main() -> Result<()> {
+    let args = parse_args(env::args().skip(1).collect())?;
+    let text =
+        fs::read_to_string(&args.data).with_context(|| format!("failed to read {}", args.data))?;
+    let samples = parse_dataset(&text).with_context(|| format!("invalid dataset {}", args.data))?;
+    let weights = match &args.start {
+        Some(path) => EvalWeights::load(path)?,
+        None => EvalWeights::HANDCRAFTED,
+    };
+
+    let started = Instant::now();
+    let mut config = args.config;
+    config.scale = args.scale.unwrap_or_else(|| fit_scale(&samples, &weights));
+    println!(
+        "{} positions, scale {:.4}, error {:.6}",
+        samples.len(),
+        config.scale,
+        mean_error(&samples, &weights, config.scale)
+    );
+    let tuned = tune(&samples, weights, &config, |pass, error| {
+        println!(
+            "pass {pass}: error {error:.6} ({:.0?} elapsed)",
+            started.elapsed()
+        );
+    });
+    fs::write(&args.out, serde_json::to_string_pretty(&tuned)? + "\n")
+        .with_context(|| format!("failed to write {}", args.out))?;
+    println!("wrote tuned weights to {}", args.out);
+    Ok(())
+}
+
+fn parse_args(raw: Vec<String>) -> Result<Args> {
+    let mut data = None;
+    let mut out = None;
+    let mut start = None;
+    let mut scale = None;
+    let mut config = TuneConfig {
+        max_passes: 100,
+        ..TuneConfig::default()
+    };
+
+    let mut raw = raw.into_iter();
+    while let Some(flag) = raw.next() {
+        let mut value = || {
+            raw.next()
+                .ok_or_else(|| anyhow!("{flag} needs a value\n{USAGE}"))
+        };
+        match flag.as_str() {
+            "--data" => data = Some(value()?),
+            "--out" => out = Some(value()?),
+            "--start" => start = Some(value()?),
+            "--scale" => scale = Some(value()?.parse()?),
+            "--passes" => config.max_passes = value()?.parse()?,
+            "--params" => {
+                config.only = value()?.split(',').map(str::to_string).collect();
+            }
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            other => bail!("unknown argument {other}\n{USAGE}"),
+        }
+    }
+
+    Ok(Args {
+        data: data.ok_or_else(|| anyhow!("--data is required\n{USAGE}"))?,
+        out: out.ok_or_else(|| anyhow!("--out is required\n{USAGE}"))?,
+        start,
+        scale,
+        config,
+    })
+}
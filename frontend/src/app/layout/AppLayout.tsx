@@ -25,6 +25,9 @@ function activeViewFromPath(pathname: string): WorkspaceView {
   if (pathname.startsWith("/replay")) {
     return "replay";
   }
+  if (pathname.startsWith("/analysis")) {
+    return "analysis";
+  }
   return "overview";
 }
 
@@ -2,6 +2,7 @@ import { act, renderHook } from "@testing-library/react";
 import { afterEach, describe, expect, it, vi } from "vitest";
 
 import { useLivePlayback } from "./livePlayback";
+import { liveRevealBacklogLimit } from "../features/watch/model";
 import { liveRevealDelayMs } from "./utils";
 
 describe("useLivePlayback", () => {
@@ -172,4 +173,112 @@ describe("useLivePlayback", () => {
     });
     expect(result.current.displayedLiveFrameCount).toBe(5);
   });
+
+  it("keeps an interactive game's browsed position while new moves arrive", () => {
+    const { result, rerender } = renderHook(
+      ({ activelyWatching, interactive, liveFrameCount, matchId }) =>
+        useLivePlayback({ activelyWatching, interactive, liveFrameCount, matchId }),
+      {
+        initialProps: {
+          activelyWatching: true,
+          interactive: true,
+          liveFrameCount: 5,
+          matchId: "match-1"
+        }
+      }
+    );
+
+    act(() => {
+      result.current.setSelectedLivePly(2);
+    });
+    rerender({
+      activelyWatching: true,
+      interactive: true,
+      liveFrameCount: 6,
+      matchId: "match-1"
+    });
+
+    expect(result.current.displayedLiveFrameCount).toBe(6);
+    expect(result.current.selectedLivePly).toBe(2);
+    expect(result.current.isLiveFollowing).toBe(false);
+
+    act(() => {
+      result.current.returnToLive();
+    });
+    expect(result.current.selectedLivePly).toBe(5);
+    expect(result.current.isLiveFollowing).toBe(true);
+  });
+
+  it("steps through history within the revealed frames", () => {
+    const { result } = renderHook(() =>
+      useLivePlayback({ activelyWatching: true, interactive: true, liveFrameCount: 4, matchId: "match-1" })
+    );
+
+    act(() => {
+      result.current.stepLivePly(-1);
+    });
+    expect(result.current.selectedLivePly).toBe(2);
+    expect(result.current.isLiveFollowing).toBe(false);
+
+    act(() => {
+      result.current.stepLivePly(-10);
+    });
+    expect(result.current.selectedLivePly).toBe(0);
+
+    act(() => {
+      result.current.stepLivePly(1);
+      result.current.stepLivePly(10);
+    });
+    expect(result.current.selectedLivePly).toBe(3);
+    expect(result.current.isLiveFollowing).toBe(true);
+  });
+
+  it("paces reveals at the chosen delay and shows everything at once without one", () => {
+    vi.useFakeTimers();
+
+    const { result, rerender } = renderHook(
+      ({ liveFrameCount, revealDelayMs }) =>
+        useLivePlayback({ activelyWatching: true, interactive: false, liveFrameCount, matchId: "match-1", revealDelayMs }),
+      { initialProps: { liveFrameCount: 2, revealDelayMs: 2000 } }
+    );
+
+    rerender({ liveFrameCount: 5, revealDelayMs: 2000 });
+    act(() => {
+      vi.advanceTimersByTime(liveRevealDelayMs);
+    });
+    expect(result.current.displayedLiveFrameCount).toBe(2);
+
+    act(() => {
+      vi.advanceTimersByTime(2000 - liveRevealDelayMs);
+    });
+    expect(result.current.displayedLiveFrameCount).toBe(3);
+
+    rerender({ liveFrameCount: 5, revealDelayMs: 0 });
+    expect(result.current.displayedLiveFrameCount).toBe(5);
+    expect(result.current.selectedLivePly).toBe(4);
+  });
+
+  it("skips ahead when the viewer falls more than the backlog limit behind", () => {
+    vi.useFakeTimers();
+
+    const { result, rerender } = renderHook(
+      ({ liveFrameCount }) =>
+        useLivePlayback({ activelyWatching: true, interactive: false, liveFrameCount, matchId: "match-1" }),
+      { initialProps: { liveFrameCount: 1 } }
+    );
+
+    rerender({ liveFrameCount: liveRevealBacklogLimit + 11 });
+    expect(result.current.displayedLiveFrameCount).toBe(1);
+
+    act(() => {
+      vi.advanceTimersByTime(liveRevealDelayMs);
+    });
+    expect(result.current.displayedLiveFrameCount).toBe(11);
+    expect(result.current.isLiveFollowing).toBe(true);
+
+    act(() => {
+      vi.advanceTimersByTime(liveRevealDelayMs);
+    });
+    expect(result.current.displayedLiveFrameCount).toBe(12);
+  });
 });
@@ -5,7 +5,8 @@ export type WorkspaceView =
   | "play-engine"
   | "events"
   | "tournaments"
-  | "replay";
+  | "replay"
+  | "analysis";
 
 export const workspaceViews: Array<{ id: WorkspaceView; label: string; detail: string; path: string }> = [
   { id: "overview", label: "Home", detail: "Live standings and recent games", path: "/" },
@@ -14,5 +15,6 @@ export const workspaceViews: Array<{ id: WorkspaceView; label: string; detail: s
   { id: "play-engine", label: "Play vs Engine", detail: "Launch and play human games", path: "/play-engine" },
   { id: "events", label: "Events", detail: "Start backend-defined runs", path: "/events" },
   { id: "tournaments", label: "Tournaments", detail: "Bracket-like matchup map", path: "/tournaments" },
-  { id: "replay", label: "Replay", detail: "Boards, moves and results", path: "/replay" }
+  { id: "replay", label: "Replay", detail: "Boards, moves and results", path: "/replay" },
+  { id: "analysis", label: "Analysis", detail: "Explore positions with an engine", path: "/analysis" }
 ];
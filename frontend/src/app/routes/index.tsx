@@ -1,6 +1,7 @@
 import { HashRouter, Route, Routes } from "react-router-dom";
 
 import { AppLayout } from "../layout/AppLayout";
+import { AnalysisPage } from "../../features/analysis/route";
 import { useDebugBootstrap } from "../../features/debug/useDebugBootstrap";
 import { EngineDetailPage, EnginesPage } from "../../features/engines/route";
 import { EventsPage } from "../../features/events/route";
@@ -30,6 +31,7 @@ export function AppRoutes() {
           <Route path="/events" element={<EventsPage />} />
           <Route path="/tournaments" element={<TournamentsPage />} />
           <Route path="/replay" element={<ReplayPage />} />
+          <Route path="/analysis" element={<AnalysisPage />} />
         </Route>
         <Route path="/watch/:matchId" element={<WatchPage />} />
       </Routes>
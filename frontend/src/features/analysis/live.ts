@@ -0,0 +1,70 @@
+import { useEffect, useReducer, useRef } from "react";
+
+import { wsUrl } from "../../app/api";
+import type { PositionAnalysis, Variant } from "../../shared/api/types";
+import { analysisLineCount, analysisSessionReducer, initialAnalysisSession } from "./session";
+
+type AnalysisWsServerMessage =
+  | ({ message_type: "analysis" } & PositionAnalysis)
+  | { message_type: "error"; error: string };
+
+/**
+ * Keeps one analysis socket open while the page is shown. The server runs
+ * `go infinite` on each started position and shuts the engine down when the
+ * socket closes, so leaving the page stops the analysis.
+ */
+export function useAnalysisSession(initialFen: string, variant: Variant) {
+  const [state, dispatch] = useReducer(analysisSessionReducer, initialFen, initialAnalysisSession);
+  const socketRef = useRef<WebSocket | null>(null);
+
+  useEffect(() => {
+    let closing = false;
+    const socket = new WebSocket(wsUrl("/analysis/ws"));
+    socketRef.current = socket;
+    socket.onmessage = (messageEvent) => {
+      try {
+        const message = JSON.parse(messageEvent.data) as AnalysisWsServerMessage;
+        if (message.message_type === "analysis") {
+          dispatch({ type: "report", analysis: message });
+        } else {
+          dispatch({ type: "error", error: message.error });
+        }
+      } catch {
+        // Ignore frames this client does not understand.
+      }
+    };
+    socket.onclose = () => {
+      if (!closing) {
+        dispatch({ type: "error", error: "The analysis connection closed." });
+      }
+    };
+    return () => {
+      closing = true;
+      socketRef.current = null;
+      socket.close();
+    };
+  }, []);
+
+  useEffect(() => {
+    const socket = socketRef.current;
+    if (!socket || state.status === "idle") {
+      return;
+    }
+    const send = () =>
+      socket.send(
+        JSON.stringify(
+          state.status === "running"
+            ? { message_type: "start", fen: state.fen, variant, multipv: analysisLineCount }
+            : { message_type: "stop" }
+        )
+      );
+    if (socket.readyState === WebSocket.OPEN) {
+      send();
+      return;
+    }
+    socket.addEventListener("open", send, { once: true });
+    return () => socket.removeEventListener("open", send);
+  }, [state.status, state.fen, variant]);
+
+  return { state, dispatch };
+}
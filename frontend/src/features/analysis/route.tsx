@@ -0,0 +1,158 @@
+import { useState } from "react";
+import { useSearchParams } from "react-router-dom";
+
+import type { BoardMoveMarker, Variant } from "../../shared/api/types";
+import {
+  boardIndexToSquare,
+  fenToBoard,
+  legalMovesByOrigin,
+  maybePromotion,
+  orientSquares,
+  squareIndex
+} from "../../shared/chess/board";
+import { formatEngineScore } from "../../shared/lib/format";
+import { BoardView, EmptyState, EvalBar, Field } from "../../shared/ui";
+import { useAnalysisSession } from "./live";
+import { fenAfterMoves, sanMoves } from "./session";
+
+const startFen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+export function AnalysisPage() {
+  const [searchParams] = useSearchParams();
+  const variant: Variant = searchParams.get("variant") === "chess960" ? "chess960" : "standard";
+  const { state, dispatch } = useAnalysisSession(searchParams.get("fen") || startFen, variant);
+  const [fenInput, setFenInput] = useState(state.fen);
+  const [fenError, setFenError] = useState("");
+  const [selectedSquare, setSelectedSquare] = useState("");
+  const [boardFlipped, setBoardFlipped] = useState(false);
+
+  const orientation = boardFlipped ? "black" : "white";
+  const boardSquares = fenToBoard(state.fen);
+  // Moves are played on the board with chess.js, which only knows standard chess.
+  const movesByOrigin = variant === "standard" ? legalMovesByOrigin(state.fen) : new Map<string, BoardMoveMarker[]>();
+  const running = state.status === "running";
+  const lines = state.analysis?.lines ?? [];
+  const bestMove = lines[0]?.moves_uci[0];
+
+  function showPosition(fen: string) {
+    dispatch({ type: "position", fen });
+    setFenInput(fen);
+    setFenError("");
+    setSelectedSquare("");
+  }
+
+  function loadFen() {
+    const fen = fenInput.trim();
+    const next = variant === "standard" ? fenAfterMoves(fen, []) : fen;
+    if (!next) {
+      setFenError("That is not a legal position.");
+      return;
+    }
+    showPosition(next);
+  }
+
+  function playLine(movesUci: string[]) {
+    const next = fenAfterMoves(state.fen, movesUci);
+    if (next) {
+      showPosition(next);
+    }
+  }
+
+  function handleSquareClick(index: number) {
+    const square = boardIndexToSquare(index, orientation);
+    const targets = movesByOrigin.get(selectedSquare) ?? [];
+    if (targets.some((marker) => marker.square === square)) {
+      const piece = boardSquares[squareIndex(selectedSquare)];
+      playLine([`${selectedSquare}${square}${maybePromotion(selectedSquare, square, piece)}`]);
+      return;
+    }
+    setSelectedSquare(movesByOrigin.has(square) ? square : "");
+  }
+
+  return (
+    <section className="panel analysis-panel">
+      <div className="panel-header">
+        <h2>Analysis</h2>
+        <span>
+          {running
+            ? state.analysis?.depth
+              ? `Searching, depth ${state.analysis.depth}`
+              : "Searching..."
+            : state.status === "stopped"
+              ? "Stopped"
+              : "Engine idle"}
+        </span>
+      </div>
+      {state.error && <section className="banner banner-error">{state.error}</section>}
+
+      <div className="replay-content">
+        <div className="board-stage">
+          <BoardView
+            squares={orientSquares(boardSquares, orientation)}
+            selectedSquare={selectedSquare}
+            legalMoveMarkers={movesByOrigin.get(selectedSquare) ?? []}
+            interactive={movesByOrigin.size > 0}
+            hoverableSquares={new Set(movesByOrigin.keys())}
+            onSquareClick={handleSquareClick}
+            engineArrows={bestMove ? [{ from: bestMove.slice(0, 2), to: bestMove.slice(2, 4) }] : []}
+            orientation={orientation}
+          />
+          <EvalBar score={state.analysis?.score ?? null} pending={running && !state.analysis} />
+        </div>
+
+        <div className="replay-meta">
+          <Field label="Position" hint={fenError || "Paste a FEN or play moves on the board"}>
+            <input value={fenInput} onChange={(event) => setFenInput(event.target.value)} />
+          </Field>
+          <div className="analysis-actions">
+            <button type="button" onClick={() => dispatch({ type: running ? "stop" : "start" })}>
+              {running ? "Stop" : "Analyze"}
+            </button>
+            <button type="button" className="button-ghost" onClick={loadFen}>
+              Load FEN
+            </button>
+            <button type="button" className="button-ghost" onClick={() => showPosition(startFen)}>
+              Start position
+            </button>
+            <label className="checkbox">
+              <input
+                type="checkbox"
+                checked={boardFlipped}
+                onChange={(event) => setBoardFlipped(event.target.checked)}
+              />
+              Flip board
+            </label>
+          </div>
+
+          {lines.length > 0 ? (
+            <div className="analysis-lines">
+              {lines.map((line, rank) => (
+                <div className="analysis-line" key={rank}>
+                  <strong>{formatEngineScore(line.score)}</strong>
+                  <span>
+                    {sanMoves(state.fen, line.moves_uci).map((move, index) => (
+                      <button
+                        type="button"
+                        className="analysis-line-move"
+                        key={`${index}-${move}`}
+                        onClick={() => playLine(line.moves_uci.slice(0, index + 1))}
+                      >
+                        {move}
+                      </button>
+                    ))}
+                  </span>
+                </div>
+              ))}
+            </div>
+          ) : (
+            <EmptyState>
+              {running
+                ? "Waiting for the engine's first lines..."
+                : "Start the analysis to see the engine's best lines. Click a move in a line to play up to it."}
+            </EmptyState>
+          )}
+        </div>
+      </div>
+    </section>
+  );
+}
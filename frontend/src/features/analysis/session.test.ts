@@ -0,0 +1,71 @@
+import { describe, expect, it } from "vitest";
+
+import type { PositionAnalysis } from "../../shared/api/types";
+import { analysisSessionReducer, fenAfterMoves, initialAnalysisSession, sanMoves } from "./session";
+
+const startFen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+const afterE4 = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1";
+
+function report(fen: string, value: number): PositionAnalysis {
+  return {
+    fen,
+    engine_version_id: "engine-1",
+    score: { kind: "centipawns", value },
+    depth: 10,
+    lines: [{ score: { kind: "centipawns", value }, moves_uci: ["e2e4"] }]
+  };
+}
+
+describe("analysisSessionReducer", () => {
+  it("shows reports only while running", () => {
+    let state = analysisSessionReducer(initialAnalysisSession(startFen), { type: "report", analysis: report(startFen, 20) });
+    expect(state.analysis).toBeNull();
+
+    state = analysisSessionReducer(state, { type: "start" });
+    state = analysisSessionReducer(state, { type: "report", analysis: report(startFen, 20) });
+    expect(state.status).toBe("running");
+    expect(state.analysis?.score).toEqual({ kind: "centipawns", value: 20 });
+
+    state = analysisSessionReducer(state, { type: "stop" });
+    state = analysisSessionReducer(state, { type: "report", analysis: report(startFen, 35) });
+    expect(state.status).toBe("stopped");
+    expect(state.analysis?.score).toEqual({ kind: "centipawns", value: 20 });
+  });
+
+  it("drops the old report and late replies when the position changes", () => {
+    let state = analysisSessionReducer(initialAnalysisSession(startFen), { type: "start" });
+    state = analysisSessionReducer(state, { type: "report", analysis: report(startFen, 20) });
+
+    state = analysisSessionReducer(state, { type: "position", fen: afterE4 });
+    expect(state).toMatchObject({ status: "running", fen: afterE4, analysis: null });
+
+    state = analysisSessionReducer(state, { type: "report", analysis: report(startFen, 25) });
+    expect(state.analysis).toBeNull();
+    state = analysisSessionReducer(state, { type: "report", analysis: report(afterE4, 30) });
+    expect(state.analysis?.fen).toBe(afterE4);
+
+    expect(analysisSessionReducer(state, { type: "position", fen: afterE4 })).toBe(state);
+  });
+
+  it("stops on errors and clears them on restart", () => {
+    let state = analysisSessionReducer(initialAnalysisSession(startFen), { type: "start" });
+    state = analysisSessionReducer(state, { type: "error", error: "invalid FEN" });
+    expect(state).toMatchObject({ status: "stopped", error: "invalid FEN" });
+
+    state = analysisSessionReducer(state, { type: "start" });
+    expect(state).toMatchObject({ status: "running", error: "" });
+  });
+});
+
+describe("analysis lines", () => {
+  it("spells lines in SAN and keeps moves it cannot play in UCI", () => {
+    expect(sanMoves(startFen, ["e2e4", "e7e5", "g1f3"])).toEqual(["e4", "e5", "Nf3"]);
+    expect(sanMoves(startFen, ["e2e4", "e2e4", "g1f3"])).toEqual(["e4", "e2e4", "g1f3"]);
+    expect(sanMoves("7k/P7/8/8/8/8/8/K7 w - - 0 1", ["a7a8q"])).toEqual(["a8=Q+"]);
+  });
+
+  it("plays a line up to a clicked move", () => {
+    expect(fenAfterMoves(startFen, ["e2e4"])).toBe(afterE4);
+    expect(fenAfterMoves(startFen, ["e2e5"])).toBeNull();
+  });
+});
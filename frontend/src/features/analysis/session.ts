@@ -0,0 +1,85 @@
+import { Chess } from "chess.js";
+
+import type { PositionAnalysis } from "../../shared/api/types";
+
+export const analysisLineCount = 3;
+
+export type AnalysisStatus = "idle" | "running" | "stopped";
+
+/** What the analysis tab is searching and the latest report for it. */
+export interface AnalysisSessionState {
+  status: AnalysisStatus;
+  fen: string;
+  analysis: PositionAnalysis | null;
+  error: string;
+}
+
+export type AnalysisSessionEvent =
+  | { type: "start" }
+  | { type: "stop" }
+  | { type: "position"; fen: string }
+  | { type: "report"; analysis: PositionAnalysis }
+  | { type: "error"; error: string };
+
+export function initialAnalysisSession(fen: string): AnalysisSessionState {
+  return { status: "idle", fen, analysis: null, error: "" };
+}
+
+/**
+ * Moving to another position drops the old report at once; reports still
+ * in flight for an earlier position, or arriving after a stop, are ignored.
+ */
+export function analysisSessionReducer(
+  state: AnalysisSessionState,
+  event: AnalysisSessionEvent
+): AnalysisSessionState {
+  switch (event.type) {
+    case "start":
+      return { ...state, status: "running", analysis: null, error: "" };
+    case "stop":
+      return state.status === "running" ? { ...state, status: "stopped" } : state;
+    case "position":
+      return event.fen === state.fen ? state : { ...state, fen: event.fen, analysis: null, error: "" };
+    case "report":
+      return state.status === "running" && event.analysis.fen === state.fen
+        ? { ...state, analysis: event.analysis }
+        : state;
+    case "error":
+      return { ...state, status: "stopped", error: event.error };
+  }
+}
+
+/** A UCI line in SAN, falling back to UCI from the first move that does not apply. */
+export function sanMoves(fen: string, movesUci: string[]) {
+  const san: string[] = [];
+  try {
+    const chess = new Chess(fen);
+    for (const move of movesUci) {
+      san.push(chess.move(uciMove(move)).san);
+    }
+  } catch {
+    // The rest of the line stays in UCI.
+  }
+  return [...san, ...movesUci.slice(san.length)];
+}
+
+/** The FEN after playing `movesUci` from `fen`, or `null` if one is illegal. */
+export function fenAfterMoves(fen: string, movesUci: string[]) {
+  try {
+    const chess = new Chess(fen);
+    for (const move of movesUci) {
+      chess.move(uciMove(move));
+    }
+    return chess.fen();
+  } catch {
+    return null;
+  }
+}
+
+function uciMove(move: string) {
+  return {
+    from: move.slice(0, 2),
+    to: move.slice(2, 4),
+    promotion: move.length > 4 ? (move[4] as "q" | "r" | "b" | "n") : undefined
+  };
+}
@@ -110,6 +110,9 @@ export function EnginesPage() {
                     {formatVariant(pool.variant)} • {formatTimeControl(pool.time_control)}
                   </p>
                   <p>{pool.fairness.swap_colors ? "Colors swap between paired games." : "Colors stay as scheduled."}</p>
+                  {pool.fairness.adjudication?.resign || pool.fairness.adjudication?.draw ? (
+                    <p>Decided games are adjudicated from the engines' scores.</p>
+                  ) : null}
                   {pool.description ? <p>{pool.description}</p> : null}
                 </div>
                 <div className="chip">{selectedPoolId === pool.id ? "Selected" : "Format"}</div>
@@ -9,6 +9,10 @@ export interface StartHumanGameInput {
   pool_id: string;
   engine_version_id: string;
   human_side: "white" | "black" | "random";
+  start_fen?: string;
+  pgn?: string;
+  /** UCI Skill Level from 0 to 20; games below full strength are unrated. */
+  engine_skill_level?: number;
 }
 
 export function useStartHumanGameMutation() {
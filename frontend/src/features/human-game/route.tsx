@@ -9,6 +9,7 @@ import { formatTimeControl, formatVariant } from "../../shared/lib/format";
 import { findPoolForChoices, timeControlKey, uniquePoolTimeControls, uniquePoolVariants } from "../../shared/lib/pools";
 import { supportsVariant } from "../../shared/lib/variants";
 import { useStartHumanGameMutation } from "./api";
+import { FULL_STRENGTH, type HumanSide, loadHumanGameSettings, saveHumanGameSettings } from "./settings";
 
 export function HumanGamePage() {
   const navigate = useNavigate();
@@ -18,11 +19,15 @@ export function HumanGamePage() {
   const pools = usePoolsQuery();
   const humanProfile = useHumanProfileQuery();
   const startHumanGame = useStartHumanGameMutation();
+  const [savedSettings] = useState(loadHumanGameSettings);
   const [humanGameName, setHumanGameName] = useState("");
-  const [humanVariant, setHumanVariant] = useState<Variant | "">("");
-  const [humanTimeControlKey, setHumanTimeControlKey] = useState("");
-  const [humanEngineId, setHumanEngineId] = useState("");
-  const [humanSide, setHumanSide] = useState<"white" | "black" | "random">("random");
+  const [humanVariant, setHumanVariant] = useState<Variant | "">(savedSettings.variant);
+  const [humanTimeControlKey, setHumanTimeControlKey] = useState(savedSettings.time_control_key);
+  const [humanEngineId, setHumanEngineId] = useState(savedSettings.engine_version_id);
+  const [humanSide, setHumanSide] = useState<HumanSide>(savedSettings.human_side);
+  const [startSource, setStartSource] = useState<"opening" | "fen" | "pgn">("opening");
+  const [startPosition, setStartPosition] = useState("");
+  const [skillLevel, setSkillLevel] = useState(savedSettings.skill_level);
 
   const playablePools = pools.data ?? [];
   const variantChoices = uniquePoolVariants(playablePools);
@@ -33,17 +38,28 @@ export function HumanGamePage() {
     : (versions.data ?? []);
 
   useEffect(() => {
-    if (!humanVariant && variantChoices[0]) {
+    // A remembered choice that is no longer registered falls back to the first one.
+    if (variantChoices[0] && !variantChoices.includes(humanVariant as Variant)) {
       setHumanVariant(variantChoices[0]);
     }
   }, [humanVariant, variantChoices]);
 
   useEffect(() => {
-    if (!humanTimeControlKey && timeControlChoices[0]) {
+    if (timeControlChoices[0] && !timeControlChoices.some((choice) => timeControlKey(choice) === humanTimeControlKey)) {
       setHumanTimeControlKey(timeControlKey(timeControlChoices[0]));
     }
   }, [humanTimeControlKey, timeControlChoices]);
 
+  useEffect(() => {
+    saveHumanGameSettings({
+      variant: humanVariant,
+      time_control_key: humanTimeControlKey,
+      engine_version_id: humanEngineId,
+      human_side: humanSide,
+      skill_level: skillLevel
+    });
+  }, [humanEngineId, humanSide, humanTimeControlKey, humanVariant, skillLevel]);
+
   useEffect(() => {
     if (humanEngineId && selectedPool) {
       const currentVersion = versions.data?.find((version) => version.id === humanEngineId);
@@ -67,6 +83,8 @@ export function HumanGamePage() {
     return <RouteErrorState message={error.message} />;
   }
 
+  const selectedVersion = versions.data?.find((version) => version.id === humanEngineId);
+  const supportsSkillLevel = selectedVersion?.tags.includes("classical") ?? false;
   const agentNameById = Object.fromEntries((agents.data ?? []).map((agent) => [agent.id, agent.name]));
   const versionNameById = Object.fromEntries(
     (versions.data ?? []).map((version) => [
@@ -83,6 +101,12 @@ export function HumanGamePage() {
       return;
     }
 
+    const setupText = startPosition.trim();
+    if (startSource !== "opening" && !setupText) {
+      showError(startSource === "fen" ? "Paste a FEN to start from." : "Paste a PGN to continue from.");
+      return;
+    }
+
     const engineName = versionNameById[humanEngineId] ?? "Engine";
     const chosenName = humanGameName.trim() || `You vs ${engineName}`;
 
@@ -91,9 +115,13 @@ export function HumanGamePage() {
         name: chosenName,
         pool_id: selectedPool.id,
         engine_version_id: humanEngineId,
-        human_side: humanSide
+        human_side: humanSide,
+        start_fen: startSource === "fen" ? setupText : undefined,
+        pgn: startSource === "pgn" ? setupText : undefined,
+        engine_skill_level: supportsSkillLevel && skillLevel < FULL_STRENGTH ? skillLevel : undefined
       });
       setHumanGameName("");
+      setStartPosition("");
       navigate(`/watch/${encodeURIComponent(response.match_id)}`);
     } catch (mutationError) {
       showError(mutationError instanceof Error ? mutationError.message : "Request failed");
@@ -170,7 +198,7 @@ export function HumanGamePage() {
             </select>
           </Field>
           <Field label="Your side">
-            <select value={humanSide} onChange={(event) => setHumanSide(event.target.value as "white" | "black" | "random")}>
+            <select value={humanSide} onChange={(event) => setHumanSide(event.target.value as HumanSide)}>
               <option value="white">White</option>
               <option value="black">Black</option>
               <option value="random">Random</option>
@@ -178,6 +206,52 @@ export function HumanGamePage() {
           </Field>
         </div>
 
+        {supportsSkillLevel ? (
+          <Field
+            label={`Engine strength: ${skillLevel === FULL_STRENGTH ? "full" : `level ${skillLevel}`}`}
+            hint={skillLevel < FULL_STRENGTH ? "Games below full strength are unrated" : undefined}
+          >
+            <input
+              type="range"
+              min={0}
+              max={FULL_STRENGTH}
+              step={1}
+              value={skillLevel}
+              onChange={(event) => setSkillLevel(Number(event.target.value))}
+            />
+          </Field>
+        ) : null}
+
+        <Field
+          label="Start position"
+          hint={startSource === "pgn" ? "The game continues after the last move" : undefined}
+        >
+          <select
+            value={startSource}
+            onChange={(event) => setStartSource(event.target.value as "opening" | "fen" | "pgn")}
+          >
+            <option value="opening">Pool opening</option>
+            <option value="fen">Set up from FEN</option>
+            <option value="pgn">Continue a PGN</option>
+          </select>
+        </Field>
+        {startSource !== "opening" ? (
+          <Field label={startSource === "fen" ? "FEN" : "PGN"}>
+            <textarea
+              className="replay-textarea"
+              rows={startSource === "fen" ? 2 : 6}
+              value={startPosition}
+              onChange={(event) => setStartPosition(event.target.value)}
+              placeholder={
+                startSource === "fen"
+                  ? "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1"
+                  : "1. e4 e5 2. Nf3 Nc6 3. Bb5 *"
+              }
+              spellCheck={false}
+            />
+          </Field>
+        ) : null}
+
         <div className="duel-preview">
           <EngineSideCard
             side="white"
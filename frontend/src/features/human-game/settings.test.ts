@@ -0,0 +1,36 @@
+import { describe, expect, it } from "vitest";
+
+import { defaultHumanGameSettings, parseHumanGameSettings, type HumanGameSettings } from "./settings";
+
+describe("parseHumanGameSettings", () => {
+  it("round-trips saved settings", () => {
+    const settings: HumanGameSettings = {
+      variant: "chess960",
+      time_control_key: "120000:1000",
+      engine_version_id: "engine-version",
+      human_side: "black",
+      skill_level: 6
+    };
+
+    expect(parseHumanGameSettings(JSON.stringify(settings))).toEqual(settings);
+  });
+
+  it("falls back per field on unknown fields and bad values", () => {
+    const stored = JSON.stringify({
+      variant: "crazyhouse",
+      time_control_key: 60,
+      engine_version_id: "engine-version",
+      human_side: "both",
+      skill_level: 99,
+      board_theme: "walnut"
+    });
+
+    expect(parseHumanGameSettings(stored)).toEqual({
+      ...defaultHumanGameSettings,
+      engine_version_id: "engine-version",
+      skill_level: 20
+    });
+    expect(parseHumanGameSettings("{not json")).toEqual(defaultHumanGameSettings);
+    expect(parseHumanGameSettings(null)).toEqual(defaultHumanGameSettings);
+  });
+});
@@ -0,0 +1,71 @@
+import type { Variant } from "../../shared/api/types";
+
+export const humanGameSettingsKey = "arena:human-game-settings";
+export const FULL_STRENGTH = 20;
+
+export type HumanSide = "white" | "black" | "random";
+
+/** The Play vs Engine choices remembered between visits. */
+export interface HumanGameSettings {
+  variant: Variant | "";
+  time_control_key: string;
+  engine_version_id: string;
+  human_side: HumanSide;
+  skill_level: number;
+}
+
+export const defaultHumanGameSettings: HumanGameSettings = {
+  variant: "",
+  time_control_key: "",
+  engine_version_id: "",
+  human_side: "random",
+  skill_level: FULL_STRENGTH
+};
+
+/**
+ * Reads stored settings field by field: unknown fields are ignored and any
+ * missing or malformed value falls back to its default, so an old or
+ * hand-edited entry never blocks the setup form.
+ */
+export function parseHumanGameSettings(raw: string | null): HumanGameSettings {
+  let stored: unknown;
+  try {
+    stored = raw ? JSON.parse(raw) : null;
+  } catch {
+    stored = null;
+  }
+  if (!stored || typeof stored !== "object") {
+    return { ...defaultHumanGameSettings };
+  }
+  const value = stored as Record<string, unknown>;
+  const text = (field: unknown) => (typeof field === "string" ? field : "");
+  return {
+    variant: value.variant === "standard" || value.variant === "chess960" ? value.variant : "",
+    time_control_key: text(value.time_control_key),
+    engine_version_id: text(value.engine_version_id),
+    human_side:
+      value.human_side === "white" || value.human_side === "black" || value.human_side === "random"
+        ? value.human_side
+        : defaultHumanGameSettings.human_side,
+    skill_level:
+      typeof value.skill_level === "number" && Number.isInteger(value.skill_level)
+        ? Math.min(FULL_STRENGTH, Math.max(0, value.skill_level))
+        : defaultHumanGameSettings.skill_level
+  };
+}
+
+export function loadHumanGameSettings() {
+  try {
+    return parseHumanGameSettings(window.localStorage.getItem(humanGameSettingsKey));
+  } catch {
+    return { ...defaultHumanGameSettings };
+  }
+}
+
+export function saveHumanGameSettings(settings: HumanGameSettings) {
+  try {
+    window.localStorage.setItem(humanGameSettingsKey, JSON.stringify(settings));
+  } catch {
+    // Ignore storage failures.
+  }
+}
@@ -1,7 +1,7 @@
 import { useNavigate } from "react-router-dom";
 
 import { useEventPresetsQuery, useGamesQuery, useLeaderboardQuery, useMatchesQuery, usePoolsQuery, useTournamentsQuery, useAgentVersionsQuery } from "../../shared/queries/arena";
-import { formatLabel, formatTimeControl, formatVariant, matchResultText } from "../../shared/lib/format";
+import { formatEloEstimate, formatLabel, formatTimeControl, formatVariant, matchResultText } from "../../shared/lib/format";
 import { participantName } from "../../shared/lib/participants";
 import { EmptyState, RouteErrorState, RouteLoadingState, StatusBadge } from "../../shared/ui";
 
@@ -141,6 +141,7 @@ export function OverviewPage() {
                   <strong>{participantName(entry.participant, entry.participant.id)}</strong>
                   <p>
                     {entry.games_played} games • {entry.wins}W {entry.draws}D {entry.losses}L
+                    {entry.performance ? ` • ${formatEloEstimate(entry.performance)}` : null}
                   </p>
                 </div>
                 <div className="leader-rating">{entry.rating.toFixed(1)}</div>
@@ -1,6 +1,15 @@
-import type { GameRecord, MatchSeries, Tournament } from "../../app/types";
-import { formatLabel, formatTournamentKind, matchResultText, roundLabel, statusTone, winnerText } from "../../shared/lib/format";
+import type { GameRecord, GameStats, MatchSeries, OpeningScore, Tournament } from "../../app/types";
+import {
+  formatDuration,
+  formatLabel,
+  formatTournamentKind,
+  matchResultText,
+  roundLabel,
+  statusTone,
+  winnerText
+} from "../../shared/lib/format";
 import { participantName } from "../../shared/lib/participants";
+import { useTournamentOpeningsQuery, useTournamentStatsQuery } from "../../shared/queries/arena";
 import { StatusBadge } from "../../shared/ui";
 
 export function TournamentMapCard({
@@ -24,6 +33,8 @@ export function TournamentMapCard({
   }
 
   const completedCount = matches.filter((match) => match.status === "completed").length;
+  const stats = useTournamentStatsQuery(tournament.id, 3000).data;
+  const openings = useTournamentOpeningsQuery(tournament.id, 3000).data ?? [];
 
   return (
     <div className="tournament-card">
@@ -38,10 +49,23 @@ export function TournamentMapCard({
             {tournament.participant_version_ids.length} participants • {completedCount}/{matches.length} matches
             finished
           </p>
+          {stats && stats.games > 0 ? <p>{gameStatsText(stats)}</p> : null}
         </div>
         <StatusBadge tone={statusTone(tournament.status)}>{formatLabel(tournament.status)}</StatusBadge>
       </div>
 
+      {openings.length > 1 ? (
+        <div className="opening-scores">
+          <div className="section-heading">Openings</div>
+          {openings.map((opening) => (
+            <div className="info-row" key={opening.start_fen}>
+              <span>{opening.label ?? opening.start_fen}</span>
+              <span className="subtle">{openingScoreText(opening)}</span>
+            </div>
+          ))}
+        </div>
+      ) : null}
+
       <div className="tournament-rounds">
         {[...rounds.entries()].map(([roundIndex, roundMatches]) => (
           <div className="tournament-round" key={roundIndex}>
@@ -84,3 +108,23 @@ export function TournamentMapCard({
     </div>
   );
 }
+
+function gameStatsText(stats: GameStats) {
+  const terminations = Object.entries(stats.terminations)
+    .sort(([, left], [, right]) => (right ?? 0) - (left ?? 0))
+    .map(([termination, count]) => `${count} ${formatLabel(termination)}`)
+    .join(", ");
+  return (
+    `${stats.white_wins} white wins, ${stats.black_wins} black wins, ${stats.draws} draws • ` +
+    `${Math.round(stats.average_plies)} plies and ${formatDuration(stats.average_duration_ms)} per game on average • ` +
+    terminations
+  );
+}
+
+function openingScoreText(opening: OpeningScore) {
+  return (
+    `${opening.games} game${opening.games === 1 ? "" : "s"} • ` +
+    `+${opening.white_wins} =${opening.draws} -${opening.black_wins} • ` +
+    `White scores ${Math.round(opening.white_score * 100)}%`
+  );
+}
@@ -0,0 +1,37 @@
+import { describe, expect, it } from "vitest";
+
+import { engineScoreWhiteShare, formatEngineScore } from "../../shared/lib/format";
+import { isCurrentAnalysis } from "./analysis";
+
+const startFen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+const afterE4 = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1";
+
+describe("engine analysis", () => {
+  it("only accepts analysis of the position currently shown", () => {
+    const analysis = {
+      fen: startFen,
+      engine_version_id: "engine-1",
+      score: { kind: "centipawns" as const, value: 20 }
+    };
+
+    expect(isCurrentAnalysis(analysis, startFen)).toBe(true);
+    expect(isCurrentAnalysis(analysis, afterE4)).toBe(false);
+    expect(isCurrentAnalysis(null, startFen)).toBe(false);
+    expect(isCurrentAnalysis(analysis, "")).toBe(false);
+  });
+
+  it("formats centipawn and mate scores", () => {
+    expect(formatEngineScore({ kind: "centipawns", value: 35 })).toBe("+0.35");
+    expect(formatEngineScore({ kind: "centipawns", value: -120 })).toBe("-1.20");
+    expect(formatEngineScore({ kind: "centipawns", value: 0 })).toBe("0.00");
+    expect(formatEngineScore({ kind: "mate", value: 3 })).toBe("M3");
+    expect(formatEngineScore({ kind: "mate", value: -2 })).toBe("-M2");
+  });
+
+  it("pins the eval bar to the winning side on mate", () => {
+    expect(engineScoreWhiteShare({ kind: "centipawns", value: 0 })).toBe(0.5);
+    expect(engineScoreWhiteShare({ kind: "centipawns", value: 400 })).toBeGreaterThan(0.7);
+    expect(engineScoreWhiteShare({ kind: "mate", value: 1 })).toBe(1);
+    expect(engineScoreWhiteShare({ kind: "mate", value: -1 })).toBe(0);
+  });
+});
@@ -0,0 +1,37 @@
+import { useEffect, useState } from "react";
+
+import { fetchJson } from "../../app/api";
+import type { PositionAnalysis, Variant } from "../../shared/api/types";
+
+/** Analysis replies can arrive after the board moved on; only the one for the shown position counts. */
+export function isCurrentAnalysis(analysis: PositionAnalysis | null, fen: string) {
+  return Boolean(analysis && fen && analysis.fen === fen);
+}
+
+export function useEngineAnalysis({ fen, variant, enabled }: { fen: string; variant: Variant; enabled: boolean }) {
+  const [analysis, setAnalysis] = useState<PositionAnalysis | null>(null);
+
+  useEffect(() => {
+    if (!enabled || !fen) {
+      return;
+    }
+    let cancelled = false;
+    void fetchJson<PositionAnalysis>("/analysis", {
+      method: "POST",
+      body: JSON.stringify({ fen, variant })
+    })
+      .then((next) => {
+        if (!cancelled && isCurrentAnalysis(next, fen)) {
+          setAnalysis(next);
+        }
+      })
+      .catch(() => {
+        // Analysis is advisory; the eval bar keeps its last value.
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [enabled, fen, variant]);
+
+  return enabled && isCurrentAnalysis(analysis, fen) ? analysis : null;
+}
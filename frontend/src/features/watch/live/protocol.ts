@@ -1,4 +1,5 @@
 import type {
+  EngineThinkingEvent,
   GameTermination,
   LiveErrorMessage,
   LiveIntentAck,
@@ -60,6 +61,7 @@ export function snapshotFromEvent(current: LiveMatchSnapshot, event: LiveProtoco
         status: event.status,
         fen: event.fen,
         moves: event.moves,
+        move_times_ms: event.move_times_ms,
         white_remaining_ms: event.white_remaining_ms,
         black_remaining_ms: event.black_remaining_ms,
         side_to_move: event.side_to_move,
@@ -88,6 +90,7 @@ export function snapshotFromEvent(current: LiveMatchSnapshot, event: LiveProtoco
         termination: event.termination,
         fen: event.fen,
         moves: event.moves,
+        move_times_ms: event.move_times_ms,
         white_remaining_ms: event.white_remaining_ms,
         black_remaining_ms: event.black_remaining_ms,
         side_to_move: event.side_to_move,
@@ -97,7 +100,11 @@ export function snapshotFromEvent(current: LiveMatchSnapshot, event: LiveProtoco
 }
 
 export function isProtocolEvent(message: LiveWsServerMessage): message is LiveProtocolEvent {
-  return "event_type" in message;
+  return "event_type" in message && message.event_type !== "engine_thinking";
+}
+
+export function isEngineThinking(message: LiveWsServerMessage): message is EngineThinkingEvent {
+  return "event_type" in message && message.event_type === "engine_thinking";
 }
 
 export function isIntentAck(message: LiveWsServerMessage): message is LiveIntentAck {
@@ -3,14 +3,17 @@ import { useEffect, useRef, useState } from "react";
 import { createClientActionId, recordWsDebug, setUiDebugState } from "../../../app/debug";
 import { fetchJson, wsUrl } from "../../../app/api";
 import type {
+  EngineThinkingEvent,
   LiveMatchSnapshot,
   LiveProtocolEvent,
   LiveSubmitMoveMessage,
+  LiveTakebackMessage,
   LiveWsClientMessage,
   LiveWsServerMessage
 } from "../../../shared/api/types";
 import {
   type ConfirmedLiveState,
+  isEngineThinking,
   isErrorMessage,
   isIntentAck,
   isMissingLiveStateError,
@@ -21,6 +24,7 @@ import { isTerminalSnapshot, reduceEvent } from "./reducer";
 const transientMissingLiveStateRetryLimit = 5;
 export function useConfirmedLiveMatch(matchId: string) {
   const [state, setState] = useState<ConfirmedLiveState | null>(null);
+  const [thinking, setThinking] = useState<EngineThinkingEvent | null>(null);
   const [error, setError] = useState("");
   const [isConnected, setIsConnected] = useState(false);
   const stateRef = useRef<ConfirmedLiveState | null>(null);
@@ -33,6 +37,7 @@ export function useConfirmedLiveMatch(matchId: string) {
   useEffect(() => {
     if (!matchId) {
       setState(null);
+      setThinking(null);
       stateRef.current = null;
       setError("");
       setIsConnected(false);
@@ -91,6 +96,10 @@ export function useConfirmedLiveMatch(matchId: string) {
         missingLiveStateRetryCountRef.current = 0;
         stateRef.current = result.state;
         setState(result.state);
+        if (event.event_type !== "clock_sync") {
+          // A committed move or finished game makes the last search report stale.
+          setThinking(null);
+        }
         setUiDebugState({
           current_snapshot_seq: result.state.snapshot?.seq,
           current_live_status: result.state.snapshot?.status,
@@ -153,6 +162,13 @@ export function useConfirmedLiveMatch(matchId: string) {
             handleProtocolEvent(message);
             return;
           }
+          if (isEngineThinking(message)) {
+            // Progress for an older position can still arrive after the move it led to.
+            if (message.seq >= (stateRef.current?.snapshot?.seq ?? 0)) {
+              setThinking(message);
+            }
+            return;
+          }
           if (isErrorMessage(message)) {
             const shouldRetryMissingState =
               !stateRef.current?.snapshot &&
@@ -274,10 +290,41 @@ export function useConfirmedLiveMatch(matchId: string) {
     socket.send(JSON.stringify(payload));
   };
 
+  const requestTakeback = async () => {
+    const socket = socketRef.current;
+    if (!socket || socket.readyState !== WebSocket.OPEN) {
+      throw new Error("Live connection is not ready");
+    }
+    const payload: LiveTakebackMessage = {
+      message_type: "takeback",
+      intent_id: crypto.randomUUID(),
+      client_action_id: createClientActionId(),
+      ws_connection_id: wsConnectionIdRef.current ?? undefined
+    };
+    setUiDebugState({
+      last_intent_id: payload.intent_id,
+      last_client_action_id: payload.client_action_id,
+      ws_connection_id: wsConnectionIdRef.current ?? undefined
+    });
+    recordWsDebug({
+      at: new Date().toISOString(),
+      event: "ws.takeback",
+      match_id: matchId,
+      url: socket.url,
+      ws_connection_id: wsConnectionIdRef.current ?? undefined,
+      client_action_id: payload.client_action_id,
+      intent_id: payload.intent_id,
+      payload
+    });
+    socket.send(JSON.stringify(payload));
+  };
+
   return {
     snapshot: state?.snapshot ?? null,
     timeline: state?.timeline ?? [],
+    thinking,
     submitMove,
+    requestTakeback,
     error,
     isConnected
   };
@@ -1,12 +1,14 @@
 import { useEffect, useReducer } from "react";
 
-import { liveRevealDelayMs } from "./model";
+import { liveRevealBacklogLimit, liveRevealDelayMs } from "./model";
 
 interface LivePlaybackContext {
   activelyWatching: boolean;
   interactive: boolean;
   liveFrameCount: number;
   matchId: string;
+  /** Delay between revealed frames; zero shows every frame as it arrives. */
+  revealDelayMs?: number;
 }
 
 interface LivePlaybackState {
@@ -20,13 +22,15 @@ type LivePlaybackAction =
   | ({ type: "sync_context" } & LivePlaybackContext)
   | { type: "reveal_next"; liveFrameCount: number }
   | { type: "set_following"; value: boolean }
-  | { type: "set_selected_live_ply"; value: number };
+  | { type: "set_selected_live_ply"; value: number }
+  | { type: "step_live_ply"; delta: number };
 
 export interface UseLivePlaybackOptions extends LivePlaybackContext {}
 
 export interface LivePlaybackViewModel extends LivePlaybackState {
   returnToLive: () => void;
   setSelectedLivePly: (value: number) => void;
+  stepLivePly: (delta: number) => void;
 }
 
 function latestPly(liveFrameCount: number) {
@@ -52,15 +56,27 @@ export function reduceLivePlaybackState(
 ): LivePlaybackState {
   switch (action.type) {
     case "sync_context": {
-      const { activelyWatching, interactive, liveFrameCount, matchId } = action;
+      const { activelyWatching, interactive, liveFrameCount, matchId, revealDelayMs } = action;
       if (!matchId) {
         return createInitialLivePlaybackState();
       }
 
-      if (interactive || !activelyWatching || state.matchId !== matchId) {
+      if (!activelyWatching || state.matchId !== matchId) {
         return fullSyncState(matchId, liveFrameCount);
       }
 
+      if (interactive || revealDelayMs === 0) {
+        // Human games and unpaced viewers reveal every frame at once, but a user browsing history keeps their view.
+        return {
+          matchId,
+          displayedLiveFrameCount: liveFrameCount,
+          selectedLivePly: state.isLiveFollowing
+            ? latestPly(liveFrameCount)
+            : Math.min(state.selectedLivePly, latestPly(liveFrameCount)),
+          isLiveFollowing: state.isLiveFollowing
+        };
+      }
+
       const nextDisplayedLiveFrameCount = Math.min(state.displayedLiveFrameCount, liveFrameCount);
       const maxVisiblePly = latestPly(nextDisplayedLiveFrameCount);
       return {
@@ -71,7 +87,11 @@ export function reduceLivePlaybackState(
       };
     }
     case "reveal_next": {
-      const nextDisplayedLiveFrameCount = Math.min(state.displayedLiveFrameCount + 1, action.liveFrameCount);
+      // A viewer that fell too far behind the runner skips ahead instead of replaying the whole backlog.
+      const nextDisplayedLiveFrameCount = Math.min(
+        Math.max(state.displayedLiveFrameCount + 1, action.liveFrameCount - liveRevealBacklogLimit),
+        action.liveFrameCount
+      );
       const nextVisiblePly = latestPly(nextDisplayedLiveFrameCount);
       return {
         ...state,
@@ -85,9 +105,11 @@ export function reduceLivePlaybackState(
         isLiveFollowing: action.value,
         selectedLivePly: action.value ? latestPly(state.displayedLiveFrameCount) : state.selectedLivePly
       };
-    case "set_selected_live_ply": {
+    case "set_selected_live_ply":
+    case "step_live_ply": {
       const maxVisiblePly = latestPly(state.displayedLiveFrameCount);
-      const nextSelectedLivePly = Math.min(action.value, maxVisiblePly);
+      const requestedPly = action.type === "step_live_ply" ? state.selectedLivePly + action.delta : action.value;
+      const nextSelectedLivePly = Math.min(Math.max(requestedPly, 0), maxVisiblePly);
       return {
         ...state,
         selectedLivePly: nextSelectedLivePly,
@@ -99,10 +121,11 @@ export function reduceLivePlaybackState(
 
 export function useLivePlayback(options: UseLivePlaybackOptions): LivePlaybackViewModel {
   const [state, dispatch] = useReducer(reduceLivePlaybackState, undefined, createInitialLivePlaybackState);
+  const revealDelayMs = options.revealDelayMs ?? liveRevealDelayMs;
 
   useEffect(() => {
-    dispatch({ type: "sync_context", ...options });
-  }, [options.activelyWatching, options.interactive, options.liveFrameCount, options.matchId]);
+    dispatch({ type: "sync_context", ...options, revealDelayMs });
+  }, [options.activelyWatching, options.interactive, options.liveFrameCount, options.matchId, revealDelayMs]);
 
   useEffect(() => {
     if (!options.matchId || options.interactive || !options.activelyWatching) {
@@ -115,7 +138,7 @@ export function useLivePlayback(options: UseLivePlaybackOptions): LivePlaybackVi
 
     const timer = window.setTimeout(() => {
       dispatch({ type: "reveal_next", liveFrameCount: options.liveFrameCount });
-    }, liveRevealDelayMs);
+    }, revealDelayMs);
 
     return () => window.clearTimeout(timer);
   }, [
@@ -123,12 +146,14 @@ export function useLivePlayback(options: UseLivePlaybackOptions): LivePlaybackVi
     options.interactive,
     options.liveFrameCount,
     options.matchId,
+    revealDelayMs,
     state.displayedLiveFrameCount
   ]);
 
   return {
     ...state,
     setSelectedLivePly: (value) => dispatch({ type: "set_selected_live_ply", value }),
+    stepLivePly: (delta) => dispatch({ type: "step_live_ply", delta }),
     returnToLive: () => dispatch({ type: "set_following", value: true })
   };
 }
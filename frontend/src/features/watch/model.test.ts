@@ -0,0 +1,230 @@
+import { describe, expect, it } from "vitest";
+
+import type { MatchSeries } from "../../app/types";
+import {
+  bestPromotion,
+  boardClipboardApplies,
+  liveRevealDelayKey,
+  liveRevealDelayMs,
+  loadLiveRevealDelay,
+  maxLiveRevealDelayMs,
+  parseMoveEntry,
+  pastedPositionInput,
+  premoveMove,
+  rematchInput,
+  saveLiveRevealDelay,
+  shortcutAction,
+  thinkingSummary
+} from "./model";
+
+const humanGame: MatchSeries = {
+  id: "match-1",
+  tournament_id: "tournament-1",
+  pool_id: "pool-1",
+  round_index: 0,
+  white_version_id: "engine-version",
+  black_version_id: "human-player",
+  game_index: 0,
+  status: "completed",
+  watch_state: "replay",
+  game_id: "game-1",
+  created_at: "2026-04-14T18:47:00.000Z",
+  white_participant: { kind: "engine_version", id: "engine-version", display_name: "Alpha Beta v1" },
+  black_participant: { kind: "human_player", id: "human-player", display_name: "You" },
+  interactive: true
+};
+
+describe("rematchInput", () => {
+  it("swaps colors and keeps the pool and engine", () => {
+    expect(rematchInput(humanGame)).toEqual({
+      name: "You vs Alpha Beta v1",
+      pool_id: "pool-1",
+      engine_version_id: "engine-version",
+      human_side: "white"
+    });
+
+    const asWhite = rematchInput({
+      ...humanGame,
+      white_participant: humanGame.black_participant,
+      black_participant: humanGame.white_participant
+    });
+    expect(asWhite?.human_side).toBe("black");
+    expect(asWhite?.engine_version_id).toBe("engine-version");
+  });
+
+  it("offers no rematch for engine-only matches", () => {
+    expect(
+      rematchInput({
+        ...humanGame,
+        interactive: false,
+        black_participant: { kind: "engine_version", id: "other-version", display_name: "Other" }
+      })
+    ).toBeNull();
+  });
+});
+
+describe("pastedPositionInput", () => {
+  const sicilian = "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2";
+
+  it("starts from the pasted position with the same players on the same sides", () => {
+    expect(pastedPositionInput(humanGame, "standard", `  ${sicilian}\n`)).toEqual({
+      input: {
+        name: "You vs Alpha Beta v1",
+        pool_id: "pool-1",
+        engine_version_id: "engine-version",
+        human_side: "black",
+        start_fen: sicilian
+      }
+    });
+  });
+
+  it("rejects text that is not a FEN and positions that are already over", () => {
+    expect(pastedPositionInput(humanGame, "standard", "")).toEqual({ error: "Paste a FEN to start from." });
+    expect(pastedPositionInput(humanGame, "standard", "e4 e5 Nf3")).toHaveProperty("error");
+    expect(
+      pastedPositionInput(humanGame, "standard", "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3")
+    ).toEqual({ error: "That position is already decided." });
+  });
+
+  it("needs an engine opponent", () => {
+    const engineGame = {
+      ...humanGame,
+      interactive: false,
+      black_participant: { kind: "engine_version" as const, id: "other-version", display_name: "Other" }
+    };
+    expect(pastedPositionInput(engineGame, "standard", sicilian)).toHaveProperty("error");
+  });
+});
+
+describe("boardClipboardApplies", () => {
+  it("copies and pastes the board only outside form fields and text selections", () => {
+    expect(boardClipboardApplies("DIV")).toBe(true);
+    expect(boardClipboardApplies(undefined)).toBe(true);
+    expect(boardClipboardApplies("INPUT")).toBe(false);
+    expect(boardClipboardApplies("TEXTAREA")).toBe(false);
+    expect(boardClipboardApplies("DIV", "Alpha Beta v1")).toBe(false);
+  });
+});
+
+describe("thinkingSummary", () => {
+  it("lists only the statistics the engine reported", () => {
+    const thinking = {
+      protocol_version: 1,
+      event_type: "engine_thinking" as const,
+      match_id: "match-1",
+      seq: 4,
+      server_now_unix_ms: 0,
+      side: "black" as const,
+      depth: 12,
+      score: { kind: "centipawns" as const, value: -35 },
+      nodes: 1_204_000,
+      nps: 850_400,
+      pv: ["e5", "Nf3"]
+    };
+
+    expect(thinkingSummary(thinking)).toBe("depth 12 • -0.35 • 1,204,000 nodes • 850 kN/s");
+    expect(thinkingSummary({ ...thinking, score: null, nodes: null, nps: null })).toBe("depth 12");
+  });
+});
+
+describe("premoveMove", () => {
+  const afterE4 = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+
+  it("plays a premove that is still legal after the reply", () => {
+    expect(premoveMove(afterE4, { from: "e7", to: "e5" })).toBe("e7e5");
+    const afterD5 = "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2";
+    expect(premoveMove(afterD5, { from: "e4", to: "d5" })).toBe("e4d5");
+  });
+
+  it("drops a premove the reply made illegal", () => {
+    const afterE6 = "rnbqkbnr/pppp1ppp/4p3/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2";
+    expect(premoveMove(afterE6, { from: "e4", to: "d5" })).toBeNull();
+    expect(premoveMove(afterE4, { from: "e2", to: "e4" })).toBeNull();
+  });
+
+  it("keeps the chosen promotion piece and defaults to a queen", () => {
+    const promoting = "8/4P3/8/8/8/8/k7/4K3 w - - 0 1";
+    expect(premoveMove(promoting, { from: "e7", to: "e8", promotion: "n" })).toBe("e7e8n");
+    expect(premoveMove(promoting, { from: "e7", to: "e8" })).toBe("e7e8q");
+  });
+});
+
+describe("bestPromotion", () => {
+  it("underpromotes to a knight that mates", () => {
+    expect(bestPromotion("6nb/5Ppk/7p/8/8/8/8/K7 w - - 0 1", "f7", "f8")).toBe("n");
+  });
+
+  it("avoids a queen that stalemates", () => {
+    expect(bestPromotion("8/1P6/8/8/8/8/8/5K1k w - - 0 1", "b7", "b8")).toBe("r");
+  });
+
+  it("otherwise takes the queen", () => {
+    expect(bestPromotion("8/4P3/8/8/8/8/k7/4K3 w - - 0 1", "e7", "e8")).toBe("q");
+    expect(premoveMove("6nb/5Ppk/7p/8/8/8/8/K7 w - - 0 1", { from: "f7", to: "f8" })).toBe("f7f8n");
+  });
+});
+
+describe("parseMoveEntry", () => {
+  const start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+  it("reads coordinate and SAN moves", () => {
+    expect(parseMoveEntry(start, "e2e4")).toEqual({ uci: "e2e4" });
+    expect(parseMoveEntry(start, " E2-E4 ")).toEqual({ uci: "e2e4" });
+    expect(parseMoveEntry(start, "Nf3")).toEqual({ uci: "g1f3" });
+    expect(parseMoveEntry(start, "e4")).toEqual({ uci: "e2e4" });
+    const castling = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+    expect(parseMoveEntry(castling, "O-O")).toEqual({ uci: "e1g1" });
+    expect(parseMoveEntry(castling, "0-0-0")).toEqual({ uci: "e1c1" });
+  });
+
+  it("accepts check marks and promotion spellings", () => {
+    const promoting = "8/4P3/8/8/8/8/k7/4K3 w - - 0 1";
+    expect(parseMoveEntry(promoting, "e7e8")).toEqual({ uci: "e7e8q" });
+    expect(parseMoveEntry(promoting, "e7e8n")).toEqual({ uci: "e7e8n" });
+    expect(parseMoveEntry(promoting, "e8=R")).toEqual({ uci: "e7e8r" });
+    const checking = "4k3/8/8/8/8/8/8/R3K3 w Q - 0 1";
+    expect(parseMoveEntry(checking, "Ra8+")).toEqual({ uci: "a1a8" });
+  });
+
+  it("reports illegal and empty entries", () => {
+    expect(parseMoveEntry(start, "e2e5")).toEqual({ error: "Illegal move: e2e5" });
+    expect(parseMoveEntry(start, "Nf6")).toEqual({ error: "Illegal move: Nf6" });
+    expect(parseMoveEntry(start, "e2e4q")).toEqual({ error: "Illegal move: e2e4q" });
+    expect(parseMoveEntry(start, "  ")).toEqual({ error: "Type a move such as e2e4 or Nf3." });
+  });
+});
+
+describe("shortcutAction", () => {
+  it("maps board keys to actions in either case", () => {
+    expect(shortcutAction({ key: "f" })).toBe("flip");
+    expect(shortcutAction({ key: "F" })).toBe("flip");
+    expect(shortcutAction({ key: "N" })).toBe("new_game");
+    expect(shortcutAction({ key: "ArrowLeft", targetTag: "DIV" })).toBe("previous");
+    expect(shortcutAction({ key: "End" })).toBe("last");
+    expect(shortcutAction({ key: "x" })).toBeNull();
+  });
+
+  it("leaves typing in form fields and modified keys alone", () => {
+    expect(shortcutAction({ key: "f", targetTag: "INPUT" })).toBeNull();
+    expect(shortcutAction({ key: "ArrowLeft", targetTag: "TEXTAREA" })).toBeNull();
+    expect(shortcutAction({ key: "n", ctrlKey: true })).toBeNull();
+    expect(shortcutAction({ key: "f", metaKey: true })).toBeNull();
+  });
+});
+
+describe("loadLiveRevealDelay", () => {
+  it("round-trips the saved delay and falls back for missing or invalid values", () => {
+    window.localStorage.removeItem(liveRevealDelayKey);
+    expect(loadLiveRevealDelay()).toBe(liveRevealDelayMs);
+
+    saveLiveRevealDelay(0);
+    expect(loadLiveRevealDelay()).toBe(0);
+
+    window.localStorage.setItem(liveRevealDelayKey, "99999");
+    expect(loadLiveRevealDelay()).toBe(maxLiveRevealDelayMs);
+
+    window.localStorage.setItem(liveRevealDelayKey, "soon");
+    expect(loadLiveRevealDelay()).toBe(liveRevealDelayMs);
+    window.localStorage.removeItem(liveRevealDelayKey);
+  });
+});
@@ -1,13 +1,241 @@
-import type { MatchSeries } from "../../app/types";
+import { Chess, type Move, validateFen } from "chess.js";
+
+import type { MatchSeries, Variant } from "../../app/types";
+import type { EngineThinkingEvent } from "../../shared/api/types";
+import { fenToBoard, legalMovesByOrigin, maybePromotion, squareIndex } from "../../shared/chess/board";
+import { formatEngineScore } from "../../shared/lib/format";
+import type { StartHumanGameInput } from "../human-game/api";
 
 export const liveRevealDelayMs = 600;
+export const maxLiveRevealDelayMs = 3000;
+/** Unrevealed frames a paced viewer may fall behind before playback skips ahead. */
+export const liveRevealBacklogLimit = 40;
+export const liveRevealDelayKey = "arena:live-reveal-delay";
 export const lastWatchedKey = "arena:last-watched-match";
 export const pendingLiveWatchWindowMs = 15_000;
+export const boardFlippedKey = "arena:board-flipped";
+export const autoQueenKey = "arena:auto-queen";
+
+export type PromotionPiece = "q" | "r" | "b" | "n";
+
+/** A move queued while the opponent thinks, played when the turn comes back. */
+export interface Premove {
+  from: string;
+  to: string;
+  promotion?: PromotionPiece;
+}
 
 export function isTerminalLiveStatus(status: string) {
   return status === "completed" || status === "failed" || status === "skipped" || status === "finished" || status === "aborted";
 }
 
+export function loadBoardFlipped() {
+  try {
+    return window.localStorage.getItem(boardFlippedKey) === "true";
+  } catch {
+    return false;
+  }
+}
+
+export function saveBoardFlipped(flipped: boolean) {
+  try {
+    window.localStorage.setItem(boardFlippedKey, String(flipped));
+  } catch {
+    // Ignore storage failures.
+  }
+}
+
+/** Whether promotions skip the piece picker; on unless turned off. */
+export function loadAutoQueen() {
+  try {
+    return window.localStorage.getItem(autoQueenKey) !== "false";
+  } catch {
+    return true;
+  }
+}
+
+export function saveAutoQueen(autoQueen: boolean) {
+  try {
+    window.localStorage.setItem(autoQueenKey, String(autoQueen));
+  } catch {
+    // Ignore storage failures.
+  }
+}
+
+export function loadLiveRevealDelay() {
+  try {
+    const stored = window.localStorage.getItem(liveRevealDelayKey);
+    const delay = stored === null ? Number.NaN : Number(stored);
+    return Number.isFinite(delay) ? Math.min(Math.max(delay, 0), maxLiveRevealDelayMs) : liveRevealDelayMs;
+  } catch {
+    return liveRevealDelayMs;
+  }
+}
+
+export function saveLiveRevealDelay(delayMs: number) {
+  try {
+    window.localStorage.setItem(liveRevealDelayKey, String(delayMs));
+  } catch {
+    // Ignore storage failures.
+  }
+}
+
+/**
+ * The premove as a UCI move if it is legal in `fen`, the position after the
+ * opponent's reply, or null when that reply made it illegal. A premoved
+ * promotion without a chosen piece gets the best piece for that position.
+ */
+export function premoveMove(fen: string, premove: Premove) {
+  const legal = legalMovesByOrigin(fen).get(premove.from)?.some((marker) => marker.square === premove.to) ?? false;
+  if (!legal) {
+    return null;
+  }
+  const piece = fenToBoard(fen)[squareIndex(premove.from)] ?? "";
+  const promotion = maybePromotion(premove.from, premove.to, piece)
+    ? premove.promotion ?? bestPromotion(fen, premove.from, premove.to)
+    : "";
+  return `${premove.from}${premove.to}${promotion}`;
+}
+
+const promotionChoices: PromotionPiece[] = ["q", "n", "r", "b"];
+const materialValues: Record<string, number> = { p: 100, n: 320, b: 330, r: 500, q: 900, k: 0 };
+const promotionMateScore = 100_000;
+
+/**
+ * The promotion piece for `from`-`to` in `fen`, from a two-ply search: each
+ * piece is scored by the material balance after the opponent's best reply,
+ * with mates and draws scored outright. Ties go to the queen, so an
+ * underpromotion is only picked when it mates, avoids stalemate, or keeps
+ * more material.
+ */
+export function bestPromotion(fen: string, from: string, to: string): PromotionPiece {
+  let best: PromotionPiece = "q";
+  let bestScore = -Infinity;
+  for (const piece of promotionChoices) {
+    const chess = new Chess(fen);
+    try {
+      chess.move({ from, to, promotion: piece });
+    } catch {
+      continue;
+    }
+    const score = scoreAfterBestReply(chess);
+    if (score > bestScore) {
+      best = piece;
+      bestScore = score;
+    }
+  }
+  return best;
+}
+
+/** The position's value for the side that just moved, once the opponent has made their best reply. */
+function scoreAfterBestReply(chess: Chess) {
+  const mover = chess.turn() === "w" ? "b" : "w";
+  if (chess.isCheckmate()) {
+    return promotionMateScore;
+  }
+  if (chess.isDraw()) {
+    return 0;
+  }
+  let worst = Infinity;
+  for (const reply of chess.moves({ verbose: true })) {
+    chess.move(reply);
+    const score = chess.isCheckmate() ? -promotionMateScore : chess.isDraw() ? 0 : materialBalance(chess, mover);
+    chess.undo();
+    worst = Math.min(worst, score);
+  }
+  return worst;
+}
+
+function materialBalance(chess: Chess, side: "w" | "b") {
+  let balance = 0;
+  for (const square of chess.board().flat()) {
+    if (square) {
+      balance += (square.color === side ? 1 : -1) * materialValues[square.type];
+    }
+  }
+  return balance;
+}
+
+/** A typed move: the UCI move to play, or why it cannot be played. */
+export type MoveEntry = { uci: string } | { error: string };
+
+/**
+ * Reads a move typed as coordinates (`e2e4`, `e2-e4`, `e7e8q`) or SAN
+ * (`Nf3`, `exd5`, `O-O`, `e8=Q+`) against the legal moves in `fen`. A
+ * coordinate promotion without a piece becomes a queen.
+ */
+export function parseMoveEntry(fen: string, text: string): MoveEntry {
+  const entry = text.trim();
+  if (!entry) {
+    return { error: "Type a move such as e2e4 or Nf3." };
+  }
+  let moves: Move[];
+  try {
+    moves = new Chess(fen).moves({ verbose: true });
+  } catch {
+    return { error: "There is no position to move in." };
+  }
+
+  const coordinates = /^([a-h][1-8])-?([a-h][1-8])=?([qrbn])?$/i.exec(entry);
+  if (coordinates) {
+    const [from, to] = [coordinates[1].toLowerCase(), coordinates[2].toLowerCase()];
+    const promotion = coordinates[3]?.toLowerCase();
+    const move = moves.find(
+      (candidate) =>
+        candidate.from === from &&
+        candidate.to === to &&
+        candidate.promotion === (candidate.promotion ? promotion ?? "q" : promotion)
+    );
+    if (move) {
+      return { uci: `${move.from}${move.to}${move.promotion ?? ""}` };
+    }
+  }
+
+  const san = normalizeSan(entry);
+  const move = moves.find((candidate) => normalizeSan(candidate.san) === san);
+  return move ? { uci: `${move.from}${move.to}${move.promotion ?? ""}` } : { error: `Illegal move: ${entry}` };
+}
+
+/** SAN without check marks, annotations or the promotion `=`, with `0-0` read as `O-O`. */
+function normalizeSan(san: string) {
+  return san.replace(/[+#!?]+$/, "").replace(/=/g, "").replace(/0/g, "O");
+}
+
+export type ShortcutAction = "first" | "previous" | "next" | "last" | "cancel" | "flip" | "new_game";
+
+const shortcutKeys: Record<string, ShortcutAction> = {
+  Home: "first",
+  ArrowLeft: "previous",
+  ArrowRight: "next",
+  End: "last",
+  Escape: "cancel",
+  f: "flip",
+  F: "flip",
+  n: "new_game",
+  N: "new_game"
+};
+
+/**
+ * The board shortcut for a key press, or null when the key is not one or
+ * when it was typed into a form field or combined with a modifier, so text
+ * inputs and browser shortcuts keep working.
+ */
+export function shortcutAction(press: {
+  key: string;
+  targetTag?: string;
+  ctrlKey?: boolean;
+  metaKey?: boolean;
+  altKey?: boolean;
+}): ShortcutAction | null {
+  if (press.targetTag && ["INPUT", "SELECT", "TEXTAREA"].includes(press.targetTag)) {
+    return null;
+  }
+  if (press.ctrlKey || press.metaKey || press.altKey) {
+    return null;
+  }
+  return shortcutKeys[press.key] ?? null;
+}
+
 export function liveClockElapsedMs(options: {
   status?: string;
   isLiveFollowing: boolean;
@@ -33,3 +261,94 @@ export function isPendingLiveWatchMatch(match: MatchSeries, nowMs = Date.now())
 
   return Math.max(0, nowMs - createdAtMs) <= pendingLiveWatchWindowMs;
 }
+
+/** The engine a human is playing in `match`, or null for engine-only games. */
+function humanGameSides(match: MatchSeries) {
+  const humanPlaysWhite = match.white_participant.kind === "human_player";
+  const engine = humanPlaysWhite ? match.black_participant : match.white_participant;
+  if (!match.interactive || engine.kind !== "engine_version") {
+    return null;
+  }
+  return { engine, humanPlaysWhite };
+}
+
+export function rematchInput(match: MatchSeries): StartHumanGameInput | null {
+  const sides = humanGameSides(match);
+  if (!sides) {
+    return null;
+  }
+  return {
+    name: `You vs ${sides.engine.display_name}`,
+    pool_id: match.pool_id,
+    engine_version_id: sides.engine.id,
+    human_side: sides.humanPlaysWhite ? "black" : "white"
+  };
+}
+
+/** A pasted FEN: the game to start from it, or why it cannot start. */
+export type PastedPosition = { input: StartHumanGameInput } | { error: string };
+
+/**
+ * A new game from a pasted FEN against the same engine, with the human
+ * keeping their side. Standard positions are checked here so a typo or a
+ * finished position is reported without a round trip; the server checks
+ * Chess960 castling rights itself.
+ */
+export function pastedPositionInput(match: MatchSeries, variant: Variant, text: string): PastedPosition {
+  const sides = humanGameSides(match);
+  if (!sides) {
+    return { error: "Only games against an engine can start from a pasted position." };
+  }
+  const fen = text.trim().replace(/\s+/g, " ");
+  if (!fen) {
+    return { error: "Paste a FEN to start from." };
+  }
+  if (variant === "standard") {
+    const validation = validateFen(fen);
+    if (!validation.ok) {
+      return { error: validation.error ?? "Invalid FEN." };
+    }
+    const chess = new Chess(fen);
+    if (chess.isCheckmate() || chess.isStalemate()) {
+      return { error: "That position is already decided." };
+    }
+  }
+  return {
+    input: {
+      name: `You vs ${sides.engine.display_name}`,
+      pool_id: match.pool_id,
+      engine_version_id: sides.engine.id,
+      human_side: sides.humanPlaysWhite ? "white" : "black",
+      start_fen: fen
+    }
+  };
+}
+
+/**
+ * Whether Ctrl+C or Ctrl+V should copy or paste the board's FEN: not while
+ * typing in a form field, and not when copying text the user selected.
+ */
+export function boardClipboardApplies(targetTag: string | undefined, selectedText = "") {
+  if (targetTag && ["INPUT", "SELECT", "TEXTAREA"].includes(targetTag)) {
+    return false;
+  }
+  return selectedText.length === 0;
+}
+
+/** One line of search statistics; the score is from the thinking side's point of view. */
+export function thinkingSummary(thinking: EngineThinkingEvent) {
+  const parts: string[] = [];
+  if (thinking.depth != null) {
+    parts.push(`depth ${thinking.depth}`);
+  }
+  if (thinking.score) {
+    parts.push(formatEngineScore(thinking.score));
+  }
+  if (thinking.nodes != null) {
+    parts.push(`${thinking.nodes.toLocaleString("en-US")} nodes`);
+  }
+  if (thinking.nps != null) {
+    parts.push(`${Math.round(thinking.nps / 1000).toLocaleString("en-US")} kN/s`);
+  }
+  return parts.join(" • ");
+}
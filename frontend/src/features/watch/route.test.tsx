@@ -31,6 +31,10 @@ vi.mock("./livePlayback", () => ({
   useLivePlayback: mocks.useLivePlaybackMock
 }));
 
+vi.mock("./analysis", () => ({
+  useEngineAnalysis: () => null
+}));
+
 vi.mock("../replay/api", () => ({
   useReplayQuery: mocks.useReplayQueryMock
 }));
@@ -39,6 +43,10 @@ vi.mock("../../app/debug", () => ({
   setUiDebugState: mocks.setUiDebugStateMock
 }));
 
+vi.mock("../human-game/api", () => ({
+  useStartHumanGameMutation: () => ({ isPending: false, mutateAsync: vi.fn() })
+}));
+
 vi.mock("../debug/DebugDrawer", () => ({
   DebugDrawer: () => null
 }));
@@ -65,7 +73,8 @@ const match: MatchSeries = {
     id: "human-player",
     display_name: "You"
   },
-  interactive: true
+  interactive: true,
+  takeback_allowed: false
 };
 
 const finishedSnapshot: LiveMatchSnapshot = {
@@ -195,6 +204,7 @@ describe("WatchPage", () => {
         }
       ],
       submitMove: vi.fn(),
+      requestTakeback: vi.fn(),
       error: "",
       isConnected: false
     });
@@ -221,6 +231,8 @@ describe("WatchPage", () => {
     expect(screen.getByText("White takes the point by Timeout.")).toBeTruthy();
     expect(screen.getByText("Replay details are loading while the final position stays on screen.")).toBeTruthy();
     expect(screen.getByText("Moves")).toBeTruthy();
+    expect(screen.getByRole("dialog", { name: "Game over" })).toBeTruthy();
+    expect(screen.getByRole("button", { name: "Rematch" })).toBeTruthy();
   });
 
   it("shows critical urgency only for the active low-time side", () => {
@@ -258,6 +270,7 @@ describe("WatchPage", () => {
         }
       ],
       submitMove: vi.fn(),
+      requestTakeback: vi.fn(),
       error: "",
       isConnected: true
     });
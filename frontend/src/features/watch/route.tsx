@@ -1,17 +1,23 @@
-import { useEffect, useState } from "react";
+import { FormEvent, useEffect, useRef, useState } from "react";
 import { useNavigate, useParams } from "react-router-dom";
 
 import { setUiDebugState } from "../../app/debug";
-import type { BoardMoveMarker } from "../../app/types";
+import type { BoardMoveMarker, GameResult, GameTermination } from "../../app/types";
 import {
+  buildPgn,
   buildReplayFrames,
   boardIndexToSquare,
+  boardSoundForMove,
+  capturedPieces,
+  checkedKingSquare,
   fenToBoard,
   legalMovesByOrigin,
   maybePromotion,
   orientSquares,
   squareName
 } from "../../shared/chess/board";
+import { pieceImages } from "../../shared/chess/pieces";
+import { loadSoundMuted, playBoardSound, saveSoundMuted } from "../../shared/chess/sounds";
 import {
   formatClock,
   outcomeHeadline,
@@ -26,12 +32,37 @@ import {
 import { loadErrorMessage } from "../../shared/lib/errors";
 import { participantName } from "../../shared/lib/participants";
 import { useGamesQuery, useMatchesQuery, usePoolsQuery, useTournamentsQuery } from "../../shared/queries/arena";
-import { BoardView, EmptyState, EngineSideCard, MoveList, StatCard, StatusBadge } from "../../shared/ui";
+import { BoardView, EmptyState, EngineSideCard, EvalBar, MoveList, StatCard, StatusBadge } from "../../shared/ui";
 import { DebugDrawer } from "../debug/DebugDrawer";
+import { type StartHumanGameInput, useStartHumanGameMutation } from "../human-game/api";
 import { useReplayQuery } from "../replay/api";
 import { useConfirmedLiveMatch } from "./live";
+import { useEngineAnalysis } from "./analysis";
 import { useLivePlayback } from "./livePlayback";
-import { isPendingLiveWatchMatch, isTerminalLiveStatus, lastWatchedKey, liveClockElapsedMs } from "./model";
+import {
+  boardClipboardApplies,
+  isPendingLiveWatchMatch,
+  isTerminalLiveStatus,
+  lastWatchedKey,
+  liveClockElapsedMs,
+  loadAutoQueen,
+  loadBoardFlipped,
+  loadLiveRevealDelay,
+  maxLiveRevealDelayMs,
+  parseMoveEntry,
+  pastedPositionInput,
+  premoveMove,
+  rematchInput,
+  saveAutoQueen,
+  saveBoardFlipped,
+  saveLiveRevealDelay,
+  shortcutAction,
+  thinkingSummary,
+  type Premove,
+  type PromotionPiece
+} from "./model";
+
+const promotionPieces: PromotionPiece[] = ["q", "r", "b", "n"];
 
 export function WatchPage() {
   const navigate = useNavigate();
@@ -41,6 +72,7 @@ export function WatchPage() {
   const tournaments = useTournamentsQuery(3000);
   const games = useGamesQuery();
   const [selectedPly, setSelectedPly] = useState(0);
+  const [analysisEnabled, setAnalysisEnabled] = useState(true);
   const [selectedBoardSquare, setSelectedBoardSquare] = useState("");
   const [invalidBoardSquare, setInvalidBoardSquare] = useState("");
   const [isSubmittingHumanMove, setIsSubmittingHumanMove] = useState(false);
@@ -49,6 +81,18 @@ export function WatchPage() {
   const [boardEventFlash, setBoardEventFlash] = useState(false);
   const [latestMoveFlash, setLatestMoveFlash] = useState(false);
   const [resultReveal, setResultReveal] = useState(false);
+  const [soundMuted, setSoundMuted] = useState(loadSoundMuted);
+  const [boardFlipped, setBoardFlipped] = useState(loadBoardFlipped);
+  const [autoQueen, setAutoQueen] = useState(loadAutoQueen);
+  const [liveRevealDelay, setLiveRevealDelay] = useState(loadLiveRevealDelay);
+  const [premove, setPremove] = useState<Premove | null>(null);
+  const [pendingPromotion, setPendingPromotion] = useState<{ from: string; to: string } | null>(null);
+  const [moveEntry, setMoveEntry] = useState("");
+  const [moveEntryError, setMoveEntryError] = useState("");
+  const [clipboardMessage, setClipboardMessage] = useState("");
+  const [gameOverDismissed, setGameOverDismissed] = useState(false);
+  const soundedFrameCountRef = useRef<number | null>(null);
+  const startHumanGame = useStartHumanGameMutation();
 
   const selectedLiveMatch = (matches.data ?? []).find((match) => match.id === matchId) ?? null;
   const selectedWatchGame =
@@ -113,6 +157,7 @@ export function WatchPage() {
           white_participant: selectedLiveMatch.white_participant,
           black_participant: selectedLiveMatch.black_participant,
           interactive: selectedLiveMatch.interactive,
+          takeback_allowed: selectedLiveMatch.takeback_allowed,
           human_turn:
             selectedLiveMatch.interactive &&
             ((selectedLiveMatch.white_participant.kind === "human_player" && confirmedLiveSnapshot.side_to_move === "white") ||
@@ -123,7 +168,8 @@ export function WatchPage() {
     matchId: rawLiveGame?.match_id ?? "",
     liveFrameCount: rawLiveGame?.live_frames.length ?? 0,
     interactive: rawLiveGame?.interactive ?? false,
-    activelyWatching: true
+    activelyWatching: true,
+    revealDelayMs: liveRevealDelay
   });
 
   useEffect(() => {
@@ -193,8 +239,46 @@ export function WatchPage() {
   useEffect(() => {
     setSelectedBoardSquare("");
     setInvalidBoardSquare("");
+    setPremove(null);
+    setPendingPromotion(null);
+    setMoveEntry("");
+    setMoveEntryError("");
+    setClipboardMessage("");
   }, [rawLiveGame?.match_id]);
 
+  useEffect(() => {
+    if (!clipboardMessage) {
+      return;
+    }
+    const timer = window.setTimeout(() => setClipboardMessage(""), 4000);
+    return () => window.clearTimeout(timer);
+  }, [clipboardMessage]);
+
+  useEffect(() => {
+    // A premove waits for the turn to come back and is dropped silently if
+    // the reply made it illegal or the game ended.
+    if (!premove || !rawLiveGame) {
+      return;
+    }
+    if (rawLiveGame.status !== "running") {
+      setPremove(null);
+      return;
+    }
+    if (!rawLiveGame.human_turn) {
+      return;
+    }
+    const move = premoveMove(rawLiveGame.current_fen, premove);
+    setPremove(null);
+    if (move) {
+      void submitHumanMove(move);
+    }
+  }, [rawLiveGame?.current_fen, rawLiveGame?.human_turn, rawLiveGame?.status]);
+
+  useEffect(() => {
+    setGameOverDismissed(false);
+    soundedFrameCountRef.current = null;
+  }, [matchId]);
+
   useEffect(() => {
     if (!invalidBoardSquare) {
       return;
@@ -219,6 +303,7 @@ export function WatchPage() {
   const watchReplayFrames = buildReplayFrames(selectedWatchReplay);
   const watchReplayFen = watchReplayFrames[Math.min(selectedPly, Math.max(watchReplayFrames.length - 1, 0))] ?? "";
   const watchReplaySquares = watchReplayFen ? fenToBoard(watchReplayFen) : [];
+  const watchReplayCaptures = capturedPieces(watchReplaySquares);
   const allLiveFrames = rawLiveGame?.live_frames ?? [];
   const displayedLiveFrameCount = livePlayback.displayedLiveFrameCount;
   const selectedLivePly = livePlayback.selectedLivePly;
@@ -232,6 +317,7 @@ export function WatchPage() {
   const visibleLivePly = visibleLiveFrame?.ply ?? 0;
   const liveFen = visibleLiveFrame?.fen ?? "";
   const liveBoardSquares = liveFen ? fenToBoard(liveFen) : [];
+  const liveCaptures = capturedPieces(liveBoardSquares);
   const displayedLiveMoves = revealedLiveFrames.flatMap((frame) => (frame.move_uci ? [frame.move_uci] : []));
   const visibleLiveUpdatedAtMs = visibleLiveFrame ? new Date(visibleLiveFrame.updated_at).getTime() : 0;
   const runningClockElapsedMs = liveClockElapsedMs({
@@ -256,9 +342,34 @@ export function WatchPage() {
   const liveWhiteParticipant = rawLiveGame?.white_participant ?? selectedLiveMatch?.white_participant ?? null;
   const liveBlackParticipant = rawLiveGame?.black_participant ?? selectedLiveMatch?.black_participant ?? null;
   const interactiveLive = rawLiveGame?.interactive ?? selectedLiveMatch?.interactive ?? false;
-  const liveBoardOrientation = interactiveLive && liveBlackParticipant?.kind === "human_player" ? "black" : "white";
+  const playerOrientation = interactiveLive && liveBlackParticipant?.kind === "human_player" ? "black" : "white";
+  const liveBoardOrientation = boardFlipped ? (playerOrientation === "white" ? "black" : "white") : playerOrientation;
   const orientedLiveBoardSquares = orientSquares(liveBoardSquares, liveBoardOrientation);
+  const sideToMoveParticipant = liveSideToMove === "white" ? liveWhiteParticipant : liveBlackParticipant;
+  const engineThinking =
+    isLiveFollowing && !terminalVisibleLive && Boolean(sideToMoveParticipant) && sideToMoveParticipant?.kind !== "human_player";
+  const liveThinking =
+    engineThinking && confirmedLiveMatch.thinking?.side === liveSideToMove ? confirmedLiveMatch.thinking : null;
+  const analysedFen = selectedLiveMatch?.watch_state === "replay" ? watchReplayFen : liveFen;
+  const analysisActive = analysisEnabled && !(selectedLiveMatch?.watch_state !== "replay" && engineThinking);
+  const analysis = useEngineAnalysis({ fen: analysedFen, variant: liveVariant, enabled: analysisActive });
   const standardMoveHints = liveVariant === "standard";
+  const humanSide = !interactiveLive
+    ? null
+    : liveWhiteParticipant?.kind === "human_player"
+      ? "white"
+      : liveBlackParticipant?.kind === "human_player"
+        ? "black"
+        : null;
+  // Premoves are checked against the reply with chess.js, so standard chess only.
+  const premoveReady = Boolean(
+    rawLiveGame &&
+      humanSide &&
+      standardMoveHints &&
+      !rawLiveGame.human_turn &&
+      rawLiveGame.status === "running" &&
+      isLiveFollowing
+  );
   const legalMovesForCurrentPosition =
     rawLiveGame && standardMoveHints ? legalMovesByOrigin(rawLiveGame.current_fen) : new Map<string, BoardMoveMarker[]>();
   const selectedSquareMarkers = selectedBoardSquare ? legalMovesForCurrentPosition.get(selectedBoardSquare) ?? [] : [];
@@ -267,7 +378,9 @@ export function WatchPage() {
       ? standardMoveHints
         ? new Set(legalMovesForCurrentPosition.keys())
         : selectableHumanPieceSquares(liveBoardSquares, liveSideToMove)
-      : new Set<string>();
+      : premoveReady && humanSide
+        ? selectableHumanPieceSquares(liveBoardSquares, humanSide)
+        : new Set<string>();
   const selectedLiveTournament = selectedLiveMatch ? tournamentById[selectedLiveMatch.tournament_id] : undefined;
   const pendingSelectedLiveMatch = selectedLiveMatch !== null && isPendingLiveWatchMatch(selectedLiveMatch);
   const pendingLiveMatch =
@@ -286,6 +399,23 @@ export function WatchPage() {
   const blackUrgency = urgencyForClock(displayedBlackClockMs, liveSideToMove === "black");
   const visibleLatestPly = displayedLiveMoves.length;
   const replayLatestPly = selectedWatchReplay?.moves_uci.length ?? 0;
+  const liveCheckSquare = liveFen ? checkedKingSquare(liveFen) : "";
+  const replayCheckSquare = watchReplayFen ? checkedKingSquare(watchReplayFen) : "";
+  const rematch = selectedLiveMatch ? rematchInput(selectedLiveMatch) : null;
+
+  useEffect(() => {
+    // Only moves revealed while following the live feed make a sound, not the history loaded on open.
+    const previousCount = soundedFrameCountRef.current;
+    soundedFrameCountRef.current = revealedLiveFrames.length;
+    if (previousCount === null || revealedLiveFrames.length <= previousCount || !isLiveFollowing || soundMuted) {
+      return;
+    }
+    const latest = revealedLiveFrames.at(-1);
+    const previous = revealedLiveFrames.at(-2);
+    if (latest?.move_uci && previous) {
+      playBoardSound(boardSoundForMove(previous.fen, latest.fen, latest.move_uci, isTerminalLiveStatus(latest.status)));
+    }
+  }, [revealedLiveFrames.length]);
 
   useEffect(() => {
     if (!terminalVisibleLive) {
@@ -297,12 +427,29 @@ export function WatchPage() {
   }, [terminalVisibleLive, visibleLiveResult, visibleLiveTermination]);
 
   function liveStatusMessage() {
+    if (clipboardMessage) {
+      return clipboardMessage;
+    }
     if (terminalVisibleLive) {
       return "Final move played. Replay details are loading below.";
     }
+    if (interactiveLive && !isLiveFollowing) {
+      return "Viewing an earlier position. Return to live to play your move.";
+    }
+    if (interactiveLive && moveEntryError) {
+      return moveEntryError;
+    }
     if (interactiveLive) {
       if (rawLiveGame?.human_turn) {
-        return isSubmittingHumanMove ? "Submitting your move." : "Your move. Click a piece, then its destination.";
+        return isSubmittingHumanMove
+          ? "Submitting your move."
+          : "Your move. Click a piece, then its destination, or type it below.";
+      }
+      if (premove) {
+        return `Premove ${premove.from}-${premove.to} queued. Click its squares or press Escape to cancel.`;
+      }
+      if (premoveReady) {
+        return "Engine thinking. You can premove your reply.";
       }
       if (liveSideToMove === "white" || liveSideToMove === "black") {
         return "Engine thinking. Stay ready for the reply.";
@@ -319,6 +466,250 @@ export function WatchPage() {
     return confirmedLiveMatch.isConnected ? "Live board updating." : "Reconnecting live feed.";
   }
 
+  const showingReplay = selectedLiveMatch?.watch_state === "replay" && Boolean(selectedWatchReplay);
+  const replayMaxPly = Math.max(watchReplayFrames.length - 1, 0);
+  const gamePgn = showingReplay
+    ? selectedWatchReplay?.pgn ?? ""
+    : rawLiveGame
+      ? buildPgn(liveVariant, rawLiveGame.start_fen, displayedLiveMoves)
+      : "";
+
+  function stepReplayPly(delta: number) {
+    setSelectedPly((current) => Math.min(Math.max(current + delta, 0), replayMaxPly));
+  }
+
+  useEffect(() => {
+    const onKeyDown = (event: KeyboardEvent) => {
+      const action = shortcutAction({
+        key: event.key,
+        targetTag: (event.target as HTMLElement | null)?.tagName,
+        ctrlKey: event.ctrlKey,
+        metaKey: event.metaKey,
+        altKey: event.altKey
+      });
+      const step = showingReplay ? stepReplayPly : livePlayback.stepLivePly;
+      const jumpTo = showingReplay ? setSelectedPly : livePlayback.setSelectedLivePly;
+      if (action === "cancel") {
+        if (!premove && !pendingPromotion) {
+          return;
+        }
+        setPremove(null);
+        setPendingPromotion(null);
+      } else if (action === "previous") {
+        step(-1);
+      } else if (action === "next") {
+        step(1);
+      } else if (action === "first") {
+        jumpTo(0);
+      } else if (action === "last") {
+        if (showingReplay) {
+          setSelectedPly(replayMaxPly);
+        } else {
+          livePlayback.returnToLive();
+        }
+      } else if (action === "flip") {
+        toggleBoardFlipped(!boardFlipped);
+      } else if (action === "new_game") {
+        if (!rematch) {
+          return;
+        }
+        startNewGame();
+      } else {
+        return;
+      }
+      event.preventDefault();
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  });
+
+  useEffect(() => {
+    // Ctrl+C and Ctrl+V on the board copy its FEN and start a game from a
+    // pasted one; selected text and form fields keep the browser's behaviour.
+    const onCopy = (event: ClipboardEvent) => {
+      const targetTag = (event.target as HTMLElement | null)?.tagName;
+      if (!analysedFen || !boardClipboardApplies(targetTag, window.getSelection()?.toString())) {
+        return;
+      }
+      event.clipboardData?.setData("text/plain", analysedFen);
+      event.preventDefault();
+      setClipboardMessage("FEN copied.");
+    };
+    const onPaste = (event: ClipboardEvent) => {
+      if (!rematch || !boardClipboardApplies((event.target as HTMLElement | null)?.tagName)) {
+        return;
+      }
+      event.preventDefault();
+      startFromPastedFen(event.clipboardData?.getData("text/plain") ?? "");
+    };
+    window.addEventListener("copy", onCopy);
+    window.addEventListener("paste", onPaste);
+    return () => {
+      window.removeEventListener("copy", onCopy);
+      window.removeEventListener("paste", onPaste);
+    };
+  });
+
+  const analysisPanel = (
+    <div className="watch-analysis">
+      <EvalBar score={analysis?.score ?? null} pending={analysisActive && !analysis} />
+      <label className="checkbox">
+        <input
+          type="checkbox"
+          checked={analysisEnabled}
+          onChange={(event) => setAnalysisEnabled(event.target.checked)}
+        />
+        Engine analysis
+      </label>
+      <button
+        type="button"
+        className="button-ghost"
+        disabled={!analysedFen}
+        onClick={() => navigate(`/analysis?${new URLSearchParams({ fen: analysedFen, variant: liveVariant })}`)}
+      >
+        Open in analysis
+      </button>
+      <div className="watch-clipboard">
+        <button
+          type="button"
+          className="button-ghost"
+          disabled={!analysedFen}
+          onClick={() => void copyToClipboard(analysedFen, "FEN")}
+        >
+          Copy FEN
+        </button>
+        <button
+          type="button"
+          className="button-ghost"
+          disabled={!gamePgn}
+          onClick={() => void copyToClipboard(gamePgn, "PGN")}
+        >
+          Copy PGN
+        </button>
+        {rematch ? (
+          <button
+            type="button"
+            className="button-ghost"
+            disabled={startHumanGame.isPending}
+            onClick={() => void pasteFen()}
+          >
+            Paste FEN
+          </button>
+        ) : null}
+      </div>
+      <label className="checkbox">
+        <input type="checkbox" checked={!soundMuted} onChange={(event) => toggleSound(!event.target.checked)} />
+        Sound effects
+      </label>
+      <label className="checkbox">
+        <input type="checkbox" checked={boardFlipped} onChange={(event) => toggleBoardFlipped(event.target.checked)} />
+        Flip board
+      </label>
+      {interactiveLive ? (
+        <label className="checkbox">
+          <input type="checkbox" checked={autoQueen} onChange={(event) => toggleAutoQueen(event.target.checked)} />
+          Always promote to queen
+        </label>
+      ) : null}
+    </div>
+  );
+
+  function toggleSound(muted: boolean) {
+    setSoundMuted(muted);
+    saveSoundMuted(muted);
+  }
+
+  function toggleBoardFlipped(flipped: boolean) {
+    setBoardFlipped(flipped);
+    saveBoardFlipped(flipped);
+  }
+
+  function toggleAutoQueen(enabled: boolean) {
+    setAutoQueen(enabled);
+    saveAutoQueen(enabled);
+  }
+
+  async function launchHumanGame(input: StartHumanGameInput) {
+    setError("");
+    try {
+      const response = await startHumanGame.mutateAsync(input);
+      navigate(`/watch/${encodeURIComponent(response.match_id)}`);
+    } catch (launchError) {
+      setError(loadErrorMessage(launchError));
+    }
+  }
+
+  async function startRematch() {
+    if (rematch) {
+      await launchHumanGame(rematch);
+    }
+  }
+
+  /** Whether a new game may replace this one, asking first if it is still being played. */
+  function confirmNewGame() {
+    if (startHumanGame.isPending) {
+      return false;
+    }
+    return rawLiveGame?.status !== "running" || window.confirm("This game is still in progress. Start a new one anyway?");
+  }
+
+  /** The `N` shortcut: a rematch, after confirming if this game is still being played. */
+  function startNewGame() {
+    if (confirmNewGame()) {
+      void startRematch();
+    }
+  }
+
+  /** A new game from a pasted FEN, or the reason it cannot start in the status line. */
+  function startFromPastedFen(text: string) {
+    if (!selectedLiveMatch) {
+      return;
+    }
+    const pasted = pastedPositionInput(selectedLiveMatch, liveVariant, text);
+    if ("error" in pasted) {
+      setClipboardMessage(pasted.error);
+      return;
+    }
+    if (confirmNewGame()) {
+      void launchHumanGame(pasted.input);
+    }
+  }
+
+  async function pasteFen() {
+    let text: string;
+    try {
+      text = await navigator.clipboard.readText();
+    } catch {
+      setClipboardMessage("The clipboard could not be read. Press Ctrl+V on the board instead.");
+      return;
+    }
+    startFromPastedFen(text);
+  }
+
+  async function copyToClipboard(text: string, what: "FEN" | "PGN") {
+    try {
+      await navigator.clipboard.writeText(text);
+      setClipboardMessage(`${what} copied.`);
+    } catch {
+      setClipboardMessage(`The ${what} could not be copied.`);
+    }
+  }
+
+  function gameOverOverlay(result: GameResult | null, termination: GameTermination | null) {
+    if (gameOverDismissed) {
+      return null;
+    }
+    return (
+      <GameOverOverlay
+        result={result}
+        termination={termination}
+        rematchPending={startHumanGame.isPending}
+        onRematch={rematch ? () => void startRematch() : undefined}
+        onDismiss={() => setGameOverDismissed(true)}
+      />
+    );
+  }
+
   const reviewReplayHref = selectedLiveMatch?.game_id ? `/replay?gameId=${encodeURIComponent(selectedLiveMatch.game_id)}` : "";
 
   async function submitHumanMove(uci: string) {
@@ -337,11 +728,77 @@ export function WatchPage() {
     }
   }
 
-  function handleBoardSquareClick(index: number) {
-    if (!rawLiveGame || !interactiveLive || !rawLiveGame.human_turn || isSubmittingHumanMove) {
+  async function requestTakeback() {
+    setIsSubmittingHumanMove(true);
+    setError("");
+    try {
+      await confirmedLiveMatch.requestTakeback();
+      setSelectedBoardSquare("");
+    } catch (takebackError) {
+      setError(loadErrorMessage(takebackError));
+    } finally {
+      setIsSubmittingHumanMove(false);
+    }
+  }
+
+  function submitMoveEntry(event: FormEvent) {
+    event.preventDefault();
+    if (!rawLiveGame || !canMoveOnBoard()) {
+      setMoveEntryError("Wait for your turn to type a move.");
+      return;
+    }
+    const parsed = parseMoveEntry(rawLiveGame.current_fen, moveEntry);
+    if ("error" in parsed) {
+      setMoveEntryError(parsed.error);
       return;
     }
+    setMoveEntry("");
+    setMoveEntryError("");
+    void submitHumanMove(parsed.uci);
+  }
+
+  function canMoveOnBoard() {
+    return Boolean(rawLiveGame && interactiveLive && rawLiveGame.human_turn && !isSubmittingHumanMove && isLiveFollowing);
+  }
+
+  function submitBoardMove(from: string, to: string) {
+    const fromIndex = liveBoardSquares.findIndex((_, boardIndex) => squareName(boardIndex) === from);
+    const fromPiece = fromIndex >= 0 ? liveBoardSquares[fromIndex] : "";
+    const promotes = maybePromotion(from, to, fromPiece) !== "";
+    if (promotes && !autoQueen && premoveReady) {
+      // The best piece depends on the reply, so it is picked when the premove is played.
+      completeBoardMove(from, to);
+      return;
+    }
+    if (promotes && !autoQueen) {
+      setSelectedBoardSquare("");
+      setPendingPromotion({ from, to });
+      return;
+    }
+    completeBoardMove(from, to, promotes ? "q" : undefined);
+  }
+
+  /** Plays the move, or queues it as the premove while the engine thinks. */
+  function completeBoardMove(from: string, to: string, promotion?: PromotionPiece) {
+    setPendingPromotion(null);
+    if (premoveReady) {
+      setSelectedBoardSquare("");
+      setPremove({ from, to, promotion });
+      return;
+    }
+    void submitHumanMove(`${from}${to}${promotion ?? ""}`);
+  }
+
+  function handleBoardSquareClick(index: number) {
     const square = boardIndexToSquare(index, liveBoardOrientation);
+    if (premove && (square === premove.from || square === premove.to)) {
+      setPremove(null);
+      setSelectedBoardSquare("");
+      return;
+    }
+    if (!canMoveOnBoard() && !premoveReady) {
+      return;
+    }
     const piece = liveBoardSquares[liveBoardOrientation === "white" ? index : 63 - index];
     const selectable = selectableSquares.has(square);
 
@@ -364,13 +821,34 @@ export function WatchPage() {
       return;
     }
     const legalDestination = selectedSquareMarkers.find((marker) => marker.square === square);
-    if (!legalDestination && standardMoveHints) {
+    if (!legalDestination && standardMoveHints && !premoveReady) {
       setInvalidBoardSquare(square);
       return;
     }
-    const fromIndex = liveBoardSquares.findIndex((_, boardIndex) => squareName(boardIndex) === selectedBoardSquare);
-    const fromPiece = fromIndex >= 0 ? liveBoardSquares[fromIndex] : "";
-    void submitHumanMove(`${selectedBoardSquare}${square}${maybePromotion(selectedBoardSquare, square, fromPiece)}`);
+    submitBoardMove(selectedBoardSquare, square);
+  }
+
+  function handleBoardPieceDragStart(index: number) {
+    const square = boardIndexToSquare(index, liveBoardOrientation);
+    if ((!canMoveOnBoard() && !premoveReady) || !selectableSquares.has(square)) {
+      return;
+    }
+    setSelectedBoardSquare(square);
+    setError("");
+  }
+
+  function handleBoardPieceDrop(fromIndex: number, toIndex: number | null) {
+    setSelectedBoardSquare("");
+    if ((!canMoveOnBoard() && !premoveReady) || toIndex === null || toIndex === fromIndex) {
+      return;
+    }
+    const from = boardIndexToSquare(fromIndex, liveBoardOrientation);
+    const to = boardIndexToSquare(toIndex, liveBoardOrientation);
+    const legal = legalMovesForCurrentPosition.get(from)?.some((marker) => marker.square === to) ?? false;
+    if (!legal && standardMoveHints && !premoveReady) {
+      return;
+    }
+    submitBoardMove(from, to);
   }
 
   const watchTitle = selectedLiveMatch?.interactive
@@ -472,7 +950,14 @@ export function WatchPage() {
                     replayWinnerSide ? `watch-board-wrap-winner-${replayWinnerSide}` : selectedWatchReplay.result === "draw" ? "watch-board-wrap-draw" : ""
                   }`}
                 >
-                  <BoardView squares={watchReplaySquares} />
+                  <BoardView
+                    squares={orientSquares(watchReplaySquares, liveBoardOrientation)}
+                    checkSquare={replayCheckSquare}
+                    orientation={liveBoardOrientation}
+                  />
+                  {selectedWatchReplay.result && selectedPly === replayMaxPly
+                    ? gameOverOverlay(selectedWatchReplay.result, selectedWatchReplay.termination)
+                    : null}
                 </div>
               ) : (
                 <EmptyState>Board replay is unavailable for this game.</EmptyState>
@@ -491,6 +976,21 @@ export function WatchPage() {
                     onChange={(event) => setSelectedPly(Number(event.target.value))}
                   />
                 </div>
+                <div className="watch-live-controls">
+                  <button type="button" className="button-ghost" onClick={() => setSelectedPly(0)}>
+                    First
+                  </button>
+                  <button type="button" className="button-ghost" onClick={() => stepReplayPly(-1)}>
+                    Previous
+                  </button>
+                  <button type="button" className="button-ghost" onClick={() => stepReplayPly(1)}>
+                    Next
+                  </button>
+                  <button type="button" className="button-ghost" onClick={() => setSelectedPly(replayMaxPly)}>
+                    Last
+                  </button>
+                </div>
+                {analysisPanel}
               </div>
             </div>
 
@@ -501,6 +1001,8 @@ export function WatchPage() {
                   title={selectedLiveMatch.white_participant.kind === "human_player" ? "You" : "White engine"}
                   name={participantName(selectedLiveMatch.white_participant, "White")}
                   clock={formatClock(selectedWatchGame?.white_time_left_ms ?? 0)}
+                  captured={watchReplayCaptures.white}
+                  materialAdvantage={watchReplayCaptures.balance}
                   winner={replayWinnerSide === "white"}
                 />
                 <EngineSideCard
@@ -508,6 +1010,8 @@ export function WatchPage() {
                   title={selectedLiveMatch.black_participant.kind === "human_player" ? "You" : "Black engine"}
                   name={participantName(selectedLiveMatch.black_participant, "Black")}
                   clock={formatClock(selectedWatchGame?.black_time_left_ms ?? 0)}
+                  captured={watchReplayCaptures.black}
+                  materialAdvantage={-watchReplayCaptures.balance}
                   winner={replayWinnerSide === "black"}
                 />
               </div>
@@ -534,7 +1038,12 @@ export function WatchPage() {
                   <h2>Moves</h2>
                   <span>{selectedWatchReplay.moves_uci.length} total</span>
                 </div>
-                <MoveList moves={selectedWatchReplay.moves_uci} activePly={selectedPly} latestPly={replayLatestPly} />
+                <MoveList
+                  moves={selectedWatchReplay.moves_uci}
+                  activePly={selectedPly}
+                  latestPly={replayLatestPly}
+                  onSelectPly={setSelectedPly}
+                />
               </div>
             </div>
           </div>
@@ -619,16 +1128,63 @@ export function WatchPage() {
                     selectedSquare={selectedBoardSquare}
                     legalMoveMarkers={selectedSquareMarkers}
                     invalidSquare={invalidBoardSquare}
-                    interactive={interactiveLive && rawLiveGame.human_turn && !isSubmittingHumanMove}
+                    checkSquare={liveCheckSquare}
+                    premoveSquares={premove ? [premove.from, premove.to] : []}
+                    interactive={
+                      (interactiveLive && rawLiveGame.human_turn && !isSubmittingHumanMove && isLiveFollowing) || premoveReady
+                    }
                     hoverableSquares={selectableSquares}
                     onSquareClick={handleBoardSquareClick}
+                    onPieceDragStart={handleBoardPieceDragStart}
+                    onPieceDrop={handleBoardPieceDrop}
                     orientation={liveBoardOrientation}
                   />
+                  {terminalVisibleLive && isLiveFollowing ? gameOverOverlay(visibleLiveResult, visibleLiveTermination) : null}
                 </div>
               ) : (
                 <EmptyState>Live board display is unavailable for this game.</EmptyState>
               )}
 
+              {pendingPromotion ? (
+                <div className="promotion-picker" role="group" aria-label="Promotion piece">
+                  <span>Promote to</span>
+                  {promotionPieces.map((piece) => (
+                    <button
+                      key={piece}
+                      type="button"
+                      className="button-ghost"
+                      onClick={() => completeBoardMove(pendingPromotion.from, pendingPromotion.to, piece)}
+                    >
+                      <img src={pieceImages[humanSide === "black" ? piece : piece.toUpperCase()]} alt={piece.toUpperCase()} />
+                    </button>
+                  ))}
+                  <button type="button" className="button-ghost" onClick={() => setPendingPromotion(null)}>
+                    Cancel
+                  </button>
+                </div>
+              ) : null}
+
+              {interactiveLive && standardMoveHints && !terminalVisibleLive ? (
+                <form className="move-entry" onSubmit={submitMoveEntry}>
+                  <input
+                    aria-label="Type a move"
+                    aria-invalid={Boolean(moveEntryError)}
+                    value={moveEntry}
+                    onChange={(event) => {
+                      setMoveEntry(event.target.value);
+                      setMoveEntryError("");
+                    }}
+                    placeholder="e2e4 or Nf3, then Enter"
+                    autoComplete="off"
+                    spellCheck={false}
+                  />
+                  <button type="submit" className="button-ghost" disabled={!canMoveOnBoard() || !moveEntry.trim()}>
+                    Play
+                  </button>
+                  <span className="subtle">F flips the board, N starts a new game, Ctrl+V starts from a copied FEN, arrows step through moves.</span>
+                </form>
+              ) : null}
+
               <div className="watch-controls">
                 <div className="scrubber-row">
                   <span>
@@ -642,6 +1198,24 @@ export function WatchPage() {
                     onChange={(event) => livePlayback.setSelectedLivePly(Number(event.target.value))}
                   />
                 </div>
+                {!interactiveLive ? (
+                  <div className="scrubber-row">
+                    <span>{liveRevealDelay === 0 ? "No delay" : `${(liveRevealDelay / 1000).toFixed(1)}s per move`}</span>
+                    <input
+                      type="range"
+                      aria-label="Delay per move"
+                      min={0}
+                      max={maxLiveRevealDelayMs}
+                      step={100}
+                      value={liveRevealDelay}
+                      onChange={(event) => {
+                        const delayMs = Number(event.target.value);
+                        setLiveRevealDelay(delayMs);
+                        saveLiveRevealDelay(delayMs);
+                      }}
+                    />
+                  </div>
+                ) : null}
                 <div className="watch-live-controls">
                   <StatusBadge tone={terminalVisibleLive ? "quiet" : liveSideToMove === "white" ? "quiet" : "warning"}>
                     {terminalVisibleLive
@@ -655,12 +1229,32 @@ export function WatchPage() {
                           : "Black to move"}
                   </StatusBadge>
                   <span className="subtle">{liveStatusMessage()}</span>
+                  <button type="button" className="button-ghost" onClick={() => livePlayback.setSelectedLivePly(0)}>
+                    First
+                  </button>
+                  <button type="button" className="button-ghost" onClick={() => livePlayback.stepLivePly(-1)}>
+                    Previous
+                  </button>
+                  <button type="button" className="button-ghost" onClick={() => livePlayback.stepLivePly(1)}>
+                    Next
+                  </button>
+                  {interactiveLive && rawLiveGame?.takeback_allowed && rawLiveGame.human_turn && (confirmedLiveMatch.snapshot?.moves.length ?? 0) >= 2 ? (
+                    <button
+                      type="button"
+                      className="button-ghost"
+                      disabled={isSubmittingHumanMove}
+                      onClick={() => void requestTakeback()}
+                    >
+                      Takeback
+                    </button>
+                  ) : null}
                   {!isLiveFollowing ? (
                     <button type="button" className="button-ghost" onClick={livePlayback.returnToLive}>
                       Return to live
                     </button>
                   ) : null}
                 </div>
+                {analysisPanel}
               </div>
             </div>
 
@@ -671,6 +1265,8 @@ export function WatchPage() {
                   title={liveWhiteParticipant?.kind === "human_player" ? "You" : "White engine"}
                   name={participantName(liveWhiteParticipant, "White")}
                   clock={formatClock(displayedWhiteClockMs)}
+                  captured={liveCaptures.white}
+                  materialAdvantage={liveCaptures.balance}
                   active={liveSideToMove === "white"}
                   urgency={whiteUrgency}
                   winner={visibleWinnerSide === "white"}
@@ -680,6 +1276,8 @@ export function WatchPage() {
                   title={liveBlackParticipant?.kind === "human_player" ? "You" : "Black engine"}
                   name={participantName(liveBlackParticipant, "Black")}
                   clock={formatClock(displayedBlackClockMs)}
+                  captured={liveCaptures.black}
+                  materialAdvantage={-liveCaptures.balance}
                   active={liveSideToMove === "black"}
                   urgency={blackUrgency}
                   winner={visibleWinnerSide === "black"}
@@ -700,6 +1298,14 @@ export function WatchPage() {
                 </span>
               </div>
 
+              {liveThinking ? (
+                <div className="result-strip">
+                  <strong>{participantName(sideToMoveParticipant, "Engine")} is thinking</strong>
+                  <span>{thinkingSummary(liveThinking)}</span>
+                  {liveThinking.pv.length > 0 ? <span>{liveThinking.pv.join(" ")}</span> : null}
+                </div>
+              ) : null}
+
               <div className="move-panel">
                 <div className="panel-header move-panel-header">
                   <h2>Moves</h2>
@@ -710,6 +1316,8 @@ export function WatchPage() {
                   activePly={visibleLivePly}
                   latestPly={visibleLatestPly}
                   animateLatest={latestMoveFlash}
+                  moveTimesMs={confirmedLiveSnapshot?.move_times_ms}
+                  onSelectPly={livePlayback.setSelectedLivePly}
                 />
               </div>
             </div>
@@ -724,6 +1332,37 @@ export function WatchPage() {
   );
 }
 
+function GameOverOverlay({
+  result,
+  termination,
+  rematchPending,
+  onRematch,
+  onDismiss
+}: {
+  result: GameResult | null;
+  termination: GameTermination | null;
+  rematchPending: boolean;
+  onRematch?: () => void;
+  onDismiss: () => void;
+}) {
+  return (
+    <div className="board-game-over" role="dialog" aria-label="Game over">
+      <strong>{outcomeHeadline(result)}</strong>
+      {termination ? <span>by {formatLabel(termination)}</span> : null}
+      <div className="board-game-over-actions">
+        {onRematch ? (
+          <button type="button" disabled={rematchPending} onClick={onRematch}>
+            {rematchPending ? "Starting rematch" : "Rematch"}
+          </button>
+        ) : null}
+        <button type="button" className="button-ghost" onClick={onDismiss}>
+          View board
+        </button>
+      </div>
+    </div>
+  );
+}
+
 function urgencyForClock(ms: number, active: boolean) {
   if (!active) {
     return "normal" as const;
@@ -25,6 +25,7 @@ export type GameTermination =
   | "abort"
   | "illegal_move"
   | "move_limit"
+  | "adjudication"
   | "engine_failure"
   | "unknown"
   | "none";
@@ -76,6 +77,12 @@ export interface FairnessConfig {
   swap_colors: boolean;
   opening_suite_id?: string | null;
   opening_seed?: number | null;
+  adjudication?: AdjudicationConfig;
+}
+
+export interface AdjudicationConfig {
+  resign?: { score_cp: number; move_count: number } | null;
+  draw?: { score_cp: number; move_count: number; min_move_number: number } | null;
 }
 
 export interface BenchmarkPool {
@@ -129,6 +136,29 @@ export interface MatchSeries {
   white_participant: Participant;
   black_participant: Participant;
   interactive: boolean;
+  takeback_allowed: boolean;
+}
+
+export interface EloEstimate {
+  elo: number;
+  error_margin: number;
+  los: number;
+}
+
+export type EngineScore = { kind: "centipawns"; value: number } | { kind: "mate"; value: number };
+
+export interface PositionAnalysis {
+  fen: string;
+  engine_version_id: string;
+  score?: EngineScore | null;
+  depth?: number | null;
+  lines?: AnalysisLine[];
+}
+
+/** One ranked engine line, scored from White's point of view. */
+export interface AnalysisLine {
+  score: EngineScore;
+  moves_uci: string[];
 }
 
 export interface LeaderboardEntry {
@@ -138,6 +168,7 @@ export interface LeaderboardEntry {
   wins: number;
   draws: number;
   losses: number;
+  performance?: EloEstimate | null;
 }
 
 export interface GameRecord {
@@ -161,6 +192,27 @@ export interface GameRecord {
   black_participant: Participant;
 }
 
+export interface GameStats {
+  games: number;
+  white_wins: number;
+  black_wins: number;
+  draws: number;
+  average_plies: number;
+  average_duration_ms: number;
+  terminations: Partial<Record<GameTermination, number>>;
+}
+
+export interface OpeningScore {
+  opening_id: string | null;
+  label: string | null;
+  start_fen: string;
+  games: number;
+  white_wins: number;
+  black_wins: number;
+  draws: number;
+  white_score: number;
+}
+
 export interface ReplayPayload {
   id: string;
   variant: Variant;
@@ -184,6 +236,7 @@ export interface LiveMatchSnapshot {
   start_fen: string;
   fen: string;
   moves: string[];
+  move_times_ms?: (number | null)[];
   white_remaining_ms: number;
   black_remaining_ms: number;
   side_to_move: ProtocolLiveSide;
@@ -201,6 +254,7 @@ export interface MoveCommittedEvent {
   start_fen: string;
   fen: string;
   moves: string[];
+  move_times_ms?: (number | null)[];
   white_remaining_ms: number;
   black_remaining_ms: number;
   side_to_move: ProtocolLiveSide;
@@ -232,6 +286,7 @@ export interface GameFinishedEvent {
   start_fen: string;
   fen: string;
   moves: string[];
+  move_times_ms?: (number | null)[];
   white_remaining_ms: number;
   black_remaining_ms: number;
   side_to_move: ProtocolLiveSide;
@@ -240,6 +295,20 @@ export interface GameFinishedEvent {
 
 export type LiveProtocolEvent = LiveMatchSnapshot | MoveCommittedEvent | ClockSyncEvent | GameFinishedEvent;
 
+export interface EngineThinkingEvent {
+  protocol_version: number;
+  event_type: "engine_thinking";
+  match_id: string;
+  seq: number;
+  server_now_unix_ms: number;
+  side: ProtocolLiveSide;
+  depth?: number | null;
+  score?: EngineScore | null;
+  nodes?: number | null;
+  nps?: number | null;
+  pv: string[];
+}
+
 export interface LiveIntentAck {
   message_type: "intent_ack";
   match_id: string;
@@ -272,8 +341,15 @@ export interface LiveSubmitMoveMessage {
   move_uci: string;
 }
 
-export type LiveWsServerMessage = LiveProtocolEvent | LiveIntentAck | LiveErrorMessage;
-export type LiveWsClientMessage = LiveSubscribeMessage | LiveSubmitMoveMessage;
+export interface LiveTakebackMessage {
+  message_type: "takeback";
+  intent_id?: string;
+  client_action_id?: string;
+  ws_connection_id?: string;
+}
+
+export type LiveWsServerMessage = LiveProtocolEvent | EngineThinkingEvent | LiveIntentAck | LiveErrorMessage;
+export type LiveWsClientMessage = LiveSubscribeMessage | LiveSubmitMoveMessage | LiveTakebackMessage;
 
 export interface HumanPlayerProfile {
   id: string;
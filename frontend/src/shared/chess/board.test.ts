@@ -0,0 +1,132 @@
+import { describe, expect, it } from "vitest";
+
+import {
+  boardIndexAtPoint,
+  boardSoundForMove,
+  buildPgn,
+  capturedPieces,
+  checkedKingSquare,
+  fenToBoard,
+  squareCenter
+} from "./board";
+
+describe("boardIndexAtPoint", () => {
+  const rect = { left: 100, top: 50, width: 400, height: 400 };
+
+  it("maps pointer coordinates to the square under the cursor", () => {
+    expect(boardIndexAtPoint(rect, 101, 51)).toBe(0);
+    expect(boardIndexAtPoint(rect, 499, 449)).toBe(63);
+    expect(boardIndexAtPoint(rect, 175, 425)).toBe(57);
+  });
+
+  it("returns null when the pointer leaves the board", () => {
+    expect(boardIndexAtPoint(rect, 99, 200)).toBeNull();
+    expect(boardIndexAtPoint(rect, 300, 451)).toBeNull();
+    expect(boardIndexAtPoint({ left: 0, top: 0, width: 0, height: 0 }, 0, 0)).toBeNull();
+  });
+});
+
+describe("squareCenter", () => {
+  it("places squares from White's side of the board", () => {
+    expect(squareCenter("a1", "white")).toEqual({ x: 0.5, y: 7.5 });
+    expect(squareCenter("h8", "white")).toEqual({ x: 7.5, y: 0.5 });
+    expect(squareCenter("e4", "white")).toEqual({ x: 4.5, y: 4.5 });
+  });
+
+  it("mirrors both axes when the board is flipped", () => {
+    expect(squareCenter("a1", "black")).toEqual({ x: 7.5, y: 0.5 });
+    expect(squareCenter("h8", "black")).toEqual({ x: 0.5, y: 7.5 });
+    expect(squareCenter("e4", "black")).toEqual({ x: 3.5, y: 3.5 });
+  });
+});
+
+describe("checkedKingSquare", () => {
+  it("returns the square of the king in check", () => {
+    expect(checkedKingSquare("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3")).toBe("e1");
+    expect(checkedKingSquare("7k/7R/8/8/8/8/8/4K3 b - - 0 1")).toBe("h8");
+  });
+
+  it("returns nothing for quiet or unreadable positions", () => {
+    expect(checkedKingSquare("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")).toBe("");
+    expect(checkedKingSquare("not a fen")).toBe("");
+  });
+});
+
+describe("boardSoundForMove", () => {
+  const start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+  it("tells quiet moves, captures and checks apart", () => {
+    expect(boardSoundForMove(start, "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1", "e2e4", false)).toBe("move");
+    expect(
+      boardSoundForMove(
+        "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2",
+        "rnbqkbnr/ppp1pppp/8/3P4/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2",
+        "e4d5",
+        false
+      )
+    ).toBe("capture");
+    expect(
+      boardSoundForMove(
+        "rnbqkbnr/ppp2ppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+        "rnbqkbnr/ppp2ppp/3P4/8/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 3",
+        "e5d6",
+        false
+      )
+    ).toBe("capture");
+    expect(boardSoundForMove("7k/8/8/8/8/8/7R/4K3 w - - 0 1", "7k/7R/8/8/8/8/8/4K3 b - - 1 1", "h2h7", false)).toBe("check");
+  });
+
+  it("does not treat Chess960 king-takes-rook castling as a capture", () => {
+    expect(boardSoundForMove("4k3/8/8/8/8/8/8/4K2R w K - 0 1", "4k3/8/8/8/8/8/8/5RK1 b - - 1 1", "e1h1", false)).toBe("move");
+  });
+
+  it("prefers the game-end sound for the final move", () => {
+    expect(boardSoundForMove(start, start, "e2e4", true)).toBe("end");
+  });
+});
+
+describe("capturedPieces", () => {
+  it("is empty and level at the start", () => {
+    expect(capturedPieces(fenToBoard("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"))).toEqual({
+      white: [],
+      black: [],
+      balance: 0
+    });
+  });
+
+  it("counts an en passant capture as a pawn", () => {
+    expect(capturedPieces(fenToBoard("rnbqkbnr/ppp2ppp/3P4/8/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 3"))).toEqual({
+      white: ["p", "p"],
+      black: [],
+      balance: 2
+    });
+  });
+
+  it("does not count a promoted pawn as captured", () => {
+    // White's second queen came from a pawn; only a knight was lost.
+    expect(capturedPieces(fenToBoard("1nbQkbnr/pppp1ppp/8/8/8/8/PPPP1PPP/R1BQKBNR b KQk - 0 8"))).toEqual({
+      white: ["p", "r", "q"],
+      black: ["N"],
+      balance: 20
+    });
+  });
+});
+
+describe("buildPgn", () => {
+  it("writes the moves as SAN and tags a set-up start position", () => {
+    const start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    expect(buildPgn("standard", start, ["e2e4", "e7e5", "g1f3"])).toContain("1. e4 e5 2. Nf3");
+
+    const italian = "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3";
+    const pgn = buildPgn("standard", italian, ["g8f6"]);
+    expect(pgn).toContain('[SetUp "1"]');
+    expect(pgn).toContain(`[FEN "${italian}"]`);
+    expect(pgn).toContain("Nf6");
+  });
+
+  it("returns nothing it cannot replay", () => {
+    const start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    expect(buildPgn("standard", start, ["e2e5"])).toBe("");
+    expect(buildPgn("chess960", start, ["e2e4"])).toBe("");
+  });
+});
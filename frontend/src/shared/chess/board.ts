@@ -11,11 +11,7 @@ export function buildFrames(variant: Variant, startFen: string, movesUci: string
     const chess = new Chess(startFen);
     const frames = [chess.fen()];
     for (const move of movesUci) {
-      chess.move({
-        from: move.slice(0, 2),
-        to: move.slice(2, 4),
-        promotion: move.length > 4 ? (move[4] as "q" | "r" | "b" | "n") : undefined
-      });
+      playUci(chess, move);
       frames.push(chess.fen());
     }
     return frames;
@@ -24,6 +20,34 @@ export function buildFrames(variant: Variant, startFen: string, movesUci: string
   }
 }
 
+/**
+ * The moves so far as PGN, with SetUp and FEN tags when the game did not
+ * start from the initial position. Empty when chess.js cannot replay them.
+ */
+export function buildPgn(variant: Variant, startFen: string, movesUci: string[]) {
+  if (variant !== "standard") {
+    return "";
+  }
+
+  try {
+    const chess = new Chess(startFen);
+    for (const move of movesUci) {
+      playUci(chess, move);
+    }
+    return chess.pgn();
+  } catch {
+    return "";
+  }
+}
+
+function playUci(chess: Chess, move: string) {
+  chess.move({
+    from: move.slice(0, 2),
+    to: move.slice(2, 4),
+    promotion: move.length > 4 ? (move[4] as "q" | "r" | "b" | "n") : undefined
+  });
+}
+
 export function buildReplayFrames(replay: ReplayPayload | null) {
   if (!replay) {
     return [];
@@ -52,6 +76,47 @@ export function fenToBoard(fen: string) {
   return squares;
 }
 
+const pieceValues: Record<string, number> = { p: 1, n: 3, b: 3, r: 5, q: 9 };
+const startCounts: Record<string, number> = { p: 8, n: 2, b: 2, r: 2, q: 1 };
+
+export interface Captures {
+  /** Black pieces White has taken, cheapest first. */
+  white: string[];
+  /** White pieces Black has taken, cheapest first. */
+  black: string[];
+  /** White's material minus Black's, in pawns. */
+  balance: number;
+}
+
+/**
+ * Reads captures off the board by comparing each side's piece counts with
+ * the starting ones. A piece beyond its starting count was a promoted pawn,
+ * so it is not counted as a pawn capture.
+ */
+export function capturedPieces(squares: string[]): Captures {
+  const missing = (color: "white" | "black") => {
+    const own = squares.filter((piece) => piece && (piece === piece.toUpperCase()) === (color === "white"));
+    const count = (kind: string) => own.filter((piece) => piece.toLowerCase() === kind).length;
+    const promoted = ["n", "b", "r", "q"].reduce(
+      (total, kind) => total + Math.max(0, count(kind) - startCounts[kind]),
+      0
+    );
+    const letters: string[] = [];
+    for (const kind of ["p", "n", "b", "r", "q"]) {
+      const lost = kind === "p" ? startCounts.p - count("p") - promoted : startCounts[kind] - count(kind);
+      for (let index = 0; index < lost; index += 1) {
+        letters.push(color === "white" ? kind.toUpperCase() : kind);
+      }
+    }
+    return letters;
+  };
+  const balance = squares.reduce((total, piece) => {
+    const value = pieceValues[piece.toLowerCase()] ?? 0;
+    return piece === piece.toUpperCase() ? total + value : total - value;
+  }, 0);
+  return { white: missing("black"), black: missing("white"), balance };
+}
+
 export function orientSquares(squares: string[], orientation: "white" | "black") {
   return orientation === "white" ? squares : [...squares].reverse();
 }
@@ -93,6 +158,42 @@ export function legalMovesByOrigin(fen: string) {
   }
 }
 
+export function checkedKingSquare(fen: string) {
+  try {
+    const chess = new Chess(fen);
+    if (!chess.inCheck()) {
+      return "";
+    }
+    const king = chess.turn() === "w" ? "K" : "k";
+    const index = fenToBoard(fen).indexOf(king);
+    return index >= 0 ? squareName(index) : "";
+  } catch {
+    return "";
+  }
+}
+
+export type BoardSound = "move" | "capture" | "check" | "end";
+
+export function boardSoundForMove(previousFen: string, fen: string, moveUci: string, gameOver: boolean): BoardSound {
+  if (gameOver) {
+    return "end";
+  }
+  if (checkedKingSquare(fen)) {
+    return "check";
+  }
+  const before = fenToBoard(previousFen);
+  const target = before[squareIndex(moveUci.slice(2, 4))];
+  const mover = before[squareIndex(moveUci.slice(0, 2))] ?? "";
+  const enPassant = mover.toLowerCase() === "p" && moveUci[0] !== moveUci[2] && !target;
+  // Chess960 castling is sent as the king taking its own rook, which is not a capture.
+  const capturesOpponent = Boolean(target) && (target === target.toUpperCase()) !== (mover === mover.toUpperCase());
+  return capturesOpponent || enPassant ? "capture" : "move";
+}
+
+export function squareIndex(square: string) {
+  return (8 - Number(square[1])) * 8 + "abcdefgh".indexOf(square[0]);
+}
+
 export function groupedMoveRows(moves: string[]) {
   const rows: Array<{ index: number; white: string; black?: string }> = [];
   for (let index = 0; index < moves.length; index += 2) {
@@ -104,3 +205,27 @@ export function groupedMoveRows(moves: string[]) {
   }
   return rows;
 }
+
+export function boardIndexAtPoint(
+  rect: { left: number; top: number; width: number; height: number },
+  x: number,
+  y: number
+) {
+  if (rect.width <= 0 || rect.height <= 0) {
+    return null;
+  }
+  const file = Math.floor(((x - rect.left) / rect.width) * 8);
+  const rank = Math.floor(((y - rect.top) / rect.height) * 8);
+  if (file < 0 || file > 7 || rank < 0 || rank > 7) {
+    return null;
+  }
+  return rank * 8 + file;
+}
+
+export function squareCenter(square: string, orientation: "white" | "black") {
+  const file = "abcdefgh".indexOf(square[0]);
+  const rank = Number(square[1]) - 1;
+  const column = orientation === "white" ? file : 7 - file;
+  const row = orientation === "white" ? 7 - rank : rank;
+  return { x: column + 0.5, y: row + 0.5 };
+}
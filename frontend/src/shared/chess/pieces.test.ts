@@ -0,0 +1,16 @@
+import { describe, expect, it } from "vitest";
+
+import { pieceGlyphs, pieceImages } from "./pieces";
+
+describe("pieceImages", () => {
+  it("has a bundled image and a fallback glyph for all twelve pieces", () => {
+    const pieces = [..."KQBNRPkqbnrp"];
+
+    expect(Object.keys(pieceImages).sort()).toEqual([...pieces].sort());
+    for (const piece of pieces) {
+      expect(pieceImages[piece]).toMatch(/\.svg|^data:image\/svg\+xml/);
+      expect(pieceGlyphs[piece]).toHaveLength(1);
+    }
+    expect(new Set(Object.values(pieceImages)).size).toBe(12);
+  });
+});
@@ -25,3 +25,19 @@ export const pieceImages: Record<string, string> = {
   r: blackRook,
   p: blackPawn
 };
+
+/** Unicode pieces, shown in place of an image that fails to load. */
+export const pieceGlyphs: Record<string, string> = {
+  K: "\u2654",
+  Q: "\u2655",
+  B: "\u2657",
+  N: "\u2658",
+  R: "\u2656",
+  P: "\u2659",
+  k: "\u265a",
+  q: "\u265b",
+  b: "\u265d",
+  n: "\u265e",
+  r: "\u265c",
+  p: "\u265f"
+};
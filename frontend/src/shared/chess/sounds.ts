@@ -0,0 +1,59 @@
+import type { BoardSound } from "./board";
+
+export const soundMutedKey = "arena:sound-muted";
+
+const tones: Record<BoardSound, Array<{ frequency: number; durationMs: number }>> = {
+  move: [{ frequency: 520, durationMs: 60 }],
+  capture: [
+    { frequency: 320, durationMs: 50 },
+    { frequency: 240, durationMs: 70 }
+  ],
+  check: [
+    { frequency: 760, durationMs: 70 },
+    { frequency: 960, durationMs: 90 }
+  ],
+  end: [
+    { frequency: 660, durationMs: 120 },
+    { frequency: 550, durationMs: 120 },
+    { frequency: 440, durationMs: 220 }
+  ]
+};
+
+let audioContext: AudioContext | null = null;
+
+export function playBoardSound(sound: BoardSound) {
+  try {
+    const context = (audioContext ??= new AudioContext());
+    let startsAt = context.currentTime;
+    for (const tone of tones[sound]) {
+      const oscillator = context.createOscillator();
+      const gain = context.createGain();
+      const endsAt = startsAt + tone.durationMs / 1000;
+      oscillator.frequency.value = tone.frequency;
+      gain.gain.setValueAtTime(0.12, startsAt);
+      gain.gain.exponentialRampToValueAtTime(0.001, endsAt);
+      oscillator.connect(gain).connect(context.destination);
+      oscillator.start(startsAt);
+      oscillator.stop(endsAt);
+      startsAt = endsAt;
+    }
+  } catch {
+    // Audio is optional; browsers without Web Audio simply stay silent.
+  }
+}
+
+export function loadSoundMuted() {
+  try {
+    return window.localStorage.getItem(soundMutedKey) === "true";
+  } catch {
+    return false;
+  }
+}
+
+export function saveSoundMuted(muted: boolean) {
+  try {
+    window.localStorage.setItem(soundMutedKey, String(muted));
+  } catch {
+    // Ignore storage failures.
+  }
+}
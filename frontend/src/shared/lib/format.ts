@@ -1,4 +1,4 @@
-import type { GameResult, GameTermination, TimeControl, TournamentKind, Variant } from "../api/types";
+import type { EloEstimate, EngineScore, GameResult, GameTermination, TimeControl, TournamentKind, Variant } from "../api/types";
 
 export function formatVariant(value: Variant) {
   return value === "standard" ? "Standard" : "Chess960";
@@ -27,6 +27,28 @@ export function formatTimeControl(timeControl: TimeControl) {
   return `${formatDuration(timeControl.initial_ms)} + ${formatDuration(timeControl.increment_ms)}`;
 }
 
+export function formatEloEstimate(estimate: EloEstimate) {
+  const elo = Math.round(estimate.elo);
+  const sign = elo > 0 ? "+" : "";
+  return `${sign}${elo} ±${Math.round(estimate.error_margin)} Elo, LOS ${Math.round(estimate.los * 100)}%`;
+}
+
+export function formatEngineScore(score: EngineScore) {
+  if (score.kind === "mate") {
+    return score.value < 0 ? `-M${-score.value}` : `M${score.value}`;
+  }
+  const pawns = score.value / 100;
+  return `${pawns > 0 ? "+" : ""}${pawns.toFixed(2)}`;
+}
+
+/** White's share of the eval bar, from 0 to 1; mates pin it to one end. */
+export function engineScoreWhiteShare(score: EngineScore) {
+  if (score.kind === "mate") {
+    return score.value < 0 ? 0 : 1;
+  }
+  return 1 / (1 + Math.exp(-score.value / 400));
+}
+
 export function formatClock(ms: number) {
   if (!Number.isFinite(ms)) {
     return "--:--";
@@ -8,9 +8,11 @@ import type {
   BenchmarkPool,
   EventPreset,
   GameRecord,
+  GameStats,
   HumanPlayerProfile,
   LeaderboardEntry,
   MatchSeries,
+  OpeningScore,
   Tournament
 } from "../api/types";
 
@@ -25,7 +27,9 @@ export const arenaQueryKeys = {
   leaderboard: (poolId?: string) => ["leaderboard", poolId ?? "all"] as const,
   humanProfile: ["humanProfile"] as const,
   replay: (gameId: string) => ["replay", gameId] as const,
-  tournamentMatches: (tournamentId: string) => ["tournamentMatches", tournamentId] as const
+  tournamentMatches: (tournamentId: string) => ["tournamentMatches", tournamentId] as const,
+  tournamentStats: (tournamentId: string) => ["tournamentStats", tournamentId] as const,
+  tournamentOpenings: (tournamentId: string) => ["tournamentOpenings", tournamentId] as const
 };
 
 export function useAgentsQuery(refetchInterval?: number) {
@@ -134,3 +138,19 @@ export function useTournamentMatchesQuery(tournamentId: string, enabled = true)
     queryFn: () => fetchJson<MatchSeries[]>(`/matches?tournament_id=${encodeURIComponent(tournamentId)}`)
   });
 }
+
+export function useTournamentStatsQuery(tournamentId: string, refetchInterval?: number) {
+  return useQuery({
+    queryKey: arenaQueryKeys.tournamentStats(tournamentId),
+    queryFn: () => fetchJson<GameStats>(`/tournaments/${encodeURIComponent(tournamentId)}/stats`),
+    refetchInterval
+  });
+}
+
+export function useTournamentOpeningsQuery(tournamentId: string, refetchInterval?: number) {
+  return useQuery({
+    queryKey: arenaQueryKeys.tournamentOpenings(tournamentId),
+    queryFn: () => fetchJson<OpeningScore[]>(`/tournaments/${encodeURIComponent(tournamentId)}/openings`),
+    refetchInterval
+  });
+}
@@ -1,30 +1,210 @@
-import { pieceImages } from "../chess/pieces";
+import { useEffect, useRef, useState, type PointerEvent as ReactPointerEvent } from "react";
+
+import { pieceGlyphs, pieceImages } from "../chess/pieces";
 import type { BoardMoveMarker } from "../api/types";
-import { boardIndexToSquare } from "../chess/board";
+import { boardIndexAtPoint, boardIndexToSquare, squareCenter } from "../chess/board";
+
+const dragThresholdPx = 4;
+
+type PieceDrag = {
+  fromIndex: number;
+  pointerId: number;
+  startX: number;
+  startY: number;
+  x: number;
+  y: number;
+  started: boolean;
+};
+
+export type BoardArrow = {
+  from: string;
+  to: string;
+};
 
 export function BoardView({
   squares,
   selectedSquare,
   legalMoveMarkers = [],
   invalidSquare,
+  checkSquare,
+  premoveSquares = [],
   interactive = false,
   hoverableSquares,
   onSquareClick,
+  onPieceDragStart,
+  onPieceDrop,
+  engineArrows = [],
   orientation = "white"
 }: {
   squares: string[];
   selectedSquare?: string;
   legalMoveMarkers?: BoardMoveMarker[];
   invalidSquare?: string;
+  checkSquare?: string;
+  /** Origin and destination of a queued premove. */
+  premoveSquares?: string[];
   interactive?: boolean;
   hoverableSquares?: Set<string>;
   onSquareClick?: (index: number) => void;
+  onPieceDragStart?: (index: number) => void;
+  onPieceDrop?: (fromIndex: number, toIndex: number | null) => void;
+  /** Arrows drawn for the engine, such as its best move, apart from the user's own. */
+  engineArrows?: BoardArrow[];
   orientation?: "white" | "black";
 }) {
+  const boardRef = useRef<HTMLDivElement>(null);
+  const suppressClickRef = useRef(false);
+  const [drag, setDrag] = useState<PieceDrag | null>(null);
+  const annotationStartRef = useRef<string | null>(null);
+  const [arrows, setArrows] = useState<BoardArrow[]>([]);
+  const [highlights, setHighlights] = useState<string[]>([]);
+  const [brokenPieceImages, setBrokenPieceImages] = useState<ReadonlySet<string>>(() => new Set());
+  const dragEnabled = interactive && Boolean(onPieceDrop);
   const markerBySquare = new Map(legalMoveMarkers.map((marker) => [marker.square, marker.kind]));
+
+  useEffect(() => {
+    if (!dragEnabled) {
+      setDrag(null);
+    }
+  }, [dragEnabled]);
+
+  function handlePointerDown(event: ReactPointerEvent<HTMLButtonElement>, index: number) {
+    const square = boardIndexToSquare(index, orientation);
+    if (!dragEnabled || event.button !== 0 || !squares[index] || !(hoverableSquares?.has(square) ?? false)) {
+      return;
+    }
+    event.currentTarget.setPointerCapture?.(event.pointerId);
+    setDrag({
+      fromIndex: index,
+      pointerId: event.pointerId,
+      startX: event.clientX,
+      startY: event.clientY,
+      x: event.clientX,
+      y: event.clientY,
+      started: false
+    });
+  }
+
+  function handlePointerMove(event: ReactPointerEvent<HTMLButtonElement>) {
+    if (!drag || event.pointerId !== drag.pointerId) {
+      return;
+    }
+    const moved = Math.hypot(event.clientX - drag.startX, event.clientY - drag.startY);
+    const started = drag.started || moved > dragThresholdPx;
+    if (started && !drag.started) {
+      onPieceDragStart?.(drag.fromIndex);
+    }
+    setDrag({ ...drag, x: event.clientX, y: event.clientY, started });
+  }
+
+  function handlePointerUp(event: ReactPointerEvent<HTMLButtonElement>) {
+    if (!drag || event.pointerId !== drag.pointerId) {
+      return;
+    }
+    setDrag(null);
+    if (!drag.started) {
+      return;
+    }
+    // The browser may still fire a click for this gesture; the drop already handled it.
+    suppressClickRef.current = true;
+    window.setTimeout(() => {
+      suppressClickRef.current = false;
+    }, 0);
+    const rect = boardRef.current?.getBoundingClientRect();
+    const toIndex = rect ? boardIndexAtPoint(rect, event.clientX, event.clientY) : null;
+    onPieceDrop?.(drag.fromIndex, toIndex);
+  }
+
+  function handlePointerCancel(event: ReactPointerEvent<HTMLButtonElement>) {
+    if (!drag || event.pointerId !== drag.pointerId) {
+      return;
+    }
+    setDrag(null);
+    if (drag.started) {
+      onPieceDrop?.(drag.fromIndex, null);
+    }
+  }
+
+  function squareAtPoint(x: number, y: number) {
+    const rect = boardRef.current?.getBoundingClientRect();
+    const index = rect ? boardIndexAtPoint(rect, x, y) : null;
+    return index === null ? null : boardIndexToSquare(index, orientation);
+  }
+
+  function handleBoardPointerDown(event: ReactPointerEvent<HTMLDivElement>) {
+    if (event.button === 0) {
+      setArrows([]);
+      setHighlights([]);
+    } else if (event.button === 2) {
+      annotationStartRef.current = squareAtPoint(event.clientX, event.clientY);
+    }
+  }
+
+  function handleBoardPointerUp(event: ReactPointerEvent<HTMLDivElement>) {
+    const from = annotationStartRef.current;
+    if (event.button !== 2 || !from) {
+      return;
+    }
+    annotationStartRef.current = null;
+    const to = squareAtPoint(event.clientX, event.clientY);
+    if (!to) {
+      return;
+    }
+    if (to === from) {
+      setHighlights((current) =>
+        current.includes(from) ? current.filter((square) => square !== from) : [...current, from]
+      );
+      return;
+    }
+    setArrows((current) =>
+      current.some((arrow) => arrow.from === from && arrow.to === to)
+        ? current.filter((arrow) => arrow.from !== from || arrow.to !== to)
+        : [...current, { from, to }]
+    );
+  }
+
+  function handleClick(index: number) {
+    if (!interactive) {
+      return;
+    }
+    if (suppressClickRef.current) {
+      suppressClickRef.current = false;
+      return;
+    }
+    onSquareClick?.(index);
+  }
+
+  function arrowLine(arrow: BoardArrow, className: string, markerId: string) {
+    const from = squareCenter(arrow.from, orientation);
+    const to = squareCenter(arrow.to, orientation);
+    const length = Math.hypot(to.x - from.x, to.y - from.y);
+    // Stop the shaft short so the arrow head ends near the center of the target square.
+    const shorten = Math.min(0.35, length / 2);
+    return (
+      <line
+        key={`${className}-${arrow.from}-${arrow.to}`}
+        className={className}
+        x1={from.x}
+        y1={from.y}
+        x2={to.x - ((to.x - from.x) / length) * shorten}
+        y2={to.y - ((to.y - from.y) / length) * shorten}
+        markerEnd={`url(#${markerId})`}
+      />
+    );
+  }
+
+  const draggingIndex = drag?.started ? drag.fromIndex : null;
+  const draggingPiece = draggingIndex !== null ? squares[draggingIndex] : "";
+
   return (
     <div className="board-frame">
-      <div className="board">
+      <div
+        className={`board ${draggingIndex !== null ? "board-dragging" : ""}`}
+        ref={boardRef}
+        onPointerDown={handleBoardPointerDown}
+        onPointerUp={handleBoardPointerUp}
+        onContextMenu={(event) => event.preventDefault()}
+      >
         {squares.map((piece, index) => {
           const square = boardIndexToSquare(index, orientation);
           const markerKind = markerBySquare.get(square);
@@ -35,12 +215,29 @@ export function BoardView({
               type="button"
               className={`square ${(Math.floor(index / 8) + index) % 2 === 0 ? "light" : "dark"} ${
                 selectedSquare === square ? "square-selected" : ""
-              } ${invalidSquare === square ? "square-invalid" : ""} ${interactive ? "square-interactive" : ""} ${
+              } ${invalidSquare === square ? "square-invalid" : ""} ${
+                checkSquare === square ? "square-check" : ""
+              } ${premoveSquares.includes(square) ? "square-premove" : ""} ${interactive ? "square-interactive" : ""} ${
                 piece ? "square-has-piece" : ""
               } ${hoverable ? "square-hoverable-piece" : ""}`}
-              onClick={() => onSquareClick?.(index)}
-              disabled={!interactive}
+              onClick={() => handleClick(index)}
+              onPointerDown={(event) => handlePointerDown(event, index)}
+              onPointerMove={handlePointerMove}
+              onPointerUp={handlePointerUp}
+              onPointerCancel={handlePointerCancel}
+              aria-disabled={!interactive}
+              tabIndex={interactive ? undefined : -1}
             >
+              {index % 8 === 0 ? (
+                <span className="square-coord square-coord-rank" aria-hidden="true">
+                  {square[1]}
+                </span>
+              ) : null}
+              {index >= 56 ? (
+                <span className="square-coord square-coord-file" aria-hidden="true">
+                  {square[0]}
+                </span>
+              ) : null}
               {markerKind ? (
                 <span
                   className={`square-marker ${
@@ -49,11 +246,59 @@ export function BoardView({
                   aria-hidden="true"
                 />
               ) : null}
-              {piece ? <img className="piece-image" src={pieceImages[piece]} alt="" draggable={false} /> : null}
+              {piece && brokenPieceImages.has(piece) ? (
+                <span
+                  className={`piece-glyph ${piece === piece.toUpperCase() ? "piece-glyph-white" : "piece-glyph-black"} ${
+                    draggingIndex === index ? "piece-image-dragging" : ""
+                  }`}
+                  aria-hidden="true"
+                >
+                  {pieceGlyphs[piece]}
+                </span>
+              ) : piece ? (
+                <img
+                  className={`piece-image ${draggingIndex === index ? "piece-image-dragging" : ""}`}
+                  src={pieceImages[piece]}
+                  alt=""
+                  draggable={false}
+                  onError={() => setBrokenPieceImages((current) => new Set(current).add(piece))}
+                />
+              ) : null}
             </button>
           );
         })}
+        {arrows.length > 0 || highlights.length > 0 || engineArrows.length > 0 ? (
+          <svg className="board-annotations" viewBox="0 0 8 8" aria-hidden="true">
+            <defs>
+              <marker id="board-arrow-head" markerWidth="4" markerHeight="4" refX="2" refY="2" orient="auto">
+                <path d="M0,0 L4,2 L0,4 Z" />
+              </marker>
+              <marker id="board-engine-arrow-head" markerWidth="4" markerHeight="4" refX="2" refY="2" orient="auto">
+                <path d="M0,0 L4,2 L0,4 Z" />
+              </marker>
+            </defs>
+            {highlights.map((square) => {
+              const center = squareCenter(square, orientation);
+              return <circle key={square} className="board-annotation-highlight" cx={center.x} cy={center.y} r={0.45} />;
+            })}
+            {engineArrows.map((arrow) => arrowLine(arrow, "board-engine-arrow", "board-engine-arrow-head"))}
+            {arrows.map((arrow) => arrowLine(arrow, "board-annotation-arrow", "board-arrow-head"))}
+          </svg>
+        ) : null}
       </div>
+      {drag?.started && draggingPiece && !brokenPieceImages.has(draggingPiece) ? (
+        <img
+          className="piece-drag-ghost"
+          src={pieceImages[draggingPiece]}
+          alt=""
+          draggable={false}
+          style={{
+            left: drag.x,
+            top: drag.y,
+            width: (boardRef.current?.getBoundingClientRect().width ?? 0) / 8
+          }}
+        />
+      ) : null}
     </div>
   );
 }
@@ -1,8 +1,12 @@
+import { pieceImages } from "../chess/pieces";
+
 export function EngineSideCard({
   side,
   title,
   name,
   clock,
+  captured,
+  materialAdvantage = 0,
   active = false,
   urgency = "normal",
   winner = false
@@ -11,6 +15,10 @@ export function EngineSideCard({
   title: string;
   name: string;
   clock?: string;
+  /** Opponent pieces this side has taken, as FEN letters. */
+  captured?: string[];
+  /** How many pawns of material this side is ahead by, shown when positive. */
+  materialAdvantage?: number;
   active?: boolean;
   urgency?: "normal" | "warning" | "critical";
   winner?: boolean;
@@ -26,6 +34,14 @@ export function EngineSideCard({
       <span>{title}</span>
       <strong>{name}</strong>
       {clock ? <p className="engine-card-clock">{clock}</p> : null}
+      {captured ? (
+        <div className="engine-card-captures">
+          {captured.map((piece, index) => (
+            <img key={`${piece}-${index}`} src={pieceImages[piece]} alt={piece} draggable={false} />
+          ))}
+          {materialAdvantage > 0 ? <em>+{materialAdvantage}</em> : null}
+        </div>
+      ) : null}
     </div>
   );
 }
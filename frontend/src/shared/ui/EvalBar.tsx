@@ -0,0 +1,15 @@
+import type { EngineScore } from "../api/types";
+import { engineScoreWhiteShare, formatEngineScore } from "../lib/format";
+
+export function EvalBar({ score, pending = false }: { score: EngineScore | null; pending?: boolean }) {
+  const whiteShare = score ? engineScoreWhiteShare(score) : 0.5;
+
+  return (
+    <div className="eval-bar" title="Engine evaluation from White's point of view">
+      <div className="eval-bar-track">
+        <div className="eval-bar-white" style={{ width: `${(whiteShare * 100).toFixed(1)}%` }} />
+      </div>
+      <strong>{score ? formatEngineScore(score) : pending ? "…" : "--"}</strong>
+    </div>
+  );
+}
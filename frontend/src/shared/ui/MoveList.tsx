@@ -1,16 +1,22 @@
 import { groupedMoveRows } from "../chess/board";
+import { formatDuration } from "../lib/format";
 import { EmptyState } from "./EmptyState";
 
 export function MoveList({
   moves,
   activePly,
   latestPly,
-  animateLatest = false
+  animateLatest = false,
+  moveTimesMs,
+  onSelectPly
 }: {
   moves: string[];
   activePly: number;
   latestPly?: number;
   animateLatest?: boolean;
+  /** Thinking time per ply, indexed like `moves`; unknown times are null. */
+  moveTimesMs?: (number | null)[];
+  onSelectPly?: (ply: number) => void;
 }) {
   const rows = groupedMoveRows(moves);
 
@@ -28,11 +34,53 @@ export function MoveList({
         return (
           <div className={`move-row ${rowHasLatest ? "move-row-latest" : ""} ${animateLatest && rowHasLatest ? "move-row-flash" : ""}`} key={row.index}>
             <span className="move-index">{row.index}.</span>
-            <span className={activePly === whitePly ? "move-active" : ""}>{row.white}</span>
-            <span className={activePly === blackPly ? "move-active" : ""}>{row.black ?? ""}</span>
+            <MoveCell
+              move={row.white}
+              ply={whitePly}
+              active={activePly === whitePly}
+              thinkingMs={moveTimesMs?.[whitePly - 1]}
+              onSelectPly={onSelectPly}
+            />
+            <MoveCell
+              move={row.black ?? ""}
+              ply={blackPly}
+              active={activePly === blackPly}
+              thinkingMs={moveTimesMs?.[blackPly - 1]}
+              onSelectPly={onSelectPly}
+            />
           </div>
         );
       })}
     </div>
   );
 }
+
+function MoveCell({
+  move,
+  ply,
+  active,
+  thinkingMs,
+  onSelectPly
+}: {
+  move: string;
+  ply: number;
+  active: boolean;
+  thinkingMs?: number | null;
+  onSelectPly?: (ply: number) => void;
+}) {
+  const thinking = move && thinkingMs != null ? <span className="move-time">{formatDuration(thinkingMs)}</span> : null;
+  if (!onSelectPly || !move) {
+    return (
+      <span className={active ? "move-active" : ""}>
+        {move}
+        {thinking}
+      </span>
+    );
+  }
+  return (
+    <button type="button" className={`move-button ${active ? "move-active" : ""}`} onClick={() => onSelectPly(ply)}>
+      {move}
+      {thinking}
+    </button>
+  );
+}
@@ -2,6 +2,7 @@ export * from "./BoardView";
 export * from "./EmptyState";
 export * from "./EngineDocumentation";
 export * from "./EngineSideCard";
+export * from "./EvalBar";
 export * from "./Field";
 export * from "./MetricCard";
 export * from "./MoveList";